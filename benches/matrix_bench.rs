@@ -0,0 +1,90 @@
+//! Linear-algebra benchmarks, isolated from generation and the runtime.
+//!
+//! Included by `wbaes_bench.rs` as a module so that these groups run with the rest of the
+//! suite. Every input comes from a fixed ChaCha seed, so results are comparable across machines
+//! and runs.
+
+use criterion::Criterion;
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+
+use wbaes_gen::{mc_sr_matrix_256, Matrix256, Matrix8, SparseMatrix256};
+
+pub fn bench_matrix8(c: &mut Criterion) {
+    let mut rng = ChaCha20Rng::from_seed([10u8; 32]);
+    let left = Matrix8::random_invertible(&mut rng);
+    let right = Matrix8::random_invertible(&mut rng);
+    let mut group = c.benchmark_group("matrix8");
+    group.bench_function("mul", |b| b.iter(|| left.mul(&right)));
+    group.bench_function("invert", |b| b.iter(|| left.invert()));
+    group.finish();
+}
+
+pub fn bench_matrix256(c: &mut Criterion) {
+    let mut rng = ChaCha20Rng::from_seed([8u8; 32]);
+    let matrix = Matrix256::random_sparse_unsplit(&mut rng);
+    let mut group = c.benchmark_group("matrix256");
+    // The samplers draw from their own stream, so the inputs below do not depend on how many
+    // iterations criterion ran.
+    let mut sampler_rng = ChaCha20Rng::from_seed([9u8; 32]);
+    group.bench_function("random_sparse_unsplit", |b| {
+        b.iter(|| Matrix256::random_sparse_unsplit(&mut sampler_rng));
+    });
+    group.bench_function("random_sparse_unsplit_naive", |b| {
+        b.iter(|| Matrix256::random_sparse_unsplit_naive(&mut sampler_rng));
+    });
+    group.bench_function("is_invertible", |b| b.iter(|| matrix.is_invertible()));
+    group.bench_function("invert", |b| b.iter(|| matrix.invert()));
+    let dense = matrix
+        .invert()
+        .expect("sparse unsplit matrices are invertible");
+    group.bench_function("mul_naive_dense", |b| b.iter(|| dense.mul_naive(&dense)));
+    group.bench_function("mul_four_russians_dense", |b| {
+        b.iter(|| dense.mul_four_russians(&dense))
+    });
+    group.bench_function("mul_sparse_dense", |b| b.iter(|| matrix.mul(&dense)));
+    let random_dense = Matrix256::random_invertible_dense(&mut rng);
+    group.bench_function("mul_dense", |b| b.iter(|| dense.mul(&random_dense)));
+    group.bench_function("invert_dense", |b| b.iter(|| random_dense.invert()));
+    let layer = mc_sr_matrix_256();
+    group.bench_function("invert_mul_layer", |b| {
+        b.iter(|| matrix.invert().map(|inv| inv.mul(&layer)))
+    });
+    let banded = SparseMatrix256::from_dense(&matrix);
+    group.bench_function("mul_dense_banded_four_russians", |b| {
+        b.iter(|| dense.mul_four_russians(&matrix))
+    });
+    group.bench_function("mul_dense_banded_rhs", |b| {
+        b.iter(|| dense.mul_banded_rhs(&banded))
+    });
+    group.bench_function("lu", |b| b.iter(|| matrix.lu()));
+    let lu = matrix.lu();
+    group.bench_function("lu_solve_matrix_layer", |b| {
+        b.iter(|| lu.solve_matrix(&layer))
+    });
+    let inputs: Vec<[u8; 32]> = (0..256)
+        .map(|_| {
+            let mut input = [0u8; 32];
+            rng.fill_bytes(&mut input);
+            input
+        })
+        .collect();
+    group.bench_function("apply_to_bytes", |b| {
+        b.iter(|| dense.apply_to_bytes(&inputs[0]))
+    });
+    group.bench_function("apply_to_bytes_x256", |b| {
+        b.iter(|| {
+            inputs
+                .iter()
+                .map(|input| dense.apply_to_bytes(input))
+                .collect::<Vec<_>>()
+        })
+    });
+    group.bench_function("apply_to_many_x256", |b| {
+        b.iter(|| dense.apply_to_many(&inputs))
+    });
+    group.bench_function("submatrix_byte_map", |b| {
+        b.iter(|| dense.submatrix_byte_map(5))
+    });
+    group.finish();
+}
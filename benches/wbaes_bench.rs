@@ -1,11 +1,16 @@
-use criterion::{criterion_group, criterion_main, Criterion};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
 use rand::{RngCore, SeedableRng};
 use rand_chacha::ChaCha20Rng;
 
-use aes_core::{encrypt_block, expand_key, Aes128Key};
-use wbaes_gen::{Generator, GeneratorConfig};
+use aes_core::{
+    encrypt_block, encrypt_block_ttable, encrypt_blocks_par, encrypt_pair_bitsliced, expand_key,
+    Aes128, Aes128Key,
+};
+use wbaes_gen::{gf2, Generator, GeneratorConfig, Matrix128, Matrix8};
 use wbaes_runtime::WbCipher256;
 
+mod matrix_bench;
+
 fn bench_generation(c: &mut Criterion) {
     let mut group = c.benchmark_group("generation");
     group.sample_size(10);
@@ -16,11 +21,49 @@ fn bench_generation(c: &mut Criterion) {
                 ChaCha20Rng::from_seed([1u8; 32]),
                 GeneratorConfig {
                     external_encodings: false,
+                    ..GeneratorConfig::default()
                 },
             );
             gen.generate_instance(&key);
         });
     });
+    // Requires the `parallel` feature of wbaes-gen.
+    for (name, parallel) in [
+        ("generate_instance_sequential", false),
+        ("generate_instance_parallel", true),
+    ] {
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                let mut gen = Generator::from_seed([1u8; 32]);
+                gen.set_parallel(parallel);
+                gen.generate_instance(&Aes128Key::from([0u8; 16]));
+            });
+        });
+    }
+    group.finish();
+}
+
+fn bench_gf2(c: &mut Criterion) {
+    let mut rng = ChaCha20Rng::from_seed([12u8; 32]);
+    let (mut x, mut y) = ([0u8; 32], [0u8; 32]);
+    rng.fill_bytes(&mut x);
+    rng.fill_bytes(&mut y);
+    let matrix8 = Matrix8::random_invertible(&mut rng);
+    let matrix128 = Matrix128::random_sparse_unsplit(&mut rng);
+    let mut input = [0u8; 16];
+    rng.fill_bytes(&mut input);
+
+    let mut group = c.benchmark_group("gf2");
+    group.bench_function("dot", |b| b.iter(|| gf2::dot(&x, &y)));
+    group.bench_function("hamming_weight_256", |b| {
+        b.iter(|| gf2::hamming_weight_256(&x))
+    });
+    group.bench_function("matrix8_apply_x256", |b| {
+        b.iter(|| (0..=255u8).fold(0u8, |acc, value| acc ^ matrix8.apply(value)))
+    });
+    group.bench_function("matrix128_apply_to_bytes", |b| {
+        b.iter(|| matrix128.apply_to_bytes(&input))
+    });
     group.finish();
 }
 
@@ -30,6 +73,7 @@ fn bench_runtime(c: &mut Criterion) {
         ChaCha20Rng::from_seed([2u8; 32]),
         GeneratorConfig {
             external_encodings: false,
+            ..GeneratorConfig::default()
         },
     );
     let instance = gen.generate_instance(&key);
@@ -61,5 +105,61 @@ fn bench_runtime(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(benches, bench_generation, bench_runtime);
+fn bench_aes_core(c: &mut Criterion) {
+    let round_keys = expand_key(&Aes128Key::from([4u8; 16]));
+    let mut block = [0u8; 16];
+    ChaCha20Rng::from_seed([5u8; 32]).fill_bytes(&mut block);
+
+    let mut group = c.benchmark_group("aes_core");
+    group.bench_function("encrypt_block", |b| {
+        b.iter(|| encrypt_block(&block, &round_keys));
+    });
+    // Requires the `ttable` feature of aes-core.
+    group.bench_function("encrypt_block_ttable", |b| {
+        b.iter(|| encrypt_block_ttable(&block, &round_keys));
+    });
+    // Same two-block workload as `cmd_check`.
+    let mut pair = [0u8; 32];
+    pair[..16].copy_from_slice(&block);
+    pair[16..].copy_from_slice(&block);
+    group.bench_function("encrypt_pair_bytewise", |b| {
+        b.iter(|| {
+            let _ = encrypt_block(&block, &round_keys);
+            let _ = encrypt_block(&block, &round_keys);
+        });
+    });
+    group.bench_function("encrypt_pair_bitsliced", |b| {
+        b.iter(|| encrypt_pair_bitsliced(&pair, &round_keys));
+    });
+    group.finish();
+}
+
+// Requires the `rayon` feature of aes-core.
+fn bench_parallel(c: &mut Criterion) {
+    let aes = Aes128::new(&Aes128Key::from([6u8; 16]));
+    let mut group = c.benchmark_group("aes_core_parallel");
+    for size in [16 * 1024, 256 * 1024, 4 * 1024 * 1024] {
+        let mut data = vec![0u8; size];
+        ChaCha20Rng::from_seed([7u8; 32]).fill_bytes(&mut data);
+        group.throughput(Throughput::Bytes(size as u64));
+        group.bench_with_input(BenchmarkId::new("sequential", size), &size, |b, _| {
+            b.iter(|| aes.encrypt_blocks(&mut data).unwrap());
+        });
+        group.bench_with_input(BenchmarkId::new("rayon", size), &size, |b, _| {
+            b.iter(|| encrypt_blocks_par(&mut data, aes.round_keys()).unwrap());
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_generation,
+    matrix_bench::bench_matrix8,
+    matrix_bench::bench_matrix256,
+    bench_gf2,
+    bench_runtime,
+    bench_aes_core,
+    bench_parallel
+);
 criterion_main!(benches);
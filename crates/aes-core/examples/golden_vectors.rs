@@ -0,0 +1,49 @@
+//! Writes `tests/data/golden_vectors.json`: AES-128 `{key, plaintext,
+//! ciphertext}` triples derived from fixed `ChaCha20Rng` seeds.
+//!
+//! Regenerate with `cargo run -p aes-core --example golden_vectors [OUT]`.
+//! The committed file is checked by `tests/golden_vectors.rs`, so it should
+//! only change when the seeding scheme does.
+
+use std::path::PathBuf;
+
+use aes_core::{encrypt_block, expand_key, Aes128Key, Block};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use serde_json::json;
+
+/// Number of seeds, `0..SEEDS`.
+const SEEDS: u64 = 64;
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn main() -> std::io::Result<()> {
+    let out = std::env::args()
+        .nth(1)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| {
+            PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/data/golden_vectors.json")
+        });
+
+    let vectors: Vec<_> = (0..SEEDS)
+        .map(|seed| {
+            let mut rng = ChaCha20Rng::seed_from_u64(seed);
+            let key: [u8; 16] = rng.gen();
+            let plaintext: Block = rng.gen();
+            let ciphertext = encrypt_block(&plaintext, &expand_key(&Aes128Key::from(key)));
+            json!({
+                "seed": seed,
+                "key": hex(&key),
+                "plaintext": hex(&plaintext),
+                "ciphertext": hex(&ciphertext),
+            })
+        })
+        .collect();
+
+    let text = serde_json::to_string_pretty(&vectors).expect("vectors serialize");
+    std::fs::write(&out, text + "\n")?;
+    println!("wrote {} vectors to {}", vectors.len(), out.display());
+    Ok(())
+}
@@ -0,0 +1,120 @@
+//! AES-128 cipher object owning its expanded key schedule.
+
+use crate::block::Block;
+use crate::cipher::{decrypt_block, encrypt_block, expand_key};
+use crate::error::AesError;
+use crate::key::{Aes128Key, RoundKeys};
+
+/// AES-128 with the round keys expanded once at construction.
+#[derive(Clone, Debug)]
+pub struct Aes128 {
+    round_keys: RoundKeys,
+}
+
+impl Aes128 {
+    /// Expands `key` into a ready-to-use cipher.
+    pub fn new(key: &Aes128Key) -> Self {
+        Self {
+            round_keys: expand_key(key),
+        }
+    }
+
+    /// Returns the expanded round keys.
+    pub fn round_keys(&self) -> &RoundKeys {
+        &self.round_keys
+    }
+
+    /// Encrypts a block in place.
+    pub fn encrypt(&self, block: &mut Block) {
+        *block = encrypt_block(block, &self.round_keys);
+    }
+
+    /// Decrypts a block in place.
+    pub fn decrypt(&self, block: &mut Block) {
+        *block = decrypt_block(block, &self.round_keys);
+    }
+
+    /// Encrypts every 16-byte block of `data` in place (ECB over the buffer).
+    pub fn encrypt_blocks(&self, data: &mut [u8]) -> Result<(), AesError> {
+        self.map_blocks(data, Self::encrypt)
+    }
+
+    /// Decrypts every 16-byte block of `data` in place (ECB over the buffer).
+    pub fn decrypt_blocks(&self, data: &mut [u8]) -> Result<(), AesError> {
+        self.map_blocks(data, Self::decrypt)
+    }
+
+    fn map_blocks(&self, data: &mut [u8], op: fn(&Self, &mut Block)) -> Result<(), AesError> {
+        if !data.len().is_multiple_of(16) {
+            return Err(AesError::InvalidBlockLength { len: data.len() });
+        }
+        for chunk in data.chunks_exact_mut(16) {
+            let block: &mut Block = chunk.try_into().expect("chunk length is 16");
+            op(self, block);
+        }
+        Ok(())
+    }
+}
+
+impl From<&Aes128Key> for Aes128 {
+    fn from(key: &Aes128Key) -> Self {
+        Self::new(key)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for Aes128 {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.round_keys);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for Aes128 {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{RngCore, SeedableRng};
+
+    #[test]
+    fn object_matches_free_functions() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        let mut key_bytes = [0u8; 16];
+        rng.fill_bytes(&mut key_bytes);
+        let key = Aes128Key::from(key_bytes);
+        let aes = Aes128::new(&key);
+        let round_keys = expand_key(&key);
+
+        let mut data = [0u8; 64];
+        rng.fill_bytes(&mut data);
+        let original = data;
+        aes.encrypt_blocks(&mut data).expect("aligned buffer");
+        for (chunk, plain) in data.chunks(16).zip(original.chunks(16)) {
+            let plain: Block = plain.try_into().unwrap();
+            assert_eq!(chunk, encrypt_block(&plain, &round_keys));
+        }
+        aes.decrypt_blocks(&mut data).expect("aligned buffer");
+        assert_eq!(data, original);
+
+        let mut block: Block = original[..16].try_into().unwrap();
+        aes.encrypt(&mut block);
+        aes.decrypt(&mut block);
+        assert_eq!(block, original[..16]);
+    }
+
+    #[test]
+    fn unaligned_buffers_are_rejected_untouched() {
+        let aes = Aes128::new(&Aes128Key::from([0u8; 16]));
+        let mut data = [0x5au8; 17];
+        assert_eq!(
+            aes.encrypt_blocks(&mut data),
+            Err(AesError::InvalidBlockLength { len: 17 })
+        );
+        assert_eq!(
+            aes.decrypt_blocks(&mut data),
+            Err(AesError::InvalidBlockLength { len: 17 })
+        );
+        assert_eq!(data, [0x5au8; 17]);
+    }
+}
@@ -0,0 +1,67 @@
+//! Bit-difference measurements for key-schedule diffusion experiments.
+
+use crate::block::Block;
+use crate::key::RoundKeys;
+
+/// Number of bit positions in which `a` and `b` differ.
+pub fn hamming_distance(a: &Block, b: &Block) -> u32 {
+    a.iter()
+        .zip(b.iter())
+        .map(|(x, y)| (x ^ y).count_ones())
+        .sum()
+}
+
+/// Per-round Hamming distances between two AES-128 key schedules.
+pub fn round_key_diff(rk1: &RoundKeys, rk2: &RoundKeys) -> [u32; 11] {
+    core::array::from_fn(|round| hamming_distance(rk1.get(round), rk2.get(round)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cipher::expand_key;
+    use crate::key::Aes128Key;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn hamming_distance_counts_bits() {
+        assert_eq!(hamming_distance(&[0; 16], &[0; 16]), 0);
+        assert_eq!(hamming_distance(&[0; 16], &[0xff; 16]), 128);
+        let mut b = [0u8; 16];
+        b[3] = 0b1010_0001;
+        assert_eq!(hamming_distance(&[0; 16], &b), 3);
+    }
+
+    #[test]
+    fn one_key_bit_flip_avalanches_into_the_last_round_key() {
+        const TRIALS: u32 = 1000;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(298);
+        let mut totals = [0u32; 11];
+        for _ in 0..TRIALS {
+            let key: [u8; 16] = rng.gen();
+            let mut flipped = key;
+            let bit = rng.gen_range(0..128);
+            flipped[bit / 8] ^= 1 << (bit % 8);
+
+            let diff = round_key_diff(
+                &expand_key(&Aes128Key::from(key)),
+                &expand_key(&Aes128Key::from(flipped)),
+            );
+            assert_eq!(diff[0], 1);
+            for (total, d) in totals.iter_mut().zip(diff) {
+                *total += d;
+            }
+        }
+        // Differences spread over the first four rounds, then plateau near 50
+        // of 128 bits rather than 64: every word is the XOR of earlier words,
+        // so a flipped bit keeps cancelling against its own copies.
+        let means: Vec<f64> = totals
+            .iter()
+            .map(|&total| f64::from(total) / f64::from(TRIALS))
+            .collect();
+        assert!(means[1] < means[2] && means[2] < means[3] && means[3] < means[4]);
+        for (round, &mean) in means.iter().enumerate().skip(5) {
+            assert!((45.0..55.0).contains(&mean), "round {round} mean {mean}");
+        }
+    }
+}
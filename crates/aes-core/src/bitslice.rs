@@ -0,0 +1,309 @@
+//! Bitsliced encryption of two blocks at once in a fixsliced round structure.
+//!
+//! The 32-byte input is stored as eight 32-bit planes: bit `16 * b + i` of plane
+//! `j` is bit `j` of byte `i` of block `b`. Following the fixslicing approach,
+//! ShiftRows is never applied to the state. After round `r` the stored state is
+//! the real state permuted by `SR^-r`, so each round uses one of four
+//! MixColumns variants `SR^-r ∘ MC ∘ SR^r`, round keys are pre-permuted the same
+//! way, and a single permutation restores byte order at the end.
+//!
+//! SubBytes is evaluated on the planes as GF(2^8) inversion (`x^254` via an
+//! addition chain of bitsliced multiplications) followed by the affine map, so
+//! there are no table lookups or secret-dependent branches. Plane permutations
+//! move bits one at a time with fixed shifts; this favors clarity over speed.
+
+use crate::key::RoundKeys;
+
+/// Eight bit planes holding two AES states.
+type Planes = [u32; 8];
+
+/// Byte permutation given as source indices: `out[p] = in[src[p]]`.
+type Perm = [u8; 16];
+
+const IDENTITY: Perm = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
+
+/// ShiftRows: row `r` of column `c` comes from column `c + r`.
+const fn shift_rows_perm() -> Perm {
+    let mut src = [0u8; 16];
+    let mut p = 0;
+    while p < 16 {
+        let (c, r) = (p / 4, p % 4);
+        src[p] = (4 * ((c + r) % 4) + r) as u8;
+        p += 1;
+    }
+    src
+}
+
+/// Rotation of rows within each column: row `r` comes from row `r + j`.
+const fn row_rotation_perm(j: usize) -> Perm {
+    let mut src = [0u8; 16];
+    let mut p = 0;
+    while p < 16 {
+        let (c, r) = (p / 4, p % 4);
+        src[p] = (4 * c + (r + j) % 4) as u8;
+        p += 1;
+    }
+    src
+}
+
+/// Returns the table for applying `q` first, then `p`.
+const fn compose(p: &Perm, q: &Perm) -> Perm {
+    let mut src = [0u8; 16];
+    let mut i = 0;
+    while i < 16 {
+        src[i] = q[p[i] as usize];
+        i += 1;
+    }
+    src
+}
+
+const fn invert(p: &Perm) -> Perm {
+    let mut src = [0u8; 16];
+    let mut i = 0;
+    while i < 16 {
+        src[p[i] as usize] = i as u8;
+        i += 1;
+    }
+    src
+}
+
+/// `SR^k` for `k` in `0..4` (`SR^4` is the identity).
+const SR_POW: [Perm; 4] = {
+    let sr = shift_rows_perm();
+    let sr2 = compose(&sr, &sr);
+    let sr3 = compose(&sr2, &sr);
+    [IDENTITY, sr, sr2, sr3]
+};
+
+/// `SR^-k` for `k` in `0..4`.
+const SR_INV_POW: [Perm; 4] = [
+    invert(&SR_POW[0]),
+    invert(&SR_POW[1]),
+    invert(&SR_POW[2]),
+    invert(&SR_POW[3]),
+];
+
+/// Row rotations conjugated by ShiftRows: `ROT[k][j - 1] = SR^-k ∘ ROT_j ∘ SR^k`.
+const ROT: [[Perm; 3]; 4] = {
+    let mut out = [[IDENTITY; 3]; 4];
+    let mut k = 0;
+    while k < 4 {
+        let mut j = 1;
+        while j < 4 {
+            let rot = row_rotation_perm(j);
+            out[k][j - 1] = compose(&SR_INV_POW[k], &compose(&rot, &SR_POW[k]));
+            j += 1;
+        }
+        k += 1;
+    }
+    out
+};
+
+fn permute_bytes(bytes: &[u8; 32], perm: &Perm) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    for half in 0..2 {
+        for (p, &src) in perm.iter().enumerate() {
+            out[16 * half + p] = bytes[16 * half + src as usize];
+        }
+    }
+    out
+}
+
+fn permute_word(word: u32, perm: &Perm) -> u32 {
+    let mut out = 0u32;
+    for half in 0..2 {
+        for (p, &src) in perm.iter().enumerate() {
+            let bit = (word >> (16 * half + src as usize)) & 1;
+            out |= bit << (16 * half + p);
+        }
+    }
+    out
+}
+
+fn permute_planes(planes: &Planes, perm: &Perm) -> Planes {
+    let mut out = [0u32; 8];
+    for (o, w) in out.iter_mut().zip(planes.iter()) {
+        *o = permute_word(*w, perm);
+    }
+    out
+}
+
+fn pack(bytes: &[u8; 32]) -> Planes {
+    let mut planes = [0u32; 8];
+    for (j, plane) in planes.iter_mut().enumerate() {
+        for (i, byte) in bytes.iter().enumerate() {
+            *plane |= u32::from((byte >> j) & 1) << i;
+        }
+    }
+    planes
+}
+
+fn unpack(planes: &Planes) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    for (j, plane) in planes.iter().enumerate() {
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte |= (((plane >> i) & 1) as u8) << j;
+        }
+    }
+    bytes
+}
+
+fn xor(a: &Planes, b: &Planes) -> Planes {
+    let mut out = *a;
+    for (o, w) in out.iter_mut().zip(b.iter()) {
+        *o ^= *w;
+    }
+    out
+}
+
+/// Bitsliced multiplication in GF(2^8) modulo x^8 + x^4 + x^3 + x + 1.
+fn gf_mul(a: &Planes, b: &Planes) -> Planes {
+    let mut product = [0u32; 15];
+    for i in 0..8 {
+        for j in 0..8 {
+            product[i + j] ^= a[i] & b[j];
+        }
+    }
+    for k in (8..15).rev() {
+        let hi = product[k];
+        product[k - 4] ^= hi;
+        product[k - 5] ^= hi;
+        product[k - 7] ^= hi;
+        product[k - 8] ^= hi;
+    }
+    let mut out = [0u32; 8];
+    out.copy_from_slice(&product[..8]);
+    out
+}
+
+/// Bitsliced S-box: inversion via `x^254`, then the FIPS-197 affine transform.
+fn sub_bytes(x: &Planes) -> Planes {
+    let x2 = gf_mul(x, x);
+    let x3 = gf_mul(&x2, x);
+    let x6 = gf_mul(&x3, &x3);
+    let x12 = gf_mul(&x6, &x6);
+    let x15 = gf_mul(&x12, &x3);
+    let x30 = gf_mul(&x15, &x15);
+    let x60 = gf_mul(&x30, &x30);
+    let x120 = gf_mul(&x60, &x60);
+    let x240 = gf_mul(&x120, &x120);
+    let x252 = gf_mul(&x240, &x12);
+    let inv = gf_mul(&x252, &x2);
+
+    let mut out = [0u32; 8];
+    for (i, o) in out.iter_mut().enumerate() {
+        *o = inv[i] ^ inv[(i + 4) % 8] ^ inv[(i + 5) % 8] ^ inv[(i + 6) % 8] ^ inv[(i + 7) % 8];
+        if (0x63 >> i) & 1 == 1 {
+            *o = !*o;
+        }
+    }
+    out
+}
+
+/// Multiplication by `x` on every byte.
+fn xtime(x: &Planes) -> Planes {
+    [
+        x[7],
+        x[0] ^ x[7],
+        x[1],
+        x[2] ^ x[7],
+        x[3] ^ x[7],
+        x[4],
+        x[5],
+        x[6],
+    ]
+}
+
+/// MixColumns variant for a state stored as `SR^-k` of the real state.
+fn mix_columns(x: &Planes, k: usize) -> Planes {
+    let r1 = permute_planes(x, &ROT[k][0]);
+    let r2 = permute_planes(x, &ROT[k][1]);
+    let r3 = permute_planes(x, &ROT[k][2]);
+    // MC(a) = 2·a ⊕ 3·rot1(a) ⊕ rot2(a) ⊕ rot3(a) = 2·(a ⊕ rot1(a)) ⊕ rot1(a) ⊕ rot2(a) ⊕ rot3(a)
+    xor(&xor(&xtime(&xor(x, &r1)), &r1), &xor(&r2, &r3))
+}
+
+/// Packs round key `round`, duplicated for both blocks and permuted by `SR^-round`.
+fn round_key_planes<const N: usize>(round_keys: &RoundKeys<N>, round: usize) -> Planes {
+    let key = round_keys.get(round);
+    let mut both = [0u8; 32];
+    both[..16].copy_from_slice(key);
+    both[16..].copy_from_slice(key);
+    pack(&permute_bytes(&both, &SR_INV_POW[round % 4]))
+}
+
+/// Encrypts two blocks with bitsliced AES; matches two calls to [`encrypt_block`](crate::encrypt_block).
+pub fn encrypt_pair_bitsliced<const N: usize>(
+    blocks: &[u8; 32],
+    round_keys: &RoundKeys<N>,
+) -> [u8; 32] {
+    let rounds = N - 1;
+    let mut state = xor(&pack(blocks), &round_key_planes(round_keys, 0));
+    for round in 1..rounds {
+        let mixed = mix_columns(&sub_bytes(&state), round % 4);
+        state = xor(&mixed, &round_key_planes(round_keys, round));
+    }
+    state = xor(&sub_bytes(&state), &round_key_planes(round_keys, rounds));
+    permute_bytes(&unpack(&state), &SR_POW[rounds % 4])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::Block;
+    use crate::cipher::{encrypt_block, expand_key, expand_key_192};
+    use crate::key::{Aes128Key, Aes192Key};
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn pair_matches_nist_vector() {
+        let key = Aes128Key::from([
+            0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf,
+            0x4f, 0x3c,
+        ]);
+        let plain: Block = [
+            0x32, 0x43, 0xf6, 0xa8, 0x88, 0x5a, 0x30, 0x8d, 0x31, 0x31, 0x98, 0xa2, 0xe0, 0x37,
+            0x07, 0x34,
+        ];
+        let cipher: Block = [
+            0x39, 0x25, 0x84, 0x1d, 0x02, 0xdc, 0x09, 0xfb, 0xdc, 0x11, 0x85, 0x97, 0x19, 0x6a,
+            0x0b, 0x32,
+        ];
+        let mut pair = [0u8; 32];
+        pair[..16].copy_from_slice(&plain);
+        pair[16..].copy_from_slice(&plain);
+        let out = encrypt_pair_bitsliced(&pair, &expand_key(&key));
+        assert_eq!(out[..16], cipher);
+        assert_eq!(out[16..], cipher);
+    }
+
+    #[test]
+    fn pair_matches_two_reference_encryptions() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(265);
+        for _ in 0..200 {
+            let round_keys = expand_key(&Aes128Key::from(rng.gen::<[u8; 16]>()));
+            let pair: [u8; 32] = rng.gen();
+            let out = encrypt_pair_bitsliced(&pair, &round_keys);
+            let first: Block = pair[..16].try_into().unwrap();
+            let second: Block = pair[16..].try_into().unwrap();
+            assert_eq!(out[..16], encrypt_block(&first, &round_keys));
+            assert_eq!(out[16..], encrypt_block(&second, &round_keys));
+        }
+    }
+
+    #[test]
+    fn pair_supports_aes192_round_count() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(266);
+        let round_keys = expand_key_192(&Aes192Key::from(rng.gen::<[u8; 24]>()));
+        let pair: [u8; 32] = rng.gen();
+        let out = encrypt_pair_bitsliced(&pair, &round_keys);
+        let first: Block = pair[..16].try_into().unwrap();
+        assert_eq!(out[..16], encrypt_block(&first, &round_keys));
+    }
+
+    #[test]
+    fn pack_unpack_round_trip() {
+        let bytes: [u8; 32] = rand::rngs::StdRng::seed_from_u64(267).gen();
+        assert_eq!(unpack(&pack(&bytes)), bytes);
+    }
+}
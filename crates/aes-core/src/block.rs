@@ -10,3 +10,89 @@ pub fn xor_in_place(dst: &mut Block, rhs: &Block) {
         *d ^= *r;
     }
 }
+
+/// Compares two byte strings without exiting early on the first difference.
+///
+/// Returns `false` immediately if the lengths differ; lengths are treated as
+/// public. Use this for every MAC, tag, or checksum comparison.
+pub fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let diff = a
+        .iter()
+        .zip(b.iter())
+        .fold(0u8, |acc, (x, y)| acc | (x ^ y));
+    core::hint::black_box(diff) == 0
+}
+
+/// Two AES blocks side by side, the unit processed by the white-box tables.
+pub type DoubleBlock = [u8; 32];
+
+/// XORs two double blocks, writing the result into `dst`.
+#[inline]
+pub fn xor32_in_place(dst: &mut DoubleBlock, rhs: &DoubleBlock) {
+    for (d, r) in dst.iter_mut().zip(rhs.iter()) {
+        *d ^= *r;
+    }
+}
+
+/// Returns the first and second block of a double block.
+#[inline]
+pub fn split_double(block: &DoubleBlock) -> (&Block, &Block) {
+    let (first, second) = block.split_at(16);
+    (
+        first.try_into().expect("first half is 16 bytes"),
+        second.try_into().expect("second half is 16 bytes"),
+    )
+}
+
+/// Concatenates two blocks into a double block.
+#[inline]
+pub fn join_double(first: &Block, second: &Block) -> DoubleBlock {
+    let mut out = [0u8; 32];
+    out[..16].copy_from_slice(first);
+    out[16..].copy_from_slice(second);
+    out
+}
+
+/// Formats a double block as 64 lowercase hex digits.
+pub fn double_block_hex(block: &DoubleBlock) -> String {
+    block.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ct_eq_compares_contents_and_lengths() {
+        assert!(ct_eq(b"", b""));
+        assert!(ct_eq(&[1, 2, 3], &[1, 2, 3]));
+        assert!(!ct_eq(&[1, 2, 3], &[1, 2, 4]));
+        assert!(!ct_eq(&[0x80, 2, 3], &[0, 2, 3]));
+        assert!(!ct_eq(&[1, 2, 3], &[1, 2]));
+        assert!(!ct_eq(&[], &[0]));
+    }
+
+    #[test]
+    fn split_join_round_trip() {
+        let double: DoubleBlock = core::array::from_fn(|i| i as u8);
+        let (first, second) = split_double(&double);
+        assert_eq!(first[0], 0);
+        assert_eq!(second[0], 16);
+        assert_eq!(join_double(first, second), double);
+    }
+
+    #[test]
+    fn xor_and_hex() {
+        let mut a: DoubleBlock = [0xf0; 32];
+        xor32_in_place(&mut a, &[0xff; 32]);
+        assert_eq!(a, [0x0f; 32]);
+        let copy = a;
+        xor32_in_place(&mut a, &copy);
+        assert_eq!(a, [0; 32]);
+        let hex = double_block_hex(&join_double(&[0xab; 16], &[0x01; 16]));
+        assert_eq!(hex, format!("{}{}", "ab".repeat(16), "01".repeat(16)));
+    }
+}
@@ -1,22 +1,25 @@
-//! AES-128 key schedule and block encryption/decryption.
+//! AES key schedules and block encryption/decryption.
 
 use core::convert::TryInto;
 
 use crate::block::Block;
-use crate::key::{Aes128Key, RoundKeys};
+use crate::key::{Aes128Key, Aes192Key, Aes256Key, RoundKeys, RoundKeys192, RoundKeys256};
 use crate::round::{
     add_round_key, inv_mix_columns, inv_shift_rows, inv_sub_bytes, mix_columns, shift_rows,
     sub_bytes,
 };
 use crate::sbox::sbox;
 
-const RCON: [u8; 10] = [0x01, 0x02, 0x04, 0x08, 0x10, 0x20, 0x40, 0x80, 0x1b, 0x36];
+/// Round constants: `RCON[i]` is the high byte of `Rcon[i + 1]` in FIPS-197, i.e. `x^i` in GF(2^8).
+pub const RCON: [u8; 10] = [0x01, 0x02, 0x04, 0x08, 0x10, 0x20, 0x40, 0x80, 0x1b, 0x36];
 
-fn rot_word(word: u32) -> u32 {
+/// RotWord: cyclic left rotation of the word's bytes, `[a0, a1, a2, a3] -> [a1, a2, a3, a0]`.
+pub fn rot_word(word: u32) -> u32 {
     word.rotate_left(8)
 }
 
-fn sub_word(word: u32) -> u32 {
+/// SubWord: applies the S-box to each byte of a big-endian word.
+pub fn sub_word(word: u32) -> u32 {
     let b0 = sbox((word >> 24) as u8) as u32;
     let b1 = sbox((word >> 16) as u8) as u32;
     let b2 = sbox((word >> 8) as u8) as u32;
@@ -32,45 +35,123 @@ fn be_from_u32(word: u32) -> [u8; 4] {
     word.to_be_bytes()
 }
 
+/// Expands a 128-bit key into the 44 schedule words `w[0..44]` of FIPS-197 §5.2.
+pub fn expand_key_words(key: &Aes128Key) -> [u32; 44] {
+    let w = schedule_words(&key.0, 44);
+    core::array::from_fn(|i| w[i])
+}
+
 /// Expands a 128-bit key into 11 round keys.
 pub fn expand_key(key: &Aes128Key) -> RoundKeys {
-    let mut w = [0u32; 44];
-    for (i, chunk) in key.0.chunks_exact(4).enumerate() {
+    round_keys_from_words(&expand_key_words(key))
+}
+
+/// Expands a 192-bit key into 13 round keys.
+pub fn expand_key_192(key: &Aes192Key) -> RoundKeys192 {
+    round_keys_from_words(&schedule_words(&key.0, 52))
+}
+
+/// Expands a 256-bit key into 15 round keys.
+pub fn expand_key_256(key: &Aes256Key) -> RoundKeys256 {
+    round_keys_from_words(&schedule_words(&key.0, 60))
+}
+
+/// FIPS-197 key expansion for a key of `key.len() / 4` words into `total_words` words.
+fn schedule_words(key: &[u8], total_words: usize) -> [u32; 60] {
+    let nk = key.len() / 4;
+    let mut w = [0u32; 60];
+    for (i, chunk) in key.chunks_exact(4).enumerate() {
         let bytes: [u8; 4] = chunk.try_into().expect("chunk length is four");
         w[i] = u32_from_be(&bytes);
     }
 
-    for i in 4..44 {
+    for i in nk..total_words {
         let mut temp = w[i - 1];
-        if i % 4 == 0 {
-            temp = sub_word(rot_word(temp)) ^ (u32::from(RCON[(i / 4) - 1]) << 24);
+        if i % nk == 0 {
+            temp = sub_word(rot_word(temp)) ^ (u32::from(RCON[(i / nk) - 1]) << 24);
+        } else if nk > 6 && i % nk == 4 {
+            temp = sub_word(temp);
         }
-        w[i] = w[i - 4] ^ temp;
+        w[i] = w[i - nk] ^ temp;
     }
+    w
+}
 
-    let mut round_keys = [[0u8; 16]; 11];
-    for round in 0..11 {
+/// Packs the first `4 * N` schedule words into `N` round keys.
+fn round_keys_from_words<const N: usize>(w: &[u32]) -> RoundKeys<N> {
+    let mut round_keys = [[0u8; 16]; N];
+    for (round, round_key) in round_keys.iter_mut().enumerate() {
         for word_idx in 0..4 {
-            let word = w[round * 4 + word_idx];
-            let bytes = be_from_u32(word);
+            let bytes = be_from_u32(w[round * 4 + word_idx]);
             let offset = word_idx * 4;
-            round_keys[round][offset] = bytes[0];
-            round_keys[round][offset + 1] = bytes[1];
-            round_keys[round][offset + 2] = bytes[2];
-            round_keys[round][offset + 3] = bytes[3];
+            round_key[offset..offset + 4].copy_from_slice(&bytes);
         }
     }
 
     RoundKeys(round_keys)
 }
 
+/// Recovers the AES-128 key from round key `round` (0..=10) by running the schedule backwards.
+///
+/// # Panics
+///
+/// Panics if `round > 10`.
+pub fn recover_key(round: usize, round_key: &Block) -> Aes128Key {
+    assert!(round <= 10, "AES-128 has round keys 0..=10, got {round}");
+    let mut w = [0u32; 44];
+    for (j, chunk) in round_key.chunks_exact(4).enumerate() {
+        let bytes: [u8; 4] = chunk.try_into().expect("chunk length is four");
+        w[4 * round + j] = u32_from_be(&bytes);
+    }
+
+    // Forward: w[i + 4] = w[i] ^ temp(w[i + 3]), so w[i] = w[i + 4] ^ temp(w[i + 3]).
+    for i in (0..4 * round).rev() {
+        let mut temp = w[i + 3];
+        if i.is_multiple_of(4) {
+            temp = sub_word(rot_word(temp)) ^ (u32::from(RCON[i / 4]) << 24);
+        }
+        w[i] = w[i + 4] ^ temp;
+    }
+
+    let mut key = [0u8; 16];
+    for (j, chunk) in key.chunks_exact_mut(4).enumerate() {
+        chunk.copy_from_slice(&be_from_u32(w[j]));
+    }
+    Aes128Key(key)
+}
+
 /// Encrypts a single 16-byte block with pre-expanded round keys.
-pub fn encrypt_block(block: &Block, round_keys: &RoundKeys) -> Block {
+///
+/// The number of rounds follows from the key schedule: 10, 12, or 14 for AES-128,
+/// AES-192, or AES-256.
+pub fn encrypt_block<const N: usize>(block: &Block, round_keys: &RoundKeys<N>) -> Block {
+    encrypt_block_rounds(block, round_keys, N - 1)
+}
+
+/// Encrypts a block with a reduced number of rounds.
+///
+/// Runs the initial AddRoundKey, `n_rounds - 1` full rounds, and a final round
+/// (no MixColumns) keyed with round key `n_rounds`. With `n_rounds == N - 1`
+/// this is [`encrypt_block`].
+///
+/// # Panics
+///
+/// Panics unless `1 <= n_rounds < N`.
+pub fn encrypt_block_rounds<const N: usize>(
+    block: &Block,
+    round_keys: &RoundKeys<N>,
+    n_rounds: usize,
+) -> Block {
+    assert!(
+        (1..N).contains(&n_rounds),
+        "round count {n_rounds} outside 1..={}",
+        N - 1
+    );
     let mut state = *block;
 
     add_round_key(&mut state, round_keys.get(0));
 
-    for round in 1..10 {
+    for round in 1..n_rounds {
         sub_bytes(&mut state);
         shift_rows(&mut state);
         mix_columns(&mut state);
@@ -79,17 +160,94 @@ pub fn encrypt_block(block: &Block, round_keys: &RoundKeys) -> Block {
 
     sub_bytes(&mut state);
     shift_rows(&mut state);
-    add_round_key(&mut state, round_keys.get(10));
+    add_round_key(&mut state, round_keys.get(n_rounds));
 
     state
 }
 
+/// Encrypts a block, recording the state after every AddRoundKey.
+///
+/// `trace[0]` is the whitened input and `trace[r]` the state at the end of
+/// round `r` (the "start of round `r + 1`" column of FIPS-197 Appendix B); the
+/// second element is the ciphertext (`trace[N - 1]`).
+pub fn encrypt_block_traced<const N: usize>(
+    block: &Block,
+    round_keys: &RoundKeys<N>,
+) -> ([Block; N], Block) {
+    let rounds = N - 1;
+    let mut trace = [[0u8; 16]; N];
+    let mut state = *block;
+
+    add_round_key(&mut state, round_keys.get(0));
+    trace[0] = state;
+    for (round, slot) in trace.iter_mut().enumerate().take(rounds).skip(1) {
+        sub_bytes(&mut state);
+        shift_rows(&mut state);
+        mix_columns(&mut state);
+        add_round_key(&mut state, round_keys.get(round));
+        *slot = state;
+    }
+    sub_bytes(&mut state);
+    shift_rows(&mut state);
+    add_round_key(&mut state, round_keys.get(rounds));
+    trace[rounds] = state;
+
+    (trace, state)
+}
+
+/// Decrypts a block, recording the state after every AddRoundKey.
+///
+/// `trace[i]` follows the `i`-th key addition in decryption order (round keys
+/// `N - 1` down to 0); the second element is the plaintext (`trace[N - 1]`).
+pub fn decrypt_block_traced<const N: usize>(
+    block: &Block,
+    round_keys: &RoundKeys<N>,
+) -> ([Block; N], Block) {
+    let rounds = N - 1;
+    let mut trace = [[0u8; 16]; N];
+    let mut state = *block;
+
+    add_round_key(&mut state, round_keys.get(rounds));
+    trace[0] = state;
+    for round in (1..rounds).rev() {
+        inv_shift_rows(&mut state);
+        inv_sub_bytes(&mut state);
+        add_round_key(&mut state, round_keys.get(round));
+        trace[rounds - round] = state;
+        inv_mix_columns(&mut state);
+    }
+    inv_shift_rows(&mut state);
+    inv_sub_bytes(&mut state);
+    add_round_key(&mut state, round_keys.get(0));
+    trace[rounds] = state;
+
+    (trace, state)
+}
+
 /// Decrypts a single 16-byte block with pre-expanded round keys.
-pub fn decrypt_block(block: &Block, round_keys: &RoundKeys) -> Block {
+pub fn decrypt_block<const N: usize>(block: &Block, round_keys: &RoundKeys<N>) -> Block {
+    decrypt_block_rounds(block, round_keys, N - 1)
+}
+
+/// Inverts [`encrypt_block_rounds`] with the same `n_rounds`.
+///
+/// # Panics
+///
+/// Panics unless `1 <= n_rounds < N`.
+pub fn decrypt_block_rounds<const N: usize>(
+    block: &Block,
+    round_keys: &RoundKeys<N>,
+    n_rounds: usize,
+) -> Block {
+    assert!(
+        (1..N).contains(&n_rounds),
+        "round count {n_rounds} outside 1..={}",
+        N - 1
+    );
     let mut state = *block;
 
-    add_round_key(&mut state, round_keys.get(10));
-    for round in (1..10).rev() {
+    add_round_key(&mut state, round_keys.get(n_rounds));
+    for round in (1..n_rounds).rev() {
         inv_shift_rows(&mut state);
         inv_sub_bytes(&mut state);
         add_round_key(&mut state, round_keys.get(round));
@@ -102,10 +260,40 @@ pub fn decrypt_block(block: &Block, round_keys: &RoundKeys) -> Block {
     state
 }
 
+/// Expands `key` into round keys for the equivalent inverse cipher (FIPS-197 §5.3.5).
+///
+/// Round keys 1 through 9 have InvMixColumns applied; the first and last are
+/// unchanged. Use with [`decrypt_block_eq`].
+pub fn expand_key_dec(key: &Aes128Key) -> RoundKeys {
+    expand_key(key).to_equivalent_inverse()
+}
+
+/// Decrypts a block with the equivalent inverse cipher.
+///
+/// Rounds run InvSubBytes, InvShiftRows, InvMixColumns, AddRoundKey, mirroring
+/// the encryption round order; `round_keys` must come from [`expand_key_dec`].
+pub fn decrypt_block_eq<const N: usize>(block: &Block, round_keys: &RoundKeys<N>) -> Block {
+    let rounds = N - 1;
+    let mut state = *block;
+
+    add_round_key(&mut state, round_keys.get(rounds));
+    for round in (1..rounds).rev() {
+        inv_sub_bytes(&mut state);
+        inv_shift_rows(&mut state);
+        inv_mix_columns(&mut state);
+        add_round_key(&mut state, round_keys.get(round));
+    }
+    inv_sub_bytes(&mut state);
+    inv_shift_rows(&mut state);
+    add_round_key(&mut state, round_keys.get(0));
+
+    state
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::key::Aes128Key;
+    use crate::key::{Aes128Key, Aes192Key, Aes256Key};
     use rand::RngCore;
 
     const NIST_KEY: [u8; 16] = [
@@ -121,6 +309,105 @@ mod tests {
         0x5a,
     ];
 
+    const FIPS_A2_KEY: [u8; 24] = [
+        0x8e, 0x73, 0xb0, 0xf7, 0xda, 0x0e, 0x64, 0x52, 0xc8, 0x10, 0xf3, 0x2b, 0x80, 0x90, 0x79,
+        0xe5, 0x62, 0xf8, 0xea, 0xd2, 0x52, 0x2c, 0x6b, 0x7b,
+    ];
+    const FIPS_A2_LAST_ROUND_KEY: [u8; 16] = [
+        0xe9, 0x8b, 0xa0, 0x6f, 0x44, 0x8c, 0x77, 0x3c, 0x8e, 0xcc, 0x72, 0x04, 0x01, 0x00, 0x22,
+        0x02,
+    ];
+    const FIPS_C2_KEY: [u8; 24] = [
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e,
+        0x0f, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
+    ];
+    const FIPS_C2_CIPHER: [u8; 16] = [
+        0xdd, 0xa9, 0x7c, 0xa4, 0x86, 0x4c, 0xdf, 0xe0, 0x6e, 0xaf, 0x70, 0xa0, 0xec, 0x0d, 0x71,
+        0x91,
+    ];
+
+    const FIPS_A3_KEY: [u8; 32] = [
+        0x60, 0x3d, 0xeb, 0x10, 0x15, 0xca, 0x71, 0xbe, 0x2b, 0x73, 0xae, 0xf0, 0x85, 0x7d, 0x77,
+        0x81, 0x1f, 0x35, 0x2c, 0x07, 0x3b, 0x61, 0x08, 0xd7, 0x2d, 0x98, 0x10, 0xa3, 0x09, 0x14,
+        0xdf, 0xf4,
+    ];
+    const FIPS_A3_LAST_ROUND_KEY: [u8; 16] = [
+        0xfe, 0x48, 0x90, 0xd1, 0xe6, 0x18, 0x8d, 0x0b, 0x04, 0x6d, 0xf3, 0x44, 0x70, 0x6c, 0x63,
+        0x1e,
+    ];
+    const FIPS_C3_KEY: [u8; 32] = [
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e,
+        0x0f, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d,
+        0x1e, 0x1f,
+    ];
+    const FIPS_C3_CIPHER: [u8; 16] = [
+        0x8e, 0xa2, 0xb7, 0xca, 0x51, 0x67, 0x45, 0xbf, 0xea, 0xfc, 0x49, 0x90, 0x4b, 0x49, 0x60,
+        0x89,
+    ];
+
+    #[test]
+    fn full_round_count_matches_encrypt_block() {
+        let round_keys = expand_key(&Aes128Key::from(NIST_KEY));
+        assert_eq!(
+            encrypt_block_rounds(&NIST_PLAIN, &round_keys, 10),
+            NIST_CIPHER
+        );
+        assert_eq!(
+            decrypt_block_rounds(&NIST_CIPHER, &round_keys, 10),
+            NIST_PLAIN
+        );
+    }
+
+    #[test]
+    fn one_round_of_zero_key_and_block_is_hand_checkable() {
+        // State after AddRoundKey(0) is all zeros; SubBytes gives 0x63 everywhere and
+        // ShiftRows leaves it unchanged. Round key 1 of the zero key is
+        // 62636363 repeated, so the output is 01000000 repeated.
+        let round_keys = expand_key(&Aes128Key::from([0u8; 16]));
+        let expected = [1, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0];
+        assert_eq!(encrypt_block_rounds(&[0u8; 16], &round_keys, 1), expected);
+        assert_eq!(decrypt_block_rounds(&expected, &round_keys, 1), [0u8; 16]);
+    }
+
+    #[test]
+    fn reduced_rounds_round_trip() {
+        let mut rng = rand::thread_rng();
+        let mut key_bytes = [0u8; 16];
+        rng.fill_bytes(&mut key_bytes);
+        let round_keys = expand_key(&Aes128Key::from(key_bytes));
+        for n_rounds in 1..=10 {
+            let mut block = [0u8; 16];
+            rng.fill_bytes(&mut block);
+            let ct = encrypt_block_rounds(&block, &round_keys, n_rounds);
+            assert_eq!(decrypt_block_rounds(&ct, &round_keys, n_rounds), block);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "outside 1..=10")]
+    fn zero_rounds_are_rejected() {
+        let round_keys = expand_key(&Aes128Key::from([0u8; 16]));
+        encrypt_block_rounds(&[0u8; 16], &round_keys, 0);
+    }
+
+    #[test]
+    fn recover_key_inverts_schedule_from_every_round() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..32 {
+            let mut key_bytes = [0u8; 16];
+            rng.fill_bytes(&mut key_bytes);
+            let key = Aes128Key::from(key_bytes);
+            let round_keys = expand_key(&key);
+            for round in 0..=10 {
+                assert_eq!(
+                    recover_key(round, round_keys.get(round)),
+                    key,
+                    "round {round}"
+                );
+            }
+        }
+    }
+
     #[test]
     fn encrypt_matches_nist_vector() {
         let key = Aes128Key::from(NIST_KEY);
@@ -137,6 +424,136 @@ mod tests {
         assert_eq!(pt, NIST_PLAIN);
     }
 
+    /// FIPS-197 Appendix B: key, input, and the state at the start of rounds 1–10.
+    const APPENDIX_B_KEY: [u8; 16] = [
+        0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f,
+        0x3c,
+    ];
+    const APPENDIX_B_INPUT: [u8; 16] = [
+        0x32, 0x43, 0xf6, 0xa8, 0x88, 0x5a, 0x30, 0x8d, 0x31, 0x31, 0x98, 0xa2, 0xe0, 0x37, 0x07,
+        0x34,
+    ];
+    const APPENDIX_B_ROUND_STARTS: [&str; 11] = [
+        "193de3bea0f4e22b9ac68d2ae9f84808",
+        "a49c7ff2689f352b6b5bea43026a5049",
+        "aa8f5f0361dde3ef82d24ad26832469a",
+        "486c4eee671d9d0d4de3b138d65f58e7",
+        "e0927fe8c86363c0d9b1355085b8be01",
+        "f1006f55c1924cef7cc88b325db5d50c",
+        "260e2e173d41b77de86472a9fdd28b25",
+        "5a4142b11949dc1fa3e019657a8c040c",
+        "ea835cf00445332d655d98ad8596b0c5",
+        "eb40f21e592e38848ba113e71bc342d2",
+        "3925841d02dc09fbdc118597196a0b32",
+    ];
+
+    fn block_from_hex(hex: &str) -> Block {
+        let mut block = [0u8; 16];
+        for (i, byte) in block.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).unwrap();
+        }
+        block
+    }
+
+    #[test]
+    fn encrypt_trace_matches_fips_appendix_b() {
+        let round_keys = expand_key(&Aes128Key::from(APPENDIX_B_KEY));
+        let (trace, output) = encrypt_block_traced(&APPENDIX_B_INPUT, &round_keys);
+        for (round, expected) in APPENDIX_B_ROUND_STARTS.iter().enumerate() {
+            assert_eq!(trace[round], block_from_hex(expected), "round {round}");
+        }
+        assert_eq!(output, encrypt_block(&APPENDIX_B_INPUT, &round_keys));
+    }
+
+    #[test]
+    fn decrypt_trace_mirrors_encrypt_trace() {
+        let round_keys = expand_key(&Aes128Key::from(APPENDIX_B_KEY));
+        let (enc_trace, ciphertext) = encrypt_block_traced(&APPENDIX_B_INPUT, &round_keys);
+        let (dec_trace, plaintext) = decrypt_block_traced(&ciphertext, &round_keys);
+        assert_eq!(plaintext, APPENDIX_B_INPUT);
+        assert_eq!(dec_trace[10], APPENDIX_B_INPUT);
+        // Removing round key r again during decryption yields the encryption
+        // state at the end of round r with that key added twice.
+        for round in 1..=10 {
+            let mut state = dec_trace[10 - round];
+            crate::block::xor_in_place(&mut state, round_keys.get(round));
+            assert_eq!(state, enc_trace[round], "round {round}");
+        }
+    }
+
+    /// FIPS-197 Appendix B state after MixColumns in rounds 1–9, and after
+    /// ShiftRows in round 10.
+    const APPENDIX_B_ROUND_OUTPUTS: [&str; 10] = [
+        "046681e5e0cb199a48f8d37a2806264c",
+        "584dcaf11b4b5aacdbe7caa81b6bb0e5",
+        "75ec0993200b633353c0cf7cbb25d0dc",
+        "0fd6daa9603138bf6fc0106b5eb31301",
+        "25d1a9adbd11d168b63a338e4c4cc0b0",
+        "4b868d6d2c4a8980339df4e837d218d8",
+        "1415b5bf461615ec274656d7342ad843",
+        "00512fd1b1c889ff54766dcdfa1b99ea",
+        "473794ed40d4e4a5a3703aa64c9f42bc",
+        "e9317db5cb322c723d2e895faf090794",
+    ];
+
+    #[test]
+    fn decrypt_trace_runs_fips_appendix_b_backwards() {
+        let round_keys = expand_key(&Aes128Key::from(APPENDIX_B_KEY));
+        let ciphertext = block_from_hex(APPENDIX_B_ROUND_STARTS[10]);
+        let (trace, plaintext) = decrypt_block_traced(&ciphertext, &round_keys);
+        // Stripping round key r + 1 and undoing SubBytes/ShiftRows lands on
+        // round r's output before its key was added.
+        for (step, expected) in APPENDIX_B_ROUND_OUTPUTS.iter().rev().enumerate() {
+            assert_eq!(trace[step], block_from_hex(expected), "step {step}");
+        }
+        assert_eq!(trace[10], APPENDIX_B_INPUT);
+        assert_eq!(plaintext, APPENDIX_B_INPUT);
+    }
+
+    #[test]
+    fn to_equivalent_inverse_keeps_outer_keys_and_feeds_decrypt_block_eq() {
+        let round_keys = expand_key(&Aes128Key::from(APPENDIX_B_KEY));
+        let dec_keys = round_keys.to_equivalent_inverse();
+        assert_eq!(dec_keys.get(0), round_keys.get(0));
+        assert_eq!(dec_keys.get(10), round_keys.get(10));
+        for round in 1..10 {
+            let mut expected = *round_keys.get(round);
+            inv_mix_columns(&mut expected);
+            assert_eq!(dec_keys.get(round), &expected, "round {round}");
+        }
+        assert_eq!(dec_keys, expand_key_dec(&Aes128Key::from(APPENDIX_B_KEY)));
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..32 {
+            let mut block = [0u8; 16];
+            rng.fill_bytes(&mut block);
+            assert_eq!(
+                decrypt_block_eq(&block, &dec_keys),
+                decrypt_block(&block, &round_keys)
+            );
+        }
+    }
+
+    #[test]
+    fn equivalent_inverse_matches_nist_vector() {
+        let dec_keys = expand_key_dec(&Aes128Key::from(NIST_KEY));
+        assert_eq!(decrypt_block_eq(&NIST_CIPHER, &dec_keys), NIST_PLAIN);
+    }
+
+    #[test]
+    fn equivalent_inverse_round_trip_random() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let mut key_bytes = [0u8; 16];
+            let mut block = [0u8; 16];
+            rng.fill_bytes(&mut key_bytes);
+            rng.fill_bytes(&mut block);
+            let key = Aes128Key::from(key_bytes);
+            let ct = encrypt_block(&block, &expand_key(&key));
+            assert_eq!(decrypt_block_eq(&ct, &expand_key_dec(&key)), block);
+        }
+    }
+
     #[test]
     fn encrypt_decrypt_round_trip_random() {
         let mut rng = rand::thread_rng();
@@ -152,4 +569,108 @@ mod tests {
             assert_eq!(pt, block);
         }
     }
+
+    #[test]
+    fn expand_key_words_match_fips_appendix_a1() {
+        let key = Aes128Key::from([
+            0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf,
+            0x4f, 0x3c,
+        ]);
+        let expected: [u32; 44] = [
+            0x2b7e1516, 0x28aed2a6, 0xabf71588, 0x09cf4f3c, 0xa0fafe17, 0x88542cb1, 0x23a33939,
+            0x2a6c7605, 0xf2c295f2, 0x7a96b943, 0x5935807a, 0x7359f67f, 0x3d80477d, 0x4716fe3e,
+            0x1e237e44, 0x6d7a883b, 0xef44a541, 0xa8525b7f, 0xb671253b, 0xdb0bad00, 0xd4d1c6f8,
+            0x7c839d87, 0xcaf2b8bc, 0x11f915bc, 0x6d88a37a, 0x110b3efd, 0xdbf98641, 0xca0093fd,
+            0x4e54f70e, 0x5f5fc9f3, 0x84a64fb2, 0x4ea6dc4f, 0xead27321, 0xb58dbad2, 0x312bf560,
+            0x7f8d292f, 0xac7766f3, 0x19fadc21, 0x28d12941, 0x575c006e, 0xd014f9a8, 0xc9ee2589,
+            0xe13f0cc8, 0xb6630ca6,
+        ];
+        assert_eq!(expand_key_words(&key), expected);
+
+        // Intermediate columns of the i = 4 row.
+        let after_rot = rot_word(expected[3]);
+        assert_eq!(after_rot, 0xcf4f3c09);
+        let after_sub = sub_word(after_rot);
+        assert_eq!(after_sub, 0x8a84eb01);
+        assert_eq!(after_sub ^ (u32::from(RCON[0]) << 24), 0x8b84eb01);
+    }
+
+    #[test]
+    fn round_keys_rebuilt_from_words_match_expand_key() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..32 {
+            let mut key_bytes = [0u8; 16];
+            rng.fill_bytes(&mut key_bytes);
+            let key = Aes128Key::from(key_bytes);
+            let words = expand_key_words(&key);
+            let rebuilt: [Block; 11] = core::array::from_fn(|round| {
+                let mut round_key = [0u8; 16];
+                for (chunk, word) in round_key.chunks_exact_mut(4).zip(&words[4 * round..]) {
+                    chunk.copy_from_slice(&word.to_be_bytes());
+                }
+                round_key
+            });
+            assert_eq!(RoundKeys(rebuilt), expand_key(&key));
+        }
+    }
+
+    #[test]
+    fn expand_key_192_matches_fips_appendix_a2() {
+        let round_keys = expand_key_192(&Aes192Key::from(FIPS_A2_KEY));
+        assert_eq!(round_keys.0.len(), 13);
+        assert_eq!(round_keys.get(0), &FIPS_A2_KEY[..16]);
+        assert_eq!(&round_keys.get(1)[..8], &FIPS_A2_KEY[16..]);
+        assert_eq!(round_keys.get(12), &FIPS_A2_LAST_ROUND_KEY);
+    }
+
+    #[test]
+    fn aes192_matches_fips_appendix_c2() {
+        let round_keys = expand_key_192(&Aes192Key::from(FIPS_C2_KEY));
+        assert_eq!(encrypt_block(&NIST_PLAIN, &round_keys), FIPS_C2_CIPHER);
+        assert_eq!(decrypt_block(&FIPS_C2_CIPHER, &round_keys), NIST_PLAIN);
+    }
+
+    #[test]
+    fn aes192_round_trip_random() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let mut key_bytes = [0u8; 24];
+            let mut block = [0u8; 16];
+            rng.fill_bytes(&mut key_bytes);
+            rng.fill_bytes(&mut block);
+            let rks = expand_key_192(&Aes192Key::from(key_bytes));
+            let ct = encrypt_block(&block, &rks);
+            assert_eq!(decrypt_block(&ct, &rks), block);
+        }
+    }
+
+    #[test]
+    fn expand_key_256_matches_fips_appendix_a3() {
+        let round_keys = expand_key_256(&Aes256Key::from(FIPS_A3_KEY));
+        assert_eq!(round_keys.0.len(), 15);
+        assert_eq!(round_keys.get(0), &FIPS_A3_KEY[..16]);
+        assert_eq!(round_keys.get(1), &FIPS_A3_KEY[16..]);
+        assert_eq!(round_keys.get(14), &FIPS_A3_LAST_ROUND_KEY);
+    }
+
+    #[test]
+    fn aes256_matches_fips_appendix_c3() {
+        let round_keys = expand_key_256(&Aes256Key::from(FIPS_C3_KEY));
+        assert_eq!(encrypt_block(&NIST_PLAIN, &round_keys), FIPS_C3_CIPHER);
+        assert_eq!(decrypt_block(&FIPS_C3_CIPHER, &round_keys), NIST_PLAIN);
+    }
+
+    #[test]
+    fn aes256_round_trip_random() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let mut key_bytes = [0u8; 32];
+            let mut block = [0u8; 16];
+            rng.fill_bytes(&mut key_bytes);
+            rng.fill_bytes(&mut block);
+            let rks = expand_key_256(&Aes256Key::from(key_bytes));
+            let ct = encrypt_block(&block, &rks);
+            assert_eq!(decrypt_block(&ct, &rks), block);
+        }
+    }
 }
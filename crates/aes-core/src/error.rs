@@ -0,0 +1,45 @@
+//! Error type shared by the fallible aes-core APIs.
+
+use core::fmt;
+
+/// Errors returned by slice-based and authenticated APIs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AesError {
+    /// Key material is not 16, 24, or 32 bytes as required by the key type.
+    InvalidKeyLength {
+        /// Length of the rejected key in bytes.
+        len: usize,
+    },
+    /// Buffer is not a (non-zero, where required) multiple of 16 bytes.
+    InvalidBlockLength {
+        /// Length of the rejected buffer in bytes.
+        len: usize,
+    },
+    /// The final block does not end in valid PKCS#7 padding.
+    InvalidPadding,
+    /// An authentication tag does not verify.
+    TagMismatch,
+    /// A key derivation iteration count is below the enforced minimum.
+    TooFewIterations {
+        /// The rejected iteration count.
+        iterations: u32,
+    },
+}
+
+impl fmt::Display for AesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidKeyLength { len } => write!(f, "invalid key length {len}"),
+            Self::InvalidBlockLength { len } => {
+                write!(f, "buffer length {len} is not a multiple of 16")
+            }
+            Self::InvalidPadding => write!(f, "malformed PKCS#7 padding"),
+            Self::TagMismatch => write!(f, "authentication tag mismatch"),
+            Self::TooFewIterations { iterations } => {
+                write!(f, "iteration count {iterations} is below the minimum")
+            }
+        }
+    }
+}
+
+impl std::error::Error for AesError {}
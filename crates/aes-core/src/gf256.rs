@@ -0,0 +1,107 @@
+//! Arithmetic in GF(2^8) with the AES polynomial x^8 + x^4 + x^3 + x + 1.
+//!
+//! Multiplication and inversion are branch-free in their operands; `pow`
+//! branches only on the (public) exponent.
+
+/// Multiplies by `x` (the AES `xtime` operation).
+#[inline]
+pub const fn xtime(a: u8) -> u8 {
+    (a << 1) ^ (0x1b & (a >> 7).wrapping_neg())
+}
+
+/// Multiplies two field elements.
+#[inline]
+pub const fn mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0u8;
+    let mut i = 0;
+    while i < 8 {
+        product ^= a & (b & 1).wrapping_neg();
+        a = xtime(a);
+        b >>= 1;
+        i += 1;
+    }
+    product
+}
+
+/// Raises `a` to the power `exp` by square-and-multiply.
+pub const fn pow(a: u8, mut exp: u32) -> u8 {
+    let mut result = 1u8;
+    let mut base = a;
+    while exp != 0 {
+        if exp & 1 == 1 {
+            result = mul(result, base);
+        }
+        base = mul(base, base);
+        exp >>= 1;
+    }
+    result
+}
+
+/// Returns the multiplicative inverse (`a^254`), mapping 0 to 0.
+///
+/// Uses a fixed addition chain, so the operation sequence is independent of `a`.
+pub const fn inv(a: u8) -> u8 {
+    let a2 = mul(a, a);
+    let a3 = mul(a2, a);
+    let a6 = mul(a3, a3);
+    let a12 = mul(a6, a6);
+    let a15 = mul(a12, a3);
+    let a30 = mul(a15, a15);
+    let a60 = mul(a30, a30);
+    let a120 = mul(a60, a60);
+    let a240 = mul(a120, a120);
+    let a252 = mul(a240, a12);
+    mul(a252, a2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sbox::{inv_sbox, sbox};
+
+    fn affine(x: u8) -> u8 {
+        x ^ x.rotate_left(1) ^ x.rotate_left(2) ^ x.rotate_left(3) ^ x.rotate_left(4) ^ 0x63
+    }
+
+    #[test]
+    fn inverse_is_exhaustively_correct() {
+        assert_eq!(inv(0), 0);
+        for a in 1..=255u8 {
+            assert_eq!(mul(a, inv(a)), 1, "a = {a:#04x}");
+            assert_eq!(inv(a), pow(a, 254));
+        }
+    }
+
+    #[test]
+    fn mul_matches_fips_examples_and_distributes() {
+        // FIPS-197 §4.2: {57} • {83} = {c1}, {57} • {13} = {fe}.
+        assert_eq!(mul(0x57, 0x83), 0xc1);
+        assert_eq!(mul(0x57, 0x13), 0xfe);
+        assert_eq!(xtime(0x57), 0xae);
+        assert_eq!(xtime(0x8e), 0x07);
+        for (a, b, c) in [(0x57, 0x83, 0x13), (0xff, 0x01, 0x80), (0x1b, 0xca, 0x35)] {
+            assert_eq!(mul(a, b ^ c), mul(a, b) ^ mul(a, c));
+            assert_eq!(mul(a, b), mul(b, a));
+            assert_eq!(mul(mul(a, b), c), mul(a, mul(b, c)));
+        }
+    }
+
+    #[test]
+    fn pow_small_exponents() {
+        for a in 0..=255u8 {
+            assert_eq!(pow(a, 0), 1);
+            assert_eq!(pow(a, 1), a);
+            assert_eq!(pow(a, 3), mul(a, mul(a, a)));
+        }
+        // The multiplicative group has order 255.
+        assert_eq!(pow(0x03, 255), 1);
+    }
+
+    #[test]
+    fn sbox_matches_algebraic_definition() {
+        for x in 0..=255u8 {
+            assert_eq!(sbox(x), affine(inv(x)), "x = {x:#04x}");
+            assert_eq!(inv_sbox(affine(inv(x))), x);
+        }
+    }
+}
@@ -0,0 +1,143 @@
+//! GHASH, the universal hash of GCM (NIST SP 800-38D §6.4).
+//!
+//! Blocks are elements of GF(2^128) in GCM's bit-reflected convention: the
+//! first bit of a block is the coefficient of `x^0`, and the field is reduced
+//! by `x^128 + x^7 + x^2 + x + 1`. Read as a big-endian `u128`, that puts
+//! `x^0` in the top bit, so multiplying by `x` is a right shift and the
+//! reduction constant is `0xe1 << 120`.
+
+use crate::block::Block;
+
+/// Multiplies two elements of GF(2^128) in GCM's bit-reflected representation.
+fn gf128_mul(x: u128, y: u128) -> u128 {
+    const R: u128 = 0xe1 << 120;
+    let mut z = 0u128;
+    let mut v = y;
+    for i in 0..128 {
+        if (x >> (127 - i)) & 1 == 1 {
+            z ^= v;
+        }
+        v = if v & 1 == 1 { (v >> 1) ^ R } else { v >> 1 };
+    }
+    z
+}
+
+/// Incremental GHASH keyed by the hash subkey `H`.
+#[derive(Clone, Debug)]
+pub struct Ghash {
+    h: u128,
+    acc: u128,
+}
+
+impl Ghash {
+    /// Starts a hash with subkey `h`; in GCM, `h` is the encryption of the zero block.
+    pub fn new(h: &Block) -> Self {
+        Self {
+            h: u128::from_be_bytes(*h),
+            acc: 0,
+        }
+    }
+
+    /// Absorbs one full block: `X_i = (X_{i-1} ⊕ block) · H`.
+    pub fn update(&mut self, block: &Block) {
+        self.acc = gf128_mul(self.acc ^ u128::from_be_bytes(*block), self.h);
+    }
+
+    /// Absorbs `data`, zero-padding its final partial block.
+    ///
+    /// Each call pads separately, matching how GCM hashes the AAD and the
+    /// ciphertext as two independently padded strings.
+    pub fn update_padded(&mut self, data: &[u8]) {
+        for chunk in data.chunks(16) {
+            let mut block = [0u8; 16];
+            block[..chunk.len()].copy_from_slice(chunk);
+            self.update(&block);
+        }
+    }
+
+    /// Returns the current intermediate value `X_i`.
+    pub fn value(&self) -> Block {
+        self.acc.to_be_bytes()
+    }
+
+    /// Absorbs the length block `[aad_bits]_64 || [ct_bits]_64` and returns the hash.
+    pub fn finalize(mut self, aad_bits: u64, ct_bits: u64) -> Block {
+        let lengths = (u128::from(aad_bits) << 64) | u128::from(ct_bits);
+        self.update(&lengths.to_be_bytes());
+        self.value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(hex: &str) -> Block {
+        core::array::from_fn(|i| u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).unwrap())
+    }
+
+    fn bytes(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn gcm_spec_test_case_2() {
+        let mut ghash = Ghash::new(&block("66e94bd4ef8a2c3b884cfa59ca342b2e"));
+        ghash.update(&block("0388dace60b6a392f328c2b971b2fe78"));
+        assert_eq!(ghash.value(), block("5e2ec746917062882c85b0685353deb7"));
+        assert_eq!(
+            ghash.finalize(0, 128),
+            block("f38cbb1ad69223dcc3457ae5b6b0f885")
+        );
+    }
+
+    #[test]
+    fn gcm_spec_test_case_4() {
+        let aad = bytes("feedfacedeadbeeffeedfacedeadbeefabaddad2");
+        let ciphertext = bytes(
+            "42831ec2217774244b7221b784d0d49ce3aa212f2c02a4e035c17e2329aca12e\
+             21d514b25466931c7d8f6a5aac84aa051ba30b396a0aac973d58e091",
+        );
+        let intermediates = [
+            "ed56aaf8a72d67049fdb9228edba1322",
+            "cd47221ccef0554ee4bb044c88150352",
+            "54f5e1b2b5a8f9525c23924751a3ca51",
+            "324f585c6ffc1359ab371565d6c45f93",
+            "ca7dd446af4aa70cc3c0cd5abba6aa1c",
+            "1590df9b2eb6768289e57d56274c8570",
+        ];
+
+        let mut ghash = Ghash::new(&block("b83b533708bf535d0aa6e52980d53b78"));
+        let mut seen = Vec::new();
+        for data in [&aad, &ciphertext] {
+            for chunk in data.chunks(16) {
+                ghash.update_padded(chunk);
+                seen.push(ghash.value());
+            }
+        }
+        let expected: Vec<Block> = intermediates.iter().map(|x| block(x)).collect();
+        assert_eq!(seen, expected);
+
+        let mut padded = Ghash::new(&block("b83b533708bf535d0aa6e52980d53b78"));
+        padded.update_padded(&aad);
+        padded.update_padded(&ciphertext);
+        assert_eq!(padded.value(), ghash.value());
+        assert_eq!(
+            ghash.finalize(160, 480),
+            block("698e57f70e6ecc7fd9463b7260a9ae5f")
+        );
+    }
+
+    #[test]
+    fn multiplication_by_one_is_identity() {
+        // The field's 1 is the block with only its first bit set.
+        let one = 1u128 << 127;
+        let x = u128::from_be_bytes(block("66e94bd4ef8a2c3b884cfa59ca342b2e"));
+        assert_eq!(gf128_mul(x, one), x);
+        assert_eq!(gf128_mul(one, x), x);
+        assert_eq!(gf128_mul(x, 0), 0);
+    }
+}
@@ -0,0 +1,155 @@
+//! Passphrase-based key derivation with PBKDF2-HMAC-SHA-256 (`kdf` feature).
+//!
+//! HMAC and PBKDF2 (RFC 8018 §5.2) are implemented here on top of the `sha2`
+//! hash; the derived key is the first 16 bytes of the PBKDF2 output.
+
+use sha2::{Digest, Sha256};
+
+use crate::error::AesError;
+use crate::key::Aes128Key;
+
+/// Smallest iteration count accepted by [`derive_key_pbkdf2`].
+pub const MIN_PBKDF2_ITERATIONS: u32 = 100_000;
+
+const SHA256_BLOCK_LEN: usize = 64;
+
+/// HMAC-SHA-256 state with the padded key blocks precomputed.
+struct HmacSha256 {
+    inner: Sha256,
+    outer: Sha256,
+}
+
+impl HmacSha256 {
+    fn new(key: &[u8]) -> Self {
+        let mut block = [0u8; SHA256_BLOCK_LEN];
+        if key.len() > SHA256_BLOCK_LEN {
+            block[..32].copy_from_slice(&Sha256::digest(key));
+        } else {
+            block[..key.len()].copy_from_slice(key);
+        }
+        let mut inner = Sha256::new();
+        let mut outer = Sha256::new();
+        inner.update(block.map(|b| b ^ 0x36));
+        outer.update(block.map(|b| b ^ 0x5c));
+        Self { inner, outer }
+    }
+
+    fn mac(&self, parts: &[&[u8]]) -> [u8; 32] {
+        let mut inner = self.inner.clone();
+        for part in parts {
+            inner.update(part);
+        }
+        let mut outer = self.outer.clone();
+        outer.update(inner.finalize());
+        outer.finalize().into()
+    }
+}
+
+/// PBKDF2-HMAC-SHA-256 filling `out`; no lower bound on `iterations`.
+fn pbkdf2_hmac_sha256(password: &[u8], salt: &[u8], iterations: u32, out: &mut [u8]) {
+    let prf = HmacSha256::new(password);
+    for (i, chunk) in out.chunks_mut(32).enumerate() {
+        let index = (i as u32 + 1).to_be_bytes();
+        let mut u = prf.mac(&[salt, &index]);
+        let mut t = u;
+        for _ in 1..iterations {
+            u = prf.mac(&[&u]);
+            for (t, u) in t.iter_mut().zip(u.iter()) {
+                *t ^= *u;
+            }
+        }
+        chunk.copy_from_slice(&t[..chunk.len()]);
+    }
+}
+
+/// Derives an AES-128 key from `passphrase` with PBKDF2-HMAC-SHA-256.
+///
+/// Rejects iteration counts below [`MIN_PBKDF2_ITERATIONS`].
+pub fn derive_key_pbkdf2(
+    passphrase: &str,
+    salt: &[u8],
+    iterations: u32,
+) -> Result<Aes128Key, AesError> {
+    if iterations < MIN_PBKDF2_ITERATIONS {
+        return Err(AesError::TooFewIterations { iterations });
+    }
+    let mut key = [0u8; 16];
+    pbkdf2_hmac_sha256(passphrase.as_bytes(), salt, iterations, &mut key);
+    Ok(Aes128Key(key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    /// RFC 6070 inputs; SHA-256 outputs computed with `hashlib.pbkdf2_hmac` (OpenSSL).
+    const VECTORS: [(&[u8], &[u8], u32, &str); 5] = [
+        (
+            b"password",
+            b"salt",
+            1,
+            "120fb6cffcf8b32c43e7225256c4f837a86548c92ccc35480805987cb70be17b",
+        ),
+        (
+            b"password",
+            b"salt",
+            2,
+            "ae4d0c95af6b46d32d0adff928f06dd02a303f8ef3c251dfd6e2d85a95474c43",
+        ),
+        (
+            b"password",
+            b"salt",
+            4096,
+            "c5e478d59288c841aa530db6845c4c8d962893a001ce4e11a4963873aa98134a",
+        ),
+        (
+            b"passwordPASSWORDpassword",
+            b"saltSALTsaltSALTsaltSALTsaltSALTsalt",
+            4096,
+            "348c89dbcbd32b2f32d814b8116e84cf2b17347ebc1800181c4e2a1fb8dd53e1c635518c7dac47e9",
+        ),
+        (
+            b"pass\0word",
+            b"sa\0lt",
+            4096,
+            "89b69d0516f829893c696226650a8687",
+        ),
+    ];
+
+    #[test]
+    fn rfc6070_inputs_with_sha256() {
+        for (password, salt, iterations, expected) in VECTORS {
+            let expected = hex(expected);
+            let mut out = vec![0u8; expected.len()];
+            pbkdf2_hmac_sha256(password, salt, iterations, &mut out);
+            assert_eq!(out, expected, "c = {iterations}");
+        }
+    }
+
+    #[test]
+    fn long_hmac_keys_are_hashed_first() {
+        let password = [0x61u8; 100];
+        let mut out = [0u8; 32];
+        pbkdf2_hmac_sha256(&password, b"salt", 1, &mut out);
+        let mut hashed = [0u8; 32];
+        pbkdf2_hmac_sha256(&Sha256::digest(password), b"salt", 1, &mut hashed);
+        assert_eq!(out, hashed);
+    }
+
+    #[test]
+    fn derive_key_enforces_minimum_iterations() {
+        let key = derive_key_pbkdf2("correct horse", b"wbaes-demo", MIN_PBKDF2_ITERATIONS).unwrap();
+        assert_eq!(key.0.to_vec(), hex("cfc4d42cc8a3ed70136b64c29a129dc0"));
+        assert_eq!(
+            derive_key_pbkdf2("correct horse", b"wbaes-demo", 4096),
+            Err(AesError::TooFewIterations { iterations: 4096 })
+        );
+    }
+}
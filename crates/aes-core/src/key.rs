@@ -1,6 +1,8 @@
-//! Key types for AES-128.
+//! Key types and expanded round keys for AES.
 
 use crate::block::Block;
+use crate::error::AesError;
+use crate::round::inv_mix_columns;
 
 /// AES-128 key wrapper.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -12,14 +14,364 @@ impl From<[u8; 16]> for Aes128Key {
     }
 }
 
-/// Expanded round keys for AES-128.
+impl TryFrom<&[u8]> for Aes128Key {
+    type Error = AesError;
+
+    fn try_from(value: &[u8]) -> Result<Self, AesError> {
+        value
+            .try_into()
+            .map(Self)
+            .map_err(|_| AesError::InvalidKeyLength { len: value.len() })
+    }
+}
+
+impl Aes128Key {
+    /// Parses 32 hex digits (either case) as a key; usable in `const` items.
+    ///
+    /// # Panics
+    ///
+    /// Panics, at compile time when evaluated in a const context, if `hex` is
+    /// not exactly 32 hex digits. See [`aes128_key!`](crate::aes128_key).
+    pub const fn from_hex(hex: &str) -> Self {
+        let digits = hex.as_bytes();
+        assert!(digits.len() == 32, "AES-128 key must be 32 hex digits");
+        let mut key = [0u8; 16];
+        let mut i = 0;
+        while i < 16 {
+            key[i] = (hex_digit(digits[2 * i]) << 4) | hex_digit(digits[2 * i + 1]);
+            i += 1;
+        }
+        Self(key)
+    }
+}
+
+const fn hex_digit(digit: u8) -> u8 {
+    match digit {
+        b'0'..=b'9' => digit - b'0',
+        b'a'..=b'f' => digit - b'a' + 10,
+        b'A'..=b'F' => digit - b'A' + 10,
+        _ => panic!("AES-128 key contains a non-hex character"),
+    }
+}
+
+/// Builds an [`Aes128Key`] from a hex literal, checked at compile time.
+///
+/// ```
+/// let key = aes_core::aes128_key!("000102030405060708090a0b0c0d0e0f");
+/// assert_eq!(key.0[15], 0x0f);
+/// ```
+///
+/// A wrong length or a non-hex character is a compile error:
+///
+/// ```compile_fail
+/// let key = aes_core::aes128_key!("000102030405060708090a0b0c0d0e");
+/// ```
+///
+/// ```compile_fail
+/// let key = aes_core::aes128_key!("000102030405060708090a0b0c0d0e0g");
+/// ```
+#[macro_export]
+macro_rules! aes128_key {
+    ($hex:expr) => {{
+        const KEY: $crate::Aes128Key = $crate::Aes128Key::from_hex($hex);
+        KEY
+    }};
+}
+
+/// AES-192 key wrapper.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Aes192Key(pub [u8; 24]);
+
+impl From<[u8; 24]> for Aes192Key {
+    fn from(value: [u8; 24]) -> Self {
+        Self(value)
+    }
+}
+
+impl TryFrom<&[u8]> for Aes192Key {
+    type Error = AesError;
+
+    fn try_from(value: &[u8]) -> Result<Self, AesError> {
+        value
+            .try_into()
+            .map(Self)
+            .map_err(|_| AesError::InvalidKeyLength { len: value.len() })
+    }
+}
+
+/// AES-256 key wrapper.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Aes256Key(pub [u8; 32]);
+
+impl From<[u8; 32]> for Aes256Key {
+    fn from(value: [u8; 32]) -> Self {
+        Self(value)
+    }
+}
+
+impl TryFrom<&[u8]> for Aes256Key {
+    type Error = AesError;
+
+    fn try_from(value: &[u8]) -> Result<Self, AesError> {
+        value
+            .try_into()
+            .map(Self)
+            .map_err(|_| AesError::InvalidKeyLength { len: value.len() })
+    }
+}
+
+/// Expanded round keys; `N` is the number of round keys (11, 13, or 15 for
+/// AES-128, AES-192, or AES-256).
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub struct RoundKeys(pub [Block; 11]);
+pub struct RoundKeys<const N: usize = 11>(pub [Block; N]);
 
-impl RoundKeys {
-    /// Returns the round key at the requested index (0..=10).
+/// Expanded round keys for AES-192.
+pub type RoundKeys192 = RoundKeys<13>;
+
+/// Expanded round keys for AES-256.
+pub type RoundKeys256 = RoundKeys<15>;
+
+impl<const N: usize> RoundKeys<N> {
+    /// Returns the round key at the requested index (`0..N`).
     #[inline]
     pub fn get(&self, round: usize) -> &Block {
         &self.0[round]
     }
+
+    /// Returns the round key at `round`, or `None` if it is out of range.
+    #[inline]
+    pub fn try_get(&self, round: usize) -> Option<&Block> {
+        self.0.get(round)
+    }
+
+    /// Returns the number of round keys (`N`).
+    #[inline]
+    pub const fn len(&self) -> usize {
+        N
+    }
+
+    /// Returns `true` only for the degenerate `N = 0` schedule.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        N == 0
+    }
+
+    /// Returns the final round key (round `N - 1`).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N == 0`.
+    #[inline]
+    pub fn last(&self) -> &Block {
+        &self.0[N - 1]
+    }
+
+    /// Iterates over the round keys in round order.
+    pub fn iter(&self) -> core::slice::Iter<'_, Block> {
+        self.0.iter()
+    }
+
+    /// Returns the round keys for the equivalent inverse cipher (FIPS-197 §5.3.5).
+    ///
+    /// The first and last round keys are copied unchanged; InvMixColumns is
+    /// applied to every key in between.
+    pub fn to_equivalent_inverse(&self) -> Self {
+        let mut round_keys = *self;
+        if N > 2 {
+            for round_key in round_keys.0[1..N - 1].iter_mut() {
+                inv_mix_columns(round_key);
+            }
+        }
+        round_keys
+    }
+}
+
+impl<'a, const N: usize> IntoIterator for &'a RoundKeys<N> {
+    type Item = &'a Block;
+    type IntoIter = core::slice::Iter<'a, Block>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+mod secret {
+    use core::fmt;
+    use core::ops::Deref;
+
+    use zeroize::{Zeroize, ZeroizeOnDrop};
+
+    use super::{Aes128Key, Aes192Key, Aes256Key, RoundKeys};
+
+    impl Zeroize for Aes128Key {
+        fn zeroize(&mut self) {
+            self.0.zeroize();
+        }
+    }
+
+    impl Zeroize for Aes192Key {
+        fn zeroize(&mut self) {
+            self.0.zeroize();
+        }
+    }
+
+    impl Zeroize for Aes256Key {
+        fn zeroize(&mut self) {
+            self.0.zeroize();
+        }
+    }
+
+    impl<const N: usize> Zeroize for RoundKeys<N> {
+        fn zeroize(&mut self) {
+            for block in self.0.iter_mut() {
+                block.zeroize();
+            }
+        }
+    }
+
+    /// Non-`Copy` AES-128 key that is wiped when dropped.
+    ///
+    /// Dereferences to [`Aes128Key`] so it can be passed wherever a key reference
+    /// is expected without making further copies.
+    #[derive(Clone, PartialEq, Eq)]
+    pub struct SecretAes128Key(Aes128Key);
+
+    impl From<Aes128Key> for SecretAes128Key {
+        fn from(key: Aes128Key) -> Self {
+            Self(key)
+        }
+    }
+
+    impl From<[u8; 16]> for SecretAes128Key {
+        fn from(bytes: [u8; 16]) -> Self {
+            Self(Aes128Key(bytes))
+        }
+    }
+
+    impl Deref for SecretAes128Key {
+        type Target = Aes128Key;
+
+        fn deref(&self) -> &Aes128Key {
+            &self.0
+        }
+    }
+
+    impl fmt::Debug for SecretAes128Key {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("SecretAes128Key(..)")
+        }
+    }
+
+    impl Zeroize for SecretAes128Key {
+        fn zeroize(&mut self) {
+            self.0.zeroize();
+        }
+    }
+
+    impl Drop for SecretAes128Key {
+        fn drop(&mut self) {
+            self.zeroize();
+        }
+    }
+
+    impl ZeroizeOnDrop for SecretAes128Key {}
+}
+
+#[cfg(feature = "zeroize")]
+pub use secret::SecretAes128Key;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_hex_parses_both_cases() {
+        const KEY: Aes128Key = Aes128Key::from_hex("000102030405060708090A0B0C0D0e0f");
+        assert_eq!(KEY, Aes128Key::from(core::array::from_fn(|i| i as u8)));
+        assert_eq!(
+            crate::aes128_key!("2b7e151628aed2a6abf7158809cf4f3c").0,
+            [
+                0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf,
+                0x4f, 0x3c,
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "32 hex digits")]
+    fn from_hex_rejects_short_input_at_runtime() {
+        let short = String::from("000102030405060708090a0b0c0d0e");
+        let _ = Aes128Key::from_hex(&short);
+    }
+
+    #[test]
+    fn keys_from_slices_check_length() {
+        let bytes = [7u8; 33];
+        assert_eq!(Aes128Key::try_from(&bytes[..16]), Ok(Aes128Key([7; 16])));
+        assert_eq!(Aes192Key::try_from(&bytes[..24]), Ok(Aes192Key([7; 24])));
+        assert_eq!(Aes256Key::try_from(&bytes[..32]), Ok(Aes256Key([7; 32])));
+        assert_eq!(
+            Aes128Key::try_from(&bytes[..15]),
+            Err(AesError::InvalidKeyLength { len: 15 })
+        );
+        assert_eq!(
+            Aes256Key::try_from(&bytes[..]),
+            Err(AesError::InvalidKeyLength { len: 33 })
+        );
+    }
+
+    #[test]
+    fn try_get_rejects_out_of_range_rounds() {
+        let round_keys = RoundKeys([[1u8; 16]; 11]);
+        assert_eq!(round_keys.try_get(10), Some(&[1u8; 16]));
+        assert_eq!(round_keys.try_get(11), None);
+    }
+
+    #[test]
+    fn iteration_matches_indexed_access() {
+        let round_keys = crate::cipher::expand_key(&Aes128Key::from([0x2bu8; 16]));
+        assert_eq!(round_keys.len(), 11);
+        assert!(!round_keys.is_empty());
+        assert_eq!(round_keys.iter().count(), 11);
+        for (round, round_key) in round_keys.iter().enumerate() {
+            assert_eq!(round_key, round_keys.get(round));
+        }
+        let mut seen = 0;
+        for round_key in &round_keys {
+            assert_eq!(Some(round_key), round_keys.try_get(seen));
+            seen += 1;
+        }
+        assert_eq!(seen, round_keys.len());
+        assert_eq!(round_keys.last(), round_keys.get(10));
+    }
+
+    #[cfg(feature = "zeroize")]
+    mod secret {
+        use super::super::*;
+        use zeroize::{Zeroize, ZeroizeOnDrop};
+
+        fn assert_zeroize_on_drop<T: ZeroizeOnDrop>() {}
+
+        #[test]
+        fn secret_wrappers_zeroize_on_drop() {
+            assert_zeroize_on_drop::<SecretAes128Key>();
+            assert_zeroize_on_drop::<crate::Aes128>();
+        }
+
+        #[test]
+        fn zeroize_clears_keys_and_round_keys() {
+            let mut key = Aes128Key::from([0xa5u8; 16]);
+            let mut round_keys = crate::expand_key(&key);
+            key.zeroize();
+            round_keys.zeroize();
+            assert_eq!(key.0, [0u8; 16]);
+            assert!(round_keys.0.iter().all(|block| *block == [0u8; 16]));
+
+            let mut secret = SecretAes128Key::from([0x3cu8; 16]);
+            secret.zeroize();
+            assert_eq!(secret.0, [0u8; 16]);
+            assert_eq!(format!("{secret:?}"), "SecretAes128Key(..)");
+        }
+    }
 }
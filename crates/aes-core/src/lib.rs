@@ -1,23 +1,100 @@
-//! Reference AES-128 implementation used by the white-box generator and runtime.
+//! Reference AES implementation used by the white-box generator and runtime.
 //!
 //! This crate intentionally mirrors the FIPS-197 specification and provides:
-//! - Key schedule for AES-128.
-//! - Single-block encryption and decryption.
-//! - Public types shared across the workspace.
+//! - Key schedules for AES-128, AES-192, and AES-256 (with a word-level view
+//!   for AES-128), and AES-128 key recovery from any single round key.
+//! - Single-block encryption and decryption (including the equivalent inverse
+//!   cipher, per-round traces, and reduced-round variants), plus the `Aes128`
+//!   cipher object.
+//! - Bitsliced two-block encryption in a fixsliced round structure.
+//! - Modes of operation (`modes`): ECB (explicit opt-in), CBC with PKCS#7
+//!   padding, CFB-128, OFB, GCM, CCM, SIV, CMAC, raw and length-prepended
+//!   CBC-MAC, and an encrypt-then-MAC `seal`/`open` container.
+//! - The linear layer as data (`linear`): MixColumns matrices and ShiftRows
+//!   permutations.
+//! - GF(2^8) field arithmetic (`gf256`) and the standalone GCM universal hash
+//!   (`ghash`).
+//! - A FIPS-style known-answer `self_test` for start-up health checks, and
+//!   `verify_pairs` for checking two-block ciphers against reference AES.
+//! - Public types shared across the workspace (`Block`, `DoubleBlock` and its
+//!   helpers), including the row/column `State`
+//!   view of a block and the `AesError` type returned by fallible APIs.
+//! - Optional key-schedule diffusion measurements (`analysis` feature).
+//! - Optional PBKDF2-HMAC-SHA-256 passphrase key derivation (`kdf` feature).
+//! - Optional PKCS#8 DER/PEM key wrapping (`pem` feature).
+//! - Optional table-free S-box computed from GF(2^8) inversion (`ct-sbox` feature).
+//! - Optional T-table encryption path (`ttable` feature).
+//! - Optional parallel multi-block encryption (`rayon` feature).
+//! - Optional `serde` support for key types (`serde` feature).
+//! - Optional key zeroization and the `SecretAes128Key` wrapper (`zeroize` feature).
+//! - Optional RustCrypto `cipher` trait impls for `Aes128` (`rustcrypto` feature).
 //!
 //! The implementation aims for clarity and testability rather than constant-time
-//! guarantees; it should not be treated as side-channel hardened.
+//! guarantees; it should not be treated as side-channel hardened. The `timing`
+//! module provides a dudect-style harness to measure how far from constant
+//! time a given cipher is.
 
 #![forbid(unsafe_code)]
 #![deny(missing_docs)]
 
+mod aes128;
+#[cfg(feature = "analysis")]
+pub mod analysis;
+mod bitslice;
 mod block;
 mod cipher;
+mod error;
+#[cfg(feature = "fuzzing")]
+#[doc(hidden)]
+pub mod fuzzing;
+pub mod gf256;
+pub mod ghash;
+#[cfg(feature = "kdf")]
+mod kdf;
 mod key;
+pub mod linear;
+pub mod modes;
+#[cfg(feature = "rayon")]
+mod par;
+#[cfg(feature = "pem")]
+mod pem;
 pub mod round;
+#[cfg(feature = "rustcrypto")]
+mod rustcrypto;
 mod sbox;
+mod selftest;
+#[cfg(feature = "serde")]
+mod serde_impls;
+mod state;
+pub mod timing;
+#[cfg(feature = "ttable")]
+mod ttable;
+mod verify;
 
-pub use crate::block::Block;
-pub use crate::cipher::{decrypt_block, encrypt_block, expand_key};
-pub use crate::key::{Aes128Key, RoundKeys};
-pub use crate::sbox::sbox;
+pub use crate::aes128::Aes128;
+pub use crate::bitslice::encrypt_pair_bitsliced;
+pub use crate::block::{
+    ct_eq, double_block_hex, join_double, split_double, xor32_in_place, xor_in_place, Block,
+    DoubleBlock,
+};
+pub use crate::cipher::{
+    decrypt_block, decrypt_block_eq, decrypt_block_rounds, decrypt_block_traced, encrypt_block,
+    encrypt_block_rounds, encrypt_block_traced, expand_key, expand_key_192, expand_key_256,
+    expand_key_dec, expand_key_words, recover_key, rot_word, sub_word, RCON,
+};
+pub use crate::error::AesError;
+#[cfg(feature = "kdf")]
+pub use crate::kdf::{derive_key_pbkdf2, MIN_PBKDF2_ITERATIONS};
+#[cfg(feature = "zeroize")]
+pub use crate::key::SecretAes128Key;
+pub use crate::key::{Aes128Key, Aes192Key, Aes256Key, RoundKeys, RoundKeys192, RoundKeys256};
+#[cfg(feature = "rayon")]
+pub use crate::par::encrypt_blocks_par;
+#[cfg(feature = "pem")]
+pub use crate::pem::KeyParseError;
+pub use crate::sbox::{inv_sbox, sbox};
+pub use crate::selftest::{self_test, SelfTestError};
+pub use crate::state::State;
+#[cfg(feature = "ttable")]
+pub use crate::ttable::encrypt_block_ttable;
+pub use crate::verify::{encrypt_pair, verify_pairs, MismatchReport};
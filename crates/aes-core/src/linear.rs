@@ -0,0 +1,122 @@
+//! The AES linear layer as data: MixColumns matrices and ShiftRows permutations.
+//!
+//! The functions in [`round`](crate::round) are the executable form; these
+//! constants describe the same maps for code that builds its own matrices,
+//! such as white-box generators.
+
+use crate::gf256::mul as gmul;
+
+/// MixColumns as a 4×4 matrix over GF(2^8): `column' = M · column` (FIPS-197 §5.1.3).
+pub const MIX_COLUMNS_MATRIX: [[u8; 4]; 4] = [
+    [0x02, 0x03, 0x01, 0x01],
+    [0x01, 0x02, 0x03, 0x01],
+    [0x01, 0x01, 0x02, 0x03],
+    [0x03, 0x01, 0x01, 0x02],
+];
+
+/// InvMixColumns as a 4×4 matrix over GF(2^8) (FIPS-197 §5.3.3).
+pub const INV_MIX_COLUMNS_MATRIX: [[u8; 4]; 4] = [
+    [0x0e, 0x0b, 0x0d, 0x09],
+    [0x09, 0x0e, 0x0b, 0x0d],
+    [0x0d, 0x09, 0x0e, 0x0b],
+    [0x0b, 0x0d, 0x09, 0x0e],
+];
+
+/// Multiplies a state column by a 4×4 matrix over GF(2^8).
+pub const fn mul_column(matrix: &[[u8; 4]; 4], column: &[u8; 4]) -> [u8; 4] {
+    let mut out = [0u8; 4];
+    let mut row = 0;
+    while row < 4 {
+        let mut k = 0;
+        while k < 4 {
+            out[row] ^= gmul(matrix[row][k], column[k]);
+            k += 1;
+        }
+        row += 1;
+    }
+    out
+}
+
+/// ShiftRows as a gather: `shifted[i] = state[perm[i]]` over the column-major block layout.
+pub const fn shift_rows_permutation() -> [usize; 16] {
+    let mut perm = [0usize; 16];
+    let mut i = 0;
+    while i < 16 {
+        let (row, col) = (i % 4, i / 4);
+        perm[i] = row + 4 * ((col + row) % 4);
+        i += 1;
+    }
+    perm
+}
+
+/// InvShiftRows as a gather: `unshifted[i] = state[perm[i]]`.
+pub const fn inv_shift_rows_permutation() -> [usize; 16] {
+    let mut perm = [0usize; 16];
+    let mut i = 0;
+    while i < 16 {
+        let (row, col) = (i % 4, i / 4);
+        perm[i] = row + 4 * ((col + 4 - row) % 4);
+        i += 1;
+    }
+    perm
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::Block;
+    use crate::round::{inv_mix_columns, inv_shift_rows, mix_columns, shift_rows};
+    use rand::{Rng, SeedableRng};
+
+    fn gather(state: &Block, perm: &[usize; 16]) -> Block {
+        core::array::from_fn(|i| state[perm[i]])
+    }
+
+    #[test]
+    fn permutations_reproduce_shift_rows() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(296);
+        for _ in 0..32 {
+            let state: Block = rng.gen();
+            let mut expected = state;
+            shift_rows(&mut expected);
+            assert_eq!(gather(&state, &shift_rows_permutation()), expected);
+            inv_shift_rows(&mut expected);
+            assert_eq!(expected, state);
+            let mut expected = state;
+            inv_shift_rows(&mut expected);
+            assert_eq!(gather(&state, &inv_shift_rows_permutation()), expected);
+        }
+    }
+
+    #[test]
+    fn matrices_reproduce_mix_columns() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(2960);
+        for _ in 0..32 {
+            let state: Block = rng.gen();
+            let by_matrix = |matrix: &[[u8; 4]; 4]| -> Block {
+                let mut out = [0u8; 16];
+                for (src, dst) in state.chunks_exact(4).zip(out.chunks_exact_mut(4)) {
+                    dst.copy_from_slice(&mul_column(matrix, src.try_into().unwrap()));
+                }
+                out
+            };
+            let mut expected = state;
+            mix_columns(&mut expected);
+            assert_eq!(by_matrix(&MIX_COLUMNS_MATRIX), expected);
+            let mut expected = state;
+            inv_mix_columns(&mut expected);
+            assert_eq!(by_matrix(&INV_MIX_COLUMNS_MATRIX), expected);
+        }
+    }
+
+    #[test]
+    fn matrices_are_mutual_inverses() {
+        for i in 0..4 {
+            let column = [0, 1, 2, 3].map(|k| MIX_COLUMNS_MATRIX[k][i]);
+            let mut unit = [0u8; 4];
+            unit[i] = 1;
+            // Column i of M⁻¹·M is M⁻¹ applied to column i of M.
+            assert_eq!(mul_column(&INV_MIX_COLUMNS_MATRIX, &column), unit);
+        }
+    }
+}
@@ -0,0 +1,129 @@
+//! CBC mode with PKCS#7 padding (NIST SP 800-38A §6.2).
+
+use crate::block::{xor_in_place, Block};
+use crate::cipher::{decrypt_block, encrypt_block, expand_key};
+use crate::error::AesError;
+use crate::key::Aes128Key;
+
+/// Encrypts `plaintext` in CBC mode, appending PKCS#7 padding (always 1..=16 bytes).
+pub fn cbc_encrypt(key: &Aes128Key, iv: &Block, plaintext: &[u8]) -> Vec<u8> {
+    let round_keys = expand_key(key);
+    let pad = 16 - plaintext.len() % 16;
+    let mut data = Vec::with_capacity(plaintext.len() + pad);
+    data.extend_from_slice(plaintext);
+    data.resize(plaintext.len() + pad, pad as u8);
+
+    let mut chain = *iv;
+    for chunk in data.chunks_exact_mut(16) {
+        let mut block: Block = chunk.try_into().expect("chunk length is 16");
+        xor_in_place(&mut block, &chain);
+        chain = encrypt_block(&block, &round_keys);
+        chunk.copy_from_slice(&chain);
+    }
+    data
+}
+
+/// Decrypts CBC `ciphertext` and strips its PKCS#7 padding.
+pub fn cbc_decrypt(key: &Aes128Key, iv: &Block, ciphertext: &[u8]) -> Result<Vec<u8>, AesError> {
+    if ciphertext.is_empty() || !ciphertext.len().is_multiple_of(16) {
+        return Err(AesError::InvalidBlockLength {
+            len: ciphertext.len(),
+        });
+    }
+    let round_keys = expand_key(key);
+    let mut data = Vec::with_capacity(ciphertext.len());
+    let mut chain = *iv;
+    for chunk in ciphertext.chunks_exact(16) {
+        let block: Block = chunk.try_into().expect("chunk length is 16");
+        let mut plain = decrypt_block(&block, &round_keys);
+        xor_in_place(&mut plain, &chain);
+        data.extend_from_slice(&plain);
+        chain = block;
+    }
+
+    let pad = *data.last().expect("ciphertext is non-empty") as usize;
+    if pad == 0 || pad > 16 || data[data.len() - pad..].iter().any(|&b| b as usize != pad) {
+        return Err(AesError::InvalidPadding);
+    }
+    data.truncate(data.len() - pad);
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modes::test_vectors::{
+        IV as SP800_38A_IV, KEY as SP800_38A_KEY, PLAIN as SP800_38A_PLAIN,
+    };
+    use rand::RngCore;
+
+    /// F.2.1 CBC-AES128.Encrypt ciphertext followed by the encrypted full padding block.
+    const SP800_38A_F21_CIPHER: [u8; 80] = [
+        0x76, 0x49, 0xab, 0xac, 0x81, 0x19, 0xb2, 0x46, 0xce, 0xe9, 0x8e, 0x9b, 0x12, 0xe9, 0x19,
+        0x7d, 0x50, 0x86, 0xcb, 0x9b, 0x50, 0x72, 0x19, 0xee, 0x95, 0xdb, 0x11, 0x3a, 0x91, 0x76,
+        0x78, 0xb2, 0x73, 0xbe, 0xd6, 0xb8, 0xe3, 0xc1, 0x74, 0x3b, 0x71, 0x16, 0xe6, 0x9e, 0x22,
+        0x22, 0x95, 0x16, 0x3f, 0xf1, 0xca, 0xa1, 0x68, 0x1f, 0xac, 0x09, 0x12, 0x0e, 0xca, 0x30,
+        0x75, 0x86, 0xe1, 0xa7, 0x8c, 0xb8, 0x28, 0x07, 0x23, 0x0e, 0x13, 0x21, 0xd3, 0xfa, 0xe0,
+        0x0d, 0x18, 0xcc, 0x20, 0x12,
+    ];
+
+    #[test]
+    fn cbc_encrypt_matches_sp800_38a_f21() {
+        let key = Aes128Key::from(SP800_38A_KEY);
+        let ct = cbc_encrypt(&key, &SP800_38A_IV, &SP800_38A_PLAIN);
+        assert_eq!(ct, SP800_38A_F21_CIPHER);
+    }
+
+    #[test]
+    fn cbc_decrypt_matches_sp800_38a_f22() {
+        let key = Aes128Key::from(SP800_38A_KEY);
+        let pt = cbc_decrypt(&key, &SP800_38A_IV, &SP800_38A_F21_CIPHER).expect("valid padding");
+        assert_eq!(pt, SP800_38A_PLAIN);
+    }
+
+    #[test]
+    fn cbc_round_trip_all_tail_lengths() {
+        let mut rng = rand::thread_rng();
+        let mut key_bytes = [0u8; 16];
+        let mut iv = [0u8; 16];
+        rng.fill_bytes(&mut key_bytes);
+        rng.fill_bytes(&mut iv);
+        let key = Aes128Key::from(key_bytes);
+        for len in 0..48 {
+            let mut plaintext = vec![0u8; len];
+            rng.fill_bytes(&mut plaintext);
+            let ct = cbc_encrypt(&key, &iv, &plaintext);
+            assert_eq!(ct.len(), (len / 16 + 1) * 16);
+            assert_eq!(cbc_decrypt(&key, &iv, &ct).unwrap(), plaintext);
+        }
+    }
+
+    #[test]
+    fn cbc_decrypt_rejects_bad_length() {
+        let key = Aes128Key::from(SP800_38A_KEY);
+        assert_eq!(
+            cbc_decrypt(&key, &SP800_38A_IV, &[]),
+            Err(AesError::InvalidBlockLength { len: 0 })
+        );
+        assert_eq!(
+            cbc_decrypt(&key, &SP800_38A_IV, &SP800_38A_F21_CIPHER[..20]),
+            Err(AesError::InvalidBlockLength { len: 20 })
+        );
+    }
+
+    #[test]
+    fn cbc_decrypt_rejects_malformed_padding() {
+        let key = Aes128Key::from(SP800_38A_KEY);
+        // Without the padding block the last plaintext byte is 0x10 preceded by non-padding.
+        assert_eq!(
+            cbc_decrypt(&key, &SP800_38A_IV, &SP800_38A_F21_CIPHER[..64]),
+            Err(AesError::InvalidPadding)
+        );
+        let mut tampered = SP800_38A_F21_CIPHER;
+        tampered[70] ^= 0x01;
+        assert_eq!(
+            cbc_decrypt(&key, &SP800_38A_IV, &tampered),
+            Err(AesError::InvalidPadding)
+        );
+    }
+}
@@ -0,0 +1,145 @@
+//! Raw CBC-MAC and its length-prepended variant.
+//!
+//! Raw CBC-MAC is only secure for messages of one fixed length. Given the tag
+//! `t` of a one-block message `m`, the two-block message `m || (m ⊕ t)` has the
+//! same tag `t`, because the second chaining input is `t ⊕ m ⊕ t = m`.
+//! Prepending the message length as the first block binds the tag to the
+//! length and defeats this extension. Both variants are provided for
+//! comparison; use [`cmac`](super::cmac) for real messages.
+
+use crate::block::{ct_eq, xor_in_place, Block};
+use crate::cipher::{encrypt_block, expand_key};
+use crate::error::AesError;
+use crate::key::{Aes128Key, RoundKeys};
+
+/// CBC-encrypts the concatenated slices from a zero IV and returns the last block; each slice must be block-aligned.
+fn chain(round_keys: &RoundKeys, blocks: &[&[u8]]) -> Block {
+    let mut state = [0u8; 16];
+    for data in blocks {
+        for chunk in data.chunks_exact(16) {
+            let block: &Block = chunk.try_into().expect("chunk length is 16");
+            xor_in_place(&mut state, block);
+            state = encrypt_block(&state, round_keys);
+        }
+    }
+    state
+}
+
+/// Computes raw CBC-MAC over a non-empty, block-aligned `msg` (zero IV, no padding).
+pub fn cbc_mac(key: &Aes128Key, msg: &[u8]) -> Result<Block, AesError> {
+    if msg.is_empty() || !msg.len().is_multiple_of(16) {
+        return Err(AesError::InvalidBlockLength { len: msg.len() });
+    }
+    Ok(chain(&expand_key(key), &[msg]))
+}
+
+/// Checks `tag` against [`cbc_mac`] of `msg`.
+pub fn cbc_mac_verify(key: &Aes128Key, msg: &[u8], tag: &Block) -> Result<(), AesError> {
+    if ct_eq(&cbc_mac(key, msg)?, tag) {
+        Ok(())
+    } else {
+        Err(AesError::TagMismatch)
+    }
+}
+
+/// Computes CBC-MAC over `len(msg) || msg || 0*`, with the byte length as a 128-bit big-endian block.
+///
+/// Zero padding is unambiguous because the length is authenticated, so `msg` may have any length.
+pub fn cbc_mac_length_prepended(key: &Aes128Key, msg: &[u8]) -> Block {
+    let length = (msg.len() as u128).to_be_bytes();
+    let full = msg.len() - msg.len() % 16;
+    let mut tail = [0u8; 16];
+    tail[..msg.len() - full].copy_from_slice(&msg[full..]);
+    let tail_len = if full == msg.len() { 0 } else { 16 };
+    chain(
+        &expand_key(key),
+        &[&length, &msg[..full], &tail[..tail_len]],
+    )
+}
+
+/// Checks `tag` against [`cbc_mac_length_prepended`] of `msg`.
+pub fn cbc_mac_length_prepended_verify(
+    key: &Aes128Key,
+    msg: &[u8],
+    tag: &Block,
+) -> Result<(), AesError> {
+    if ct_eq(&cbc_mac_length_prepended(key, msg), tag) {
+        Ok(())
+    } else {
+        Err(AesError::TagMismatch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modes::test_vectors::{IV, KEY, PLAIN};
+
+    /// SP 800-38A F.2.1 final CBC ciphertext block: CBC-MAC of `PLAIN` with IV folded
+    /// into the first block.
+    const F21_LAST_BLOCK: Block = [
+        0x3f, 0xf1, 0xca, 0xa1, 0x68, 0x1f, 0xac, 0x09, 0x12, 0x0e, 0xca, 0x30, 0x75, 0x86, 0xe1,
+        0xa7,
+    ];
+
+    #[test]
+    fn raw_cbc_mac_equals_last_cbc_ciphertext_block() {
+        let mut msg = PLAIN;
+        xor_in_place((&mut msg[..16]).try_into().unwrap(), &IV);
+        let tag = cbc_mac(&Aes128Key::from(KEY), &msg).unwrap();
+        assert_eq!(tag, F21_LAST_BLOCK);
+    }
+
+    #[test]
+    fn raw_cbc_mac_rejects_unaligned_messages() {
+        let key = Aes128Key::from(KEY);
+        assert_eq!(
+            cbc_mac(&key, &PLAIN[..17]),
+            Err(AesError::InvalidBlockLength { len: 17 })
+        );
+        assert_eq!(
+            cbc_mac(&key, &[]),
+            Err(AesError::InvalidBlockLength { len: 0 })
+        );
+    }
+
+    #[test]
+    fn extension_forgery_breaks_raw_but_not_length_prepended() {
+        let key = Aes128Key::from(KEY);
+        let m: Block = PLAIN[..16].try_into().unwrap();
+
+        // The attacker sees one valid (message, tag) pair ...
+        let t = cbc_mac(&key, &m).unwrap();
+        // ... and outputs m || (m ⊕ t) with the same tag, without the key.
+        let mut second = m;
+        xor_in_place(&mut second, &t);
+        let mut forged = m.to_vec();
+        forged.extend_from_slice(&second);
+        assert_eq!(cbc_mac_verify(&key, &forged, &t), Ok(()));
+
+        // The same trick against the length-prepended variant fails.
+        let t = cbc_mac_length_prepended(&key, &m);
+        let mut second = m;
+        xor_in_place(&mut second, &t);
+        let mut forged = m.to_vec();
+        forged.extend_from_slice(&second);
+        assert_eq!(
+            cbc_mac_length_prepended_verify(&key, &forged, &t),
+            Err(AesError::TagMismatch)
+        );
+        assert_eq!(cbc_mac_length_prepended_verify(&key, &m, &t), Ok(()));
+    }
+
+    #[test]
+    fn length_prepended_binds_padding_and_length() {
+        let key = Aes128Key::from(KEY);
+        let short = cbc_mac_length_prepended(&key, &PLAIN[..15]);
+        let mut padded = PLAIN[..16].to_vec();
+        padded[15] = 0;
+        assert_ne!(short, cbc_mac_length_prepended(&key, &padded));
+        assert_ne!(
+            cbc_mac_length_prepended(&key, &[]),
+            cbc_mac_length_prepended(&key, &[0u8; 16])
+        );
+    }
+}
@@ -0,0 +1,291 @@
+//! Counter with CBC-MAC (NIST SP 800-38C, RFC 3610).
+
+use core::fmt;
+
+use crate::block::{ct_eq, xor_in_place, Block};
+use crate::cipher::{encrypt_block, expand_key};
+use crate::key::{Aes128Key, RoundKeys};
+use crate::modes::ctr;
+
+/// Errors returned by [`ccm_encrypt`] and [`ccm_decrypt`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CcmError {
+    /// Tag length is not an even number in 4..=16.
+    InvalidTagLength(usize),
+    /// Nonce length is outside 7..=13 bytes.
+    InvalidNonceLength(usize),
+    /// Payload does not fit in the length field left by the nonce.
+    PayloadTooLong,
+    /// Ciphertext is shorter than the tag.
+    TruncatedCiphertext,
+    /// The authentication tag does not verify.
+    AuthFailed,
+}
+
+impl fmt::Display for CcmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidTagLength(len) => {
+                write!(f, "CCM tag length {len} must be even and in 4..=16")
+            }
+            Self::InvalidNonceLength(len) => write!(f, "CCM nonce length {len} must be in 7..=13"),
+            Self::PayloadTooLong => write!(f, "payload too long for the CCM length field"),
+            Self::TruncatedCiphertext => write!(f, "ciphertext is shorter than the tag"),
+            Self::AuthFailed => write!(f, "authentication tag mismatch"),
+        }
+    }
+}
+
+impl std::error::Error for CcmError {}
+
+/// Checks tag and nonce lengths and that the payload length fits in `15 - nonce.len()` bytes.
+fn check_params(nonce: &[u8], tag_len: usize, payload_len: usize) -> Result<(), CcmError> {
+    if !(4..=16).contains(&tag_len) || !tag_len.is_multiple_of(2) {
+        return Err(CcmError::InvalidTagLength(tag_len));
+    }
+    if !(7..=13).contains(&nonce.len()) {
+        return Err(CcmError::InvalidNonceLength(nonce.len()));
+    }
+    let q = 15 - nonce.len();
+    if q < 8 && (payload_len as u64) >> (8 * q) != 0 {
+        return Err(CcmError::PayloadTooLong);
+    }
+    Ok(())
+}
+
+/// Builds a block `flags || nonce || value`, with `value` big-endian in the last `q` bytes.
+fn format_block(flags: u8, nonce: &[u8], value: u64) -> Block {
+    let mut block = [0u8; 16];
+    block[0] = flags;
+    block[1..1 + nonce.len()].copy_from_slice(nonce);
+    let q = 15 - nonce.len();
+    block[16 - q..].copy_from_slice(&value.to_be_bytes()[8 - q..]);
+    block
+}
+
+/// Encodes the associated-data length prefix (SP 800-38C §A.2.2).
+fn encode_aad_len(len: usize) -> Vec<u8> {
+    let len = len as u64;
+    if len < 0xff00 {
+        (len as u16).to_be_bytes().to_vec()
+    } else if len <= u64::from(u32::MAX) {
+        let mut out = vec![0xff, 0xfe];
+        out.extend_from_slice(&(len as u32).to_be_bytes());
+        out
+    } else {
+        let mut out = vec![0xff, 0xff];
+        out.extend_from_slice(&len.to_be_bytes());
+        out
+    }
+}
+
+/// Absorbs `data` into the CBC-MAC chain, zero-padding the final partial block.
+fn cbc_mac_update(round_keys: &RoundKeys, chain: &mut Block, data: &[u8]) {
+    for chunk in data.chunks(16) {
+        for (c, d) in chain.iter_mut().zip(chunk) {
+            *c ^= *d;
+        }
+        *chain = encrypt_block(chain, round_keys);
+    }
+}
+
+/// Computes the untruncated, unencrypted CBC-MAC `T` over B_0, the AAD, and the payload.
+fn mac(round_keys: &RoundKeys, nonce: &[u8], aad: &[u8], payload: &[u8], tag_len: usize) -> Block {
+    let q = 15 - nonce.len();
+    let adata = if aad.is_empty() { 0 } else { 0x40 };
+    let flags = adata | (((tag_len - 2) / 2) as u8) << 3 | (q - 1) as u8;
+    let mut chain = encrypt_block(
+        &format_block(flags, nonce, payload.len() as u64),
+        round_keys,
+    );
+    if !aad.is_empty() {
+        let mut encoded = encode_aad_len(aad.len());
+        encoded.extend_from_slice(aad);
+        cbc_mac_update(round_keys, &mut chain, &encoded);
+    }
+    cbc_mac_update(round_keys, &mut chain, payload);
+    chain
+}
+
+/// XORs `data` with the keystream from counter blocks 1, 2, ...
+fn ctr_apply(round_keys: &RoundKeys, nonce: &[u8], data: &mut [u8]) {
+    let flags = (14 - nonce.len()) as u8;
+    ctr::apply(round_keys, data, |i| {
+        format_block(flags, nonce, i as u64 + 1)
+    });
+}
+
+/// Encrypts the MAC with counter block 0.
+fn encrypt_tag(round_keys: &RoundKeys, nonce: &[u8], mut tag: Block) -> Block {
+    let flags = (14 - nonce.len()) as u8;
+    xor_in_place(
+        &mut tag,
+        &encrypt_block(&format_block(flags, nonce, 0), round_keys),
+    );
+    tag
+}
+
+/// Encrypts and authenticates `plaintext`, returning the ciphertext with a `tag_len`-byte tag appended.
+pub fn ccm_encrypt(
+    key: &Aes128Key,
+    nonce: &[u8],
+    aad: &[u8],
+    plaintext: &[u8],
+    tag_len: usize,
+) -> Result<Vec<u8>, CcmError> {
+    check_params(nonce, tag_len, plaintext.len())?;
+    let round_keys = expand_key(key);
+    let tag = encrypt_tag(
+        &round_keys,
+        nonce,
+        mac(&round_keys, nonce, aad, plaintext, tag_len),
+    );
+
+    let mut out = plaintext.to_vec();
+    ctr_apply(&round_keys, nonce, &mut out);
+    out.extend_from_slice(&tag[..tag_len]);
+    Ok(out)
+}
+
+/// Decrypts `ciphertext` (payload followed by a `tag_len`-byte tag) and verifies the tag.
+///
+/// The tag comparison does not short-circuit, and on failure no plaintext is exposed.
+pub fn ccm_decrypt(
+    key: &Aes128Key,
+    nonce: &[u8],
+    aad: &[u8],
+    ciphertext: &[u8],
+    tag_len: usize,
+) -> Result<Vec<u8>, CcmError> {
+    let payload_len = ciphertext
+        .len()
+        .checked_sub(tag_len)
+        .ok_or(CcmError::TruncatedCiphertext)?;
+    check_params(nonce, tag_len, payload_len)?;
+    let round_keys = expand_key(key);
+
+    let (payload, received) = ciphertext.split_at(payload_len);
+    let mut plaintext = payload.to_vec();
+    ctr_apply(&round_keys, nonce, &mut plaintext);
+    let expected = encrypt_tag(
+        &round_keys,
+        nonce,
+        mac(&round_keys, nonce, aad, &plaintext, tag_len),
+    );
+
+    if !ct_eq(&expected[..tag_len], received) {
+        return Err(CcmError::AuthFailed);
+    }
+    Ok(plaintext)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    const RFC3610_KEY: [u8; 16] = [
+        0xc0, 0xc1, 0xc2, 0xc3, 0xc4, 0xc5, 0xc6, 0xc7, 0xc8, 0xc9, 0xca, 0xcb, 0xcc, 0xcd, 0xce,
+        0xcf,
+    ];
+
+    /// RFC 3610 packet vectors 1-3: nonce, total packet length, expected output.
+    /// The packet is bytes `0..len`; the first 8 bytes are AAD, the rest payload.
+    const RFC3610_VECTORS: [(&str, u8, &str); 3] = [
+        (
+            "00000003020100a0a1a2a3a4a5",
+            31,
+            "588c979a61c663d2f066d0c2c0f989806d5f6b61dac38417e8d12cfdf926e0",
+        ),
+        (
+            "00000004030201a0a1a2a3a4a5",
+            32,
+            "72c91a36e135f8cf291ca894085c87e3cc15c439c9e43a3ba091d56e10400916",
+        ),
+        (
+            "00000005040302a0a1a2a3a4a5",
+            33,
+            "51b1e5f44a197d1da46b0f8e2d282ae871e838bb64da8596574adaa76fbd9fb0c5",
+        ),
+    ];
+
+    #[test]
+    fn rfc3610_packet_vectors() {
+        let key = Aes128Key::from(RFC3610_KEY);
+        for (nonce, len, expected) in RFC3610_VECTORS {
+            let nonce = hex(nonce);
+            let packet: Vec<u8> = (0..len).collect();
+            let (aad, plaintext) = packet.split_at(8);
+            let out = ccm_encrypt(&key, &nonce, aad, plaintext, 8).unwrap();
+            assert_eq!(out, hex(expected));
+            assert_eq!(ccm_decrypt(&key, &nonce, aad, &out, 8).unwrap(), plaintext);
+        }
+    }
+
+    #[test]
+    fn long_aad_uses_ffxe_length_encoding() {
+        assert_eq!(encode_aad_len(0xfeff), [0xfe, 0xff]);
+        assert_eq!(encode_aad_len(0xff00), [0xff, 0xfe, 0x00, 0x00, 0xff, 0x00]);
+
+        // Expected output computed with OpenSSL's AES-128-CCM.
+        let key = Aes128Key::from([0u8; 16]);
+        let aad: Vec<u8> = (0..70_000u32).map(|i| (i * 7 + 3) as u8).collect();
+        let out = ccm_encrypt(&key, &[0u8; 12], &aad, b"long aad", 16).unwrap();
+        assert_eq!(out, hex("02a831d5c2d5e6228eb9b7cdbcd841ba07a2c1c35c7815ca"));
+        assert_eq!(
+            ccm_decrypt(&key, &[0u8; 12], &aad, &out, 16).unwrap(),
+            b"long aad"
+        );
+    }
+
+    #[test]
+    fn minimal_nonce_and_tag() {
+        let key = Aes128Key::from([0u8; 16]);
+        let out = ccm_encrypt(&key, &[0u8; 7], &[], &[], 4).unwrap();
+        assert_eq!(out, hex("5863e479"));
+    }
+
+    #[test]
+    fn tampering_and_bad_parameters_are_rejected() {
+        let key = Aes128Key::from(RFC3610_KEY);
+        let nonce = hex(RFC3610_VECTORS[0].0);
+        let mut out = ccm_encrypt(&key, &nonce, b"aad", b"payload", 8).unwrap();
+        out[0] ^= 1;
+        assert_eq!(
+            ccm_decrypt(&key, &nonce, b"aad", &out, 8),
+            Err(CcmError::AuthFailed)
+        );
+        out[0] ^= 1;
+        assert_eq!(
+            ccm_decrypt(&key, &nonce, b"aae", &out, 8),
+            Err(CcmError::AuthFailed)
+        );
+
+        assert_eq!(
+            ccm_encrypt(&key, &nonce, &[], &[], 5),
+            Err(CcmError::InvalidTagLength(5))
+        );
+        assert_eq!(
+            ccm_encrypt(&key, &nonce, &[], &[], 18),
+            Err(CcmError::InvalidTagLength(18))
+        );
+        assert_eq!(
+            ccm_encrypt(&key, &[0u8; 6], &[], &[], 8),
+            Err(CcmError::InvalidNonceLength(6))
+        );
+        assert_eq!(
+            ccm_encrypt(&key, &[0u8; 13], &[], &[0u8; 0x1_0000], 8),
+            Err(CcmError::PayloadTooLong)
+        );
+        assert_eq!(
+            ccm_decrypt(&key, &nonce, &[], &[0u8; 3], 4),
+            Err(CcmError::TruncatedCiphertext)
+        );
+    }
+}
@@ -0,0 +1,104 @@
+//! CFB-128 mode (NIST SP 800-38A §6.3, full-block feedback).
+
+use crate::block::Block;
+use crate::cipher::expand_key;
+use crate::key::Aes128Key;
+use crate::modes::BlockEncryptor;
+
+/// CFB-128 over any forward block cipher.
+///
+/// A final segment shorter than 16 bytes uses a truncated keystream block, so
+/// ciphertext length always equals plaintext length.
+#[derive(Clone, Debug)]
+pub struct Cfb<E> {
+    cipher: E,
+    iv: Block,
+}
+
+impl<E: BlockEncryptor> Cfb<E> {
+    /// Creates a CFB context from a cipher and a 16-byte IV.
+    pub fn new(cipher: E, iv: Block) -> Self {
+        Self { cipher, iv }
+    }
+
+    /// Encrypts `data` in place.
+    pub fn encrypt(&self, data: &mut [u8]) {
+        let mut feedback = self.iv;
+        for segment in data.chunks_mut(16) {
+            let keystream = self.cipher.encrypt(&feedback);
+            for (d, k) in segment.iter_mut().zip(keystream.iter()) {
+                *d ^= *k;
+            }
+            feedback[..segment.len()].copy_from_slice(segment);
+        }
+    }
+
+    /// Decrypts `data` in place.
+    pub fn decrypt(&self, data: &mut [u8]) {
+        let mut feedback = self.iv;
+        for segment in data.chunks_mut(16) {
+            let keystream = self.cipher.encrypt(&feedback);
+            feedback[..segment.len()].copy_from_slice(segment);
+            for (d, k) in segment.iter_mut().zip(keystream.iter()) {
+                *d ^= *k;
+            }
+        }
+    }
+}
+
+/// Encrypts `plaintext` in CFB-128 mode under an AES-128 key.
+pub fn cfb_encrypt(key: &Aes128Key, iv: &Block, plaintext: &[u8]) -> Vec<u8> {
+    let mut data = plaintext.to_vec();
+    Cfb::new(expand_key(key), *iv).encrypt(&mut data);
+    data
+}
+
+/// Decrypts CFB-128 `ciphertext` under an AES-128 key.
+pub fn cfb_decrypt(key: &Aes128Key, iv: &Block, ciphertext: &[u8]) -> Vec<u8> {
+    let mut data = ciphertext.to_vec();
+    Cfb::new(expand_key(key), *iv).decrypt(&mut data);
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modes::test_vectors::{IV, KEY, PLAIN};
+    use crate::modes::EncryptOnly;
+
+    /// F.3.13 CFB128-AES128.Encrypt ciphertext.
+    const SP800_38A_F313_CIPHER: [u8; 64] = [
+        0x3b, 0x3f, 0xd9, 0x2e, 0xb7, 0x2d, 0xad, 0x20, 0x33, 0x34, 0x49, 0xf8, 0xe8, 0x3c, 0xfb,
+        0x4a, 0xc8, 0xa6, 0x45, 0x37, 0xa0, 0xb3, 0xa9, 0x3f, 0xcd, 0xe3, 0xcd, 0xad, 0x9f, 0x1c,
+        0xe5, 0x8b, 0x26, 0x75, 0x1f, 0x67, 0xa3, 0xcb, 0xb1, 0x40, 0xb1, 0x80, 0x8c, 0xf1, 0x87,
+        0xa4, 0xf4, 0xdf, 0xc0, 0x4b, 0x05, 0x35, 0x7c, 0x5d, 0x1c, 0x0e, 0xea, 0xc4, 0xc6, 0x6f,
+        0x9f, 0xf7, 0xf2, 0xe6,
+    ];
+
+    #[test]
+    fn cfb_matches_sp800_38a_f313_and_f314() {
+        let key = Aes128Key::from(KEY);
+        assert_eq!(cfb_encrypt(&key, &IV, &PLAIN), SP800_38A_F313_CIPHER);
+        assert_eq!(cfb_decrypt(&key, &IV, &SP800_38A_F313_CIPHER), PLAIN);
+    }
+
+    #[test]
+    fn cfb_partial_final_segment_is_prefix_of_full_output() {
+        let key = Aes128Key::from(KEY);
+        for len in 0..=PLAIN.len() {
+            let ct = cfb_encrypt(&key, &IV, &PLAIN[..len]);
+            assert_eq!(ct, SP800_38A_F313_CIPHER[..len]);
+            assert_eq!(cfb_decrypt(&key, &IV, &ct), PLAIN[..len]);
+        }
+    }
+
+    #[test]
+    fn cfb_decrypts_with_encrypt_only_cipher() {
+        let cfb = Cfb::new(EncryptOnly(expand_key(&Aes128Key::from(KEY))), IV);
+        let mut data = SP800_38A_F313_CIPHER;
+        cfb.decrypt(&mut data);
+        assert_eq!(data, PLAIN);
+        cfb.encrypt(&mut data);
+        assert_eq!(data, SP800_38A_F313_CIPHER);
+    }
+}
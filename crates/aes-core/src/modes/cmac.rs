@@ -0,0 +1,188 @@
+//! AES-CMAC (RFC 4493, NIST SP 800-38B).
+
+use crate::block::{ct_eq, xor_in_place, Block};
+use crate::cipher::{encrypt_block, expand_key};
+use crate::error::AesError;
+use crate::key::{Aes128Key, RoundKeys};
+
+/// Doubles an element of GF(2^128) modulo x^128 + x^7 + x^2 + x + 1.
+pub(super) fn dbl(block: &Block) -> Block {
+    let value = u128::from_be_bytes(*block);
+    let carry = (value >> 127) as u8;
+    let mut out = (value << 1).to_be_bytes();
+    out[15] ^= 0x87 & carry.wrapping_neg();
+    out
+}
+
+/// Incremental CMAC computation.
+///
+/// The most recent full block is held back until more data arrives, since the
+/// final block is masked with a different subkey.
+#[derive(Clone, Debug)]
+pub struct CmacState {
+    round_keys: RoundKeys,
+    k1: Block,
+    k2: Block,
+    chain: Block,
+    buffer: Block,
+    buffered: usize,
+}
+
+impl CmacState {
+    /// Creates a state and derives the subkeys K1 and K2.
+    pub fn new(key: &Aes128Key) -> Self {
+        let round_keys = expand_key(key);
+        let l = encrypt_block(&[0u8; 16], &round_keys);
+        let k1 = dbl(&l);
+        let k2 = dbl(&k1);
+        Self {
+            round_keys,
+            k1,
+            k2,
+            chain: [0u8; 16],
+            buffer: [0u8; 16],
+            buffered: 0,
+        }
+    }
+
+    /// Absorbs `data` into the MAC.
+    pub fn update(&mut self, mut data: &[u8]) {
+        while !data.is_empty() {
+            if self.buffered == 16 {
+                xor_in_place(&mut self.chain, &self.buffer);
+                self.chain = encrypt_block(&self.chain, &self.round_keys);
+                self.buffered = 0;
+            }
+            let take = (16 - self.buffered).min(data.len());
+            self.buffer[self.buffered..self.buffered + take].copy_from_slice(&data[..take]);
+            self.buffered += take;
+            data = &data[take..];
+        }
+    }
+
+    /// Completes the computation and returns the 128-bit tag.
+    pub fn finalize(mut self) -> Block {
+        let subkey = if self.buffered == 16 {
+            self.k1
+        } else {
+            self.buffer[self.buffered] = 0x80;
+            self.buffer[self.buffered + 1..].fill(0);
+            self.k2
+        };
+        xor_in_place(&mut self.buffer, &subkey);
+        xor_in_place(&mut self.chain, &self.buffer);
+        encrypt_block(&self.chain, &self.round_keys)
+    }
+}
+
+/// Computes the CMAC tag of `msg`.
+pub fn cmac(key: &Aes128Key, msg: &[u8]) -> Block {
+    let mut state = CmacState::new(key);
+    state.update(msg);
+    state.finalize()
+}
+
+/// Checks `tag` against the CMAC of `msg` without short-circuiting the comparison.
+pub fn cmac_verify(key: &Aes128Key, msg: &[u8], tag: &Block) -> Result<(), AesError> {
+    if ct_eq(&cmac(key, msg), tag) {
+        Ok(())
+    } else {
+        Err(AesError::TagMismatch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modes::test_vectors::{KEY, PLAIN};
+
+    /// RFC 4493 §4 examples 1–4: (message length, tag).
+    const RFC4493_TAGS: [(usize, Block); 4] = [
+        (
+            0,
+            [
+                0xbb, 0x1d, 0x69, 0x29, 0xe9, 0x59, 0x37, 0x28, 0x7f, 0xa3, 0x7d, 0x12, 0x9b, 0x75,
+                0x67, 0x46,
+            ],
+        ),
+        (
+            16,
+            [
+                0x07, 0x0a, 0x16, 0xb4, 0x6b, 0x4d, 0x41, 0x44, 0xf7, 0x9b, 0xdd, 0x9d, 0xd0, 0x4a,
+                0x28, 0x7c,
+            ],
+        ),
+        (
+            40,
+            [
+                0xdf, 0xa6, 0x67, 0x47, 0xde, 0x9a, 0xe6, 0x30, 0x30, 0xca, 0x32, 0x61, 0x14, 0x97,
+                0xc8, 0x27,
+            ],
+        ),
+        (
+            64,
+            [
+                0x51, 0xf0, 0xbe, 0xbf, 0x7e, 0x3b, 0x9d, 0x92, 0xfc, 0x49, 0x74, 0x17, 0x79, 0x36,
+                0x3c, 0xfe,
+            ],
+        ),
+    ];
+
+    #[test]
+    fn subkeys_match_rfc4493_section_4() {
+        let state = CmacState::new(&Aes128Key::from(KEY));
+        assert_eq!(
+            state.k1,
+            [
+                0xfb, 0xee, 0xd6, 0x18, 0x35, 0x71, 0x33, 0x66, 0x7c, 0x85, 0xe0, 0x8f, 0x72, 0x36,
+                0xa8, 0xde
+            ]
+        );
+        assert_eq!(
+            state.k2,
+            [
+                0xf7, 0xdd, 0xac, 0x30, 0x6a, 0xe2, 0x66, 0xcc, 0xf9, 0x0b, 0xc1, 0x1e, 0xe4, 0x6d,
+                0x51, 0x3b
+            ]
+        );
+    }
+
+    #[test]
+    fn cmac_matches_rfc4493_examples() {
+        let key = Aes128Key::from(KEY);
+        for (len, tag) in RFC4493_TAGS {
+            assert_eq!(cmac(&key, &PLAIN[..len]), tag, "message length {len}");
+            assert_eq!(cmac_verify(&key, &PLAIN[..len], &tag), Ok(()));
+        }
+    }
+
+    #[test]
+    fn incremental_updates_match_one_shot() {
+        let key = Aes128Key::from(KEY);
+        for (len, tag) in RFC4493_TAGS {
+            for split in 0..=len {
+                let mut state = CmacState::new(&key);
+                state.update(&PLAIN[..split]);
+                state.update(&[]);
+                state.update(&PLAIN[split..len]);
+                assert_eq!(state.finalize(), tag, "length {len} split at {split}");
+            }
+        }
+    }
+
+    #[test]
+    fn verify_rejects_modified_tag_and_message() {
+        let key = Aes128Key::from(KEY);
+        let (len, tag) = RFC4493_TAGS[2];
+        let mut bad_tag = tag;
+        bad_tag[0] ^= 1;
+        assert_eq!(
+            cmac_verify(&key, &PLAIN[..len], &bad_tag),
+            Err(AesError::TagMismatch)
+        );
+        assert_eq!(
+            cmac_verify(&key, &PLAIN[..len - 1], &tag),
+            Err(AesError::TagMismatch)
+        );
+    }
+}
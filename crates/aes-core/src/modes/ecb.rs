@@ -0,0 +1,113 @@
+//! Electronic Codebook mode (NIST SP 800-38A §6.1).
+//!
+//! ECB leaks equality of plaintext blocks, so the only constructor is named to
+//! make that choice visible at the call site. It exists for fixed-size,
+//! block-aligned data such as white-box test buffers.
+
+use crate::aes128::Aes128;
+use crate::error::AesError;
+use crate::key::Aes128Key;
+
+/// AES-128 in ECB mode; hands out an [`EcbEncryptor`] and an [`EcbDecryptor`].
+#[derive(Clone, Debug)]
+pub struct Ecb {
+    cipher: Aes128,
+}
+
+impl Ecb {
+    /// Creates an ECB context; the name is the opt-in acknowledging ECB's weaknesses.
+    pub fn new_i_know_ecb_is_insecure(key: &Aes128Key) -> Self {
+        Self {
+            cipher: Aes128::new(key),
+        }
+    }
+
+    /// Returns the encrypting half.
+    pub fn encryptor(&self) -> EcbEncryptor<'_> {
+        EcbEncryptor {
+            cipher: &self.cipher,
+        }
+    }
+
+    /// Returns the decrypting half.
+    pub fn decryptor(&self) -> EcbDecryptor<'_> {
+        EcbDecryptor {
+            cipher: &self.cipher,
+        }
+    }
+}
+
+/// Encrypts block-aligned buffers in ECB mode.
+#[derive(Clone, Copy, Debug)]
+pub struct EcbEncryptor<'a> {
+    cipher: &'a Aes128,
+}
+
+impl EcbEncryptor<'_> {
+    /// Encrypts `data` in place; its length must be a multiple of 16.
+    pub fn encrypt(&self, data: &mut [u8]) -> Result<(), AesError> {
+        self.cipher.encrypt_blocks(data)
+    }
+}
+
+/// Decrypts block-aligned buffers in ECB mode.
+#[derive(Clone, Copy, Debug)]
+pub struct EcbDecryptor<'a> {
+    cipher: &'a Aes128,
+}
+
+impl EcbDecryptor<'_> {
+    /// Decrypts `data` in place; its length must be a multiple of 16.
+    pub fn decrypt(&self, data: &mut [u8]) -> Result<(), AesError> {
+        self.cipher.decrypt_blocks(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::Block;
+    use crate::cipher::{encrypt_block, expand_key};
+    use crate::modes::test_vectors::{KEY, PLAIN};
+
+    /// SP 800-38A F.1.1 ECB-AES128.Encrypt ciphertext.
+    const F11_CIPHER: [u8; 64] = [
+        0x3a, 0xd7, 0x7b, 0xb4, 0x0d, 0x7a, 0x36, 0x60, 0xa8, 0x9e, 0xca, 0xf3, 0x24, 0x66, 0xef,
+        0x97, 0xf5, 0xd3, 0xd5, 0x85, 0x03, 0xb9, 0x69, 0x9d, 0xe7, 0x85, 0x89, 0x5a, 0x96, 0xfd,
+        0xba, 0xaf, 0x43, 0xb1, 0xcd, 0x7f, 0x59, 0x8e, 0xce, 0x23, 0x88, 0x1b, 0x00, 0xe3, 0xed,
+        0x03, 0x06, 0x88, 0x7b, 0x0c, 0x78, 0x5e, 0x27, 0xe8, 0xad, 0x3f, 0x82, 0x23, 0x20, 0x71,
+        0x04, 0x72, 0x5d, 0xd4,
+    ];
+
+    #[test]
+    fn multi_block_buffer_matches_per_block_reference() {
+        let key = Aes128Key::from(KEY);
+        let ecb = Ecb::new_i_know_ecb_is_insecure(&key);
+        let round_keys = expand_key(&key);
+
+        let mut data = PLAIN;
+        ecb.encryptor().encrypt(&mut data).unwrap();
+        assert_eq!(data, F11_CIPHER);
+        for (chunk, plain) in data.chunks(16).zip(PLAIN.chunks(16)) {
+            let plain: Block = plain.try_into().unwrap();
+            assert_eq!(chunk, encrypt_block(&plain, &round_keys));
+        }
+
+        ecb.decryptor().decrypt(&mut data).unwrap();
+        assert_eq!(data, PLAIN);
+    }
+
+    #[test]
+    fn unaligned_buffers_are_rejected() {
+        let ecb = Ecb::new_i_know_ecb_is_insecure(&Aes128Key::from(KEY));
+        let mut data = [0u8; 31];
+        assert_eq!(
+            ecb.encryptor().encrypt(&mut data),
+            Err(AesError::InvalidBlockLength { len: 31 })
+        );
+        assert_eq!(
+            ecb.decryptor().decrypt(&mut data),
+            Err(AesError::InvalidBlockLength { len: 31 })
+        );
+    }
+}
@@ -0,0 +1,241 @@
+//! Galois/Counter Mode (NIST SP 800-38D).
+
+use crate::block::{ct_eq, xor_in_place, Block};
+use crate::cipher::{encrypt_block, expand_key};
+use crate::error::AesError;
+use crate::ghash::Ghash;
+use crate::key::{Aes128Key, RoundKeys};
+use crate::modes::ctr;
+
+/// Computes GHASH_H(A || pad || C || pad || len(A) || len(C)).
+fn ghash(h: &Block, aad: &[u8], ciphertext: &[u8]) -> Block {
+    let mut ghash = Ghash::new(h);
+    ghash.update_padded(aad);
+    ghash.update_padded(ciphertext);
+    ghash.finalize(bit_len(aad), bit_len(ciphertext))
+}
+
+fn bit_len(data: &[u8]) -> u64 {
+    data.len() as u64 * 8
+}
+
+/// Increments the low 32 bits of a counter block.
+fn inc32(counter: &mut Block) {
+    let low = u32::from_be_bytes([counter[12], counter[13], counter[14], counter[15]]);
+    counter[12..].copy_from_slice(&low.wrapping_add(1).to_be_bytes());
+}
+
+/// Derives the pre-counter block J0 from a nonce of any length.
+fn pre_counter(h: &Block, nonce: &[u8]) -> Block {
+    if nonce.len() == 12 {
+        let mut j0 = [0u8; 16];
+        j0[..12].copy_from_slice(nonce);
+        j0[15] = 1;
+        j0
+    } else {
+        let mut ghash = Ghash::new(h);
+        ghash.update_padded(nonce);
+        ghash.finalize(0, bit_len(nonce))
+    }
+}
+
+/// XORs `data` with the keystream starting at counter block `inc32(j0)`.
+fn ctr_apply(round_keys: &RoundKeys, j0: &Block, data: &mut [u8]) {
+    let mut counter = *j0;
+    ctr::apply(round_keys, data, |_| {
+        inc32(&mut counter);
+        counter
+    });
+}
+
+/// Computes the authentication tag for `aad` and `ciphertext`.
+fn compute_tag(
+    round_keys: &RoundKeys,
+    h: &Block,
+    j0: &Block,
+    aad: &[u8],
+    ciphertext: &[u8],
+) -> Block {
+    let mut tag = ghash(h, aad, ciphertext);
+    xor_in_place(&mut tag, &encrypt_block(j0, round_keys));
+    tag
+}
+
+/// Encrypts `plaintext` and authenticates it together with `aad`, returning the ciphertext and 128-bit tag.
+///
+/// 96-bit nonces are used directly; other lengths are derived through GHASH.
+pub fn gcm_encrypt(
+    key: &Aes128Key,
+    nonce: &[u8],
+    aad: &[u8],
+    plaintext: &[u8],
+) -> (Vec<u8>, Block) {
+    let round_keys = expand_key(key);
+    let h = encrypt_block(&[0u8; 16], &round_keys);
+    let j0 = pre_counter(&h, nonce);
+
+    let mut ciphertext = plaintext.to_vec();
+    ctr_apply(&round_keys, &j0, &mut ciphertext);
+    let tag = compute_tag(&round_keys, &h, &j0, aad, &ciphertext);
+    (ciphertext, tag)
+}
+
+/// Verifies `tag` over `aad` and `ciphertext`, then decrypts.
+///
+/// The tag comparison does not short-circuit, and on failure no plaintext or computed tag is exposed.
+pub fn gcm_decrypt(
+    key: &Aes128Key,
+    nonce: &[u8],
+    aad: &[u8],
+    ciphertext: &[u8],
+    tag: &Block,
+) -> Result<Vec<u8>, AesError> {
+    let round_keys = expand_key(key);
+    let h = encrypt_block(&[0u8; 16], &round_keys);
+    let j0 = pre_counter(&h, nonce);
+
+    let expected = compute_tag(&round_keys, &h, &j0, aad, ciphertext);
+    if !ct_eq(&expected, tag) {
+        return Err(AesError::TagMismatch);
+    }
+
+    let mut plaintext = ciphertext.to_vec();
+    ctr_apply(&round_keys, &j0, &mut plaintext);
+    Ok(plaintext)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    struct Vector {
+        key: &'static str,
+        nonce: &'static str,
+        aad: &'static str,
+        plain: &'static str,
+        cipher: &'static str,
+        tag: &'static str,
+    }
+
+    const MV_KEY: &str = "feffe9928665731c6d6a8f9467308308";
+    const MV_PLAIN: &str = "d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a72\
+                            1c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b391aafd255";
+    const MV_PLAIN_60: &str = "d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a72\
+                               1c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b39";
+    const MV_AAD: &str = "feedfacedeadbeeffeedfacedeadbeefabaddad2";
+
+    /// McGrew–Viega AES-128 test cases 1–6, plus an AAD-only case.
+    const VECTORS: [Vector; 7] = [
+        Vector {
+            key: "00000000000000000000000000000000",
+            nonce: "000000000000000000000000",
+            aad: "",
+            plain: "",
+            cipher: "",
+            tag: "58e2fccefa7e3061367f1d57a4e7455a",
+        },
+        Vector {
+            key: "00000000000000000000000000000000",
+            nonce: "000000000000000000000000",
+            aad: "",
+            plain: "00000000000000000000000000000000",
+            cipher: "0388dace60b6a392f328c2b971b2fe78",
+            tag: "ab6e47d42cec13bdf53a67b21257bddf",
+        },
+        Vector {
+            key: MV_KEY,
+            nonce: "cafebabefacedbaddecaf888",
+            aad: "",
+            plain: MV_PLAIN,
+            cipher: "42831ec2217774244b7221b784d0d49ce3aa212f2c02a4e035c17e2329aca12e\
+                     21d514b25466931c7d8f6a5aac84aa051ba30b396a0aac973d58e091473f5985",
+            tag: "4d5c2af327cd64a62cf35abd2ba6fab4",
+        },
+        Vector {
+            key: MV_KEY,
+            nonce: "cafebabefacedbaddecaf888",
+            aad: MV_AAD,
+            plain: MV_PLAIN_60,
+            cipher: "42831ec2217774244b7221b784d0d49ce3aa212f2c02a4e035c17e2329aca12e\
+                     21d514b25466931c7d8f6a5aac84aa051ba30b396a0aac973d58e091",
+            tag: "5bc94fbc3221a5db94fae95ae7121a47",
+        },
+        Vector {
+            key: MV_KEY,
+            nonce: "cafebabefacedbad",
+            aad: MV_AAD,
+            plain: MV_PLAIN_60,
+            cipher: "61353b4c2806934a777ff51fa22a4755699b2a714fcdc6f83766e5f97b6c7423\
+                     73806900e49f24b22b097544d4896b424989b5e1ebac0f07c23f4598",
+            tag: "3612d2e79e3b0785561be14aaca2fccb",
+        },
+        Vector {
+            key: MV_KEY,
+            nonce: "9313225df88406e555909c5aff5269aa6a7a9538534f7da1e4c303d2a318a728\
+                    c3c0c95156809539fcf0e2429a6b525416aedbf5a0de6a57a637b39b",
+            aad: MV_AAD,
+            plain: MV_PLAIN_60,
+            cipher: "8ce24998625615b603a033aca13fb894be9112a5c3a211a8ba262a3cca7e2ca7\
+                     01e4a9a4fba43c90ccdcb281d48c7c6fd62875d2aca417034c34aee5",
+            tag: "619cc5aefffe0bfa462af43c1699d050",
+        },
+        Vector {
+            key: MV_KEY,
+            nonce: "cafebabefacedbaddecaf888",
+            aad: MV_AAD,
+            plain: "",
+            cipher: "",
+            tag: "346434fd51d5cd0c5887ec63e39b907a",
+        },
+    ];
+
+    #[test]
+    fn gcm_matches_reference_vectors() {
+        for (idx, v) in VECTORS.iter().enumerate() {
+            let key = Aes128Key::from(<[u8; 16]>::try_from(hex(v.key)).unwrap());
+            let (ct, tag) = gcm_encrypt(&key, &hex(v.nonce), &hex(v.aad), &hex(v.plain));
+            assert_eq!(ct, hex(v.cipher), "ciphertext of vector {idx}");
+            assert_eq!(tag.to_vec(), hex(v.tag), "tag of vector {idx}");
+
+            let pt =
+                gcm_decrypt(&key, &hex(v.nonce), &hex(v.aad), &ct, &tag).expect("tag verifies");
+            assert_eq!(pt, hex(v.plain), "plaintext of vector {idx}");
+        }
+    }
+
+    #[test]
+    fn gcm_rejects_tampering() {
+        let v = &VECTORS[3];
+        let key = Aes128Key::from(<[u8; 16]>::try_from(hex(v.key)).unwrap());
+        let (nonce, aad) = (hex(v.nonce), hex(v.aad));
+        let (ct, tag) = gcm_encrypt(&key, &nonce, &aad, &hex(v.plain));
+
+        let mut bad_ct = ct.clone();
+        bad_ct[0] ^= 1;
+        assert_eq!(
+            gcm_decrypt(&key, &nonce, &aad, &bad_ct, &tag),
+            Err(AesError::TagMismatch)
+        );
+
+        let mut bad_aad = aad.clone();
+        bad_aad[0] ^= 1;
+        assert_eq!(
+            gcm_decrypt(&key, &nonce, &bad_aad, &ct, &tag),
+            Err(AesError::TagMismatch)
+        );
+
+        let mut bad_tag = tag;
+        bad_tag[15] ^= 0x80;
+        assert_eq!(
+            gcm_decrypt(&key, &nonce, &aad, &ct, &bad_tag),
+            Err(AesError::TagMismatch)
+        );
+    }
+}
@@ -0,0 +1,77 @@
+//! Block cipher modes of operation built on the reference cipher.
+//!
+//! Modes reuse [`crate::encrypt_block`] and [`crate::decrypt_block`] directly; they favor
+//! readability over throughput and are not side-channel hardened.
+
+mod cbc;
+mod cbc_mac;
+mod ccm;
+mod cfb;
+mod cmac;
+mod ctr;
+mod ecb;
+mod gcm;
+mod ofb;
+mod seal;
+mod siv;
+
+pub use cbc::{cbc_decrypt, cbc_encrypt};
+pub use cbc_mac::{
+    cbc_mac, cbc_mac_length_prepended, cbc_mac_length_prepended_verify, cbc_mac_verify,
+};
+pub use ccm::{ccm_decrypt, ccm_encrypt, CcmError};
+pub use cfb::{cfb_decrypt, cfb_encrypt, Cfb};
+pub use cmac::{cmac, cmac_verify, CmacState};
+pub use ecb::{Ecb, EcbDecryptor, EcbEncryptor};
+pub use gcm::{gcm_decrypt, gcm_encrypt};
+pub use ofb::{ofb_apply, Ofb};
+pub use seal::{open, seal, SealError, SEAL_VERSION};
+pub use siv::{siv_decrypt, siv_encrypt};
+
+use crate::block::Block;
+use crate::cipher::encrypt_block;
+use crate::key::RoundKeys;
+
+/// Forward-only block cipher, enough for modes that never invert the cipher.
+pub trait BlockEncryptor {
+    /// Encrypts a single block.
+    fn encrypt(&self, block: &Block) -> Block;
+}
+
+impl<const N: usize> BlockEncryptor for RoundKeys<N> {
+    fn encrypt(&self, block: &Block) -> Block {
+        encrypt_block(block, self)
+    }
+}
+
+/// Test helper exposing only the forward direction of a cipher.
+#[cfg(test)]
+pub(crate) struct EncryptOnly(pub RoundKeys);
+
+#[cfg(test)]
+impl BlockEncryptor for EncryptOnly {
+    fn encrypt(&self, block: &Block) -> Block {
+        encrypt_block(block, &self.0)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test_vectors {
+    //! NIST SP 800-38A Appendix F inputs shared by the mode tests.
+
+    pub const KEY: [u8; 16] = [
+        0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f,
+        0x3c,
+    ];
+    pub const IV: [u8; 16] = [
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e,
+        0x0f,
+    ];
+    pub const PLAIN: [u8; 64] = [
+        0x6b, 0xc1, 0xbe, 0xe2, 0x2e, 0x40, 0x9f, 0x96, 0xe9, 0x3d, 0x7e, 0x11, 0x73, 0x93, 0x17,
+        0x2a, 0xae, 0x2d, 0x8a, 0x57, 0x1e, 0x03, 0xac, 0x9c, 0x9e, 0xb7, 0x6f, 0xac, 0x45, 0xaf,
+        0x8e, 0x51, 0x30, 0xc8, 0x1c, 0x46, 0xa3, 0x5c, 0xe4, 0x11, 0xe5, 0xfb, 0xc1, 0x19, 0x1a,
+        0x0a, 0x52, 0xef, 0xf6, 0x9f, 0x24, 0x45, 0xdf, 0x4f, 0x9b, 0x17, 0xad, 0x2b, 0x41, 0x7b,
+        0xe6, 0x6c, 0x37, 0x10,
+    ];
+}
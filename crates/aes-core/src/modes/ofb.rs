@@ -0,0 +1,83 @@
+//! OFB mode (NIST SP 800-38A §6.4).
+
+use crate::block::Block;
+use crate::cipher::expand_key;
+use crate::key::Aes128Key;
+use crate::modes::BlockEncryptor;
+
+/// OFB over any forward block cipher.
+///
+/// Encryption and decryption are the same keystream XOR; a final partial block
+/// uses a truncated keystream block.
+#[derive(Clone, Debug)]
+pub struct Ofb<E> {
+    cipher: E,
+    iv: Block,
+}
+
+impl<E: BlockEncryptor> Ofb<E> {
+    /// Creates an OFB context from a cipher and a 16-byte IV.
+    pub fn new(cipher: E, iv: Block) -> Self {
+        Self { cipher, iv }
+    }
+
+    /// XORs the keystream into `data` in place (encrypts or decrypts).
+    pub fn apply(&self, data: &mut [u8]) {
+        let mut output = self.iv;
+        for chunk in data.chunks_mut(16) {
+            output = self.cipher.encrypt(&output);
+            for (d, k) in chunk.iter_mut().zip(output.iter()) {
+                *d ^= *k;
+            }
+        }
+    }
+}
+
+/// Encrypts or decrypts `data` in OFB mode under an AES-128 key.
+pub fn ofb_apply(key: &Aes128Key, iv: &Block, data: &[u8]) -> Vec<u8> {
+    let mut out = data.to_vec();
+    Ofb::new(expand_key(key), *iv).apply(&mut out);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modes::test_vectors::{IV, KEY, PLAIN};
+    use crate::modes::EncryptOnly;
+
+    /// F.4.1 OFB-AES128.Encrypt ciphertext.
+    const SP800_38A_F41_CIPHER: [u8; 64] = [
+        0x3b, 0x3f, 0xd9, 0x2e, 0xb7, 0x2d, 0xad, 0x20, 0x33, 0x34, 0x49, 0xf8, 0xe8, 0x3c, 0xfb,
+        0x4a, 0x77, 0x89, 0x50, 0x8d, 0x16, 0x91, 0x8f, 0x03, 0xf5, 0x3c, 0x52, 0xda, 0xc5, 0x4e,
+        0xd8, 0x25, 0x97, 0x40, 0x05, 0x1e, 0x9c, 0x5f, 0xec, 0xf6, 0x43, 0x44, 0xf7, 0xa8, 0x22,
+        0x60, 0xed, 0xcc, 0x30, 0x4c, 0x65, 0x28, 0xf6, 0x59, 0xc7, 0x78, 0x66, 0xa5, 0x10, 0xd9,
+        0xc1, 0xd6, 0xae, 0x5e,
+    ];
+
+    #[test]
+    fn ofb_matches_sp800_38a_f41_and_f42() {
+        let key = Aes128Key::from(KEY);
+        assert_eq!(ofb_apply(&key, &IV, &PLAIN), SP800_38A_F41_CIPHER);
+        assert_eq!(ofb_apply(&key, &IV, &SP800_38A_F41_CIPHER), PLAIN);
+    }
+
+    #[test]
+    fn ofb_partial_final_block_is_prefix_of_full_output() {
+        let key = Aes128Key::from(KEY);
+        for len in 0..=PLAIN.len() {
+            assert_eq!(
+                ofb_apply(&key, &IV, &PLAIN[..len]),
+                SP800_38A_F41_CIPHER[..len]
+            );
+        }
+    }
+
+    #[test]
+    fn ofb_decrypts_with_encrypt_only_cipher() {
+        let ofb = Ofb::new(EncryptOnly(expand_key(&Aes128Key::from(KEY))), IV);
+        let mut data = SP800_38A_F41_CIPHER;
+        ofb.apply(&mut data);
+        assert_eq!(data, PLAIN);
+    }
+}
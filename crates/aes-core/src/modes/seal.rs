@@ -0,0 +1,205 @@
+//! Encrypt-then-MAC container for reference cipher outputs.
+//!
+//! Layout: `version (1) || nonce (16) || ciphertext || tag (16)`.
+//!
+//! The ciphertext is AES-128-CTR with the nonce as the initial counter block.
+//! The tag is CMAC over everything before it, including the version byte.
+//! Two subkeys are derived from the caller's key by encrypting distinct
+//! labelled blocks, so the CTR and CMAC keys are never the same.
+
+use core::fmt;
+
+use rand_core::{CryptoRng, RngCore};
+
+use crate::block::{ct_eq, Block};
+use crate::cipher::{encrypt_block, expand_key};
+use crate::key::{Aes128Key, RoundKeys};
+use crate::modes::cmac::CmacState;
+use crate::modes::ctr;
+
+/// Current container version.
+pub const SEAL_VERSION: u8 = 1;
+
+const NONCE_LEN: usize = 16;
+const TAG_LEN: usize = 16;
+/// Smallest valid container: header and tag around an empty ciphertext.
+const OVERHEAD: usize = 1 + NONCE_LEN + TAG_LEN;
+
+/// Errors returned by [`open`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SealError {
+    /// Input is shorter than the version byte, nonce, and tag.
+    Truncated {
+        /// Length of the rejected input.
+        len: usize,
+    },
+    /// The version byte is not [`SEAL_VERSION`].
+    UnsupportedVersion(u8),
+    /// The tag does not verify.
+    TagMismatch,
+}
+
+impl fmt::Display for SealError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Truncated { len } => {
+                write!(
+                    f,
+                    "sealed data is {len} bytes; at least {OVERHEAD} required"
+                )
+            }
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported sealed data version {version}")
+            }
+            Self::TagMismatch => write!(f, "authentication tag mismatch"),
+        }
+    }
+}
+
+impl std::error::Error for SealError {}
+
+/// Derives the CTR and CMAC subkeys as `E_K(label || 0^15)` for labels 1 and 2.
+fn subkeys(key: &Aes128Key) -> (RoundKeys, Aes128Key) {
+    let round_keys = expand_key(key);
+    let derive = |label: u8| {
+        let mut block = [0u8; 16];
+        block[0] = label;
+        Aes128Key::from(encrypt_block(&block, &round_keys))
+    };
+    (expand_key(&derive(1)), derive(2))
+}
+
+fn compute_tag(mac_key: &Aes128Key, authenticated: &[u8]) -> Block {
+    let mut mac = CmacState::new(mac_key);
+    mac.update(authenticated);
+    mac.finalize()
+}
+
+/// Encrypts `plaintext` under a fresh random nonce and appends the CMAC tag.
+pub fn seal<R>(key: &Aes128Key, plaintext: &[u8], rng: &mut R) -> Vec<u8>
+where
+    R: RngCore + CryptoRng + ?Sized,
+{
+    let (ctr_keys, mac_key) = subkeys(key);
+    let mut nonce = [0u8; NONCE_LEN];
+    rng.fill_bytes(&mut nonce);
+
+    let mut out = Vec::with_capacity(OVERHEAD + plaintext.len());
+    out.push(SEAL_VERSION);
+    out.extend_from_slice(&nonce);
+    out.extend_from_slice(plaintext);
+    ctr::apply_be128(&ctr_keys, &nonce, &mut out[1 + NONCE_LEN..]);
+    let tag = compute_tag(&mac_key, &out);
+    out.extend_from_slice(&tag);
+    out
+}
+
+/// Checks the version and tag of a [`seal`] output, then decrypts it.
+///
+/// Nothing is decrypted unless the tag verifies.
+pub fn open(key: &Aes128Key, sealed: &[u8]) -> Result<Vec<u8>, SealError> {
+    if sealed.len() < OVERHEAD {
+        return Err(SealError::Truncated { len: sealed.len() });
+    }
+    if sealed[0] != SEAL_VERSION {
+        return Err(SealError::UnsupportedVersion(sealed[0]));
+    }
+    let (authenticated, tag) = sealed.split_at(sealed.len() - TAG_LEN);
+    let tag: &Block = tag.try_into().expect("split at tag length");
+    let (ctr_keys, mac_key) = subkeys(key);
+    if !ct_eq(&compute_tag(&mac_key, authenticated), tag) {
+        return Err(SealError::TagMismatch);
+    }
+
+    let nonce: &Block = authenticated[1..1 + NONCE_LEN]
+        .try_into()
+        .expect("nonce is 16 bytes");
+    let mut plaintext = authenticated[1 + NONCE_LEN..].to_vec();
+    ctr::apply_be128(&ctr_keys, nonce, &mut plaintext);
+    Ok(plaintext)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    fn rng() -> rand::rngs::StdRng {
+        rand::rngs::StdRng::seed_from_u64(292)
+    }
+
+    #[test]
+    fn round_trips_various_lengths() {
+        let key = Aes128Key::from([0x5a; 16]);
+        let mut rng = rng();
+        for len in [0, 1, 15, 16, 17, 100] {
+            let plaintext: Vec<u8> = (0..len).map(|i| i as u8).collect();
+            let sealed = seal(&key, &plaintext, &mut rng);
+            assert_eq!(sealed.len(), OVERHEAD + len);
+            assert_eq!(sealed[0], SEAL_VERSION);
+            if len > 0 {
+                assert_ne!(&sealed[1 + NONCE_LEN..1 + NONCE_LEN + len], &plaintext[..]);
+            }
+            assert_eq!(open(&key, &sealed).unwrap(), plaintext);
+        }
+    }
+
+    #[test]
+    fn empty_plaintext_is_still_authenticated() {
+        let key = Aes128Key::from([1; 16]);
+        let sealed = seal(&key, &[], &mut rng());
+        assert_eq!(sealed.len(), OVERHEAD);
+        assert_eq!(open(&key, &sealed).unwrap(), Vec::<u8>::new());
+        assert_eq!(
+            open(&Aes128Key::from([2; 16]), &sealed),
+            Err(SealError::TagMismatch)
+        );
+    }
+
+    #[test]
+    fn every_flipped_bit_is_rejected() {
+        let key = Aes128Key::from([7; 16]);
+        let sealed = seal(&key, b"instance bytes", &mut rng());
+        for byte in 0..sealed.len() {
+            for bit in 0..8 {
+                let mut tampered = sealed.clone();
+                tampered[byte] ^= 1 << bit;
+                let expected = if byte == 0 {
+                    SealError::UnsupportedVersion(tampered[0])
+                } else {
+                    SealError::TagMismatch
+                };
+                assert_eq!(
+                    open(&key, &tampered),
+                    Err(expected),
+                    "byte {byte} bit {bit}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn truncated_inputs_are_rejected() {
+        let key = Aes128Key::from([9; 16]);
+        let sealed = seal(&key, b"abc", &mut rng());
+        for len in 0..OVERHEAD {
+            assert_eq!(
+                open(&key, &sealed[..len]),
+                Err(SealError::Truncated { len })
+            );
+        }
+        assert_eq!(
+            open(&key, &sealed[..sealed.len() - 1]),
+            Err(SealError::TagMismatch)
+        );
+    }
+
+    #[test]
+    fn nonces_differ_between_seals() {
+        let key = Aes128Key::from([3; 16]);
+        let mut rng = rng();
+        let a = seal(&key, b"same", &mut rng);
+        let b = seal(&key, b"same", &mut rng);
+        assert_ne!(a, b);
+    }
+}
@@ -0,0 +1,164 @@
+//! AES-SIV deterministic authenticated encryption (RFC 5297), AES-128 variant.
+//!
+//! The 256-bit key is split into a CMAC key (first half) for the S2V
+//! pseudo-random function and a CTR key (second half). The synthetic IV is
+//! prepended to the ciphertext and doubles as the authentication tag, so reusing
+//! a nonce (or omitting it) only reveals whether two inputs were identical.
+
+use crate::block::{ct_eq, xor_in_place, Block};
+use crate::cipher::expand_key;
+use crate::error::AesError;
+use crate::key::{Aes128Key, RoundKeys};
+use crate::modes::cmac::{cmac, dbl};
+use crate::modes::ctr;
+
+/// S2V over the associated-data strings followed by `plaintext` (RFC 5297 §2.4).
+fn s2v(key: &Aes128Key, aad: &[&[u8]], plaintext: &[u8]) -> Block {
+    let mut d = cmac(key, &[0u8; 16]);
+    for string in aad {
+        d = dbl(&d);
+        xor_in_place(&mut d, &cmac(key, string));
+    }
+    if plaintext.len() >= 16 {
+        // xorend: XOR D into the last 16 bytes.
+        let mut t = plaintext.to_vec();
+        let start = t.len() - 16;
+        for (byte, mask) in t[start..].iter_mut().zip(d.iter()) {
+            *byte ^= *mask;
+        }
+        cmac(key, &t)
+    } else {
+        let mut t = dbl(&d);
+        let mut padded = [0u8; 16];
+        padded[..plaintext.len()].copy_from_slice(plaintext);
+        padded[plaintext.len()] = 0x80;
+        xor_in_place(&mut t, &padded);
+        cmac(key, &t)
+    }
+}
+
+/// XORs `data` with the CTR keystream starting from `v` with bits 31 and 63 cleared.
+fn ctr_apply(round_keys: &RoundKeys, v: &Block, data: &mut [u8]) {
+    let mut q = *v;
+    q[8] &= 0x7f;
+    q[12] &= 0x7f;
+    ctr::apply_be128(round_keys, &q, data);
+}
+
+fn split_key(key: &[u8; 32]) -> (Aes128Key, RoundKeys) {
+    let (mac_key, ctr_key) = key.split_at(16);
+    let mac_key = Aes128Key::try_from(mac_key).expect("half of a 32-byte key");
+    let ctr_key = Aes128Key::try_from(ctr_key).expect("half of a 32-byte key");
+    (mac_key, expand_key(&ctr_key))
+}
+
+/// Encrypts `plaintext`, returning the 16-byte synthetic IV followed by the ciphertext.
+///
+/// A nonce, if used, is passed as the last element of `aad`.
+pub fn siv_encrypt(key: &[u8; 32], aad: &[&[u8]], plaintext: &[u8]) -> Vec<u8> {
+    let (mac_key, round_keys) = split_key(key);
+    let v = s2v(&mac_key, aad, plaintext);
+    let mut out = Vec::with_capacity(16 + plaintext.len());
+    out.extend_from_slice(&v);
+    out.extend_from_slice(plaintext);
+    ctr_apply(&round_keys, &v, &mut out[16..]);
+    out
+}
+
+/// Decrypts `IV || ciphertext` and verifies the synthetic IV.
+///
+/// The comparison does not short-circuit, and on failure no plaintext is exposed.
+pub fn siv_decrypt(key: &[u8; 32], aad: &[&[u8]], ciphertext: &[u8]) -> Result<Vec<u8>, AesError> {
+    if ciphertext.len() < 16 {
+        return Err(AesError::InvalidBlockLength {
+            len: ciphertext.len(),
+        });
+    }
+    let (mac_key, round_keys) = split_key(key);
+    let (v, body) = ciphertext.split_at(16);
+    let v: &Block = v.try_into().expect("split at 16");
+    let mut plaintext = body.to_vec();
+    ctr_apply(&round_keys, v, &mut plaintext);
+    if !ct_eq(&s2v(&mac_key, aad, &plaintext), v) {
+        return Err(AesError::TagMismatch);
+    }
+    Ok(plaintext)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    fn key(s: &str) -> [u8; 32] {
+        hex(s).try_into().unwrap()
+    }
+
+    #[test]
+    fn rfc5297_a1_deterministic() {
+        let key = key("fffefdfcfbfaf9f8f7f6f5f4f3f2f1f0f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff");
+        let ad = hex("101112131415161718191a1b1c1d1e1f2021222324252627");
+        let plaintext = hex("112233445566778899aabbccddee");
+        let out = siv_encrypt(&key, &[&ad], &plaintext);
+        assert_eq!(
+            out,
+            hex("85632d07c6e8f37f950acd320a2ecc9340c02b9690c4dc04daef7f6afe5c")
+        );
+        assert_eq!(siv_decrypt(&key, &[&ad], &out).unwrap(), plaintext);
+    }
+
+    #[test]
+    fn rfc5297_a2_nonce_based() {
+        let key = key("7f7e7d7c7b7a79787776757473727170404142434445464748494a4b4c4d4e4f");
+        let ad1 =
+            hex("00112233445566778899aabbccddeeffdeaddadadeaddadaffeeddccbbaa99887766554433221100");
+        let ad2 = hex("102030405060708090a0");
+        let nonce = hex("09f911029d74e35bd84156c5635688c0");
+        let plaintext = hex(
+            "7468697320697320736f6d6520706c61696e7465787420746f20656e6372797074207573696e67205349562d414553",
+        );
+        let aad: [&[u8]; 3] = [&ad1, &ad2, &nonce];
+        let out = siv_encrypt(&key, &aad, &plaintext);
+        assert_eq!(
+            out,
+            hex("7bdb6e3b432667eb06f4d14bff2fbd0fcb900f2fddbe404326601965c889bf17dba77ceb094fa663b7a3f748ba8af829ea64ad544a272e9c485b62a3fd5c0d")
+        );
+        assert_eq!(siv_decrypt(&key, &aad, &out).unwrap(), plaintext);
+    }
+
+    #[test]
+    fn flipped_bits_and_short_inputs_are_rejected() {
+        let key = [0x42u8; 32];
+        let mut out = siv_encrypt(&key, &[b"header"], b"white-box instance bytes");
+        let last = out.len() - 1;
+        out[last] ^= 0x01;
+        assert_eq!(
+            siv_decrypt(&key, &[b"header"], &out),
+            Err(AesError::TagMismatch)
+        );
+        out[last] ^= 0x01;
+        out[0] ^= 0x80;
+        assert_eq!(
+            siv_decrypt(&key, &[b"header"], &out),
+            Err(AesError::TagMismatch)
+        );
+        assert_eq!(
+            siv_decrypt(&key, &[], &[0u8; 15]),
+            Err(AesError::InvalidBlockLength { len: 15 })
+        );
+    }
+
+    #[test]
+    fn empty_plaintext_round_trips() {
+        let key = [7u8; 32];
+        let out = siv_encrypt(&key, &[], &[]);
+        assert_eq!(out.len(), 16);
+        assert_eq!(siv_decrypt(&key, &[], &out).unwrap(), Vec::<u8>::new());
+    }
+}
@@ -1,6 +1,7 @@
 //! AES round transformations.
 
 use crate::block::{xor_in_place, Block};
+use crate::gf256::{mul as gmul, xtime};
 use crate::sbox::{inv_sbox, sbox};
 
 /// Applies SubBytes to the state in place.
@@ -20,6 +21,9 @@ pub fn inv_sub_bytes(state: &mut Block) {
 }
 
 /// Performs ShiftRows in place.
+///
+/// Flat fast path over the column-major [`Block`] layout; see
+/// [`State::shift_rows`](crate::State::shift_rows) for the row/column form.
 #[inline]
 pub fn shift_rows(state: &mut Block) {
     let mut tmp = [0u8; 16];
@@ -73,32 +77,7 @@ pub fn inv_shift_rows(state: &mut Block) {
     *state = tmp;
 }
 
-fn xtime(byte: u8) -> u8 {
-    let shifted = byte << 1;
-    if byte & 0x80 != 0 {
-        shifted ^ 0x1b
-    } else {
-        shifted
-    }
-}
-
-fn gmul(mut a: u8, mut b: u8) -> u8 {
-    let mut product = 0u8;
-    for _ in 0..8 {
-        if b & 1 != 0 {
-            product ^= a;
-        }
-        let hi_bit_set = a & 0x80;
-        a <<= 1;
-        if hi_bit_set != 0 {
-            a ^= 0x1b;
-        }
-        b >>= 1;
-    }
-    product
-}
-
-fn mix_single_column(col: &mut [u8; 4]) {
+pub(crate) fn mix_single_column(col: &mut [u8; 4]) {
     let [a0, a1, a2, a3] = *col;
     col[0] = xtime(a0) ^ (xtime(a1) ^ a1) ^ a2 ^ a3;
     col[1] = a0 ^ xtime(a1) ^ (xtime(a2) ^ a2) ^ a3;
@@ -106,7 +85,7 @@ fn mix_single_column(col: &mut [u8; 4]) {
     col[3] = (xtime(a0) ^ a0) ^ a1 ^ a2 ^ xtime(a3);
 }
 
-fn inv_mix_single_column(col: &mut [u8; 4]) {
+pub(crate) fn inv_mix_single_column(col: &mut [u8; 4]) {
     let [a0, a1, a2, a3] = *col;
     col[0] = gmul(a0, 0x0e) ^ gmul(a1, 0x0b) ^ gmul(a2, 0x0d) ^ gmul(a3, 0x09);
     col[1] = gmul(a0, 0x09) ^ gmul(a1, 0x0e) ^ gmul(a2, 0x0b) ^ gmul(a3, 0x0d);
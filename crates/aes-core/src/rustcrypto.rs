@@ -0,0 +1,95 @@
+//! RustCrypto `cipher` trait implementations for [`Aes128`] (`rustcrypto` feature).
+//!
+//! With these impls the reference cipher plugs into generic RustCrypto code such
+//! as `cmac::Cmac<Aes128>` or `ctr::Ctr128BE<Aes128>`.
+
+use cipher::consts::U16;
+use cipher::{impl_simple_block_encdec, AlgorithmName, BlockCipher, Key, KeyInit, KeySizeUser};
+
+use crate::aes128::Aes128;
+use crate::block::Block;
+use crate::key::Aes128Key;
+
+impl KeySizeUser for Aes128 {
+    type KeySize = U16;
+}
+
+impl KeyInit for Aes128 {
+    fn new(key: &Key<Self>) -> Self {
+        let bytes: [u8; 16] = (*key).into();
+        Aes128::new(&Aes128Key::from(bytes))
+    }
+}
+
+impl BlockCipher for Aes128 {}
+
+impl AlgorithmName for Aes128 {
+    fn write_alg_name(f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("Aes128")
+    }
+}
+
+impl_simple_block_encdec!(
+    Aes128, U16, cipher, block,
+    encrypt: {
+        let mut b: Block = (*block.get_in()).into();
+        cipher.encrypt(&mut b);
+        *block.get_out() = b.into();
+    }
+    decrypt: {
+        let mut b: Block = (*block.get_in()).into();
+        cipher.decrypt(&mut b);
+        *block.get_out() = b.into();
+    }
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cipher::{BlockDecrypt, BlockEncrypt, KeyIvInit, StreamCipher};
+    use cmac::Mac;
+    use rand::{Rng, RngCore, SeedableRng};
+
+    #[test]
+    fn block_traits_match_aes_crate() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(11);
+        for _ in 0..16 {
+            let key: [u8; 16] = rng.gen();
+            let ours = <Aes128 as KeyInit>::new(&key.into());
+            let theirs = <aes::Aes128 as KeyInit>::new(&key.into());
+
+            let mut block = cipher::Block::<Aes128>::from(rng.gen::<[u8; 16]>());
+            let mut expected = block;
+            ours.encrypt_block(&mut block);
+            theirs.encrypt_block(&mut expected);
+            assert_eq!(block, expected);
+            ours.decrypt_block(&mut block);
+            theirs.decrypt_block(&mut expected);
+            assert_eq!(block, expected);
+        }
+    }
+
+    #[test]
+    fn generic_cmac_and_ctr_match_aes_crate() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(12);
+        for len in [0usize, 1, 15, 16, 17, 100] {
+            let key: [u8; 16] = rng.gen();
+            let iv: [u8; 16] = rng.gen();
+            let mut msg = vec![0u8; len];
+            rng.fill_bytes(&mut msg);
+
+            let mut ours = <cmac::Cmac<Aes128> as Mac>::new(&key.into());
+            let mut theirs = <cmac::Cmac<aes::Aes128> as Mac>::new(&key.into());
+            ours.update(&msg);
+            theirs.update(&msg);
+            assert_eq!(ours.finalize().into_bytes(), theirs.finalize().into_bytes());
+
+            let mut ours_ct = msg.clone();
+            let mut theirs_ct = msg.clone();
+            ctr::Ctr64BE::<Aes128>::new(&key.into(), &iv.into()).apply_keystream(&mut ours_ct);
+            ctr::Ctr64BE::<aes::Aes128>::new(&key.into(), &iv.into())
+                .apply_keystream(&mut theirs_ct);
+            assert_eq!(ours_ct, theirs_ct);
+        }
+    }
+}
@@ -0,0 +1,120 @@
+//! Power-on self-test in the style of FIPS 140 known-answer tests.
+//!
+//! Uses only stack data, so it is cheap enough to run at start-up.
+
+use core::fmt;
+
+use crate::block::Block;
+use crate::cipher::{decrypt_block, encrypt_block, expand_key};
+use crate::key::Aes128Key;
+use crate::sbox::{inv_sbox, sbox};
+
+/// Stage of [`self_test`] that produced a wrong answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SelfTestError {
+    /// The expanded key's final round key differs from the known value.
+    KeySchedule,
+    /// Encryption of the known-answer plaintext gave the wrong ciphertext.
+    Encrypt,
+    /// Decryption of the known-answer ciphertext gave the wrong plaintext.
+    Decrypt,
+    /// The S-box or its inverse disagrees with known values.
+    SBox,
+}
+
+impl fmt::Display for SelfTestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let stage = match self {
+            Self::KeySchedule => "key schedule",
+            Self::Encrypt => "encryption",
+            Self::Decrypt => "decryption",
+            Self::SBox => "S-box",
+        };
+        write!(f, "AES self-test failed at the {stage} known-answer check")
+    }
+}
+
+impl std::error::Error for SelfTestError {}
+
+/// Known-answer data for one run of the self-test.
+struct Vectors {
+    key: [u8; 16],
+    plain: Block,
+    cipher: Block,
+    last_round_key: Block,
+    /// `(x, sbox(x))` pairs from FIPS-197 Figure 7.
+    sbox: [(u8, u8); 4],
+}
+
+/// FIPS-197 Appendix C.1 (AES-128), including `round[10].k_sch`.
+const FIPS_197: Vectors = Vectors {
+    key: [
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e,
+        0x0f,
+    ],
+    plain: [
+        0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd, 0xee,
+        0xff,
+    ],
+    cipher: [
+        0x69, 0xc4, 0xe0, 0xd8, 0x6a, 0x7b, 0x04, 0x30, 0xd8, 0xcd, 0xb7, 0x80, 0x70, 0xb4, 0xc5,
+        0x5a,
+    ],
+    last_round_key: [
+        0x13, 0x11, 0x1d, 0x7f, 0xe3, 0x94, 0x4a, 0x17, 0xf3, 0x07, 0xa7, 0x8b, 0x4d, 0x2b, 0x30,
+        0xc5,
+    ],
+    sbox: [(0x00, 0x63), (0x53, 0xed), (0x9a, 0xb8), (0xff, 0x16)],
+};
+
+fn run(vectors: &Vectors) -> Result<(), SelfTestError> {
+    if vectors
+        .sbox
+        .iter()
+        .any(|&(x, y)| sbox(x) != y || inv_sbox(y) != x)
+    {
+        return Err(SelfTestError::SBox);
+    }
+
+    let round_keys = expand_key(&Aes128Key::from(vectors.key));
+    if round_keys.last() != &vectors.last_round_key {
+        return Err(SelfTestError::KeySchedule);
+    }
+    if encrypt_block(&vectors.plain, &round_keys) != vectors.cipher {
+        return Err(SelfTestError::Encrypt);
+    }
+    if decrypt_block(&vectors.cipher, &round_keys) != vectors.plain {
+        return Err(SelfTestError::Decrypt);
+    }
+    Ok(())
+}
+
+/// Runs the known-answer checks: S-box, key schedule, encryption, then decryption.
+pub fn self_test() -> Result<(), SelfTestError> {
+    run(&FIPS_197)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn self_test_passes() {
+        assert_eq!(self_test(), Ok(()));
+    }
+
+    #[test]
+    fn corrupted_vectors_identify_the_failing_stage() {
+        let mut vectors = FIPS_197;
+        vectors.sbox[2].1 ^= 1;
+        assert_eq!(run(&vectors), Err(SelfTestError::SBox));
+
+        let mut vectors = FIPS_197;
+        vectors.last_round_key[15] ^= 1;
+        assert_eq!(run(&vectors), Err(SelfTestError::KeySchedule));
+
+        let mut vectors = FIPS_197;
+        vectors.cipher[0] ^= 1;
+        assert_eq!(run(&vectors), Err(SelfTestError::Encrypt));
+    }
+}
@@ -0,0 +1,207 @@
+//! `serde` support for key types (`serde` feature).
+//!
+//! Human-readable formats (JSON, TOML) encode keys as lowercase hex strings and
+//! round keys as a list of per-round hex strings. Binary formats (bincode) use
+//! raw bytes.
+
+use core::fmt;
+
+use serde::de::{self, Deserializer, SeqAccess, Visitor};
+use serde::ser::{SerializeSeq, Serializer};
+use serde::{Deserialize, Serialize};
+
+use crate::key::{Aes128Key, Aes192Key, Aes256Key, RoundKeys};
+
+fn encode_hex(bytes: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for &byte in bytes {
+        out.push(DIGITS[(byte >> 4) as usize] as char);
+        out.push(DIGITS[(byte & 0x0f) as usize] as char);
+    }
+    out
+}
+
+fn decode_hex<E: de::Error>(text: &str) -> Result<Vec<u8>, E> {
+    if !text.len().is_multiple_of(2) {
+        return Err(E::custom("hex string has odd length"));
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| {
+            text.get(i..i + 2)
+                .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                .ok_or_else(|| E::custom("invalid hex digit"))
+        })
+        .collect()
+}
+
+/// Accepts a hex string, a byte string, or a sequence of bytes.
+struct BytesVisitor;
+
+impl<'de> Visitor<'de> for BytesVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a hex string or byte array")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Vec<u8>, E> {
+        decode_hex(v)
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Vec<u8>, E> {
+        Ok(v.to_vec())
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Vec<u8>, A::Error> {
+        let mut out = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(byte) = seq.next_element()? {
+            out.push(byte);
+        }
+        Ok(out)
+    }
+}
+
+fn deserialize_bytes<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(BytesVisitor)
+    } else {
+        deserializer.deserialize_bytes(BytesVisitor)
+    }
+}
+
+fn fixed<E: de::Error, const L: usize>(bytes: Vec<u8>) -> Result<[u8; L], E> {
+    let len = bytes.len();
+    bytes
+        .try_into()
+        .map_err(|_| E::invalid_length(len, &format!("{L} bytes").as_str()))
+}
+
+macro_rules! impl_key_serde {
+    ($ty:ident, $len:literal) => {
+        impl Serialize for $ty {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                if serializer.is_human_readable() {
+                    serializer.serialize_str(&encode_hex(&self.0))
+                } else {
+                    serializer.serialize_bytes(&self.0)
+                }
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $ty {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                fixed::<_, $len>(deserialize_bytes(deserializer)?).map($ty)
+            }
+        }
+    };
+}
+
+impl_key_serde!(Aes128Key, 16);
+impl_key_serde!(Aes192Key, 24);
+impl_key_serde!(Aes256Key, 32);
+
+impl<const N: usize> Serialize for RoundKeys<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            let mut seq = serializer.serialize_seq(Some(N))?;
+            for round_key in &self.0 {
+                seq.serialize_element(&encode_hex(round_key))?;
+            }
+            seq.end()
+        } else {
+            serializer.serialize_bytes(self.0.as_flattened())
+        }
+    }
+}
+
+impl<'de, const N: usize> Deserialize<'de> for RoundKeys<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut round_keys = [[0u8; 16]; N];
+        if deserializer.is_human_readable() {
+            let hex = Vec::<String>::deserialize(deserializer)?;
+            if hex.len() != N {
+                return Err(de::Error::invalid_length(
+                    hex.len(),
+                    &format!("{N} round keys").as_str(),
+                ));
+            }
+            for (slot, text) in round_keys.iter_mut().zip(hex.iter()) {
+                *slot = fixed(decode_hex(text)?)?;
+            }
+        } else {
+            let bytes = deserializer.deserialize_bytes(BytesVisitor)?;
+            if bytes.len() != 16 * N {
+                return Err(de::Error::invalid_length(
+                    bytes.len(),
+                    &format!("{} bytes", 16 * N).as_str(),
+                ));
+            }
+            for (slot, chunk) in round_keys.iter_mut().zip(bytes.chunks_exact(16)) {
+                slot.copy_from_slice(chunk);
+            }
+        }
+        Ok(RoundKeys(round_keys))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cipher::expand_key;
+
+    const KEY: [u8; 16] = [
+        0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f,
+        0x3c,
+    ];
+
+    #[test]
+    fn key_json_uses_hex_and_round_trips() {
+        let key = Aes128Key::from(KEY);
+        let json = serde_json::to_string(&key).unwrap();
+        assert_eq!(json, "\"2b7e151628aed2a6abf7158809cf4f3c\"");
+        assert_eq!(serde_json::from_str::<Aes128Key>(&json).unwrap(), key);
+    }
+
+    #[test]
+    fn key_bincode_uses_raw_bytes_and_round_trips() {
+        let key = Aes128Key::from(KEY);
+        let bytes = bincode::serialize(&key).unwrap();
+        assert_eq!(bytes.len(), 8 + 16);
+        assert_eq!(&bytes[8..], &KEY);
+        assert_eq!(bincode::deserialize::<Aes128Key>(&bytes).unwrap(), key);
+    }
+
+    #[test]
+    fn round_keys_round_trip_through_json_and_bincode() {
+        let round_keys = expand_key(&Aes128Key::from(KEY));
+        let json = serde_json::to_string(&round_keys).unwrap();
+        assert!(json.starts_with("[\"2b7e151628aed2a6abf7158809cf4f3c\","));
+        assert_eq!(
+            serde_json::from_str::<RoundKeys>(&json).unwrap(),
+            round_keys
+        );
+
+        let bytes = bincode::serialize(&round_keys).unwrap();
+        assert_eq!(
+            bincode::deserialize::<RoundKeys>(&bytes).unwrap(),
+            round_keys
+        );
+    }
+
+    #[test]
+    fn short_keys_are_rejected() {
+        let err = serde_json::from_str::<Aes128Key>("\"2b7e151628aed2a6abf7158809cf4f\"")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("invalid length 15"), "{err}");
+
+        // bincode encodes a `Vec<u8>` exactly like `serialize_bytes`: length, then bytes.
+        let bytes = bincode::serialize(&KEY[..15].to_vec()).unwrap();
+        let err = bincode::deserialize::<Aes128Key>(&bytes)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("invalid length 15"), "{err}");
+    }
+}
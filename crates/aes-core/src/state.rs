@@ -0,0 +1,202 @@
+//! Row/column view of the AES state.
+//!
+//! A [`Block`] holds the state in FIPS-197 input order (Figure 3): byte
+//! `in[r + 4c]` is state entry `s[r][c]`, so each run of four bytes is one
+//! column. [`State`] makes that layout explicit; the flat functions in
+//! [`round`](crate::round) remain the fast path and must agree with it.
+
+use crate::block::Block;
+use crate::round::{inv_mix_single_column, mix_single_column};
+
+/// AES state addressed by `(row, col)`, stored column-major as in FIPS-197.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct State(Block);
+
+impl State {
+    /// Interprets `bytes` as a state: `bytes[r + 4 * c]` becomes `s[r][c]`.
+    pub fn from_bytes(bytes: &Block) -> Self {
+        Self(*bytes)
+    }
+
+    /// Returns the state bytes in FIPS-197 output order.
+    pub fn to_bytes(&self) -> Block {
+        self.0
+    }
+
+    /// Returns `s[row][col]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `row` or `col` is not below 4.
+    #[inline]
+    pub fn get(&self, row: usize, col: usize) -> u8 {
+        self.0[Self::index(row, col)]
+    }
+
+    /// Sets `s[row][col]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `row` or `col` is not below 4.
+    #[inline]
+    pub fn set(&mut self, row: usize, col: usize, value: u8) {
+        self.0[Self::index(row, col)] = value;
+    }
+
+    /// Returns column `col` from top to bottom.
+    pub fn column(&self, col: usize) -> [u8; 4] {
+        [0, 1, 2, 3].map(|row| self.get(row, col))
+    }
+
+    /// Overwrites column `col` from top to bottom.
+    pub fn set_column(&mut self, col: usize, column: [u8; 4]) {
+        for (row, value) in column.into_iter().enumerate() {
+            self.set(row, col, value);
+        }
+    }
+
+    /// ShiftRows: row `r` is rotated left by `r` positions, `s'[r][c] = s[r][(c + r) mod 4]`.
+    pub fn shift_rows(&mut self) {
+        let old = *self;
+        for row in 0..4 {
+            for col in 0..4 {
+                self.set(row, col, old.get(row, (col + row) % 4));
+            }
+        }
+    }
+
+    /// Inverse ShiftRows: `s'[r][(c + r) mod 4] = s[r][c]`.
+    pub fn inv_shift_rows(&mut self) {
+        let old = *self;
+        for row in 0..4 {
+            for col in 0..4 {
+                self.set(row, (col + row) % 4, old.get(row, col));
+            }
+        }
+    }
+
+    /// MixColumns applied to each column.
+    pub fn mix_columns(&mut self) {
+        for col in 0..4 {
+            let mut column = self.column(col);
+            mix_single_column(&mut column);
+            self.set_column(col, column);
+        }
+    }
+
+    /// Inverse MixColumns applied to each column.
+    pub fn inv_mix_columns(&mut self) {
+        for col in 0..4 {
+            let mut column = self.column(col);
+            inv_mix_single_column(&mut column);
+            self.set_column(col, column);
+        }
+    }
+
+    fn index(row: usize, col: usize) -> usize {
+        assert!(
+            row < 4 && col < 4,
+            "state index ({row}, {col}) out of range"
+        );
+        row + 4 * col
+    }
+}
+
+impl From<Block> for State {
+    fn from(bytes: Block) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<State> for Block {
+    fn from(state: State) -> Self {
+        state.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cipher::expand_key;
+    use crate::key::Aes128Key;
+    use crate::round::{
+        add_round_key, inv_mix_columns, inv_shift_rows, mix_columns, shift_rows, sub_bytes,
+    };
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn layout_follows_fips197_figure_3() {
+        let bytes: Block = core::array::from_fn(|i| i as u8);
+        let state = State::from_bytes(&bytes);
+        for row in 0..4 {
+            for col in 0..4 {
+                assert_eq!(state.get(row, col), bytes[row + 4 * col]);
+            }
+        }
+        assert_eq!(state.column(1), [4, 5, 6, 7]);
+        assert_eq!(state.to_bytes(), bytes);
+    }
+
+    #[test]
+    fn shift_rows_rotates_each_row_by_its_index() {
+        let mut state = State::from_bytes(&core::array::from_fn(|i| i as u8));
+        state.shift_rows();
+        // Row r of the result starts with the original s[r][r].
+        assert_eq!(
+            [0, 1, 2, 3].map(|row| state.get(row, 0)),
+            [0x00, 0x05, 0x0a, 0x0f]
+        );
+        assert_eq!(
+            [0, 1, 2, 3].map(|col| state.get(3, col)),
+            [0x0f, 0x03, 0x07, 0x0b]
+        );
+    }
+
+    #[test]
+    fn state_operations_match_flat_fast_path() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(280);
+        for _ in 0..64 {
+            let bytes: Block = rng.gen();
+            let check = |flat: fn(&mut Block), structured: fn(&mut State)| {
+                let mut expected = bytes;
+                flat(&mut expected);
+                let mut state = State::from_bytes(&bytes);
+                structured(&mut state);
+                assert_eq!(state.to_bytes(), expected);
+            };
+            check(shift_rows, State::shift_rows);
+            check(inv_shift_rows, State::inv_shift_rows);
+            check(mix_columns, State::mix_columns);
+            check(inv_mix_columns, State::inv_mix_columns);
+        }
+    }
+
+    #[test]
+    fn state_based_rounds_reproduce_nist_vector() {
+        let key = Aes128Key::from(core::array::from_fn(|i| i as u8));
+        let round_keys = expand_key(&key);
+        let plain: Block = core::array::from_fn(|i| (i as u8) * 0x11);
+
+        let mut bytes = plain;
+        add_round_key(&mut bytes, round_keys.get(0));
+        for round in 1..=10 {
+            sub_bytes(&mut bytes);
+            let mut state = State::from_bytes(&bytes);
+            state.shift_rows();
+            if round != 10 {
+                state.mix_columns();
+            }
+            bytes = state.to_bytes();
+            add_round_key(&mut bytes, round_keys.get(round));
+        }
+
+        assert_eq!(
+            bytes,
+            [
+                0x69, 0xc4, 0xe0, 0xd8, 0x6a, 0x7b, 0x04, 0x30, 0xd8, 0xcd, 0xb7, 0x80, 0x70, 0xb4,
+                0xc5, 0x5a,
+            ]
+        );
+        assert_eq!(bytes, crate::cipher::encrypt_block(&plain, &round_keys));
+    }
+}
@@ -0,0 +1,298 @@
+//! dudect-style timing-leakage measurement with fixed and random input classes.
+//!
+//! Each sample times a batch of calls on either one fixed input or a fresh
+//! random input. The two classes are interleaved in a shuffled order so drift
+//! in clock speed or system load hits both equally. Samples above a percentile
+//! of the pooled distribution are discarded as interrupts and cache misses.
+//! Welch's t-statistic then compares the class means.
+//!
+//! A |t| above [`LEAK_THRESHOLD`] is the conventional dudect signal that
+//! timing depends on the input. A small |t| is not a proof of constant time,
+//! because `Instant` resolution and scheduler noise limit what can be seen.
+//! The loop is generic over the input type, so it serves 16-byte reference
+//! blocks and 32-byte white-box blocks alike.
+
+use core::fmt;
+use std::hint::black_box;
+use std::time::Instant;
+
+/// |t| above which the two classes are considered distinguishable.
+pub const LEAK_THRESHOLD: f64 = 4.5;
+
+/// Input class of a timed sample.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Class {
+    /// The same input every time.
+    Fixed,
+    /// A fresh input from the caller's generator.
+    Random,
+}
+
+/// Parameters for [`measure`] and [`run`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TimingConfig {
+    /// Number of timed samples, split evenly between the two classes.
+    pub samples: usize,
+    /// Calls per timed sample, to lift short operations above the clock resolution.
+    pub batch: usize,
+    /// Pooled percentile in `(0, 1]` above which samples are discarded.
+    pub trim_percentile: f64,
+    /// Seed for the class schedule.
+    pub seed: u64,
+}
+
+impl Default for TimingConfig {
+    fn default() -> Self {
+        Self {
+            samples: 100_000,
+            batch: 8,
+            trim_percentile: 0.95,
+            seed: 0x7469_6d69_6e67,
+        }
+    }
+}
+
+/// Result of one [`run`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TimingReport {
+    /// Welch's t-statistic, fixed minus random.
+    pub t: f64,
+    /// Fixed-class samples kept after trimming.
+    pub fixed_samples: usize,
+    /// Random-class samples kept after trimming.
+    pub random_samples: usize,
+    /// Samples discarded as outliers.
+    pub discarded: usize,
+}
+
+impl TimingReport {
+    /// Returns true if |t| exceeds [`LEAK_THRESHOLD`].
+    pub fn looks_leaky(&self) -> bool {
+        self.t.abs() > LEAK_THRESHOLD
+    }
+}
+
+impl fmt::Display for TimingReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "t = {:.3} (fixed: {}, random: {}, discarded: {})",
+            self.t, self.fixed_samples, self.random_samples, self.discarded
+        )
+    }
+}
+
+/// SplitMix64, enough to shuffle the class schedule without pulling in `rand`.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+/// Returns `samples` classes, half of each, in a Fisher-Yates shuffled order.
+fn schedule(samples: usize, seed: u64) -> Vec<Class> {
+    let mut classes: Vec<Class> = (0..samples)
+        .map(|i| {
+            if i < samples / 2 {
+                Class::Fixed
+            } else {
+                Class::Random
+            }
+        })
+        .collect();
+    let mut rng = SplitMix64(seed);
+    for i in (1..classes.len()).rev() {
+        let j = (rng.next() % (i as u64 + 1)) as usize;
+        classes.swap(i, j);
+    }
+    classes
+}
+
+/// Times `op` on a shuffled mix of `fixed` and `random_input()` inputs.
+///
+/// All inputs are generated before timing starts. Returns one
+/// `(class, nanoseconds)` pair per sample, in schedule order.
+pub fn measure<I, O, R, F>(
+    config: &TimingConfig,
+    fixed: &I,
+    mut random_input: R,
+    mut op: F,
+) -> Vec<(Class, u64)>
+where
+    I: Clone,
+    R: FnMut() -> I,
+    F: FnMut(&I) -> O,
+{
+    let classes = schedule(config.samples, config.seed);
+    let inputs: Vec<I> = classes
+        .iter()
+        .map(|class| match class {
+            Class::Fixed => fixed.clone(),
+            Class::Random => random_input(),
+        })
+        .collect();
+
+    classes
+        .into_iter()
+        .zip(&inputs)
+        .map(|(class, input)| {
+            let start = Instant::now();
+            for _ in 0..config.batch {
+                black_box(op(black_box(input)));
+            }
+            let elapsed = u64::try_from(start.elapsed().as_nanos()).unwrap_or(u64::MAX);
+            (class, elapsed)
+        })
+        .collect()
+}
+
+/// Keeps the samples at or below the `percentile` of the pooled timings.
+pub fn trim_outliers(samples: &[(Class, u64)], percentile: f64) -> Vec<(Class, u64)> {
+    if samples.is_empty() {
+        return Vec::new();
+    }
+    let mut sorted: Vec<u64> = samples.iter().map(|&(_, time)| time).collect();
+    sorted.sort_unstable();
+    let rank = (sorted.len() as f64 * percentile).ceil() as usize;
+    let cutoff = sorted[rank.clamp(1, sorted.len()) - 1];
+    samples
+        .iter()
+        .copied()
+        .filter(|&(_, time)| time <= cutoff)
+        .collect()
+}
+
+fn mean_and_variance(xs: &[f64]) -> (f64, f64) {
+    let n = xs.len() as f64;
+    let mean = xs.iter().sum::<f64>() / n;
+    let variance = xs.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / (n - 1.0);
+    (mean, variance)
+}
+
+/// Welch's t-statistic for the means of `a` and `b`.
+///
+/// Returns 0 if either side has fewer than two samples or both variances are zero.
+pub fn welch_t(a: &[f64], b: &[f64]) -> f64 {
+    if a.len() < 2 || b.len() < 2 {
+        return 0.0;
+    }
+    let (mean_a, var_a) = mean_and_variance(a);
+    let (mean_b, var_b) = mean_and_variance(b);
+    let denominator = (var_a / a.len() as f64 + var_b / b.len() as f64).sqrt();
+    if denominator == 0.0 {
+        0.0
+    } else {
+        (mean_a - mean_b) / denominator
+    }
+}
+
+/// Splits samples by class and computes the trimmed Welch t-test.
+pub fn analyze(samples: &[(Class, u64)], trim_percentile: f64) -> TimingReport {
+    let kept = trim_outliers(samples, trim_percentile);
+    let times = |wanted: Class| -> Vec<f64> {
+        kept.iter()
+            .filter(|&&(class, _)| class == wanted)
+            .map(|&(_, time)| time as f64)
+            .collect()
+    };
+    let fixed = times(Class::Fixed);
+    let random = times(Class::Random);
+    TimingReport {
+        t: welch_t(&fixed, &random),
+        fixed_samples: fixed.len(),
+        random_samples: random.len(),
+        discarded: samples.len() - kept.len(),
+    }
+}
+
+/// Runs [`measure`] followed by [`analyze`].
+pub fn run<I, O, R, F>(config: &TimingConfig, fixed: &I, random_input: R, op: F) -> TimingReport
+where
+    I: Clone,
+    R: FnMut() -> I,
+    F: FnMut(&I) -> O,
+{
+    analyze(
+        &measure(config, fixed, random_input, op),
+        config.trim_percentile,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn welch_t_matches_hand_computation() {
+        // Means 2 and 5, variances 1 and 1, three samples each: t = -3 / sqrt(2/3).
+        let t = welch_t(&[1.0, 2.0, 3.0], &[4.0, 5.0, 6.0]);
+        assert!((t + 3.0 / (2.0f64 / 3.0).sqrt()).abs() < 1e-12);
+        assert_eq!(welch_t(&[1.0, 1.0], &[1.0, 1.0]), 0.0);
+        assert_eq!(welch_t(&[1.0], &[2.0, 3.0]), 0.0);
+    }
+
+    #[test]
+    fn trimming_drops_only_the_tail() {
+        let samples: Vec<(Class, u64)> = (1..=100)
+            .map(|time| {
+                (
+                    if time % 2 == 0 {
+                        Class::Fixed
+                    } else {
+                        Class::Random
+                    },
+                    time,
+                )
+            })
+            .collect();
+        let kept = trim_outliers(&samples, 0.9);
+        assert_eq!(kept.len(), 90);
+        assert!(kept.iter().all(|&(_, time)| time <= 90));
+        assert_eq!(trim_outliers(&samples, 1.0).len(), 100);
+        assert!(trim_outliers(&[], 0.5).is_empty());
+    }
+
+    #[test]
+    fn schedule_is_balanced_and_shuffled() {
+        let classes = schedule(1000, 7);
+        let fixed = classes.iter().filter(|&&c| c == Class::Fixed).count();
+        assert_eq!(fixed, 500);
+        assert_ne!(
+            classes[..500]
+                .iter()
+                .filter(|&&c| c == Class::Fixed)
+                .count(),
+            500
+        );
+        assert_eq!(classes, schedule(1000, 7));
+    }
+
+    #[test]
+    fn run_reports_every_sample() {
+        let config = TimingConfig {
+            samples: 200,
+            batch: 1,
+            trim_percentile: 1.0,
+            ..TimingConfig::default()
+        };
+        let mut counter = 0u8;
+        let report = run(
+            &config,
+            &0u8,
+            || {
+                counter = counter.wrapping_add(1);
+                counter
+            },
+            |x| x.wrapping_mul(3),
+        );
+        assert_eq!(report.fixed_samples + report.random_samples, 200);
+        assert_eq!(report.discarded, 0);
+        assert!(report.t.is_finite());
+    }
+}
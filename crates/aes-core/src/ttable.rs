@@ -0,0 +1,112 @@
+//! T-table encryption path (`ttable` feature).
+//!
+//! Combines SubBytes, ShiftRows, and MixColumns into four 256-entry `u32`
+//! tables built at compile time (4 KiB total). The final round has no
+//! MixColumns and uses the S-box directly. Lookups are indexed by secret state,
+//! so this path is faster but even less side-channel resistant than the
+//! byte-oriented one.
+
+use crate::block::Block;
+use crate::gf256::xtime;
+use crate::key::RoundKeys;
+use crate::sbox::S_BOX;
+
+/// Builds `TE0[x] = (2·S[x], S[x], S[x], 3·S[x])` rotated right by `8 * rot` bits.
+const fn build_table(rot: u32) -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let s = S_BOX[i];
+        let s2 = xtime(s);
+        let s3 = s2 ^ s;
+        let word = u32::from_be_bytes([s2, s, s, s3]);
+        table[i] = word.rotate_right(8 * rot);
+        i += 1;
+    }
+    table
+}
+
+static TE: [[u32; 256]; 4] = [
+    build_table(0),
+    build_table(1),
+    build_table(2),
+    build_table(3),
+];
+
+fn load_columns(block: &Block) -> [u32; 4] {
+    let mut cols = [0u32; 4];
+    for (c, col) in cols.iter_mut().enumerate() {
+        *col = u32::from_be_bytes([
+            block[4 * c],
+            block[4 * c + 1],
+            block[4 * c + 2],
+            block[4 * c + 3],
+        ]);
+    }
+    cols
+}
+
+/// Encrypts a block using T-tables; output is identical to [`encrypt_block`](crate::encrypt_block).
+pub fn encrypt_block_ttable<const N: usize>(block: &Block, round_keys: &RoundKeys<N>) -> Block {
+    let rounds = N - 1;
+    let mut s = load_columns(block);
+    let rk0 = load_columns(round_keys.get(0));
+    for c in 0..4 {
+        s[c] ^= rk0[c];
+    }
+
+    for round in 1..rounds {
+        let rk = load_columns(round_keys.get(round));
+        let mut t = [0u32; 4];
+        for c in 0..4 {
+            t[c] = TE[0][(s[c] >> 24) as usize]
+                ^ TE[1][((s[(c + 1) % 4] >> 16) & 0xff) as usize]
+                ^ TE[2][((s[(c + 2) % 4] >> 8) & 0xff) as usize]
+                ^ TE[3][(s[(c + 3) % 4] & 0xff) as usize]
+                ^ rk[c];
+        }
+        s = t;
+    }
+
+    let rk = round_keys.last();
+    let mut out = [0u8; 16];
+    for c in 0..4 {
+        for r in 0..4 {
+            let byte = (s[(c + r) % 4] >> (24 - 8 * r)) as u8;
+            out[4 * c + r] = S_BOX[byte as usize] ^ rk[4 * c + r];
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cipher::{encrypt_block, expand_key, expand_key_256};
+    use crate::key::{Aes128Key, Aes256Key};
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn ttable_matches_reference_for_random_pairs() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(264);
+        for _ in 0..10_000 {
+            let round_keys = expand_key(&Aes128Key::from(rng.gen::<[u8; 16]>()));
+            let block: Block = rng.gen();
+            assert_eq!(
+                encrypt_block_ttable(&block, &round_keys),
+                encrypt_block(&block, &round_keys)
+            );
+        }
+    }
+
+    #[test]
+    fn ttable_supports_longer_key_schedules() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(265);
+        let round_keys = expand_key_256(&Aes256Key::from(rng.gen::<[u8; 32]>()));
+        let block: Block = rng.gen();
+        assert_eq!(
+            encrypt_block_ttable(&block, &round_keys),
+            encrypt_block(&block, &round_keys)
+        );
+    }
+}
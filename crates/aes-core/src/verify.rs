@@ -0,0 +1,117 @@
+//! Reference checks for two-block ciphers such as the white-box runtime.
+
+use core::fmt;
+
+use rand_core::RngCore;
+
+use crate::block::{double_block_hex, join_double, split_double, DoubleBlock};
+use crate::cipher::encrypt_block;
+use crate::key::RoundKeys;
+
+/// Encrypts both halves of a double block with reference AES-128.
+pub fn encrypt_pair(block: &DoubleBlock, round_keys: &RoundKeys) -> DoubleBlock {
+    let (first, second) = split_double(block);
+    join_double(
+        &encrypt_block(first, round_keys),
+        &encrypt_block(second, round_keys),
+    )
+}
+
+/// First input on which a cipher under test disagreed with reference AES.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MismatchReport {
+    /// Zero-based index of the failing sample.
+    pub sample: usize,
+    /// Plaintext given to both ciphers.
+    pub input: DoubleBlock,
+    /// Reference AES output.
+    pub expected: DoubleBlock,
+    /// Output of the cipher under test.
+    pub actual: DoubleBlock,
+}
+
+impl fmt::Display for MismatchReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "sample {}: output differs from reference AES\n  input:    {}\n  expected: {}\n  actual:   {}",
+            self.sample,
+            double_block_hex(&self.input),
+            double_block_hex(&self.expected),
+            double_block_hex(&self.actual)
+        )
+    }
+}
+
+impl std::error::Error for MismatchReport {}
+
+/// Compares `f` with [`encrypt_pair`] on `samples` random double blocks drawn from `rng`.
+pub fn verify_pairs<R, F>(
+    samples: usize,
+    rng: &mut R,
+    round_keys: &RoundKeys,
+    f: F,
+) -> Result<(), MismatchReport>
+where
+    R: RngCore + ?Sized,
+    F: Fn(&DoubleBlock) -> DoubleBlock,
+{
+    for sample in 0..samples {
+        let mut input = [0u8; 32];
+        rng.fill_bytes(&mut input);
+        let expected = encrypt_pair(&input, round_keys);
+        let actual = f(&input);
+        if actual != expected {
+            return Err(MismatchReport {
+                sample,
+                input,
+                expected,
+                actual,
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cipher::expand_key;
+    use crate::key::Aes128Key;
+    use rand::SeedableRng;
+
+    #[test]
+    fn encrypt_pair_encrypts_each_half() {
+        let round_keys = expand_key(&Aes128Key::from(core::array::from_fn(|i| i as u8)));
+        let plain: crate::Block = core::array::from_fn(|i| (i as u8) * 0x11);
+        let out = encrypt_pair(&join_double(&plain, &[0u8; 16]), &round_keys);
+        let (first, second) = split_double(&out);
+        assert_eq!(first, &encrypt_block(&plain, &round_keys));
+        assert_eq!(second, &encrypt_block(&[0u8; 16], &round_keys));
+    }
+
+    #[test]
+    fn verify_pairs_reports_first_mismatch_in_hex() {
+        let round_keys = expand_key(&Aes128Key::from([3u8; 16]));
+        let mut rng = rand::rngs::StdRng::seed_from_u64(291);
+        assert_eq!(
+            verify_pairs(8, &mut rng, &round_keys, |block| encrypt_pair(
+                block,
+                &round_keys
+            )),
+            Ok(())
+        );
+
+        let report = verify_pairs(8, &mut rng, &round_keys, |block| {
+            let mut out = encrypt_pair(block, &round_keys);
+            out[31] ^= 1;
+            out
+        })
+        .unwrap_err();
+        assert_eq!(report.sample, 0);
+        assert_eq!(report.expected, encrypt_pair(&report.input, &round_keys));
+        let text = report.to_string();
+        assert!(text.contains(&double_block_hex(&report.expected)));
+        assert!(text.contains(&double_block_hex(&report.actual)));
+    }
+}
@@ -0,0 +1,81 @@
+//! Runs the CAVP AES-128 ECB known-answer files in `tests/data` through
+//! `encrypt_block`/`decrypt_block`.
+
+mod support;
+
+use std::path::PathBuf;
+
+use aes_core::{decrypt_block, encrypt_block, expand_key, Aes128Key, Block};
+use support::{load_rsp, parse_rsp, Direction};
+
+const FILES: [&str; 5] = [
+    "ECBGFSbox128",
+    "ECBKeySbox128",
+    "ECBVarKey128",
+    "ECBVarTxt128",
+    "ECBMMT128",
+];
+
+fn data_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/data")
+        .join(format!("{name}.rsp"))
+}
+
+fn ecb(input: &[u8], mut op: impl FnMut(&Block) -> Block) -> Vec<u8> {
+    assert!(
+        input.len().is_multiple_of(16),
+        "ECB input must be whole blocks"
+    );
+    input
+        .chunks(16)
+        .flat_map(|chunk| op(&chunk.try_into().unwrap()))
+        .collect()
+}
+
+#[test]
+fn cavp_ecb_known_answers() {
+    let mut total = 0;
+    for name in FILES {
+        let cases = load_rsp(&data_path(name));
+        assert!(!cases.is_empty(), "{name}.rsp contains no vectors");
+        for case in cases {
+            let key: [u8; 16] =
+                case.key.as_slice().try_into().unwrap_or_else(|_| {
+                    panic!("{name}.rsp COUNT {}: key is not 16 bytes", case.count)
+                });
+            let round_keys = expand_key(&Aes128Key::from(key));
+            let (input, expected, actual) = match case.direction {
+                Direction::Encrypt => (
+                    &case.plaintext,
+                    &case.ciphertext,
+                    ecb(&case.plaintext, |b| encrypt_block(b, &round_keys)),
+                ),
+                Direction::Decrypt => (
+                    &case.ciphertext,
+                    &case.plaintext,
+                    ecb(&case.ciphertext, |b| decrypt_block(b, &round_keys)),
+                ),
+            };
+            assert_eq!(
+                &actual, expected,
+                "{name}.rsp [{:?}] COUNT {}: mismatch for input {input:02x?}",
+                case.direction, case.count
+            );
+            total += 1;
+        }
+    }
+    assert!(total > 100, "only {total} vectors were checked");
+}
+
+#[test]
+fn parser_handles_sections_comments_and_field_order() {
+    let text = "# comment\n\n[ENCRYPT]\n\nCOUNT = 0\nKEY = 00\nPLAINTEXT = 01\nCIPHERTEXT = 02\n\n\
+                [DECRYPT]\nCOUNT = 7\nKEY = 03\nCIPHERTEXT = 04\nPLAINTEXT = 05\n";
+    let cases = parse_rsp(text);
+    assert_eq!(cases.len(), 2);
+    assert_eq!(cases[0].direction, Direction::Encrypt);
+    assert_eq!((cases[0].count, &cases[0].ciphertext[..]), (0, &[0x02][..]));
+    assert_eq!(cases[1].direction, Direction::Decrypt);
+    assert_eq!((cases[1].count, &cases[1].plaintext[..]), (7, &[0x05][..]));
+}
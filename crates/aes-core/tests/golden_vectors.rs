@@ -0,0 +1,53 @@
+//! Checks the committed `tests/data/golden_vectors.json` against the cipher.
+//!
+//! The file is written by `examples/golden_vectors.rs`; any change to the
+//! encryption path (T-tables, bitslicing, S-box construction) must still
+//! reproduce every entry.
+
+use std::path::PathBuf;
+
+use aes_core::{decrypt_block, encrypt_block, expand_key, Aes128Key, Block};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use serde_json::Value;
+
+fn unhex<const N: usize>(value: &Value) -> [u8; N] {
+    let text = value.as_str().expect("hex string");
+    assert_eq!(text.len(), 2 * N, "hex length of {text}");
+    core::array::from_fn(|i| u8::from_str_radix(&text[2 * i..2 * i + 2], 16).unwrap())
+}
+
+fn load() -> Vec<Value> {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/data/golden_vectors.json");
+    let text = std::fs::read_to_string(&path).expect("read golden vectors");
+    serde_json::from_str(&text).expect("parse golden vectors")
+}
+
+#[test]
+fn every_golden_vector_encrypts_and_decrypts() {
+    let vectors = load();
+    assert_eq!(vectors.len(), 64);
+    for entry in &vectors {
+        let key: [u8; 16] = unhex(&entry["key"]);
+        let plaintext: Block = unhex(&entry["plaintext"]);
+        let ciphertext: Block = unhex(&entry["ciphertext"]);
+        let round_keys = expand_key(&Aes128Key::from(key));
+        assert_eq!(
+            encrypt_block(&plaintext, &round_keys),
+            ciphertext,
+            "seed {}",
+            entry["seed"]
+        );
+        assert_eq!(decrypt_block(&ciphertext, &round_keys), plaintext);
+    }
+}
+
+#[test]
+fn golden_vectors_follow_their_seeds() {
+    for entry in load() {
+        let seed = entry["seed"].as_u64().expect("seed");
+        let mut rng = ChaCha20Rng::seed_from_u64(seed);
+        assert_eq!(unhex::<16>(&entry["key"]), rng.gen::<[u8; 16]>());
+        assert_eq!(unhex::<16>(&entry["plaintext"]), rng.gen::<Block>());
+    }
+}
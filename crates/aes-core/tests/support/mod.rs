@@ -0,0 +1,123 @@
+//! Parser for NIST CAVP `.rsp` known-answer files.
+
+use std::fs;
+use std::path::Path;
+
+/// Direction of a CAVP section.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    /// `[ENCRYPT]`: PLAINTEXT is the input, CIPHERTEXT the expected output.
+    Encrypt,
+    /// `[DECRYPT]`: CIPHERTEXT is the input, PLAINTEXT the expected output.
+    Decrypt,
+}
+
+/// One COUNT entry from a `.rsp` file.
+#[derive(Clone, Debug)]
+pub struct KatCase {
+    /// Section the entry belongs to.
+    pub direction: Direction,
+    /// Value of the COUNT line.
+    pub count: u32,
+    /// Raw key bytes.
+    pub key: Vec<u8>,
+    /// Plaintext (one or more blocks).
+    pub plaintext: Vec<u8>,
+    /// Ciphertext (one or more blocks).
+    pub ciphertext: Vec<u8>,
+}
+
+#[derive(Default)]
+struct Partial {
+    count: Option<u32>,
+    key: Option<Vec<u8>>,
+    plaintext: Option<Vec<u8>>,
+    ciphertext: Option<Vec<u8>>,
+}
+
+impl Partial {
+    fn finish(&mut self, direction: Option<Direction>, line: usize) -> Option<KatCase> {
+        let partial = std::mem::take(self);
+        let count = partial.count?;
+        let direction =
+            direction.unwrap_or_else(|| panic!("line {line}: COUNT {count} outside a section"));
+        let field = |value: Option<Vec<u8>>, name: &str| {
+            value.unwrap_or_else(|| panic!("line {line}: COUNT {count} is missing {name}"))
+        };
+        Some(KatCase {
+            direction,
+            count,
+            key: field(partial.key, "KEY"),
+            plaintext: field(partial.plaintext, "PLAINTEXT"),
+            ciphertext: field(partial.ciphertext, "CIPHERTEXT"),
+        })
+    }
+}
+
+fn decode_hex(value: &str, line: usize) -> Vec<u8> {
+    assert!(
+        value.len().is_multiple_of(2),
+        "line {line}: odd-length hex value"
+    );
+    (0..value.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&value[i..i + 2], 16)
+                .unwrap_or_else(|_| panic!("line {line}: invalid hex value {value:?}"))
+        })
+        .collect()
+}
+
+/// Parses the COUNT/KEY/PLAINTEXT/CIPHERTEXT records of a `.rsp` file.
+///
+/// `#` comments and blank lines are skipped, and `[ENCRYPT]`/`[DECRYPT]`
+/// headers switch the direction of subsequent records.
+pub fn parse_rsp(text: &str) -> Vec<KatCase> {
+    let mut cases = Vec::new();
+    let mut direction = None;
+    let mut current = Partial::default();
+
+    for (idx, raw) in text.lines().enumerate() {
+        let line_no = idx + 1;
+        let line = raw.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if line.starts_with('[') {
+            cases.extend(current.finish(direction, line_no));
+            direction = match line {
+                "[ENCRYPT]" => Some(Direction::Encrypt),
+                "[DECRYPT]" => Some(Direction::Decrypt),
+                other => panic!("line {line_no}: unknown section {other}"),
+            };
+            continue;
+        }
+        let (name, value) = line
+            .split_once('=')
+            .unwrap_or_else(|| panic!("line {line_no}: expected NAME = VALUE"));
+        let value = value.trim();
+        match name.trim() {
+            "COUNT" => {
+                cases.extend(current.finish(direction, line_no));
+                current.count = Some(
+                    value
+                        .parse()
+                        .unwrap_or_else(|_| panic!("line {line_no}: invalid COUNT")),
+                );
+            }
+            "KEY" => current.key = Some(decode_hex(value, line_no)),
+            "PLAINTEXT" => current.plaintext = Some(decode_hex(value, line_no)),
+            "CIPHERTEXT" => current.ciphertext = Some(decode_hex(value, line_no)),
+            other => panic!("line {line_no}: unknown field {other}"),
+        }
+    }
+    cases.extend(current.finish(direction, text.lines().count()));
+    cases
+}
+
+/// Reads and parses a `.rsp` file.
+pub fn load_rsp(path: &Path) -> Vec<KatCase> {
+    let text =
+        fs::read_to_string(path).unwrap_or_else(|err| panic!("read {}: {err}", path.display()));
+    parse_rsp(&text)
+}
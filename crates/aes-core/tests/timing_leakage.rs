@@ -0,0 +1,33 @@
+//! Fixed-vs-random timing measurement of `encrypt_block`.
+//!
+//! This is a measurement, not a gate: the reference cipher uses table lookups
+//! and is expected to leak. Run with
+//! `cargo test -p aes-core --release --test timing_leakage -- --ignored --nocapture`.
+
+use aes_core::timing::{run, TimingConfig};
+use aes_core::{encrypt_block, expand_key, Aes128Key, Block};
+use rand::{Rng, SeedableRng};
+
+#[test]
+#[ignore = "timing measurement; slow and noisy"]
+fn encrypt_block_fixed_vs_random() {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(288);
+    let round_keys = expand_key(&Aes128Key::from(rng.gen::<[u8; 16]>()));
+    let config = TimingConfig::default();
+
+    let report = run(
+        &config,
+        &[0u8; 16],
+        || rng.gen::<Block>(),
+        |block| encrypt_block(block, &round_keys),
+    );
+
+    println!("encrypt_block: {report}");
+    if report.looks_leaky() {
+        println!("encrypt_block: timing distinguishes fixed from random inputs");
+    }
+    assert_eq!(
+        report.fixed_samples + report.random_samples + report.discarded,
+        config.samples
+    );
+}
@@ -3,15 +3,19 @@
 #![forbid(unsafe_code)]
 
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
 
-use aes_core::{decrypt_block, encrypt_block, expand_key, Aes128Key};
+use aes_core::modes::Ecb;
+use aes_core::{expand_key, verify_pairs, Aes128Key, SecretAes128Key};
 use anyhow::{bail, Context, Result};
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
 use rand::{CryptoRng, RngCore, SeedableRng};
 use rand_chacha::ChaCha20Rng;
-use wbaes_gen::{Generator, GeneratorConfig, WbInstance256};
+use wbaes_gen::{evaluation, ExternalEncodingKind, Generator, GeneratorConfig, WbInstance256};
+use wbaes_runtime::framing::{self, FrameHeader, FrameMode};
 use wbaes_runtime::WbCipher256;
+use zeroize::Zeroize;
 
 /// White-box AES CLI.
 #[derive(Parser)]
@@ -26,13 +30,24 @@ struct Cli {
     command: Commands,
 }
 
+/// Key source shared by commands that need the AES key.
+#[derive(Args)]
+#[group(required = true, multiple = false)]
+struct KeyArgs {
+    /// AES-128 key as 32 hex characters.
+    #[arg(long, value_name = "HEX")]
+    key_hex: Option<String>,
+    /// File holding the AES-128 key as PKCS#8 PEM/DER, hex, or 16 raw bytes.
+    #[arg(long, value_name = "FILE")]
+    key_file: Option<PathBuf>,
+}
+
 #[derive(Subcommand)]
 enum Commands {
     /// Generate a white-box instance from a key.
     Gen {
-        /// AES-128 key as 32 hex characters.
-        #[arg(long, value_name = "HEX")]
-        key_hex: String,
+        #[command(flatten)]
+        key: KeyArgs,
         /// Output path for the serialized instance.
         #[arg(long, value_name = "FILE")]
         out: PathBuf,
@@ -42,6 +57,18 @@ enum Commands {
         /// Enable external encodings (defaults off for easier testing).
         #[arg(long, default_value_t = false)]
         external_encodings: bool,
+        /// Make the external encodings per-byte maps the caller applies at the boundary.
+        #[arg(long, default_value_t = false, requires = "external_encodings")]
+        per_byte_encodings: bool,
+        /// Make the external encodings banded with this many super-diagonal blocks (1..=31).
+        #[arg(
+            long,
+            value_name = "N",
+            requires = "external_encodings",
+            conflicts_with = "per_byte_encodings",
+            value_parser = clap::value_parser!(u8).range(1..32)
+        )]
+        encoding_bandwidth: Option<u8>,
     },
     /// Encrypt 32-byte blocks from a file using a white-box instance.
     Enc {
@@ -54,30 +81,37 @@ enum Commands {
         /// Output ciphertext path.
         #[arg(long, value_name = "FILE")]
         output: PathBuf,
+        /// Write headerless ciphertext blocks instead of a framed container.
+        #[arg(long, default_value_t = false)]
+        raw: bool,
     },
     /// Decrypt 32-byte blocks using the AES key (assumes no external encodings).
     Dec {
         /// Path to the serialized instance (used to sanity-check encoding settings).
         #[arg(long, value_name = "FILE")]
         instance: PathBuf,
-        /// AES-128 key as 32 hex characters.
-        #[arg(long, value_name = "HEX")]
-        key_hex: String,
+        #[command(flatten)]
+        key: KeyArgs,
         /// Input file (ciphertext).
         #[arg(long, value_name = "FILE")]
         input: PathBuf,
         /// Output plaintext path.
         #[arg(long, value_name = "FILE")]
         output: PathBuf,
+        /// Treat the input as headerless ciphertext blocks.
+        #[arg(long, default_value_t = false)]
+        raw: bool,
+        /// Fail instead of warning when the header names a different instance.
+        #[arg(long, default_value_t = false, conflicts_with = "raw")]
+        strict: bool,
     },
     /// Verify a white-box instance matches AES for random samples.
     Check {
         /// Path to the serialized instance.
         #[arg(long, value_name = "FILE")]
         instance: PathBuf,
-        /// AES-128 key as 32 hex characters.
-        #[arg(long, value_name = "HEX")]
-        key_hex: String,
+        #[command(flatten)]
+        key: KeyArgs,
         /// Number of random samples to test.
         #[arg(long, default_value_t = 4)]
         samples: usize,
@@ -85,6 +119,20 @@ enum Commands {
         #[arg(long)]
         seed: Option<u64>,
     },
+    /// Compare generator configurations: generation time, size, and runtime throughput.
+    Evaluate {
+        /// JSON file holding an array of generator configurations.
+        #[arg(long, value_name = "FILE")]
+        configs: PathBuf,
+        /// Output path for the JSON report.
+        #[arg(long, value_name = "FILE")]
+        out: PathBuf,
+        #[command(flatten)]
+        key: KeyArgs,
+        /// Optional RNG seed shared by every configuration.
+        #[arg(long)]
+        seed: Option<u64>,
+    },
     /// Run a local demo: generate key + instance, encrypt random data, decrypt back.
     Demo {
         /// Optional RNG seed for reproducibility.
@@ -97,49 +145,77 @@ fn main() -> Result<()> {
     let cli = Cli::parse();
     match cli.command {
         Commands::Gen {
-            key_hex,
+            key,
             out,
             seed,
             external_encodings,
-        } => cmd_gen(&key_hex, &out, seed, external_encodings),
+            per_byte_encodings,
+            encoding_bandwidth,
+        } => {
+            let mut config = GeneratorConfig {
+                external_encodings,
+                ..GeneratorConfig::default()
+            };
+            if per_byte_encodings {
+                config.external_encoding_kind = ExternalEncodingKind::PerByte;
+            } else if let Some(bandwidth) = encoding_bandwidth {
+                config.external_encoding_kind = ExternalEncodingKind::Banded;
+                config.encoding_bandwidth = usize::from(bandwidth);
+            }
+            cmd_gen(&key, &out, seed, config)
+        }
         Commands::Enc {
             instance,
             input,
             output,
-        } => cmd_enc(&instance, &input, &output),
+            raw,
+        } => cmd_enc(&instance, &input, &output, raw),
         Commands::Dec {
             instance,
-            key_hex,
+            key,
             input,
             output,
-        } => cmd_dec(&instance, &key_hex, &input, &output),
+            raw,
+            strict,
+        } => cmd_dec(&instance, &key, &input, &output, raw, strict),
         Commands::Check {
             instance,
-            key_hex,
+            key,
             samples,
             seed,
-        } => cmd_check(&instance, &key_hex, samples, seed),
+        } => cmd_check(&instance, &key, samples, seed),
+        Commands::Evaluate {
+            configs,
+            out,
+            key,
+            seed,
+        } => cmd_evaluate(&configs, &out, &key, seed),
         Commands::Demo { seed } => cmd_demo(seed),
     }
 }
 
 fn cmd_gen(
-    key_hex: &str,
+    key_args: &KeyArgs,
     out: &PathBuf,
     seed: Option<u64>,
-    external_encodings: bool,
+    config: GeneratorConfig,
 ) -> Result<()> {
-    let key = parse_key_hex(key_hex)?;
-    let rng = seeded_rng(seed);
-    let mut gen = Generator::with_config(rng, GeneratorConfig { external_encodings });
+    let key = load_key(key_args)?;
+    let mut gen = Generator::from_seed_with_config(seed_bytes(seed), config);
     let instance = gen.generate_instance(&key);
     let bytes = instance.to_bytes().context("serialize instance")?;
     fs::write(out, bytes).with_context(|| format!("write {}", out.display()))?;
     Ok(())
 }
 
-fn cmd_enc(instance_path: &PathBuf, input_path: &PathBuf, output_path: &PathBuf) -> Result<()> {
+fn cmd_enc(
+    instance_path: &PathBuf,
+    input_path: &PathBuf,
+    output_path: &PathBuf,
+    raw: bool,
+) -> Result<()> {
     let instance = load_instance(instance_path)?;
+    let fingerprint = (!raw).then(|| instance.fingerprint());
     let cipher = WbCipher256::new(instance);
     let mut data =
         fs::read(input_path).with_context(|| format!("read {}", input_path.display()))?;
@@ -152,44 +228,42 @@ fn cmd_enc(instance_path: &PathBuf, input_path: &PathBuf, output_path: &PathBuf)
         cipher.encrypt_block(&mut block);
         chunk.copy_from_slice(&block);
     }
+    let data = frame_ciphertext(data, fingerprint)?;
     fs::write(output_path, data).with_context(|| format!("write {}", output_path.display()))?;
     Ok(())
 }
 
 fn cmd_dec(
     instance_path: &PathBuf,
-    key_hex: &str,
+    key_args: &KeyArgs,
     input_path: &PathBuf,
     output_path: &PathBuf,
+    raw: bool,
+    strict: bool,
 ) -> Result<()> {
     let instance = load_instance(instance_path)?;
-    if instance.encodings.output.is_some() {
+    if instance.encodings.output.is_some() || instance.encodings.per_byte.is_some() {
         bail!("decryption is not supported when an external output encoding is present");
     }
-    let key = parse_key_hex(key_hex)?;
-    let round_keys = expand_key(&key);
-    let mut data =
-        fs::read(input_path).with_context(|| format!("read {}", input_path.display()))?;
+    let key = load_key(key_args)?;
+    let ecb = Ecb::new_i_know_ecb_is_insecure(&key);
+    let input = fs::read(input_path).with_context(|| format!("read {}", input_path.display()))?;
+    let mut data = if raw {
+        input
+    } else {
+        unframe_ciphertext(&input, &instance.fingerprint(), strict)?.to_vec()
+    };
     if data.len() % 32 != 0 {
         bail!("input length must be a multiple of 32 bytes");
     }
-    for chunk in data.chunks_mut(32) {
-        let mut b1 = [0u8; 16];
-        let mut b2 = [0u8; 16];
-        b1.copy_from_slice(&chunk[..16]);
-        b2.copy_from_slice(&chunk[16..]);
-        let pt1 = decrypt_block(&b1, &round_keys);
-        let pt2 = decrypt_block(&b2, &round_keys);
-        chunk[..16].copy_from_slice(&pt1);
-        chunk[16..].copy_from_slice(&pt2);
-    }
+    ecb.decryptor().decrypt(&mut data)?;
     fs::write(output_path, data).with_context(|| format!("write {}", output_path.display()))?;
     Ok(())
 }
 
 fn cmd_check(
     instance_path: &PathBuf,
-    key_hex: &str,
+    key_args: &KeyArgs,
     samples: usize,
     seed: Option<u64>,
 ) -> Result<()> {
@@ -197,30 +271,54 @@ fn cmd_check(
     if instance.encodings.output.is_some() {
         bail!("check expects instances with output encodings folded into the tables");
     }
+    let per_byte = instance.encodings.per_byte;
     let cipher = WbCipher256::new(instance);
-    let key = parse_key_hex(key_hex)?;
-    let round_keys = expand_key(&key);
+    let key = load_key(key_args)?;
     let mut rng = seeded_rng(seed);
 
-    for _ in 0..samples {
-        let mut block = [0u8; 32];
-        rng.fill_bytes(&mut block);
-        let mut expected = block;
-        let mut first = [0u8; 16];
-        let mut second = [0u8; 16];
-        first.copy_from_slice(&block[..16]);
-        second.copy_from_slice(&block[16..]);
-        let b1 = encrypt_block(&first, &round_keys);
-        let b2 = encrypt_block(&second, &round_keys);
-        expected[..16].copy_from_slice(&b1);
-        expected[16..].copy_from_slice(&b2);
-
-        let mut actual = block;
+    verify_pairs(samples, &mut rng, &expand_key(&key), |block| {
+        let mut actual = *block;
+        if let Some(per_byte) = &per_byte {
+            per_byte.encode_input(&mut actual);
+        }
         cipher.encrypt_block(&mut actual);
-        if actual != expected {
-            bail!("mismatch between white-box and AES outputs");
+        if let Some(per_byte) = &per_byte {
+            per_byte.decode_output(&mut actual);
         }
-    }
+        actual
+    })
+    .context("mismatch between white-box and AES outputs")?;
+    Ok(())
+}
+
+fn cmd_evaluate(
+    configs_path: &PathBuf,
+    out: &PathBuf,
+    key_args: &KeyArgs,
+    seed: Option<u64>,
+) -> Result<()> {
+    const THROUGHPUT_BLOCKS: usize = 1 << 14;
+
+    let text = fs::read_to_string(configs_path)
+        .with_context(|| format!("read {}", configs_path.display()))?;
+    let configs: Vec<GeneratorConfig> =
+        serde_json::from_str(&text).context("parse generator configurations")?;
+    let key = load_key(key_args)?;
+
+    let rows = evaluation::run_matrix_with(&configs, &key, seed_bytes(seed), |instance| {
+        let cipher = WbCipher256::new(instance);
+        let mut block = [0u8; 32];
+        let start = Instant::now();
+        for _ in 0..THROUGHPUT_BLOCKS {
+            cipher.encrypt_block(&mut block);
+        }
+        let secs = start.elapsed().as_secs_f64();
+        Some((THROUGHPUT_BLOCKS * 32) as f64 / secs / (1024.0 * 1024.0))
+    })
+    .context("evaluate generator configurations")?;
+
+    let report = serde_json::to_string_pretty(&rows).context("serialize report")?;
+    fs::write(out, report).with_context(|| format!("write {}", out.display()))?;
     Ok(())
 }
 
@@ -228,13 +326,16 @@ fn cmd_demo(seed: Option<u64>) -> Result<()> {
     let mut rng = seeded_rng(seed);
     let mut key_bytes = [0u8; 16];
     rng.fill_bytes(&mut key_bytes);
-    let key = Aes128Key::from(key_bytes);
+    let key = SecretAes128Key::from(key_bytes);
+    let key_hex = hex::encode(key_bytes);
+    key_bytes.zeroize();
 
     let gen_seed = derive_seed(&mut rng);
-    let mut gen = Generator::with_config(
-        ChaCha20Rng::from_seed(gen_seed),
+    let mut gen = Generator::from_seed_with_config(
+        gen_seed,
         GeneratorConfig {
             external_encodings: false,
+            ..GeneratorConfig::default()
         },
     );
     let instance = gen.generate_instance(&key);
@@ -244,22 +345,16 @@ fn cmd_demo(seed: Option<u64>) -> Result<()> {
     rng.fill_bytes(&mut block);
     let plaintext_hex = hex::encode(block);
 
-    let round_keys = expand_key(&key);
     cipher.encrypt_block(&mut block);
     let ciphertext_hex = hex::encode(block);
 
     let mut decrypted = block;
-    let mut first = [0u8; 16];
-    let mut second = [0u8; 16];
-    first.copy_from_slice(&decrypted[..16]);
-    second.copy_from_slice(&decrypted[16..]);
-    let pt1 = decrypt_block(&first, &round_keys);
-    let pt2 = decrypt_block(&second, &round_keys);
-    decrypted[..16].copy_from_slice(&pt1);
-    decrypted[16..].copy_from_slice(&pt2);
+    Ecb::new_i_know_ecb_is_insecure(&key)
+        .decryptor()
+        .decrypt(&mut decrypted)?;
 
     let decrypted_hex = hex::encode(decrypted);
-    println!("demo key: {}", hex::encode(key_bytes));
+    println!("demo key: {}", key_hex);
     println!("plaintext: {}", plaintext_hex);
     println!("ciphertext: {}", ciphertext_hex);
     println!("decrypted: {}", decrypted_hex);
@@ -269,34 +364,98 @@ fn cmd_demo(seed: Option<u64>) -> Result<()> {
     Ok(())
 }
 
+/// Prepends a `Block256` container header unless `fingerprint` is `None` (raw output).
+fn frame_ciphertext(ciphertext: Vec<u8>, fingerprint: Option<[u8; 32]>) -> Result<Vec<u8>> {
+    let Some(fingerprint) = fingerprint else {
+        return Ok(ciphertext);
+    };
+    let mut framed = Vec::with_capacity(ciphertext.len() + 64);
+    framing::write_header(&mut framed, &FrameHeader::block256(fingerprint))?;
+    framed.extend_from_slice(&ciphertext);
+    Ok(framed)
+}
+
+/// Strips the container header, cross-checking the producing instance's fingerprint.
+fn unframe_ciphertext<'a>(
+    data: &'a [u8],
+    fingerprint: &[u8; 32],
+    strict: bool,
+) -> Result<&'a [u8]> {
+    let (header, body) = framing::read_header(data).context("parse ciphertext header")?;
+    if header.mode != FrameMode::Block256 {
+        bail!("unsupported ciphertext mode {:?}", header.mode);
+    }
+    if let Err(err) = header.check_fingerprint(fingerprint) {
+        if strict {
+            return Err(err.into());
+        }
+        eprintln!("warning: {err}");
+    }
+    Ok(body)
+}
+
+fn load_key(args: &KeyArgs) -> Result<SecretAes128Key> {
+    let key = match (&args.key_hex, &args.key_file) {
+        (Some(hex_str), _) => parse_key_hex(hex_str)?,
+        (None, Some(path)) => load_key_file(path)?,
+        (None, None) => bail!("either --key-hex or --key-file is required"),
+    };
+    Ok(SecretAes128Key::from(key))
+}
+
+/// Reads a key file, auto-detecting PEM, hex, raw 16-byte, or DER contents.
+fn load_key_file(path: &Path) -> Result<Aes128Key> {
+    let bytes = fs::read(path).with_context(|| format!("read {}", path.display()))?;
+    if let Ok(text) = std::str::from_utf8(&bytes) {
+        if text.contains("-----BEGIN") {
+            return Aes128Key::from_pem(text).context("parse PEM key file");
+        }
+        let trimmed = text.trim();
+        if trimmed.len() == 32 && trimmed.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return parse_key_hex(trimmed);
+        }
+    }
+    if let Ok(key) = Aes128Key::try_from(bytes.as_slice()) {
+        return Ok(key);
+    }
+    match bytes.first() {
+        Some(0x30) => Aes128Key::from_pkcs8_der(&bytes).context("parse PKCS#8 DER key file"),
+        Some(0x04) => Aes128Key::from_raw_der(&bytes).context("parse DER key file"),
+        _ => bail!(
+            "unrecognized key file format in {} (expected PEM, hex, raw, or DER)",
+            path.display()
+        ),
+    }
+}
+
 fn parse_key_hex(hex_str: &str) -> Result<Aes128Key> {
-    let bytes = hex::decode(hex_str.trim()).context("decode key hex")?;
+    let mut bytes = hex::decode(hex_str.trim()).context("decode key hex")?;
     if bytes.len() != 16 {
+        bytes.zeroize();
         bail!("AES-128 key must be 16 bytes (32 hex characters)");
     }
     let mut key = [0u8; 16];
     key.copy_from_slice(&bytes);
+    bytes.zeroize();
     Ok(Aes128Key::from(key))
 }
 
 fn load_instance(path: &PathBuf) -> Result<WbInstance256> {
     let bytes = fs::read(path).with_context(|| format!("read {}", path.display()))?;
-    WbInstance256::from_bytes(&bytes).context("deserialize instance")
+    WbInstance256::from_bytes_validated(&bytes).context("load instance")
 }
 
 fn seeded_rng(seed: Option<u64>) -> impl RngCore + CryptoRng {
+    ChaCha20Rng::from_seed(seed_bytes(seed))
+}
+
+fn seed_bytes(seed: Option<u64>) -> [u8; 32] {
+    let mut seed_bytes = [0u8; 32];
     match seed {
-        Some(value) => {
-            let mut seed_bytes = [0u8; 32];
-            seed_bytes[..8].copy_from_slice(&value.to_le_bytes());
-            ChaCha20Rng::from_seed(seed_bytes)
-        }
-        None => {
-            let mut seed_bytes = [0u8; 32];
-            rand::rngs::OsRng.fill_bytes(&mut seed_bytes);
-            ChaCha20Rng::from_seed(seed_bytes)
-        }
+        Some(value) => seed_bytes[..8].copy_from_slice(&value.to_le_bytes()),
+        None => rand::rngs::OsRng.fill_bytes(&mut seed_bytes),
     }
+    seed_bytes
 }
 
 fn derive_seed(rng: &mut impl RngCore) -> [u8; 32] {
@@ -304,3 +463,34 @@ fn derive_seed(rng: &mut impl RngCore) -> [u8; 32] {
     rng.fill_bytes(&mut seed_bytes);
     seed_bytes
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn raw_output_is_unchanged_ciphertext() {
+        let ciphertext: Vec<u8> = (0..64u8).collect();
+        let out = frame_ciphertext(ciphertext.clone(), None).expect("frame");
+        assert_eq!(out, ciphertext);
+    }
+
+    #[test]
+    fn framed_output_round_trips() {
+        let ciphertext: Vec<u8> = (0..64u8).collect();
+        let fingerprint = [9u8; 32];
+        let framed = frame_ciphertext(ciphertext.clone(), Some(fingerprint)).expect("frame");
+        assert!(framed.starts_with(&framing::FRAME_MAGIC));
+        let body = unframe_ciphertext(&framed, &fingerprint, true).expect("unframe");
+        assert_eq!(body, ciphertext.as_slice());
+    }
+
+    #[test]
+    fn strict_mode_rejects_foreign_instance() {
+        let ciphertext = vec![0u8; 32];
+        let framed = frame_ciphertext(ciphertext.clone(), Some([1u8; 32])).expect("frame");
+        assert!(unframe_ciphertext(&framed, &[2u8; 32], true).is_err());
+        let body = unframe_ciphertext(&framed, &[2u8; 32], false).expect("warn only");
+        assert_eq!(body, ciphertext.as_slice());
+    }
+}
@@ -1,12 +1,158 @@
 //! Affine encodings over GF(2).
 
+use aes_core::{xor_in_place, Block, DoubleBlock};
 use rand::{CryptoRng, RngCore};
 use serde::{Deserialize, Serialize};
+use zeroize::Zeroize;
 
-use crate::matrix::{Matrix256, Matrix8};
+use crate::fingerprint::serialized_sha256;
+use crate::gf2;
+use crate::matrix::{Matrix128, Matrix256, Matrix8};
+use crate::stats::GenStats;
+use crate::vec256::Vec256;
+
+/// 4-bit affine map `x -> lin * x ⊕ bias` on the low nibble of a byte.
+///
+/// The 4×4 GF(2) matrix is stored as four row nibbles: bit `col` of `rows[row]` is entry
+/// `(row, col)`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Affine4 {
+    rows: [u8; 4],
+    bias: u8,
+}
+
+impl Affine4 {
+    /// Constructs a map from row nibbles and a bias; bits above the low nibble are dropped.
+    pub const fn new(rows: [u8; 4], bias: u8) -> Self {
+        Self {
+            rows: [rows[0] & 0xf, rows[1] & 0xf, rows[2] & 0xf, rows[3] & 0xf],
+            bias: bias & 0xf,
+        }
+    }
+
+    /// Identity affine map.
+    pub const fn identity() -> Self {
+        Self::new([1, 2, 4, 8], 0)
+    }
+
+    /// Generates a random invertible affine map, retrying until the matrix is invertible.
+    ///
+    /// About 31% of 4×4 matrices are invertible, so this takes three draws on average.
+    pub fn random<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
+        loop {
+            let bits = rng.next_u32();
+            let candidate = Self::new(
+                [
+                    bits as u8,
+                    (bits >> 4) as u8,
+                    (bits >> 8) as u8,
+                    (bits >> 12) as u8,
+                ],
+                (bits >> 16) as u8,
+            );
+            if candidate.invert().is_some() {
+                return candidate;
+            }
+        }
+    }
+
+    /// Returns the row nibbles of the linear part.
+    pub fn rows(&self) -> [u8; 4] {
+        self.rows
+    }
+
+    /// Returns the bias nibble.
+    pub fn bias(&self) -> u8 {
+        self.bias
+    }
+
+    fn apply_linear(rows: &[u8; 4], value: u8) -> u8 {
+        let mut out = 0u8;
+        for (row_idx, row) in rows.iter().enumerate() {
+            out |= u8::from(gf2::parity_u8(row & value)) << row_idx;
+        }
+        out
+    }
+
+    /// Applies the affine map to the low nibble of `value`.
+    pub fn apply(&self, value: u8) -> u8 {
+        Self::apply_linear(&self.rows, value & 0xf) ^ self.bias
+    }
+
+    /// Returns the inverse map, if the linear part is invertible.
+    pub fn invert(&self) -> Option<Self> {
+        let mut left = self.rows;
+        let mut right = [1u8, 2, 4, 8];
+        for col in 0..4 {
+            let pivot = (col..4).find(|&row| (left[row] >> col) & 1 == 1)?;
+            left.swap(col, pivot);
+            right.swap(col, pivot);
+            for row in 0..4 {
+                if row != col && (left[row] >> col) & 1 == 1 {
+                    left[row] ^= left[col];
+                    right[row] ^= right[col];
+                }
+            }
+        }
+        Some(Self::new(right, Self::apply_linear(&right, self.bias)))
+    }
+
+    /// Composes `self` after `other` (i.e., `self ∘ other`).
+    pub fn compose(&self, other: &Self) -> Self {
+        let mut rows = [0u8; 4];
+        for (row, out) in rows.iter_mut().enumerate() {
+            for col in 0..4 {
+                let column = Self::apply_linear(&other.rows, 1 << col);
+                *out |= ((Self::apply_linear(&self.rows, column) >> row) & 1) << col;
+            }
+        }
+        Self::new(rows, self.apply(other.bias))
+    }
+
+    /// Composes in pipeline order: applies `self`, then `next` (i.e., `next ∘ self`).
+    pub fn then(&self, next: &Self) -> Self {
+        next.compose(self)
+    }
+}
+
+/// Byte encoding made of independent [`Affine4`] maps on the high and low nibbles.
+///
+/// This is the nibble-wise layout of Chow-style constructions. With affine nibble maps the
+/// result is itself an 8-bit affine map with a block-diagonal linear part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ConcatenatedNibbleEncoding {
+    /// Map applied to bits 4..8.
+    pub high: Affine4,
+    /// Map applied to bits 0..4.
+    pub low: Affine4,
+}
+
+impl ConcatenatedNibbleEncoding {
+    /// Constructs an encoding from its nibble maps.
+    pub const fn new(high: Affine4, low: Affine4) -> Self {
+        Self { high, low }
+    }
+
+    /// Generates independent random invertible nibble maps.
+    pub fn random<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
+        let high = Affine4::random(rng);
+        let low = Affine4::random(rng);
+        Self::new(high, low)
+    }
+
+    /// Applies the encoding to a byte.
+    pub fn apply(&self, value: u8) -> u8 {
+        (self.high.apply(value >> 4) << 4) | self.low.apply(value)
+    }
+
+    /// Returns the inverse encoding, if both nibble maps are invertible.
+    pub fn invert(&self) -> Option<Self> {
+        Some(Self::new(self.high.invert()?, self.low.invert()?))
+    }
+}
 
 /// 8-bit affine map `x -> lin * x ⊕ bias`.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Affine8 {
     /// Linear component.
     pub lin: Matrix8,
@@ -50,45 +196,238 @@ impl Affine8 {
         let bias = self.lin.apply(other.bias) ^ self.bias;
         Self::new(lin, bias)
     }
+
+    /// Composes in pipeline order: applies `self`, then `next` (i.e., `next ∘ self`).
+    pub fn then(&self, next: &Self) -> Self {
+        next.compose(self)
+    }
+
+    /// Returns every `x` with `self(x) = x`, by exhaustive search.
+    pub fn fixed_points(&self) -> Vec<u8> {
+        (0..=255u8).filter(|&x| self.apply(x) == x).collect()
+    }
+
+    /// Recovers `f` as an affine map, or returns `None` if it is not affine.
+    ///
+    /// The map is read off `f(0)` and the eight basis vectors, then checked on all 256 inputs.
+    /// The linear part need not be invertible.
+    pub fn fit(f: impl Fn(u8) -> u8) -> Option<Self> {
+        let bias = f(0);
+        let lin = Matrix8::from_cols(core::array::from_fn(|bit| f(1 << bit) ^ bias));
+        let fitted = Self::new(lin, bias);
+        (0..=255u8)
+            .all(|x| fitted.apply(x) == f(x))
+            .then_some(fitted)
+    }
 }
 
 /// 256-bit affine map `x -> lin * x ⊕ bias`.
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Affine256 {
     /// Linear component.
     pub lin: Matrix256,
     /// Additive bias.
-    pub bias: [u8; 32],
+    pub bias: Vec256,
 }
 
 impl Affine256 {
     /// Constructs a new affine map from components.
-    pub const fn new(lin: Matrix256, bias: [u8; 32]) -> Self {
+    pub const fn new(lin: Matrix256, bias: Vec256) -> Self {
+        Self { lin, bias }
+    }
+
+    /// Identity affine map.
+    pub fn identity() -> Self {
+        Self::new(Matrix256::identity(), Vec256::ZERO)
+    }
+
+    /// Generates a random affine map using a sparse unsplit invertible linear part.
+    pub fn random_sparse_unsplit<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
+        Self::random_sparse_unsplit_with_stats(rng, &mut GenStats::default())
+    }
+
+    /// [`Self::random_sparse_unsplit`], counting its draws into `stats`.
+    pub fn random_sparse_unsplit_with_stats<R: RngCore + CryptoRng>(
+        rng: &mut R,
+        stats: &mut GenStats,
+    ) -> Self {
+        let lin = Matrix256::random_sparse_unsplit_with_stats(rng, stats);
+        debug_assert_eq!(lin.validate_banded_unsplit(), Ok(()));
+        let bias = Vec256::random(rng);
+        Self::new(lin, bias)
+    }
+
+    /// Generates a random affine map whose linear part is [`Matrix256::random_banded`] with
+    /// the given bandwidth.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless `1 <= bandwidth <= 31`.
+    pub fn random_banded<R: RngCore + CryptoRng>(rng: &mut R, bandwidth: usize) -> Self {
+        let lin = Matrix256::random_banded(rng, bandwidth);
+        let bias = Vec256::random(rng);
+        Self::new(lin, bias)
+    }
+
+    /// Generates a random affine map with a dense invertible linear part.
+    ///
+    /// Used for external encodings, which are never split into tables and so gain nothing
+    /// from the banded structure.
+    pub fn random_dense<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
+        let lin = Matrix256::random_invertible_dense(rng);
+        let bias = Vec256::random(rng);
+        Self::new(lin, bias)
+    }
+
+    /// Applies `per_byte[i]` to byte `i` independently: the concatenated byte encoding
+    /// `f_0 ‖ … ‖ f_31`, with a block-diagonal linear part.
+    pub fn concat_bytes(per_byte: &[Affine8; 32]) -> Self {
+        let lin = Matrix256::block_diag32(&per_byte.map(|f| f.lin));
+        Self::new(lin, Vec256::new(per_byte.map(|f| f.bias)))
+    }
+
+    /// Applies the affine map.
+    pub fn apply(&self, value: &DoubleBlock) -> DoubleBlock {
+        Vec256::from(self.lin.apply_to_bytes(value))
+            .xor(&self.bias)
+            .into()
+    }
+
+    /// Applies the affine map in place.
+    pub fn apply_in_place(&self, value: &mut DoubleBlock) {
+        *value = self.apply(value);
+    }
+
+    /// Applies the inverse map, if the linear part is invertible.
+    pub fn invert(&self) -> Option<Self> {
+        let lin_inv = self.lin.invert()?;
+        let bias = lin_inv.apply_to_bytes(self.bias.as_bytes()).into();
+        Some(Self::new(lin_inv, bias))
+    }
+
+    /// Composes `self` after `other` (i.e., `self ∘ other`).
+    pub fn compose(&self, other: &Self) -> Self {
+        let mut out = Self::identity();
+        self.compose_into(other, &mut out);
+        out
+    }
+
+    /// Writes `self ∘ other` into `out`, reusing its matrix allocation.
+    pub fn compose_into(&self, other: &Self, out: &mut Self) {
+        self.lin.mul_into(&other.lin, &mut out.lin);
+        let bias_from_other = self.lin.apply_to_bytes(other.bias.as_bytes());
+        out.bias = self.bias.xor(&bias_from_other.into());
+    }
+
+    /// Composes in pipeline order: applies `self`, then `next` (i.e., `next ∘ self`).
+    ///
+    /// Prefer this to [`Self::compose`] when building an encoding chain stage by stage, so the
+    /// code reads in the order the maps are applied.
+    pub fn then(&self, next: &Self) -> Self {
+        next.compose(self)
+    }
+
+    /// Writes `next ∘ self` into `out`, reusing its matrix allocation.
+    pub fn then_into(&self, next: &Self, out: &mut Self) {
+        next.compose_into(self, out);
+    }
+
+    /// Describes the fixed points `{x : self(x) = x}`, the solutions of `(lin ⊕ I)·x = bias`.
+    ///
+    /// Returns an empty vector if there are none. Otherwise the first element is one fixed
+    /// point `p` and the rest are a kernel basis `k_1..k_d` of `lin ⊕ I`; the fixed points are
+    /// exactly `p ⊕ span(k_1..k_d)`.
+    pub fn fixed_points(&self) -> Vec<DoubleBlock> {
+        let shifted = self.lin.add(&Matrix256::identity());
+        let Some(particular) = shifted.solve(self.bias.as_bytes()) else {
+            return Vec::new();
+        };
+        let mut points = vec![particular];
+        points.extend(shifted.kernel_basis());
+        points
+    }
+
+    /// Recovers `f` as an affine map from `f(0)` and the 256 basis vectors, or returns `None`
+    /// if it disagrees with `f` on one of `samples` uniformly random inputs.
+    ///
+    /// Unlike [`Affine8::fit`] the check is probabilistic: a map that is affine except on a
+    /// small set of inputs can pass. The linear part need not be invertible.
+    pub fn fit(f: impl Fn(&DoubleBlock) -> DoubleBlock, samples: usize) -> Option<Self> {
+        let bias = Vec256::from(f(&[0u8; 32]));
+        let lin = Matrix256::from_linear_transform(|v| {
+            *v = Vec256::from(f(v)).xor(&bias).into();
+        });
+        let fitted = Self::new(lin, bias);
+        fitted
+            .agrees_with(f, samples, &mut rand::thread_rng())
+            .then_some(fitted)
+    }
+
+    /// Returns whether `f` agrees with the map on `samples` random inputs.
+    ///
+    /// The affine counterpart of [`Matrix256::agrees_with`]; two distinct affine maps agree on
+    /// a random input with probability at most 1/2.
+    pub fn agrees_with<R: RngCore>(
+        &self,
+        f: impl Fn(&DoubleBlock) -> DoubleBlock,
+        samples: usize,
+        rng: &mut R,
+    ) -> bool {
+        (0..samples).all(|_| {
+            let mut x = [0u8; 32];
+            rng.fill_bytes(&mut x);
+            self.apply(&x) == f(&x)
+        })
+    }
+
+    /// Returns `self(x ⊕ delta) ⊕ self(x)`, which is `lin · delta` for every `x`.
+    pub fn differential(&self, delta: &DoubleBlock) -> DoubleBlock {
+        self.lin.apply_to_bytes(delta)
+    }
+
+    /// Returns the SHA-256 of the serialized map, a stable identifier across runs.
+    pub fn fingerprint(&self) -> [u8; 32] {
+        serialized_sha256(self)
+    }
+}
+
+/// 128-bit affine map `x -> lin * x ⊕ bias`, the encoding of single-block instances.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Affine128 {
+    /// Linear component.
+    pub lin: Matrix128,
+    /// Additive bias.
+    pub bias: Block,
+}
+
+impl Affine128 {
+    /// Constructs a new affine map from components.
+    pub const fn new(lin: Matrix128, bias: Block) -> Self {
         Self { lin, bias }
     }
 
     /// Identity affine map.
     pub fn identity() -> Self {
-        Self::new(Matrix256::identity(), [0u8; 32])
+        Self::new(Matrix128::identity(), [0u8; 16])
     }
 
     /// Generates a random affine map using a sparse unsplit invertible linear part.
     pub fn random_sparse_unsplit<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
-        let lin = Matrix256::random_sparse_unsplit(rng);
-        let mut bias = [0u8; 32];
+        let lin = Matrix128::random_sparse_unsplit(rng);
+        let mut bias = [0u8; 16];
         rng.fill_bytes(&mut bias);
         Self::new(lin, bias)
     }
 
     /// Applies the affine map.
-    pub fn apply(&self, value: &[u8; 32]) -> [u8; 32] {
+    pub fn apply(&self, value: &Block) -> Block {
         let mut out = self.lin.apply_to_bytes(value);
         xor_in_place(&mut out, &self.bias);
         out
     }
 
     /// Applies the affine map in place.
-    pub fn apply_in_place(&self, value: &mut [u8; 32]) {
+    pub fn apply_in_place(&self, value: &mut Block) {
         *value = self.apply(value);
     }
 
@@ -101,26 +440,70 @@ impl Affine256 {
 
     /// Composes `self` after `other` (i.e., `self ∘ other`).
     pub fn compose(&self, other: &Self) -> Self {
-        let lin = self.lin.mul(&other.lin);
-        let bias_from_other = self.lin.apply_to_bytes(&other.bias);
-        let mut bias = self.bias;
-        xor_in_place(&mut bias, &bias_from_other);
-        Self::new(lin, bias)
+        Self::new(self.lin.mul(&other.lin), self.apply(&other.bias))
+    }
+
+    /// Composes in pipeline order: applies `self`, then `next` (i.e., `next ∘ self`).
+    pub fn then(&self, next: &Self) -> Self {
+        next.compose(self)
+    }
+}
+
+// As for `Matrix8`, wiping a `Copy` value leaves earlier copies intact.
+impl Zeroize for Affine8 {
+    fn zeroize(&mut self) {
+        self.lin.zeroize();
+        self.bias.zeroize();
+    }
+}
+
+impl Zeroize for Affine256 {
+    fn zeroize(&mut self) {
+        self.lin.zeroize();
+        self.bias.zeroize();
     }
 }
 
-fn xor_in_place(dst: &mut [u8; 32], src: &[u8; 32]) {
-    for (d, s) in dst.iter_mut().zip(src.iter()) {
-        *d ^= *s;
+impl Zeroize for Affine128 {
+    fn zeroize(&mut self) {
+        self.lin.zeroize();
+        self.bias.zeroize();
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aes_core::xor32_in_place;
     use rand::SeedableRng;
     use rand_chacha::ChaCha20Rng;
 
+    #[test]
+    fn zeroize_wipes_encodings_including_on_drop() {
+        let mut rng = ChaCha20Rng::from_seed([64u8; 32]);
+        let mut small = Affine8::random(&mut rng);
+        small.zeroize();
+        assert_eq!(small, Affine8::new(Matrix8::zero(), 0));
+
+        let mut encoding = Affine256::random_dense(&mut rng);
+        encoding.zeroize();
+        assert_eq!(encoding, Affine256::new(Matrix256::zero(), Vec256::ZERO));
+
+        // `Zeroizing` wipes on drop; borrowing the value lets the test look at it afterwards.
+        struct Borrowed<'a>(&'a mut Affine256);
+        impl Zeroize for Borrowed<'_> {
+            fn zeroize(&mut self) {
+                self.0.zeroize();
+            }
+        }
+        let mut encoding = Affine256::random_sparse_unsplit(&mut rng);
+        {
+            let wrapped = zeroize::Zeroizing::new(Borrowed(&mut encoding));
+            assert!(wrapped.0.lin.is_invertible());
+        }
+        assert_eq!(encoding, Affine256::new(Matrix256::zero(), Vec256::ZERO));
+    }
+
     #[test]
     fn affine8_roundtrip() {
         let mut rng = ChaCha20Rng::from_seed([10u8; 32]);
@@ -144,6 +527,254 @@ mod tests {
         let direct = a.apply(b.apply(value));
         let via_comp = composed.apply(value);
         assert_eq!(direct, via_comp);
+        assert_eq!(b.then(&a), composed);
+        assert_eq!(a.then(&b).apply(value), b.apply(a.apply(value)));
+    }
+
+    #[test]
+    fn affine8_fixed_points() {
+        assert_eq!(Affine8::identity().fixed_points().len(), 256);
+        assert!(Affine8::new(Matrix8::identity(), 1)
+            .fixed_points()
+            .is_empty());
+        let mut rng = ChaCha20Rng::from_seed([14u8; 32]);
+        for _ in 0..32 {
+            let aff = Affine8::random(&mut rng);
+            let points = aff.fixed_points();
+            assert!(points.iter().all(|&x| aff.apply(x) == x));
+            // An affine fixed-point set is empty or has a power-of-two size.
+            assert!(points.is_empty() || points.len().is_power_of_two());
+        }
+    }
+
+    #[test]
+    fn affine8_fit_recovers_affine_maps_only() {
+        let mut rng = ChaCha20Rng::from_seed([68u8; 32]);
+        for _ in 0..32 {
+            let aff = Affine8::random(&mut rng);
+            assert_eq!(Affine8::fit(|x| aff.apply(x)), Some(aff));
+        }
+        let singular = Affine8::new(Matrix8::from_rows([1, 0, 4, 0, 0, 0, 0, 0x81]), 0x3c);
+        assert_eq!(Affine8::fit(|x| singular.apply(x)), Some(singular));
+        assert_eq!(Affine8::fit(aes_core::sbox), None);
+        // Affine on every basis vector but one other input.
+        assert_eq!(Affine8::fit(|x| if x == 0xff { 1 } else { x }), None);
+    }
+
+    #[test]
+    fn affine256_fit_recovers_affine_maps_only() {
+        let mut rng = ChaCha20Rng::from_seed([69u8; 32]);
+        for aff in [
+            Affine256::random_sparse_unsplit(&mut rng),
+            Affine256::random_dense(&mut rng),
+        ] {
+            assert_eq!(Affine256::fit(|x| aff.apply(x), 16), Some(aff));
+        }
+        let bytewise_sbox = |x: &DoubleBlock| x.map(aes_core::sbox);
+        assert_eq!(Affine256::fit(bytewise_sbox, 16), None);
+    }
+
+    #[test]
+    fn affine256_agrees_with_detects_bias_and_linear_differences() {
+        let mut rng = ChaCha20Rng::from_seed([72u8; 32]);
+        let aff = Affine256::random_sparse_unsplit(&mut rng);
+        assert!(aff.agrees_with(|x| aff.apply(x), 64, &mut rng));
+        assert!(!aff.agrees_with(|x| aff.lin.apply_to_bytes(x), 64, &mut rng));
+        let tweaked = Affine256::new(aff.lin.add(&Matrix256::identity()), aff.bias);
+        assert!(!aff.agrees_with(|x| tweaked.apply(x), 64, &mut rng));
+        crate::assert_linear_map_eq!(|x| aff.apply(x), |x| aff
+            .compose(&Affine256::identity())
+            .apply(x));
+    }
+
+    #[test]
+    fn affine256_fixed_points_span_the_solution_space() {
+        let mut rng = ChaCha20Rng::from_seed([15u8; 32]);
+        let random = Affine256::random_sparse_unsplit(&mut rng);
+        let mut fixed = [0u8; 32];
+        rng.fill_bytes(&mut fixed);
+        // Choose the bias so that `fixed` is a fixed point: bias = (lin ⊕ I)·fixed.
+        let mut bias = random.lin.apply_to_bytes(&fixed);
+        xor32_in_place(&mut bias, &fixed);
+        let bias = Vec256::from(bias);
+        let aff = Affine256::new(random.lin.clone(), bias);
+
+        let points = aff.fixed_points();
+        let (particular, kernel) = points.split_first().expect("fixed point exists");
+        assert_eq!(&aff.apply(particular), particular);
+        assert_eq!(
+            kernel.len() as u32,
+            256 - aff.lin.add(&Matrix256::identity()).rank()
+        );
+        for mask in 0..(1u32 << kernel.len().min(8)) {
+            let mut x = *particular;
+            for (i, k) in kernel.iter().enumerate().take(8) {
+                if (mask >> i) & 1 == 1 {
+                    xor32_in_place(&mut x, k);
+                }
+            }
+            assert_eq!(aff.apply(&x), x);
+        }
+        // The planted fixed point differs from the particular one by a kernel vector.
+        let mut diff = fixed;
+        xor32_in_place(&mut diff, particular);
+        assert_eq!(aff.differential(&diff), diff);
+
+        assert_eq!(Affine256::identity().fixed_points().len(), 257);
+        let mut shift = Affine256::identity();
+        shift.bias.set_bit(24, true);
+        assert!(shift.fixed_points().is_empty());
+    }
+
+    #[test]
+    fn affine256_differential_is_input_independent() {
+        let mut rng = ChaCha20Rng::from_seed([16u8; 32]);
+        let aff = Affine256::random_sparse_unsplit(&mut rng);
+        let mut delta = [0u8; 32];
+        rng.fill_bytes(&mut delta);
+        let expected = aff.differential(&delta);
+        for _ in 0..16 {
+            let mut x = [0u8; 32];
+            rng.fill_bytes(&mut x);
+            let mut shifted = x;
+            xor32_in_place(&mut shifted, &delta);
+            let mut observed = aff.apply(&shifted);
+            xor32_in_place(&mut observed, &aff.apply(&x));
+            assert_eq!(observed, expected);
+        }
+    }
+
+    #[test]
+    fn then_chains_in_pipeline_order() {
+        let mut rng = ChaCha20Rng::from_seed([21u8; 32]);
+        let mut key = [0u8; 32];
+        rng.fill_bytes(&mut key);
+        let key_add = Affine256::new(Matrix256::identity(), Vec256::from(key));
+        let min = Affine256::random_dense(&mut rng);
+        let a1_inv = Affine256::random_sparse_unsplit(&mut rng)
+            .invert()
+            .expect("invertible");
+
+        // The generator's input encoding: add k0, then `Min`, then `A^(1)⁻¹`.
+        let pipeline = key_add.then(&min).then(&a1_inv);
+        assert_eq!(pipeline, a1_inv.compose(&min.compose(&key_add)));
+        let mut scratch = Affine256::identity();
+        let mut out = Affine256::identity();
+        key_add.then_into(&min, &mut scratch);
+        scratch.then_into(&a1_inv, &mut out);
+        assert_eq!(out, pipeline);
+        crate::assert_linear_map_eq!(|x| pipeline.apply(x), |x: &DoubleBlock| {
+            let mut state = *x;
+            xor32_in_place(&mut state, &key);
+            a1_inv.apply(&min.apply(&state))
+        });
+
+        // Swapping any two stages changes the map, so an ordering bug cannot go unnoticed.
+        for swapped in [
+            min.then(&key_add).then(&a1_inv),
+            key_add.then(&a1_inv).then(&min),
+            a1_inv.compose(&key_add.compose(&min)),
+        ] {
+            assert!(
+                crate::gf2::basis_disagreement(|x| pipeline.apply(x), |x| swapped.apply(x))
+                    .is_some()
+            );
+        }
+    }
+
+    #[test]
+    fn compose_into_matches_compose() {
+        let mut rng = ChaCha20Rng::from_seed([20u8; 32]);
+        let a = Affine256::random_sparse_unsplit(&mut rng);
+        let b = Affine256::random_dense(&mut rng);
+        let mut out = Affine256::random_dense(&mut rng);
+        a.compose_into(&b, &mut out);
+        assert_eq!(out, a.compose(&b));
+        b.compose_into(&a, &mut out);
+        assert_eq!(out, b.compose(&a));
+        let mut x = [0u8; 32];
+        rng.fill_bytes(&mut x);
+        assert_eq!(out.apply(&x), b.apply(&a.apply(&x)));
+    }
+
+    #[test]
+    fn concat_bytes_applies_each_byte_map() {
+        let mut rng = ChaCha20Rng::from_seed([19u8; 32]);
+        let per_byte: [Affine8; 32] = core::array::from_fn(|_| Affine8::random(&mut rng));
+        let concat = Affine256::concat_bytes(&per_byte);
+        for _ in 0..16 {
+            let mut x = [0u8; 32];
+            rng.fill_bytes(&mut x);
+            let expected: DoubleBlock = core::array::from_fn(|i| per_byte[i].apply(x[i]));
+            assert_eq!(concat.apply(&x), expected);
+        }
+        let inverse = concat.invert().expect("every byte map is invertible");
+        let inverses = per_byte.map(|f| f.invert().unwrap());
+        assert_eq!(inverse, Affine256::concat_bytes(&inverses));
+        assert_eq!(
+            Affine256::concat_bytes(&[Affine8::identity(); 32]),
+            Affine256::identity()
+        );
+    }
+
+    #[test]
+    fn affine4_invert_and_compose_exhaustively() {
+        let mut rng = ChaCha20Rng::from_seed([17u8; 32]);
+        for _ in 0..64 {
+            let a = Affine4::random(&mut rng);
+            let b = Affine4::random(&mut rng);
+            let a_inv = a.invert().expect("random maps are invertible");
+            let composed = a.compose(&b);
+            assert_eq!(b.then(&a), composed);
+            let mut images = [false; 16];
+            for x in 0..16u8 {
+                assert_eq!(a_inv.apply(a.apply(x)), x);
+                assert_eq!(composed.apply(x), a.apply(b.apply(x)));
+                assert!(a.apply(x) < 16);
+                images[usize::from(a.apply(x))] = true;
+            }
+            assert!(images.iter().all(|&hit| hit));
+            assert_eq!(a.compose(&a_inv), Affine4::identity());
+        }
+        assert!(Affine4::new([1, 2, 4, 4], 0).invert().is_none());
+        assert_eq!(Affine4::new([0xff; 4], 0xff), Affine4::new([0xf; 4], 0xf));
+    }
+
+    #[test]
+    fn concatenated_nibble_encoding_is_block_diagonal_affine() {
+        let mut rng = ChaCha20Rng::from_seed([18u8; 32]);
+        for _ in 0..16 {
+            let enc = ConcatenatedNibbleEncoding::random(&mut rng);
+            let inv = enc.invert().unwrap();
+            // Fit an Affine8 from the images of 0 and the basis vectors.
+            let bias = enc.apply(0);
+            let cols: [u8; 8] = core::array::from_fn(|i| enc.apply(1 << i) ^ bias);
+            let fitted = Affine8::new(Matrix8::from_cols(cols), bias);
+            for x in 0..=255u8 {
+                assert_eq!(inv.apply(enc.apply(x)), x);
+                assert_eq!(fitted.apply(x), enc.apply(x));
+                assert_eq!(enc.apply(x) >> 4, enc.high.apply(x >> 4));
+            }
+            for (low, high) in (0..4).zip(4..8) {
+                for other in 0..4 {
+                    assert!(!fitted.lin.bit(low, 4 + other));
+                    assert!(!fitted.lin.bit(high, other));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn affine128_invert_and_compose() {
+        let mut rng = ChaCha20Rng::from_seed([65u8; 32]);
+        let f = Affine128::random_sparse_unsplit(&mut rng);
+        let g = Affine128::random_sparse_unsplit(&mut rng);
+        assert!(f.lin.is_sparse_unsplit());
+        let f_inv = f.invert().expect("sparse unsplit maps are invertible");
+        let x: Block = core::array::from_fn(|i| (i as u8).wrapping_mul(29));
+        assert_eq!(f_inv.apply(&f.apply(&x)), x);
+        assert_eq!(f.then(&g).apply(&x), g.apply(&f.apply(&x)));
+        assert_eq!(f.then(&f_inv), Affine128::identity());
     }
 
     #[test]
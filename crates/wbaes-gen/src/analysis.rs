@@ -0,0 +1,82 @@
+//! Structural analysis of the encodings, as groundwork for attack tooling.
+
+use aes_core::{gf256, sbox};
+
+use crate::affine::Affine8;
+use crate::matrix::Matrix8;
+
+/// Returns every affine self-equivalence `(A, B)` of the AES S-box: `B(S(A(x))) = S(x)` for
+/// all `x`.
+///
+/// `S = Aff ∘ Inv`, with `Inv` the field inversion (`0 ↦ 0`) and `Aff` the S-box output affine
+/// map. The affine self-equivalences of `Inv` are the 2040 linear maps `x ↦ a · x^(2^k)`
+/// (`a ≠ 0`, `0 ≤ k < 8`), paired with `y ↦ a⁻¹ · y^(2^k)`; conjugating the latter by `Aff`
+/// gives the S-box's. The list is ordered by `k`, then `a`, and starts with the identity pair.
+pub fn sbox_affine_self_equivalences() -> Vec<(Affine8, Affine8)> {
+    let output = sbox_output_affine();
+    let output_inv = output.invert().expect("the S-box affine map is invertible");
+    let mut pairs = Vec::with_capacity(8 * 255);
+    for k in 0..8 {
+        let frobenius =
+            Matrix8::from_cols(core::array::from_fn(|bit| gf256::pow(1 << bit, 1 << k)));
+        for a in 1..=255u8 {
+            let input = Affine8::new(Matrix8::gf256_mul(a).mul(&frobenius), 0);
+            // `Inv ∘ input = inversion_side ∘ Inv`, so `S ∘ input = (Aff ∘ inversion_side ∘
+            // Aff⁻¹) ∘ S`, and `B` is the inverse of that conjugate.
+            let inversion_side = Affine8::new(Matrix8::gf256_mul(gf256::inv(a)).mul(&frobenius), 0);
+            let forward = output_inv.then(&inversion_side).then(&output);
+            let output_side = forward.invert().expect("conjugate of an invertible map");
+            pairs.push((input, output_side));
+        }
+    }
+    pairs
+}
+
+/// Returns `Aff`, the affine map with `S(x) = Aff(x⁻¹)`, fitted from the S-box itself.
+fn sbox_output_affine() -> Affine8 {
+    let bias = sbox(0);
+    let lin = Matrix8::from_cols(core::array::from_fn(|bit| {
+        sbox(gf256::inv(1 << bit)) ^ bias
+    }));
+    Affine8::new(lin, bias)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn output_affine_reproduces_sbox() {
+        let output = sbox_output_affine();
+        assert_eq!(output.bias, 0x63);
+        for x in 0..=255u8 {
+            assert_eq!(output.apply(gf256::inv(x)), sbox(x));
+        }
+    }
+
+    #[test]
+    fn there_are_2040_distinct_self_equivalences() {
+        let pairs = sbox_affine_self_equivalences();
+        assert_eq!(pairs.len(), 2040);
+        assert_eq!(pairs[0], (Affine8::identity(), Affine8::identity()));
+        let inputs: HashSet<_> = pairs.iter().map(|(a, _)| *a).collect();
+        let all: HashSet<_> = pairs.iter().copied().collect();
+        assert_eq!(inputs.len(), 2040);
+        assert_eq!(all.len(), 2040);
+    }
+
+    #[test]
+    fn every_pair_satisfies_the_identity_exhaustively() {
+        for (a, b) in sbox_affine_self_equivalences() {
+            assert!(a.lin.is_invertible() && b.lin.is_invertible());
+            for x in 0..=255u8 {
+                assert_eq!(
+                    b.apply(sbox(a.apply(x))),
+                    sbox(x),
+                    "{a:?}, {b:?} at {x:#04x}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,382 @@
+//! Canonical text form of matrices and affine maps, for exchange with other tools.
+//!
+//! A matrix is a header line `matrix <n>x<n> lsb0` followed by its `n` rows, one per line,
+//! each as `n / 8` bytes of lowercase hex. Row bytes follow the crate's bit order (`lsb0`):
+//! entry `(row, col)` is bit `col % 8` of byte `col / 8` of the row. An affine map is a
+//! header `affine <n> lsb0`, a line `bias <hex>`, then the rows of its linear part. Every line
+//! ends with `\n`.
+//!
+//! ```text
+//! affine 8 lsb0
+//! bias 63
+//! 01
+//! 02
+//! ...
+//! ```
+//!
+//! [`as_string`] plugs the format into serde, so that an encoding embedded in a JSON
+//! document is one string instead of a large array.
+
+use core::fmt;
+use core::fmt::Write as _;
+
+use crate::affine::{Affine256, Affine8};
+use crate::matrix::{zeroed_rows, Matrix256, Matrix8};
+use crate::vec256::{bytes_to_segments, Vec256};
+
+/// Conversion to and from the canonical text form.
+pub trait CanonicalText: Sized {
+    /// Formats `self` in canonical text form.
+    fn to_canonical_string(&self) -> String;
+
+    /// Parses the canonical text form written by [`Self::to_canonical_string`].
+    fn from_canonical_string(text: &str) -> Result<Self, CanonicalFormatError>;
+}
+
+/// Rejected canonical text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CanonicalFormatError {
+    /// The header names another type, dimension, or bit order.
+    Header {
+        /// Header the target type expects.
+        expected: String,
+        /// First line of the input.
+        found: String,
+    },
+    /// The input does not have one line per row (plus header and bias lines).
+    LineCount {
+        /// Expected number of lines.
+        expected: usize,
+        /// Number of lines found.
+        actual: usize,
+    },
+    /// A line is not hex of the expected length, or lacks its `bias ` prefix.
+    Line {
+        /// 1-based line number.
+        line: usize,
+    },
+}
+
+impl fmt::Display for CanonicalFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Header { expected, found } => {
+                write!(f, "expected header `{expected}`, found `{found}`")
+            }
+            Self::LineCount { expected, actual } => {
+                write!(f, "expected {expected} lines, found {actual}")
+            }
+            Self::Line { line } => write!(f, "line {line} is malformed"),
+        }
+    }
+}
+
+impl std::error::Error for CanonicalFormatError {}
+
+impl CanonicalText for Matrix8 {
+    fn to_canonical_string(&self) -> String {
+        let mut out = matrix_header(8);
+        push_rows(&mut out, self.rows().iter().map(core::slice::from_ref));
+        out
+    }
+
+    fn from_canonical_string(text: &str) -> Result<Self, CanonicalFormatError> {
+        let lines = split(text, &matrix_header(8), 8)?;
+        parse_matrix8(&lines, 2)
+    }
+}
+
+impl CanonicalText for Matrix256 {
+    fn to_canonical_string(&self) -> String {
+        let mut out = matrix_header(256);
+        push_rows(&mut out, (0..256).map(|row| self.row(row)));
+        out
+    }
+
+    fn from_canonical_string(text: &str) -> Result<Self, CanonicalFormatError> {
+        let lines = split(text, &matrix_header(256), 256)?;
+        parse_matrix256(&lines, 2)
+    }
+}
+
+impl CanonicalText for Affine8 {
+    fn to_canonical_string(&self) -> String {
+        let mut out = affine_header(8);
+        push_bias(&mut out, &[self.bias]);
+        push_rows(&mut out, self.lin.rows().iter().map(core::slice::from_ref));
+        out
+    }
+
+    fn from_canonical_string(text: &str) -> Result<Self, CanonicalFormatError> {
+        let lines = split(text, &affine_header(8), 9)?;
+        let [bias] = parse_bias::<1>(lines[0])?;
+        Ok(Affine8::new(parse_matrix8(&lines[1..], 3)?, bias))
+    }
+}
+
+impl CanonicalText for Affine256 {
+    fn to_canonical_string(&self) -> String {
+        let mut out = affine_header(256);
+        push_bias(&mut out, self.bias.as_bytes());
+        push_rows(&mut out, (0..256).map(|row| self.lin.row(row)));
+        out
+    }
+
+    fn from_canonical_string(text: &str) -> Result<Self, CanonicalFormatError> {
+        let lines = split(text, &affine_header(256), 257)?;
+        let bias = Vec256::new(parse_bias::<32>(lines[0])?);
+        Ok(Affine256::new(parse_matrix256(&lines[1..], 3)?, bias))
+    }
+}
+
+/// Serde adapter storing a value as its canonical text, for `#[serde(with = "...")]`.
+///
+/// ```
+/// use serde::{Deserialize, Serialize};
+/// use wbaes_gen::Affine256;
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Exchange {
+///     #[serde(with = "wbaes_gen::canonical::as_string")]
+///     encoding: Affine256,
+/// }
+/// ```
+pub mod as_string {
+    use serde::de::Error as _;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use super::CanonicalText;
+
+    /// Serializes `value` as a string.
+    pub fn serialize<T: CanonicalText, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&value.to_canonical_string())
+    }
+
+    /// Parses a string written by [`serialize`].
+    pub fn deserialize<'de, T: CanonicalText, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        T::from_canonical_string(&text).map_err(D::Error::custom)
+    }
+}
+
+fn matrix_header(n: usize) -> String {
+    format!("matrix {n}x{n} lsb0\n")
+}
+
+fn affine_header(n: usize) -> String {
+    format!("affine {n} lsb0\n")
+}
+
+fn push_bias(out: &mut String, bias: &[u8]) {
+    out.push_str("bias ");
+    push_hex(out, bias);
+}
+
+fn push_rows<B: AsRef<[u8]>>(out: &mut String, rows: impl Iterator<Item = B>) {
+    for row in rows {
+        push_hex(out, row.as_ref());
+    }
+}
+
+fn push_hex(out: &mut String, bytes: &[u8]) {
+    for byte in bytes {
+        write!(out, "{byte:02x}").expect("writing to a String cannot fail");
+    }
+    out.push('\n');
+}
+
+/// Checks the header and line count, returning the lines after the header.
+fn split<'a>(
+    text: &'a str,
+    header: &str,
+    body_lines: usize,
+) -> Result<Vec<&'a str>, CanonicalFormatError> {
+    let mut lines = text.lines();
+    let first = lines.next().unwrap_or_default();
+    if first != header.trim_end() {
+        return Err(CanonicalFormatError::Header {
+            expected: header.trim_end().to_owned(),
+            found: first.to_owned(),
+        });
+    }
+    let body: Vec<&str> = lines.collect();
+    if body.len() != body_lines {
+        return Err(CanonicalFormatError::LineCount {
+            expected: body_lines + 1,
+            actual: body.len() + 1,
+        });
+    }
+    Ok(body)
+}
+
+/// Decodes one line of exactly `N` hex-encoded bytes; `line` is its 1-based number.
+fn parse_hex<const N: usize>(text: &str, line: usize) -> Result<[u8; N], CanonicalFormatError> {
+    if text.len() != 2 * N || !text.bytes().all(|c| c.is_ascii_hexdigit()) {
+        return Err(CanonicalFormatError::Line { line });
+    }
+    let mut out = [0u8; N];
+    for (byte, index) in out.iter_mut().zip((0..text.len()).step_by(2)) {
+        *byte = u8::from_str_radix(&text[index..index + 2], 16).expect("checked hex digits");
+    }
+    Ok(out)
+}
+
+fn parse_bias<const N: usize>(text: &str) -> Result<[u8; N], CanonicalFormatError> {
+    let hex = text
+        .strip_prefix("bias ")
+        .ok_or(CanonicalFormatError::Line { line: 2 })?;
+    parse_hex(hex, 2)
+}
+
+/// Parses eight row lines, the first being line `first_line` of the input.
+fn parse_matrix8(lines: &[&str], first_line: usize) -> Result<Matrix8, CanonicalFormatError> {
+    let mut rows = [0u8; 8];
+    for (index, (row, line)) in rows.iter_mut().zip(lines).enumerate() {
+        [*row] = parse_hex::<1>(line, first_line + index)?;
+    }
+    Ok(Matrix8::from_rows(rows))
+}
+
+/// Parses 256 row lines, the first being line `first_line` of the input.
+fn parse_matrix256(lines: &[&str], first_line: usize) -> Result<Matrix256, CanonicalFormatError> {
+    let mut rows = zeroed_rows();
+    for (index, (row, line)) in rows.iter_mut().zip(lines).enumerate() {
+        *row = bytes_to_segments(&parse_hex::<32>(line, first_line + index)?);
+    }
+    Ok(Matrix256::from_words(rows))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    #[test]
+    fn all_types_roundtrip() {
+        let mut rng = ChaCha20Rng::from_seed([72u8; 32]);
+        let m8 = Matrix8::random_invertible(&mut rng);
+        let a8 = Affine8::random(&mut rng);
+        let m256 = Matrix256::random_invertible_dense(&mut rng);
+        let a256 = Affine256::random_sparse_unsplit(&mut rng);
+        assert_eq!(
+            Matrix8::from_canonical_string(&m8.to_canonical_string()),
+            Ok(m8)
+        );
+        assert_eq!(
+            Affine8::from_canonical_string(&a8.to_canonical_string()),
+            Ok(a8)
+        );
+        assert_eq!(
+            Matrix256::from_canonical_string(&m256.to_canonical_string()),
+            Ok(m256)
+        );
+        assert_eq!(
+            Affine256::from_canonical_string(&a256.to_canonical_string()),
+            Ok(a256)
+        );
+    }
+
+    #[test]
+    fn affine8_layout() {
+        let aff = Affine8::new(
+            Matrix8::from_rows([1, 2, 4, 8, 0x10, 0x20, 0x40, 0x81]),
+            0x63,
+        );
+        let text = aff.to_canonical_string();
+        assert_eq!(
+            text,
+            "affine 8 lsb0\nbias 63\n01\n02\n04\n08\n10\n20\n40\n81\n"
+        );
+        // Uppercase hex and a missing final newline are accepted.
+        let relaxed = text.trim_end().replace("81", "8A");
+        assert_eq!(
+            Affine8::from_canonical_string(&relaxed).map(|a| a.lin.rows()[7]),
+            Ok(0x8a)
+        );
+    }
+
+    #[test]
+    fn malformed_input_is_rejected() {
+        let m8 = Matrix8::identity().to_canonical_string();
+        assert_eq!(
+            Matrix256::from_canonical_string(&m8),
+            Err(CanonicalFormatError::Header {
+                expected: "matrix 256x256 lsb0".into(),
+                found: "matrix 8x8 lsb0".into(),
+            })
+        );
+        assert!(matches!(
+            Affine8::from_canonical_string(&m8),
+            Err(CanonicalFormatError::Header { .. })
+        ));
+        assert!(matches!(
+            Matrix8::from_canonical_string(""),
+            Err(CanonicalFormatError::Header { .. })
+        ));
+
+        let short: String = m8.lines().take(8).map(|line| format!("{line}\n")).collect();
+        assert_eq!(
+            Matrix8::from_canonical_string(&short),
+            Err(CanonicalFormatError::LineCount {
+                expected: 9,
+                actual: 8
+            })
+        );
+        for (bad_row, line) in [("1", 2), ("0g", 2), ("+1", 2), ("010", 2)] {
+            let text = m8.replacen("01", bad_row, 1);
+            assert_eq!(
+                Matrix8::from_canonical_string(&text),
+                Err(CanonicalFormatError::Line { line }),
+                "{bad_row}"
+            );
+        }
+
+        let a8 = Affine8::identity().to_canonical_string();
+        let no_prefix = a8.replace("bias ", "");
+        assert_eq!(
+            Affine8::from_canonical_string(&no_prefix),
+            Err(CanonicalFormatError::Line { line: 2 })
+        );
+        let wide_row = Matrix256::identity()
+            .to_canonical_string()
+            .replacen("\n01", "\n0100", 1);
+        assert_eq!(
+            Matrix256::from_canonical_string(&wide_row),
+            Err(CanonicalFormatError::Line { line: 2 })
+        );
+    }
+
+    #[test]
+    fn json_adapter_embeds_a_string() {
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Exchange {
+            #[serde(with = "as_string")]
+            encoding: Affine256,
+            #[serde(with = "as_string")]
+            byte: Affine8,
+        }
+
+        let mut rng = ChaCha20Rng::from_seed([73u8; 32]);
+        let doc = Exchange {
+            encoding: Affine256::random_dense(&mut rng),
+            byte: Affine8::random(&mut rng),
+        };
+        let json = serde_json::to_string(&doc).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            value["encoding"].as_str(),
+            Some(doc.encoding.to_canonical_string().as_str())
+        );
+        assert_eq!(serde_json::from_str::<Exchange>(&json).unwrap(), doc);
+
+        let broken = json.replace("affine 256", "affine 128");
+        let err = serde_json::from_str::<Exchange>(&broken).unwrap_err();
+        assert!(err.to_string().contains("expected header"), "{err}");
+    }
+}
@@ -0,0 +1,424 @@
+//! Chow et al. (2002) white-box AES-128, as an alternative to the revisited scheme.
+//!
+//! Each of rounds 1–9 runs, per state byte after ShiftRows, a T-box (round key and S-box)
+//! merged with its Ty table (one MixColumns column) and a 32×32 mixing bijection `MB`; XOR
+//! tables sum the four words of a column, then 8→32-bit tables remove `MB` and apply the 8×8
+//! mixing bijections `L` expected by the next round, and a second XOR network sums again.
+//! Round 10 is a plain 8→8 T-box that also adds the last round key. Every table output can
+//! carry random 4-bit encodings, which the consuming table undoes. This first cut encrypts
+//! only and has no external encodings: the plaintext goes in and the ciphertext comes out.
+
+use aes_core::linear::{mul_column, shift_rows_permutation, MIX_COLUMNS_MATRIX};
+use aes_core::{expand_key, sbox, Aes128Key};
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use serde_big_array::BigArray;
+use zeroize::Zeroize;
+
+use crate::affine::{Affine4, ConcatenatedNibbleEncoding};
+use crate::fingerprint::serialized_sha256;
+use crate::gf2;
+use crate::instance::{InstanceError, InstanceParams};
+use crate::matrix::Matrix8;
+
+/// ShiftRows as a gather over the column-major state: byte `i` after it is byte `SR[i]`.
+const SR: [usize; 16] = shift_rows_permutation();
+
+/// Configuration for [`crate::Generator::generate_chow_instance`].
+///
+/// Mixing bijections are always on.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChowConfig {
+    /// Whether table outputs carry random 4-bit nibble encodings.
+    pub nibble_encodings: bool,
+}
+
+/// 8→32-bit lookup table.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Table8x32(#[serde(with = "BigArray")] [u32; 256]);
+
+impl Table8x32 {
+    /// Looks up `input`.
+    pub fn get(&self, input: u8) -> u32 {
+        self.0[usize::from(input)]
+    }
+}
+
+/// 8→8-bit lookup table.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Table8x8(#[serde(with = "BigArray")] [u8; 256]);
+
+impl Table8x8 {
+    /// Looks up `input`.
+    pub fn get(&self, input: u8) -> u8 {
+        self.0[usize::from(input)]
+    }
+}
+
+/// 4+4→4-bit XOR table, indexed by `(a << 4) | b`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct XorTable(#[serde(with = "BigArray")] [u8; 256]);
+
+impl XorTable {
+    /// Looks up the encoded XOR of nibbles `a` and `b`.
+    pub fn get(&self, a: u8, b: u8) -> u8 {
+        self.0[usize::from(((a & 0xf) << 4) | (b & 0xf))]
+    }
+}
+
+/// XOR tables summing four 32-bit words nibble by nibble.
+///
+/// `tables[0]` computes `w0 ⊕ w1`, `tables[1]` computes `w2 ⊕ w3` and `tables[2]` sums the
+/// two; `tables[step][n]` handles nibble `n` (bits `4n..4n+4`).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct XorTree {
+    /// Tables per step and nibble.
+    pub tables: [[XorTable; 8]; 3],
+}
+
+impl XorTree {
+    /// Sums four encoded words.
+    pub fn apply(&self, words: [u32; 4]) -> u32 {
+        let left = xor_words(&self.tables[0], words[0], words[1]);
+        let right = xor_words(&self.tables[1], words[2], words[3]);
+        xor_words(&self.tables[2], left, right)
+    }
+}
+
+fn xor_words(tables: &[XorTable; 8], a: u32, b: u32) -> u32 {
+    tables.iter().enumerate().fold(0, |acc, (n, table)| {
+        let shift = 4 * n;
+        acc | u32::from(table.get((a >> shift) as u8, (b >> shift) as u8)) << shift
+    })
+}
+
+/// Tables for one of rounds 1–9.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChowRound {
+    /// T-box, Ty table and `MB` per state byte after ShiftRows; byte `4j + row` feeds
+    /// column `j`.
+    pub ty_boxes: [Table8x32; 16],
+    /// Per column, sums the four `ty_boxes` words into `MB(MixColumns(column))`.
+    pub ty_xor: [XorTree; 4],
+    /// `MB⁻¹` followed by the next round's `L`, per byte `4j + k` of column `j`'s word.
+    pub mb_inv: [Table8x32; 16],
+    /// Per column, sums the four `mb_inv` words; byte `k` becomes state byte `4j + k`.
+    pub mb_xor: [XorTree; 4],
+}
+
+/// Chow et al. white-box AES-128 instance, encryption only.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WbInstanceChow {
+    /// Rounds 1–9.
+    pub rounds: Vec<ChowRound>,
+    /// Round 10 T-boxes per state byte after ShiftRows, including the last round key.
+    pub final_round: [Table8x8; 16],
+    /// Static parameters.
+    pub params: InstanceParams,
+}
+
+impl WbInstanceChow {
+    /// Serializes the instance with `bincode`.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, bincode::Error> {
+        bincode::serialize(self)
+    }
+
+    /// Deserializes an instance with `bincode`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, bincode::Error> {
+        bincode::deserialize(bytes)
+    }
+
+    /// Deserializes an instance and checks that its parameters are [`InstanceParams::chow`]
+    /// and that it has nine full rounds.
+    pub fn from_bytes_validated(bytes: &[u8]) -> Result<Self, InstanceError> {
+        let instance = Self::from_bytes(bytes).map_err(InstanceError::Decode)?;
+        if instance.params != InstanceParams::chow() || instance.rounds.len() != 9 {
+            return Err(InstanceError::UnsupportedParams(instance.params));
+        }
+        Ok(instance)
+    }
+
+    /// Returns the SHA-256 of [`Self::to_bytes`], as [`crate::WbInstance256::fingerprint`]
+    /// does.
+    pub fn fingerprint(&self) -> [u8; 32] {
+        serialized_sha256(self)
+    }
+}
+
+/// Invertible 32×32 GF(2) matrix; bit `col` of `rows[row]` is entry `(row, col)`.
+#[derive(Clone, Copy)]
+struct Mixing32 {
+    rows: [u32; 32],
+}
+
+impl Mixing32 {
+    fn random<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
+        loop {
+            let candidate = Self {
+                rows: core::array::from_fn(|_| rng.next_u32()),
+            };
+            if candidate.invert().is_some() {
+                return candidate;
+            }
+        }
+    }
+
+    fn apply(&self, value: u32) -> u32 {
+        self.rows.iter().enumerate().fold(0, |acc, (row, bits)| {
+            acc | u32::from(gf2::parity_u64(u64::from(bits & value))) << row
+        })
+    }
+
+    fn invert(&self) -> Option<Self> {
+        let mut left = self.rows;
+        let mut right: [u32; 32] = core::array::from_fn(|row| 1 << row);
+        for col in 0..32 {
+            let pivot = (col..32).find(|&row| (left[row] >> col) & 1 == 1)?;
+            left.swap(col, pivot);
+            right.swap(col, pivot);
+            for row in 0..32 {
+                if row != col && (left[row] >> col) & 1 == 1 {
+                    left[row] ^= left[col];
+                    right[row] ^= right[col];
+                }
+            }
+        }
+        Some(Self { rows: right })
+    }
+}
+
+impl Zeroize for Mixing32 {
+    fn zeroize(&mut self) {
+        self.rows.zeroize();
+    }
+}
+
+/// Nibble encodings of a 32-bit word; entry `n` covers bits `4n..4n+4`.
+type WordEncoding = [Affine4; 8];
+
+fn word_encoding<R: RngCore + CryptoRng>(rng: &mut R, enabled: bool) -> WordEncoding {
+    core::array::from_fn(|_| {
+        if enabled {
+            Affine4::random(rng)
+        } else {
+            Affine4::identity()
+        }
+    })
+}
+
+fn invert_word(encoding: &WordEncoding) -> WordEncoding {
+    encoding.map(|map| map.invert().expect("nibble encodings are invertible"))
+}
+
+fn encode_word(encoding: &WordEncoding, value: u32) -> u32 {
+    encoding.iter().enumerate().fold(0, |acc, (n, map)| {
+        acc | u32::from(map.apply((value >> (4 * n)) as u8)) << (4 * n)
+    })
+}
+
+/// Encoding of byte `k` of a word encoded with `encoding`.
+fn byte_encoding(encoding: &WordEncoding, k: usize) -> ConcatenatedNibbleEncoding {
+    ConcatenatedNibbleEncoding::new(encoding[2 * k + 1], encoding[2 * k])
+}
+
+/// Builds the XOR network for four words under `inputs`, returning it with the output encoding.
+fn xor_tree<R: RngCore + CryptoRng>(
+    rng: &mut R,
+    inputs: [&WordEncoding; 4],
+    enabled: bool,
+) -> (XorTree, WordEncoding) {
+    let left = word_encoding(rng, enabled);
+    let right = word_encoding(rng, enabled);
+    let sum = word_encoding(rng, enabled);
+    let tree = XorTree {
+        tables: [
+            xor_step(inputs[0], inputs[1], &left),
+            xor_step(inputs[2], inputs[3], &right),
+            xor_step(&left, &right, &sum),
+        ],
+    };
+    (tree, sum)
+}
+
+fn xor_step(a: &WordEncoding, b: &WordEncoding, out: &WordEncoding) -> [XorTable; 8] {
+    let (a_inv, b_inv) = (invert_word(a), invert_word(b));
+    core::array::from_fn(|n| {
+        XorTable(core::array::from_fn(|index| {
+            let x = a_inv[n].apply((index >> 4) as u8);
+            let y = b_inv[n].apply(index as u8);
+            out[n].apply(x ^ y)
+        }))
+    })
+}
+
+/// How the previous round left the state: per column, the encoding of its word, and per
+/// byte, the mixing bijection `L` applied under it.
+struct StateEncoding {
+    columns: [WordEncoding; 4],
+    mixing: [Matrix8; 16],
+}
+
+impl StateEncoding {
+    /// The unencoded plaintext entering round 1.
+    fn plaintext() -> Self {
+        Self {
+            columns: [[Affine4::identity(); 8]; 4],
+            mixing: [Matrix8::identity(); 16],
+        }
+    }
+
+    /// Maps state byte `position`, as the previous round left it, back to its AES value.
+    fn decoder(&self, position: usize) -> impl Fn(u8) -> u8 {
+        let nibbles = byte_encoding(&self.columns[position / 4], position % 4)
+            .invert()
+            .expect("nibble encodings are invertible");
+        let mixing = self.mixing[position]
+            .invert()
+            .expect("mixing bijections are invertible");
+        move |byte| mixing.apply(nibbles.apply(byte))
+    }
+}
+
+impl Zeroize for StateEncoding {
+    fn zeroize(&mut self) {
+        self.mixing.zeroize();
+    }
+}
+
+/// Builds a [`WbInstanceChow`] for `key`.
+///
+/// Randomness is drawn round by round: the four `MB`, the 16 `L` of the next round, then
+/// (with nibble encodings) the Ty-box output encodings, the first XOR networks, the `MB⁻¹`
+/// output encodings and the second XOR networks, column by column.
+pub(crate) fn build_instance<R: RngCore + CryptoRng>(
+    rng: &mut R,
+    key: &Aes128Key,
+    config: ChowConfig,
+) -> WbInstanceChow {
+    let enabled = config.nibble_encodings;
+    let mut round_keys = expand_key(key);
+    let ty: [[u32; 256]; 4] = core::array::from_fn(|row| {
+        core::array::from_fn(|t| {
+            let mut column = [0u8; 4];
+            column[row] = t as u8;
+            u32::from_le_bytes(mul_column(&MIX_COLUMNS_MATRIX, &column))
+        })
+    });
+
+    let mut previous = StateEncoding::plaintext();
+    let mut rounds = Vec::with_capacity(9);
+    for r in 0..9 {
+        let mut mb: [Mixing32; 4] = core::array::from_fn(|_| Mixing32::random(rng));
+        let mut next = StateEncoding {
+            columns: [[Affine4::identity(); 8]; 4],
+            mixing: core::array::from_fn(|_| Matrix8::random_invertible(rng)),
+        };
+        let ty_encodings: [WordEncoding; 16] =
+            core::array::from_fn(|_| word_encoding(rng, enabled));
+
+        let round_key = round_keys.get(r);
+        let ty_boxes = core::array::from_fn(|i| {
+            let source = SR[i];
+            let decode = previous.decoder(source);
+            let (column, row) = (i / 4, i % 4);
+            Table8x32(core::array::from_fn(|x| {
+                let t = sbox(decode(x as u8) ^ round_key[source]);
+                encode_word(&ty_encodings[i], mb[column].apply(ty[row][usize::from(t)]))
+            }))
+        });
+
+        let mut ty_xor = Vec::with_capacity(4);
+        let mut mixed_encodings = Vec::with_capacity(4);
+        for column in 0..4 {
+            let inputs = core::array::from_fn(|k| &ty_encodings[4 * column + k]);
+            let (tree, sum) = xor_tree(rng, inputs, enabled);
+            ty_xor.push(tree);
+            mixed_encodings.push(sum);
+        }
+
+        let mb_encodings: [WordEncoding; 16] =
+            core::array::from_fn(|_| word_encoding(rng, enabled));
+        let mb_inv = core::array::from_fn(|index| {
+            let (column, k) = (index / 4, index % 4);
+            let decode = byte_encoding(&mixed_encodings[column], k)
+                .invert()
+                .expect("nibble encodings are invertible");
+            let mb_inv = mb[column].invert().expect("MB is invertible");
+            Table8x32(core::array::from_fn(|x| {
+                let word = mb_inv.apply(u32::from(decode.apply(x as u8)) << (8 * k));
+                let mixed = core::array::from_fn(|m| {
+                    next.mixing[4 * column + m].apply(word.to_le_bytes()[m])
+                });
+                encode_word(&mb_encodings[index], u32::from_le_bytes(mixed))
+            }))
+        });
+
+        let mut mb_xor = Vec::with_capacity(4);
+        for column in 0..4 {
+            let inputs = core::array::from_fn(|k| &mb_encodings[4 * column + k]);
+            let (tree, sum) = xor_tree(rng, inputs, enabled);
+            mb_xor.push(tree);
+            next.columns[column] = sum;
+        }
+
+        rounds.push(ChowRound {
+            ty_boxes,
+            ty_xor: ty_xor.try_into().expect("four columns"),
+            mb_inv,
+            mb_xor: mb_xor.try_into().expect("four columns"),
+        });
+        mb.zeroize();
+        previous.zeroize();
+        previous = next;
+    }
+
+    let (key9, key10) = (round_keys.get(9), round_keys.get(10));
+    let final_round = core::array::from_fn(|i| {
+        let source = SR[i];
+        let decode = previous.decoder(source);
+        Table8x8(core::array::from_fn(|x| {
+            sbox(decode(x as u8) ^ key9[source]) ^ key10[i]
+        }))
+    });
+    previous.zeroize();
+    round_keys.zeroize();
+
+    WbInstanceChow {
+        rounds,
+        final_round,
+        params: InstanceParams::chow(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    #[test]
+    fn mixing32_inverse_undoes_apply() {
+        let mut rng = ChaCha20Rng::from_seed([3u8; 32]);
+        let mb = Mixing32::random(&mut rng);
+        let inv = mb.invert().expect("invertible");
+        for _ in 0..64 {
+            let value = rng.next_u32();
+            assert_eq!(inv.apply(mb.apply(value)), value);
+        }
+    }
+
+    #[test]
+    fn xor_tree_sums_encoded_words() {
+        let mut rng = ChaCha20Rng::from_seed([4u8; 32]);
+        let inputs: [WordEncoding; 4] = core::array::from_fn(|_| word_encoding(&mut rng, true));
+        let (tree, sum) = xor_tree(&mut rng, core::array::from_fn(|k| &inputs[k]), true);
+        let sum_inv = invert_word(&sum);
+        for _ in 0..64 {
+            let words: [u32; 4] = core::array::from_fn(|_| rng.next_u32());
+            let encoded = core::array::from_fn(|k| encode_word(&inputs[k], words[k]));
+            let expected = words[0] ^ words[1] ^ words[2] ^ words[3];
+            assert_eq!(encode_word(&sum_inv, tree.apply(encoded)), expected);
+        }
+    }
+}
@@ -0,0 +1,162 @@
+//! Statistical self-tests of the random encodings and masks.
+//!
+//! The tests are `#[ignore]`d because they draw hundreds of thousands of samples; run them
+//! with `cargo test -p wbaes-gen -- --ignored distribution`. Seeds are fixed, so the outcome
+//! is deterministic, and the thresholds sit several standard deviations out so that they
+//! flag a broken sampler rather than an unlucky seed.
+
+/// Returns Pearson's chi-squared statistic of `counts` against the uniform distribution.
+fn chi_squared(counts: &[u64]) -> f64 {
+    let total: u64 = counts.iter().sum();
+    let expected = total as f64 / counts.len() as f64;
+    counts
+        .iter()
+        .map(|&count| {
+            let diff = count as f64 - expected;
+            diff * diff / expected
+        })
+        .sum()
+}
+
+/// Returns a loose upper bound for the chi-squared statistic of `bins` uniform bins: the mean
+/// `bins - 1` plus six standard deviations `sqrt(2 (bins - 1))`.
+fn chi_squared_bound(bins: usize) -> f64 {
+    let dof = (bins - 1) as f64;
+    dof + 6.0 * (2.0 * dof).sqrt()
+}
+
+/// Returns the sample mean and (unbiased) variance.
+fn mean_variance(samples: &[f64]) -> (f64, f64) {
+    let n = samples.len() as f64;
+    let mean = samples.iter().sum::<f64>() / n;
+    let variance = samples.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / (n - 1.0);
+    (mean, variance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    use crate::affine::Affine8;
+    use crate::matrix::{Matrix256, Matrix8};
+    use crate::tables::HTable;
+
+    #[test]
+    fn helpers_match_hand_computed_values() {
+        assert_eq!(chi_squared(&[10, 10, 10, 10]), 0.0);
+        // Expected 10 per bin: (10² + 10²) / 10.
+        assert_eq!(chi_squared(&[20, 0, 10, 10]), 20.0);
+        assert!((chi_squared_bound(256) - (255.0 + 6.0 * 510f64.sqrt())).abs() < 1e-9);
+        assert_eq!(mean_variance(&[1.0, 2.0, 3.0, 4.0]), (2.5, 5.0 / 3.0));
+    }
+
+    #[test]
+    #[ignore = "statistical; draws 65536 affine maps"]
+    fn affine8_outputs_and_bias_are_uniform() {
+        let mut rng = ChaCha20Rng::from_seed([80u8; 32]);
+        let inputs = [0x01, 0x53, 0xff];
+        let mut bias = [0u64; 256];
+        let mut outputs = [[0u64; 256]; 3];
+        let mut linear_zero = 0u64;
+        for _ in 0..65536 {
+            let aff = Affine8::random(&mut rng);
+            bias[usize::from(aff.bias)] += 1;
+            for (i, &x) in inputs.iter().enumerate() {
+                outputs[i][usize::from(aff.apply(x))] += 1;
+                linear_zero += u64::from(aff.lin.apply(x) == 0);
+            }
+        }
+        assert!(chi_squared(&bias) < chi_squared_bound(256));
+        for outputs in &outputs {
+            assert!(chi_squared(outputs) < chi_squared_bound(256));
+        }
+        // The linear part alone is not uniform: `P · L · U` maps `0x01` to a row-shuffled
+        // first column of `L`, which is biased towards heavy values (chi-squared ≈ 8000 over
+        // the 255 non-zero outputs). The uniform bias byte hides this in the affine output.
+        assert_eq!(linear_zero, 0);
+    }
+
+    #[test]
+    #[ignore = "statistical; draws 256 banded matrices"]
+    fn sparse_unsplit_blocks_have_expected_density_and_rank() {
+        let mut rng = ChaCha20Rng::from_seed([81u8; 32]);
+        let (mut diagonal, mut super_diagonal, mut wrap) = (Vec::new(), Vec::new(), Vec::new());
+        let mut singular_super = 0usize;
+        let mut singular_wrap = 0usize;
+        let density = |block: &Matrix8| {
+            block.rows().iter().map(|row| row.count_ones()).sum::<u32>() as f64 / 64.0
+        };
+        for _ in 0..256 {
+            let matrix = Matrix256::random_sparse_unsplit(&mut rng);
+            for block in 0..32 {
+                let diag = matrix.block(block, block);
+                assert!(diag.is_invertible());
+                diagonal.push(density(&diag));
+            }
+            for block in 0..31 {
+                let upper = matrix.block(block, block + 1);
+                super_diagonal.push(density(&upper));
+                singular_super += usize::from(!upper.is_invertible());
+            }
+            let wrap_block = matrix.block(31, 0);
+            wrap.push(density(&wrap_block));
+            singular_wrap += usize::from(!wrap_block.is_invertible());
+        }
+
+        // Uniform blocks: each bit is a fair coin, so density has mean 1/2 and variance
+        // 1/256.
+        for densities in [&super_diagonal, &wrap] {
+            let (mean, variance) = mean_variance(densities);
+            assert!((mean - 0.5).abs() < 0.02, "mean density {mean}");
+            assert!(
+                (variance - 1.0 / 256.0).abs() < 0.0015,
+                "density variance {variance}"
+            );
+        }
+        // `P · L · U` diagonal blocks are invertible but not uniform over GL(8, 2); their
+        // density sits slightly above one half (≈ 0.515 with this seed).
+        let (mean, variance) = mean_variance(&diagonal);
+        assert!((mean - 0.5).abs() < 0.03, "diagonal mean density {mean}");
+        assert!(
+            variance < 1.0 / 128.0,
+            "diagonal density variance {variance}"
+        );
+
+        // A uniform 8×8 matrix is singular with probability 1 - ∏(1 - 2^-i) ≈ 0.711.
+        let fraction = singular_super as f64 / super_diagonal.len() as f64;
+        assert!(
+            (fraction - 0.711).abs() < 0.03,
+            "singular super-diagonal fraction {fraction}"
+        );
+        // The wrap block is conditioned on closing the band, which is independent enough of
+        // its own rank that the fraction stays close to the uniform one.
+        let fraction = singular_wrap as f64 / wrap.len() as f64;
+        assert!(
+            (fraction - 0.711).abs() < 0.1,
+            "singular wrap fraction {fraction}"
+        );
+    }
+
+    #[test]
+    #[ignore = "statistical; draws 64 mask tables"]
+    fn htable_bytes_are_uniform() {
+        let mut rng = ChaCha20Rng::from_seed([82u8; 32]);
+        let mut overall = [0u64; 256];
+        let mut by_position = [[0u64; 256]; 32];
+        for _ in 0..64 {
+            let table = HTable::random(&mut rng);
+            for x in 0..=255u8 {
+                for (position, &byte) in table.get(x).as_bytes().iter().enumerate() {
+                    overall[usize::from(byte)] += 1;
+                    by_position[position][usize::from(byte)] += 1;
+                }
+            }
+        }
+        assert!(chi_squared(&overall) < chi_squared_bound(256));
+        for counts in &by_position {
+            assert!(chi_squared(counts) < chi_squared_bound(256));
+        }
+    }
+}
@@ -0,0 +1,488 @@
+//! GF(2) matrices of arbitrary dimensions.
+
+use std::fmt;
+
+use crate::matrix::{zeroed_rows, Matrix128, Matrix256, Matrix8};
+
+/// Dense GF(2) matrix of any dimensions, for analysis rather than speed.
+///
+/// Produced by [`Matrix256::submatrix`] and [`Matrix256::restrict_to_bytes`] to study a
+/// 256-bit map restricted to some of its input and output bits, and convertible to and from the
+/// fixed-size matrices when the dimensions match. Rows are packed into `u64` words in the bit
+/// order of [`Matrix256`]; bits past `cols` are always zero.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DynMatrixGf2 {
+    rows: usize,
+    cols: usize,
+    data: Vec<u64>,
+}
+
+/// A matrix conversion between types of different dimensions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DimensionMismatch {
+    /// Dimensions of the target type, as `(rows, cols)`.
+    pub expected: (usize, usize),
+    /// Dimensions of the rejected matrix.
+    pub actual: (usize, usize),
+}
+
+impl fmt::Display for DimensionMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "expected a {}×{} matrix, got {}×{}",
+            self.expected.0, self.expected.1, self.actual.0, self.actual.1
+        )
+    }
+}
+
+impl std::error::Error for DimensionMismatch {}
+
+/// A matrix in reduced row echelon form, with the pivot column of each leading row.
+struct Reduced {
+    echelon: DynMatrixGf2,
+    pivots: Vec<usize>,
+}
+
+impl DynMatrixGf2 {
+    /// Returns the `rows`×`cols` zero matrix.
+    pub fn zero(rows: usize, cols: usize) -> Self {
+        Self {
+            rows,
+            cols,
+            data: vec![0u64; rows * cols.div_ceil(64)],
+        }
+    }
+
+    /// Returns the `n`×`n` identity matrix.
+    pub fn identity(n: usize) -> Self {
+        Self::from_fn(n, n, |row, col| row == col)
+    }
+
+    /// Builds a `rows`×`cols` matrix whose entry `(row, col)` is `f(row, col)`.
+    pub fn from_fn(rows: usize, cols: usize, f: impl Fn(usize, usize) -> bool) -> Self {
+        let mut matrix = Self::zero(rows, cols);
+        for row in 0..rows {
+            for col in 0..cols {
+                matrix.set(row, col, f(row, col));
+            }
+        }
+        matrix
+    }
+
+    /// Returns the number of rows.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Returns the number of columns.
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Returns the entry at `(row, col)`.
+    pub fn get(&self, row: usize, col: usize) -> bool {
+        assert!(col < self.cols, "column index out of range");
+        (self.row(row)[col / 64] >> (col % 64)) & 1 == 1
+    }
+
+    /// Sets the entry at `(row, col)`.
+    pub fn set(&mut self, row: usize, col: usize, value: bool) {
+        assert!(col < self.cols, "column index out of range");
+        let mask = 1u64 << (col % 64);
+        let word = &mut self.row_mut(row)[col / 64];
+        if value {
+            *word |= mask;
+        } else {
+            *word &= !mask;
+        }
+    }
+
+    /// Returns the transpose.
+    pub fn transpose(&self) -> Self {
+        Self::from_fn(self.cols, self.rows, |row, col| self.get(col, row))
+    }
+
+    /// Returns the rank over GF(2).
+    pub fn rank(&self) -> u32 {
+        self.reduce(None).0.pivots.len() as u32
+    }
+
+    /// Multiplies two matrices (`self * rhs`).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self.cols() != rhs.rows()`.
+    pub fn mul(&self, rhs: &Self) -> Self {
+        assert_eq!(self.cols, rhs.rows, "inner dimensions differ");
+        let mut out = Self::zero(self.rows, rhs.cols);
+        for row in 0..self.rows {
+            for k in (0..self.cols).filter(|&k| self.get(row, k)) {
+                xor_into(out.row_mut(row), rhs.row(k));
+            }
+        }
+        out
+    }
+
+    /// Applies the matrix to a vector of `cols()` bits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `input.len() != self.cols()`.
+    pub fn apply(&self, input: &[bool]) -> Vec<bool> {
+        let input = pack(input, self.cols);
+        (0..self.rows)
+            .map(|row| {
+                let words = self.row(row).iter().zip(&input);
+                words.fold(0, |acc, (a, b)| acc ^ (a & b)).count_ones() % 2 == 1
+            })
+            .collect()
+    }
+
+    /// Returns the inverse, or `None` if the matrix is not square or is singular.
+    pub fn invert(&self) -> Option<Self> {
+        if self.rows != self.cols {
+            return None;
+        }
+        let (reduced, inverse) = self.reduce(Some(&Self::identity(self.rows)));
+        (reduced.pivots.len() == self.rows).then(|| inverse.expect("right-hand side is carried"))
+    }
+
+    /// Returns some `x` with `self · x = b`, or `None` if the system is inconsistent.
+    ///
+    /// Free variables are set to zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `b.len() != self.rows()`.
+    pub fn solve(&self, b: &[bool]) -> Option<Vec<bool>> {
+        assert_eq!(
+            b.len(),
+            self.rows,
+            "right-hand side length differs from row count"
+        );
+        let b = Self::from_fn(self.rows, 1, |row, _| b[row]);
+        let (reduced, rhs) = self.reduce(Some(&b));
+        let rhs = rhs.expect("right-hand side is carried");
+        // Rows below the pivots reduced to zero; their right-hand sides must have too.
+        if (reduced.pivots.len()..self.rows).any(|row| rhs.get(row, 0)) {
+            return None;
+        }
+        let mut x = vec![false; self.cols];
+        for (row, &col) in reduced.pivots.iter().enumerate() {
+            x[col] = rhs.get(row, 0);
+        }
+        Some(x)
+    }
+
+    /// Returns a basis of the kernel `{x : self · x = 0}`, one vector per free column in
+    /// increasing order.
+    pub fn kernel_basis(&self) -> Vec<Vec<bool>> {
+        let Reduced { echelon, pivots } = self.reduce(None).0;
+        let free = (0..self.cols).filter(|col| !pivots.contains(col));
+        free.map(|free_col| {
+            let mut x = vec![false; self.cols];
+            x[free_col] = true;
+            for (row, &col) in pivots.iter().enumerate() {
+                x[col] = echelon.get(row, free_col);
+            }
+            x
+        })
+        .collect()
+    }
+
+    /// Brings a copy to reduced row echelon form, applying the same row operations to `rhs` if
+    /// given.
+    fn reduce(&self, rhs: Option<&Self>) -> (Reduced, Option<Self>) {
+        let mut echelon = self.clone();
+        let mut rhs = rhs.cloned();
+        if let Some(rhs) = &rhs {
+            assert_eq!(rhs.rows, self.rows, "right-hand side row count differs");
+        }
+        let mut pivots = Vec::new();
+        for col in 0..self.cols {
+            let rank = pivots.len();
+            let Some(pivot) = (rank..self.rows).find(|&row| echelon.get(row, col)) else {
+                continue;
+            };
+            echelon.swap_rows(rank, pivot);
+            if let Some(rhs) = &mut rhs {
+                rhs.swap_rows(rank, pivot);
+            }
+            for row in 0..self.rows {
+                if row != rank && echelon.get(row, col) {
+                    echelon.xor_rows(rank, row);
+                    if let Some(rhs) = &mut rhs {
+                        rhs.xor_rows(rank, row);
+                    }
+                }
+            }
+            pivots.push(col);
+        }
+        (Reduced { echelon, pivots }, rhs)
+    }
+
+    /// Checks that the matrix is `n`×`n`, for the conversions to fixed sizes.
+    fn check_dimensions(&self, n: usize) -> Result<(), DimensionMismatch> {
+        if (self.rows, self.cols) != (n, n) {
+            return Err(DimensionMismatch {
+                expected: (n, n),
+                actual: (self.rows, self.cols),
+            });
+        }
+        Ok(())
+    }
+
+    fn stride(&self) -> usize {
+        self.cols.div_ceil(64)
+    }
+
+    fn row(&self, row: usize) -> &[u64] {
+        assert!(row < self.rows, "row index out of range");
+        let stride = self.stride();
+        &self.data[row * stride..(row + 1) * stride]
+    }
+
+    fn row_mut(&mut self, row: usize) -> &mut [u64] {
+        assert!(row < self.rows, "row index out of range");
+        let stride = self.stride();
+        &mut self.data[row * stride..(row + 1) * stride]
+    }
+
+    fn swap_rows(&mut self, a: usize, b: usize) {
+        let stride = self.stride();
+        for word in 0..stride {
+            self.data.swap(a * stride + word, b * stride + word);
+        }
+    }
+
+    /// XORs row `src` into row `dst`.
+    fn xor_rows(&mut self, src: usize, dst: usize) {
+        let stride = self.stride();
+        for word in 0..stride {
+            self.data[dst * stride + word] ^= self.data[src * stride + word];
+        }
+    }
+}
+
+impl From<&Matrix8> for DynMatrixGf2 {
+    fn from(matrix: &Matrix8) -> Self {
+        Self::from_fn(8, 8, |row, col| matrix.bit(row, col))
+    }
+}
+
+impl From<&Matrix128> for DynMatrixGf2 {
+    fn from(matrix: &Matrix128) -> Self {
+        Self {
+            rows: 128,
+            cols: 128,
+            data: matrix.words().iter().flatten().copied().collect(),
+        }
+    }
+}
+
+impl From<&Matrix256> for DynMatrixGf2 {
+    fn from(matrix: &Matrix256) -> Self {
+        Self {
+            rows: 256,
+            cols: 256,
+            data: matrix.words().iter().flatten().copied().collect(),
+        }
+    }
+}
+
+impl TryFrom<&DynMatrixGf2> for Matrix8 {
+    type Error = DimensionMismatch;
+
+    fn try_from(matrix: &DynMatrixGf2) -> Result<Self, Self::Error> {
+        matrix.check_dimensions(8)?;
+        let mut out = Matrix8::zero();
+        for row in 0..8 {
+            for col in 0..8 {
+                out.set_bit(row, col, matrix.get(row, col));
+            }
+        }
+        Ok(out)
+    }
+}
+
+impl TryFrom<&DynMatrixGf2> for Matrix128 {
+    type Error = DimensionMismatch;
+
+    fn try_from(matrix: &DynMatrixGf2) -> Result<Self, Self::Error> {
+        matrix.check_dimensions(128)?;
+        let mut words = [[0u64; 2]; 128];
+        for (row, out) in words.iter_mut().enumerate() {
+            out.copy_from_slice(matrix.row(row));
+        }
+        Ok(Matrix128::from_words(words))
+    }
+}
+
+impl TryFrom<&DynMatrixGf2> for Matrix256 {
+    type Error = DimensionMismatch;
+
+    fn try_from(matrix: &DynMatrixGf2) -> Result<Self, Self::Error> {
+        matrix.check_dimensions(256)?;
+        let mut words = zeroed_rows();
+        for (row, out) in words.iter_mut().enumerate() {
+            out.copy_from_slice(matrix.row(row));
+        }
+        Ok(Matrix256::from_words(words))
+    }
+}
+
+impl Matrix256 {
+    /// Returns the restriction to the given output bits (`rows`) and input bits (`cols`), in the
+    /// order given: entry `(i, j)` is entry `(rows[i], cols[j])` of `self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an index is out of range.
+    pub fn submatrix(&self, rows: &[usize], cols: &[usize]) -> DynMatrixGf2 {
+        assert!(
+            rows.iter().chain(cols).all(|&index| index < 256),
+            "bit index out of range"
+        );
+        let words = self.words();
+        DynMatrixGf2::from_fn(rows.len(), cols.len(), |i, j| {
+            (words[rows[i]][cols[j] / 64] >> (cols[j] % 64)) & 1 == 1
+        })
+    }
+
+    /// Returns the restriction to whole output and input bytes: [`Self::submatrix`] on bits
+    /// `8 * byte .. 8 * byte + 8` of each listed byte, in order.
+    pub fn restrict_to_bytes(&self, out_bytes: &[usize], in_bytes: &[usize]) -> DynMatrixGf2 {
+        let bits = |bytes: &[usize]| -> Vec<usize> {
+            bytes
+                .iter()
+                .flat_map(|&byte| (0..8).map(move |bit| byte * 8 + bit))
+                .collect()
+        };
+        self.submatrix(&bits(out_bytes), &bits(in_bytes))
+    }
+}
+
+/// XORs `src` into `dst` word by word.
+fn xor_into(dst: &mut [u64], src: &[u64]) {
+    for (d, s) in dst.iter_mut().zip(src) {
+        *d ^= s;
+    }
+}
+
+/// Packs `bits` (exactly `len` of them) into words.
+fn pack(bits: &[bool], len: usize) -> Vec<u64> {
+    assert_eq!(bits.len(), len, "vector length differs from column count");
+    let mut words = vec![0u64; len.div_ceil(64)];
+    for (index, _) in bits.iter().enumerate().filter(|(_, &bit)| bit) {
+        words[index / 64] |= 1u64 << (index % 64);
+    }
+    words
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha20Rng;
+
+    fn random_dyn(rng: &mut ChaCha20Rng, rows: usize, cols: usize) -> DynMatrixGf2 {
+        let mut matrix = DynMatrixGf2::zero(rows, cols);
+        for row in 0..rows {
+            for col in 0..cols {
+                matrix.set(row, col, rng.gen());
+            }
+        }
+        matrix
+    }
+
+    #[test]
+    fn aligned_byte_restrictions_match_blocks() {
+        let mut rng = ChaCha20Rng::from_seed([53u8; 32]);
+        let a = Matrix256::random_sparse_unsplit(&mut rng);
+        for (row_block, col_block) in [(0, 0), (0, 1), (31, 0), (7, 20), (12, 13)] {
+            let restricted = a.restrict_to_bytes(&[row_block], &[col_block]);
+            let block = a.block(row_block, col_block);
+            assert_eq!((restricted.rows(), restricted.cols()), (8, 8));
+            for row in 0..8 {
+                for col in 0..8 {
+                    assert_eq!(restricted.get(row, col), block.bit(row, col));
+                }
+            }
+            assert_eq!(restricted.rank(), block.rank());
+        }
+    }
+
+    #[test]
+    fn full_restriction_preserves_rank() {
+        let mut rng = ChaCha20Rng::from_seed([54u8; 32]);
+        let all: Vec<usize> = (0..32).collect();
+        let mut singular = Matrix256::random_invertible_dense(&mut rng);
+        singular.set_block(3, 3, &crate::Matrix8::zero());
+        for a in [
+            Matrix256::random_sparse_unsplit(&mut rng),
+            Matrix256::identity(),
+            Matrix256::zero(),
+            singular,
+        ] {
+            let restricted = a.restrict_to_bytes(&all, &all);
+            assert_eq!(restricted.rank(), a.rank());
+            let bits: Vec<usize> = (0..256).collect();
+            assert_eq!(a.submatrix(&bits, &bits), restricted);
+        }
+    }
+
+    #[test]
+    fn submatrix_follows_index_order() {
+        let mut rng = ChaCha20Rng::from_seed([55u8; 32]);
+        let a = Matrix256::random_invertible_dense(&mut rng);
+        let rows = [255, 0, 17, 17];
+        let cols = [3, 200, 64];
+        let sub = a.submatrix(&rows, &cols);
+        assert_eq!((sub.rows(), sub.cols()), (4, 3));
+        for (i, &row) in rows.iter().enumerate() {
+            for (j, &col) in cols.iter().enumerate() {
+                let mut input = [0u8; 32];
+                input[col / 8] = 1 << (col % 8);
+                let output = a.apply_to_bytes(&input);
+                assert_eq!(sub.get(i, j), (output[row / 8] >> (row % 8)) & 1 == 1);
+            }
+        }
+        // Duplicated output bits give dependent rows.
+        assert!(sub.rank() <= 3);
+    }
+
+    #[test]
+    fn mul_apply_and_solve_are_consistent() {
+        let mut rng = ChaCha20Rng::from_seed([56u8; 32]);
+        for (rows, inner, cols) in [(5, 9, 3), (70, 130, 256), (256, 256, 1)] {
+            let a = random_dyn(&mut rng, rows, inner);
+            let b = random_dyn(&mut rng, inner, cols);
+            let product = a.mul(&b);
+            assert_eq!((product.rows(), product.cols()), (rows, cols));
+            let x: Vec<bool> = (0..cols).map(|_| rng.gen()).collect();
+            assert_eq!(product.apply(&x), a.apply(&b.apply(&x)));
+
+            let target = a.apply(&b.apply(&x));
+            let solution = a.solve(&target).expect("target is in the image");
+            assert_eq!(a.apply(&solution), target);
+        }
+
+        // A 256×256 product agrees with Matrix256::mul.
+        let left = Matrix256::random_invertible_dense(&mut rng);
+        let right = Matrix256::random_sparse_unsplit(&mut rng);
+        let all: Vec<usize> = (0..256).collect();
+        assert_eq!(
+            left.submatrix(&all, &all).mul(&right.submatrix(&all, &all)),
+            left.mul(&right).submatrix(&all, &all)
+        );
+
+        // Two equal rows with different right-hand sides are inconsistent.
+        let mut duplicated = random_dyn(&mut rng, 2, 4);
+        for col in 0..4 {
+            let value = duplicated.get(0, col);
+            duplicated.set(1, col, value);
+        }
+        assert_eq!(duplicated.solve(&[true, false]), None);
+        assert!(DynMatrixGf2::zero(3, 0).solve(&[false; 3]).is_some());
+    }
+}
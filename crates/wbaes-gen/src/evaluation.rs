@@ -0,0 +1,148 @@
+//! Comparison harness measuring generator configurations side by side.
+//!
+//! Each configuration is generated once from the same key and seed, then
+//! measured for generation time and serialized size. Runtime throughput lives
+//! in `wbaes-runtime`, which depends on this crate, so it is collected through a
+//! caller-supplied probe (see [`run_matrix_with`]); rows without a probe leave
+//! it as `None`.
+//!
+//! Resistance scores are out of scope: [`crate::analysis`] has no per-instance
+//! measure yet, so there is no column to fill. Instances are always full size,
+//! as the crate has no reduced-rounds build.
+
+use std::time::Instant;
+
+use aes_core::Aes128Key;
+use serde::{Deserialize, Serialize};
+
+use crate::generator::{GenError, Generator, GeneratorConfig};
+use crate::instance::WbInstance256;
+
+/// Measurements for a single generator configuration.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EvaluationRow {
+    /// Configuration that produced the instance.
+    pub config: GeneratorConfig,
+    /// Wall-clock generation time in milliseconds.
+    pub generation_ms: f64,
+    /// Size of the `bincode`-serialized instance in bytes.
+    pub instance_bytes: u64,
+    /// Bytes occupied by the serialized round tables alone.
+    pub table_bytes: u64,
+    /// Runtime throughput in MiB/s, when a runtime probe was supplied.
+    pub runtime_mib_per_sec: Option<f64>,
+}
+
+/// Generates one instance per configuration and records its measurements.
+///
+/// Every configuration is validated before the first instance is generated, so an invalid
+/// one fails the run without waiting for the others.
+pub fn run_matrix(
+    configs: &[GeneratorConfig],
+    key: &Aes128Key,
+    seed: [u8; 32],
+) -> Result<Vec<EvaluationRow>, GenError> {
+    run_matrix_with(configs, key, seed, |_| None)
+}
+
+/// Like [`run_matrix`], additionally passing each instance to `runtime_probe`,
+/// which returns the measured throughput in MiB/s.
+pub fn run_matrix_with(
+    configs: &[GeneratorConfig],
+    key: &Aes128Key,
+    seed: [u8; 32],
+    mut runtime_probe: impl FnMut(WbInstance256) -> Option<f64>,
+) -> Result<Vec<EvaluationRow>, GenError> {
+    for config in configs {
+        config.validate()?;
+    }
+    configs
+        .iter()
+        .map(|config| {
+            let mut gen = Generator::from_seed_with_config(seed, config.clone());
+            let start = Instant::now();
+            let instance = gen.try_generate_instance(key)?;
+            let generation_ms = start.elapsed().as_secs_f64() * 1e3;
+
+            let instance_bytes =
+                bincode::serialized_size(&instance).expect("instance serialization is infallible");
+            let table_bytes = bincode::serialized_size(&instance.rounds)
+                .expect("table serialization is infallible");
+
+            Ok(EvaluationRow {
+                config: config.clone(),
+                generation_ms,
+                instance_bytes,
+                table_bytes,
+                runtime_mib_per_sec: runtime_probe(instance),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::ExternalEncodingKind;
+
+    #[test]
+    fn dense_and_banded_rows_are_consistent() {
+        let configs = [
+            GeneratorConfig {
+                external_encodings: true,
+                ..GeneratorConfig::default()
+            },
+            GeneratorConfig {
+                external_encodings: true,
+                external_encoding_kind: ExternalEncodingKind::Banded,
+                encoding_bandwidth: 2,
+                ..GeneratorConfig::default()
+            },
+        ];
+        let key = Aes128Key::from([0x2bu8; 16]);
+        let mut payloads = Vec::new();
+        let rows = run_matrix_with(&configs, &key, [5u8; 32], |instance| {
+            let payload = (&instance.encodings, &instance.params);
+            payloads.push(bincode::serialized_size(&payload).expect("payload serializes"));
+            None
+        })
+        .expect("both configurations are valid");
+
+        assert_eq!(rows.len(), 2);
+        for ((row, config), payload) in rows.iter().zip(configs.iter()).zip(payloads) {
+            assert_eq!(&row.config, config);
+            assert!(row.generation_ms > 0.0);
+            // Everything besides the tables is the encodings and the parameters.
+            assert_eq!(row.instance_bytes - row.table_bytes, payload);
+            assert_eq!(row.runtime_mib_per_sec, None);
+        }
+        // Both shapes are folded into maps of the same size; only the contents differ.
+        assert_eq!(rows[0].table_bytes, rows[1].table_bytes);
+        assert_eq!(rows[0].instance_bytes, rows[1].instance_bytes);
+
+        let encoded = bincode::serialize(&rows).expect("report serializes");
+        let decoded: Vec<EvaluationRow> = bincode::deserialize(&encoded).expect("report decodes");
+        assert_eq!(decoded, rows);
+    }
+
+    #[test]
+    fn invalid_config_fails_before_generating() {
+        let configs = [
+            GeneratorConfig::default(),
+            GeneratorConfig {
+                external_encodings: true,
+                external_encoding_kind: ExternalEncodingKind::Banded,
+                encoding_bandwidth: 0,
+                ..GeneratorConfig::default()
+            },
+        ];
+        let key = Aes128Key::from([0x2bu8; 16]);
+        let mut probed = 0;
+        let result = run_matrix_with(&configs, &key, [5u8; 32], |_| {
+            probed += 1;
+            None
+        });
+        assert_eq!(result, Err(GenError::InvalidBandwidth(0)));
+        assert_eq!(probed, 0);
+    }
+}
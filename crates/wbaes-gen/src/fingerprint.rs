@@ -0,0 +1,17 @@
+//! Content fingerprints for the large generator types.
+
+use std::io::BufWriter;
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+/// Returns the SHA-256 of the `bincode` serialization of `value`, streamed without buffering
+/// the whole encoding.
+pub(crate) fn serialized_sha256<T: Serialize + ?Sized>(value: &T) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    {
+        let mut writer = BufWriter::new(&mut hasher);
+        bincode::serialize_into(&mut writer, value).expect("hashing writer is infallible");
+    }
+    hasher.finalize().into()
+}
@@ -1,41 +1,260 @@
 //! Instance generator for the revisited white-box AES scheme.
 
 use std::convert::TryInto;
+use std::fmt;
+use std::time::Instant;
 
-use aes_core::{expand_key, sbox, Aes128Key};
-use rand::{CryptoRng, RngCore};
+use aes_core::{
+    expand_key, expand_key_dec, inv_sbox, join_double, sbox, xor_in_place, Aes128Key, Block,
+    DoubleBlock, RoundKeys,
+};
+use rand::{CryptoRng, RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroize;
 
-use crate::affine::Affine256;
-use crate::instance::{ExternalEncodings, WbInstance256};
-use crate::linear::{mc_sr_matrix_256, sr_matrix_256};
-use crate::matrix::Matrix256;
-use crate::tables::{HTable, RoundTables};
+use crate::affine::{Affine128, Affine256, Affine8};
+use crate::chow::{self, ChowConfig, WbInstanceChow};
+use crate::instance::{
+    Direction, ExternalEncodingSecrets, ExternalEncodings, InstanceParams, PerByteEncodings,
+    WbInstance128, WbInstance256,
+};
+use crate::linear::{
+    inv_mc_inv_sr_matrix_256, inv_sr_matrix_256, mc_sr_matrix_128, mc_sr_matrix_256, sr_matrix_128,
+    sr_matrix_256,
+};
+use crate::matrix::{Matrix128, Matrix256, Matrix8};
+use crate::simd;
+use crate::stats::GenStats;
+use crate::tables::{HTable, RoundTables, RoundTables128};
+use crate::vec256::Vec256;
 
 /// Configuration for the generator.
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct GeneratorConfig {
     /// Whether to include random external encodings (`Min`, `Mout`).
+    ///
+    /// They are dense affine maps unless `external_encoding_kind` says otherwise; only the
+    /// internal `A^(r)` need the banded structure.
     pub external_encodings: bool,
+    /// Shape of the external encodings, when enabled.
+    pub external_encoding_kind: ExternalEncodingKind,
+    /// Whether to keep each round's mask tables in [`WbInstance256::masks`].
+    ///
+    /// For research and teaching only: the masks are what hides the affine contributions in
+    /// the tables. Randomness is consumed identically either way.
+    pub retain_masks: bool,
+    /// Bandwidth of [`ExternalEncodingKind::Banded`] external encodings (default 1); see
+    /// [`Affine256::random_banded`].
+    ///
+    /// The internal `A^(r)` always have bandwidth 1: each table reads the two source bytes of
+    /// its block row, so a wider band would need wider tables. A banded kind needs
+    /// `1 <= encoding_bandwidth <= 31`; see [`GenError::InvalidBandwidth`].
+    pub encoding_bandwidth: usize,
+    /// Whether the round tables are masked with the [`HTable`]s (default true).
+    ///
+    /// **Insecure, for debugging only.** When false the masks are still drawn, so randomness
+    /// is consumed identically, but are zeroed before the tables are filled: the instance is
+    /// the masked one with all-zero masks, and mismatches against AES can be bisected between
+    /// the affine algebra and the mask bookkeeping.
+    pub use_masks: bool,
+    /// Where `Min` and `Mout` come from (default [`ExternalEncodingSource::Random`]).
+    pub external: ExternalEncodingSource,
+}
+
+impl Default for GeneratorConfig {
+    fn default() -> Self {
+        Self {
+            external_encodings: false,
+            external_encoding_kind: ExternalEncodingKind::default(),
+            retain_masks: false,
+            encoding_bandwidth: 1,
+            use_masks: true,
+            external: ExternalEncodingSource::default(),
+        }
+    }
+}
+
+impl GeneratorConfig {
+    /// Checks that the generator can use this configuration: provided external encodings
+    /// must be invertible and a random banded kind needs a bandwidth
+    /// [`Matrix256::random_banded`] can sample.
+    ///
+    /// Every fallible `Generator::try_*` entry point runs this check first, before drawing
+    /// any randomness.
+    pub fn validate(&self) -> Result<(), GenError> {
+        match &self.external {
+            ExternalEncodingSource::Provided { min, mout } => {
+                let singular = |map: &Affine256| !map.lin.is_invertible();
+                if singular(min) || mout.as_ref().is_some_and(singular) {
+                    return Err(GenError::SingularEncoding);
+                }
+            }
+            ExternalEncodingSource::Random => {
+                let banded = self.external_encodings
+                    && self.external_encoding_kind == ExternalEncodingKind::Banded;
+                if banded && !(1..=31).contains(&self.encoding_bandwidth) {
+                    return Err(GenError::InvalidBandwidth(self.encoding_bandwidth));
+                }
+            }
+            ExternalEncodingSource::None => {}
+        }
+        Ok(())
+    }
+}
+
+/// Shape of the external encodings sampled by the generator.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ExternalEncodingKind {
+    /// Dense 256-bit affine maps: `Min` right after the initial AddRoundKey and `Mout` folded
+    /// into the last round.
+    #[default]
+    Dense,
+    /// 32 independent [`Affine8`] maps on each side, applied by the caller at the boundary
+    /// and stored in [`ExternalEncodings::per_byte`]; see [`PerByteEncodings`].
+    PerByte,
+    /// Like `Dense`, but with [`GeneratorConfig::encoding_bandwidth`]-banded linear parts.
+    Banded,
+}
+
+/// Source of the dense external encodings `Min` and `Mout`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExternalEncodingSource {
+    /// No external encodings, whatever [`GeneratorConfig::external_encodings`] says.
+    None,
+    /// Drawn by the generator when [`GeneratorConfig::external_encodings`] is set, shaped by
+    /// [`GeneratorConfig::external_encoding_kind`].
+    #[default]
+    Random,
+    /// Supplied by the caller, for deployments where they are fixed elsewhere, and folded
+    /// exactly as random dense ones; no randomness is drawn for them, and
+    /// [`GeneratorConfig::external_encodings`] and `external_encoding_kind` are ignored.
+    Provided {
+        /// `Min`, applied right after the initial AddRoundKey.
+        min: Affine256,
+        /// `Mout`, which maps the runtime output to the AES ciphertext; `None` leaves the
+        /// output unencoded.
+        mout: Option<Affine256>,
+    },
+}
+
+/// Reasons [`GeneratorConfig::validate`] refuses a configuration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GenError {
+    /// A map in [`ExternalEncodingSource::Provided`] is not invertible.
+    SingularEncoding,
+    /// [`GeneratorConfig::encoding_bandwidth`] is outside `1..=31` for random
+    /// [`ExternalEncodingKind::Banded`] encodings.
+    InvalidBandwidth(usize),
+}
+
+impl fmt::Display for GenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SingularEncoding => write!(f, "provided external encoding is not invertible"),
+            Self::InvalidBandwidth(bandwidth) => {
+                write!(f, "encoding bandwidth {bandwidth} is outside 1..=31")
+            }
+        }
+    }
+}
+
+impl std::error::Error for GenError {}
+
+/// An instance together with the external encodings folded into it, from
+/// [`Generator::generate_instance_with_encodings`].
+#[derive(Clone, Debug)]
+pub struct GeneratedInstance {
+    /// The white-box instance, for the untrusted side.
+    pub instance: WbInstance256,
+    /// `Min` and `Mout`, for the trusted side.
+    pub external: Option<ExternalEncodingSecrets>,
 }
 
 /// White-box instance generator parametrized by an RNG.
+///
+/// Generation is reproducible: [`Self::from_seed`] with the same seed, key and configuration
+/// always yields the same instance. Randomness is drawn in this order, and
+/// `tests/golden_instance.rs` pins the result, so any change to it is a deliberate format
+/// change that bumps [`crate::InstanceParams::version`]:
+///
+/// 1. the ten `A^(r)`, each by [`Affine256::random_sparse_unsplit`] (32 diagonal blocks, 31
+///    super-diagonal blocks, wrap blocks until one closes the band, then the bias);
+/// 2. with random external encodings, `Min` then `Mout` ([`Affine256::random_dense`] or
+///    [`Affine256::random_banded`]), or for per-byte encodings the 32 input maps then the 32
+///    output maps ([`Affine8::random`]);
+/// 3. for each round in order, 31 random bias shares (the 32nd is their XOR with the target)
+///    and then the 32 [`HTable`]s.
+///
+/// All of a round's randomness is drawn before its tables are filled, so with the `parallel`
+/// feature the tables are filled on the rayon thread pool and the instance stays
+/// byte-identical to the sequential build.
 pub struct Generator<R: RngCore + CryptoRng> {
     rng: R,
     config: GeneratorConfig,
+    parallel: bool,
+    rounds: usize,
+}
+
+impl Generator<ChaCha20Rng> {
+    /// Creates a generator with default configuration, drawing from ChaCha20 seeded with
+    /// `seed`.
+    ///
+    /// This is the reproducible way to generate: the same seed and key always give the same
+    /// instance.
+    pub fn from_seed(seed: [u8; 32]) -> Self {
+        Self::new(ChaCha20Rng::from_seed(seed))
+    }
+
+    /// [`Self::from_seed`] with an explicit configuration.
+    pub fn from_seed_with_config(seed: [u8; 32], config: GeneratorConfig) -> Self {
+        Self::with_config(ChaCha20Rng::from_seed(seed), config)
+    }
 }
 
 impl<R: RngCore + CryptoRng> Generator<R> {
     /// Creates a new generator with default configuration.
     pub fn new(rng: R) -> Self {
+        Self::with_config(rng, GeneratorConfig::default())
+    }
+
+    /// Creates a generator with explicit configuration.
+    pub fn with_config(rng: R, config: GeneratorConfig) -> Self {
         Self {
             rng,
-            config: GeneratorConfig::default(),
+            config,
+            parallel: cfg!(feature = "parallel"),
+            rounds: 10,
         }
     }
 
-    /// Creates a generator with explicit configuration.
-    pub fn with_config(rng: R, config: GeneratorConfig) -> Self {
-        Self { rng, config }
+    /// Chooses whether round tables are filled on the rayon thread pool (the default with the
+    /// `parallel` feature); the instance is the same either way.
+    #[cfg(feature = "parallel")]
+    pub fn set_parallel(&mut self, parallel: bool) {
+        self.parallel = parallel;
+    }
+
+    /// Builds [`WbInstance256`] encryption instances that compute only the first `rounds` AES
+    /// rounds, the last one without MixColumns as in
+    /// [`aes_core::encrypt_block_rounds`] (the `reduced-rounds` feature, for tests).
+    ///
+    /// The remaining rounds of the instance are zeroed and [`InstanceParams::rounds`] records
+    /// the count, which the runtime honors; [`WbInstance256::from_bytes_validated`] rejects
+    /// the instance.
+    ///
+    /// # Panics
+    ///
+    /// Unless `1 <= rounds <= 10`. [`Self::generate_instance_pair`] also panics on a reduced
+    /// generator.
+    #[cfg(feature = "reduced-rounds")]
+    pub fn set_rounds(&mut self, rounds: usize) {
+        assert!(
+            (1..=10).contains(&rounds),
+            "round count {rounds} is outside 1..=10"
+        );
+        self.rounds = rounds;
     }
 
     /// Returns a mutable reference to the configuration.
@@ -44,52 +263,326 @@ impl<R: RngCore + CryptoRng> Generator<R> {
     }
 
     /// Generates a white-box instance for the provided AES-128 key.
+    ///
+    /// # Panics
+    ///
+    /// If the configuration is invalid; see [`Self::try_generate_instance`].
     pub fn generate_instance(&mut self, key: &Aes128Key) -> WbInstance256 {
-        let round_keys = expand_key(key);
-        let mc_sr = mc_sr_matrix_256();
-        let sr_only = sr_matrix_256();
+        self.generate_instance_with_stats(key).0
+    }
 
-        let key0_block = duplicate_round_key(round_keys.get(0));
-        let key0_affine = Affine256::new(Matrix256::identity(), key0_block);
+    /// [`Self::generate_instance`], returning [`GenError`] instead of panicking when the
+    /// configuration cannot be used.
+    pub fn try_generate_instance(&mut self, key: &Aes128Key) -> Result<WbInstance256, GenError> {
+        self.generate(key).map(|(instance, _, _)| instance)
+    }
+
+    /// [`Self::generate_instance`], also returning sampler counts and per-phase timings.
+    ///
+    /// Randomness is consumed exactly as by `generate_instance`, so the instance is the same.
+    pub fn generate_instance_with_stats(&mut self, key: &Aes128Key) -> (WbInstance256, GenStats) {
+        let (instance, stats, _) = self.generate(key).expect("invalid generator configuration");
+        (instance, stats)
+    }
+
+    /// [`Self::generate_instance`], also returning the dense, banded or provided external
+    /// encodings folded into the instance so that the trusted side can remove them.
+    ///
+    /// [`GeneratedInstance::external`] is `None` without external encodings and for
+    /// [`ExternalEncodingKind::PerByte`], whose maps the instance already carries. Randomness
+    /// is consumed exactly as by `generate_instance`, so the instance is the same.
+    ///
+    /// # Panics
+    ///
+    /// If the configuration is invalid; see [`Self::try_generate_instance_with_encodings`].
+    pub fn generate_instance_with_encodings(&mut self, key: &Aes128Key) -> GeneratedInstance {
+        self.try_generate_instance_with_encodings(key)
+            .expect("invalid generator configuration")
+    }
+
+    /// [`Self::generate_instance_with_encodings`], returning [`GenError`] instead of panicking
+    /// when the configuration cannot be used.
+    pub fn try_generate_instance_with_encodings(
+        &mut self,
+        key: &Aes128Key,
+    ) -> Result<GeneratedInstance, GenError> {
+        let (instance, _, external) = self.generate(key)?;
+        Ok(GeneratedInstance { instance, external })
+    }
+
+    fn generate(
+        &mut self,
+        key: &Aes128Key,
+    ) -> Result<(WbInstance256, GenStats, Option<ExternalEncodingSecrets>), GenError> {
+        self.config.validate()?;
+        let start = Instant::now();
+        let mut stats = GenStats::default();
+        let spec = CipherSpec::new(key, Direction::Encrypt);
+        let a_encodings = self.sample_internal_encodings(&mut stats);
+        let mut boundary = self.sample_boundary();
+        let instance = self.build_instance(&spec, a_encodings, &boundary, start, &mut stats);
+        let external = self
+            .folds_external_encodings()
+            .then(|| boundary.secrets(&spec.round_keys[0]));
+        boundary.zeroize();
+        stats.total = start.elapsed();
+        Ok((instance, stats, external))
+    }
+
+    /// Generates matched encryption and decryption instances for `key`.
+    ///
+    /// The decryption instance inverts the encryption one exactly: its input encoding cancels
+    /// `Mout` and its output encoding cancels `Min` (and the per-byte encodings, which it
+    /// stores swapped and inverted), so `dec(enc(x)) == x` without the AES ciphertext ever
+    /// appearing between them. Both carry the same [`crate::InstanceParams::pair_id`].
+    ///
+    /// The encryption instance consumes randomness as [`Self::generate_instance`] does and has
+    /// the same tables; then come the 16-byte pair identifier, the decryption instance's ten
+    /// `A^(r)` and its rounds.
+    ///
+    /// # Panics
+    ///
+    /// If the configuration is invalid; see [`Self::try_generate_instance_pair`].
+    pub fn generate_instance_pair(&mut self, key: &Aes128Key) -> (WbInstance256, WbInstance256) {
+        self.try_generate_instance_pair(key)
+            .expect("invalid generator configuration")
+    }
+
+    /// [`Self::generate_instance_pair`], returning [`GenError`] instead of panicking when the
+    /// configuration cannot be used.
+    pub fn try_generate_instance_pair(
+        &mut self,
+        key: &Aes128Key,
+    ) -> Result<(WbInstance256, WbInstance256), GenError> {
+        assert_eq!(self.rounds, 10, "instance pairs need all ten rounds");
+        self.config.validate()?;
+        let start = Instant::now();
+        let mut stats = GenStats::default();
+        let enc_spec = CipherSpec::new(key, Direction::Encrypt);
+        let a_encodings = self.sample_internal_encodings(&mut stats);
+        let mut boundary = self.sample_boundary();
+        let mut enc = self.build_instance(&enc_spec, a_encodings, &boundary, start, &mut stats);
+        let mut pair_id = [0u8; 16];
+        self.rng.fill_bytes(&mut pair_id);
+
+        let mut inverse = boundary.inverse(&enc_spec.round_keys[0]);
+        boundary.zeroize();
+        drop(enc_spec);
+        let dec_spec = CipherSpec::new(key, Direction::Decrypt);
+        let a_encodings = self.sample_internal_encodings(&mut stats);
+        let mut dec = self.build_instance(&dec_spec, a_encodings, &inverse, start, &mut stats);
+        inverse.zeroize();
+
+        enc.params.pair_id = Some(pair_id);
+        dec.params.pair_id = Some(pair_id);
+        dec.params.direction = Direction::Decrypt;
+        Ok((enc, dec))
+    }
+
+    /// Generates a [`WbInstanceChow`] for `key` with the Chow et al. (2002) construction.
+    ///
+    /// An alternative to the revisited scheme for comparison: encryption only, without
+    /// external encodings, so [`GeneratorConfig`] does not apply. See [`ChowConfig`] for what
+    /// can be switched and `chow.rs` for the order randomness is drawn in.
+    pub fn generate_chow_instance(
+        &mut self,
+        key: &Aes128Key,
+        config: ChowConfig,
+    ) -> WbInstanceChow {
+        chow::build_instance(&mut self.rng, key, config)
+    }
+
+    /// Generates a single-block [`WbInstance128`] for `key`.
+    ///
+    /// The same construction on one AES state: 16 tables per round under encodings `A^(r)`
+    /// from [`Affine128::random_sparse_unsplit`]. External encodings and retained masks do not
+    /// exist at this width, so those configuration options are ignored; `use_masks` applies.
+    /// Randomness is drawn as for [`Self::generate_instance`]: the ten `A^(r)`, then for each
+    /// round 15 bias shares and the 16 mask tables.
+    pub fn generate_instance_128(&mut self, key: &Aes128Key) -> WbInstance128 {
+        let mut round_keys = expand_key(key);
+        let mc_sr = mc_sr_matrix_128();
+        let sr_only = sr_matrix_128();
+        let mut a_encodings: Vec<Affine128> = (0..10)
+            .map(|_| Affine128::random_sparse_unsplit(&mut self.rng))
+            .collect();
+
+        let mut key0_affine = Affine128::new(Matrix128::identity(), *round_keys.get(0));
+        let mut a1_inv = a_encodings[0].invert().expect("A^(1) should be invertible");
+        let input = key0_affine.then(&a1_inv);
 
-        let mut a_encodings = Vec::with_capacity(10);
-        for _ in 0..10 {
-            a_encodings.push(Affine256::random_sparse_unsplit(&mut self.rng));
+        let identity_output = Affine128::identity();
+        let mut rounds: Vec<RoundTables128> = Vec::with_capacity(10);
+        for r in 0..10 {
+            let (next_affine, linear_layer) = if r == 9 {
+                (&identity_output, &sr_only)
+            } else {
+                (&a_encodings[r + 1], &mc_sr)
+            };
+            rounds.push(build_round_128(
+                &mut self.rng,
+                &a_encodings[r],
+                next_affine,
+                linear_layer,
+                round_keys.get(r + 1),
+                self.config.use_masks,
+                self.parallel,
+            ));
         }
+        round_keys.zeroize();
+        key0_affine.zeroize();
+        a1_inv.zeroize();
+        a_encodings.zeroize();
 
-        let (min_encoding, mout_encoding) = if self.config.external_encodings {
-            (
-                Affine256::random_sparse_unsplit(&mut self.rng),
-                Some(Affine256::random_sparse_unsplit(&mut self.rng)),
-            )
-        } else {
-            (Affine256::identity(), None)
-        };
+        WbInstance128 {
+            rounds: rounds
+                .try_into()
+                .expect("round vector should have length 10"),
+            input,
+            params: InstanceParams::single_block(),
+        }
+    }
+
+    fn sample_internal_encodings(&mut self, stats: &mut GenStats) -> Vec<Affine256> {
+        (0..10)
+            .map(|_| Affine256::random_sparse_unsplit_with_stats(&mut self.rng, stats))
+            .collect()
+    }
+
+    /// Whether `Min` and `Mout` are dense or banded maps folded into the instance.
+    fn folds_external_encodings(&self) -> bool {
+        match &self.config.external {
+            ExternalEncodingSource::None => false,
+            ExternalEncodingSource::Random => {
+                self.config.external_encodings
+                    && self.config.external_encoding_kind != ExternalEncodingKind::PerByte
+            }
+            ExternalEncodingSource::Provided { .. } => true,
+        }
+    }
+
+    fn sample_boundary(&mut self) -> Boundary {
+        match &self.config.external {
+            ExternalEncodingSource::None => return Boundary::none(),
+            ExternalEncodingSource::Provided { min, mout } => {
+                return Boundary {
+                    before_key: None,
+                    min: min.clone(),
+                    mout: mout.clone(),
+                    per_byte: None,
+                }
+            }
+            ExternalEncodingSource::Random => {}
+        }
+        match (
+            self.config.external_encodings,
+            self.config.external_encoding_kind,
+        ) {
+            (false, _) => Boundary::none(),
+            (true, ExternalEncodingKind::Dense) => Boundary {
+                before_key: None,
+                min: Affine256::random_dense(&mut self.rng),
+                mout: Some(Affine256::random_dense(&mut self.rng)),
+                per_byte: None,
+            },
+            (true, ExternalEncodingKind::Banded) => {
+                let bandwidth = self.config.encoding_bandwidth;
+                Boundary {
+                    before_key: None,
+                    min: Affine256::random_banded(&mut self.rng, bandwidth),
+                    mout: Some(Affine256::random_banded(&mut self.rng, bandwidth)),
+                    per_byte: None,
+                }
+            }
+            (true, ExternalEncodingKind::PerByte) => {
+                let per_byte = PerByteEncodings {
+                    input: core::array::from_fn(|_| Affine8::random(&mut self.rng)),
+                    output: core::array::from_fn(|_| Affine8::random(&mut self.rng)),
+                };
+                Boundary::per_byte(per_byte)
+            }
+        }
+    }
 
-        let a1_inv = a_encodings[0].invert().expect("A^(1) should be invertible");
-        let min_total = min_encoding.compose(&key0_affine);
-        let input_encoding = a1_inv.compose(&min_total);
+    /// Builds the tables of `spec` under the internal encodings `a_encodings` and the
+    /// external ones in `boundary`, then wipes `a_encodings`.
+    fn build_instance(
+        &mut self,
+        spec: &CipherSpec,
+        mut a_encodings: Vec<Affine256>,
+        boundary: &Boundary,
+        start: Instant,
+        stats: &mut GenStats,
+    ) -> WbInstance256 {
+        let mut key0_affine = Affine256::new(Matrix256::identity(), spec.round_keys[0].into());
+        // Scratch for the composition chains; each product overwrites it in place.
+        let mut scratch = Affine256::identity();
+        let mut a1_inv = a_encodings[0].invert().expect("A^(1) should be invertible");
+        // Input pipeline: strip per-byte encodings, add k0, then `Min`, then `A^(1)⁻¹`.
+        let mut keyed = match &boundary.before_key {
+            Some(before_key) => before_key.then(&key0_affine),
+            None => key0_affine.clone(),
+        };
+        keyed.then_into(&boundary.min, &mut scratch);
+        let mut input_encoding = Affine256::identity();
+        scratch.then_into(&a1_inv, &mut input_encoding);
 
+        let identity_output = Affine256::identity();
+        stats.encodings = start.elapsed();
         let mut rounds: Vec<RoundTables> = Vec::with_capacity(10);
+        let mut masks = self.config.retain_masks.then(|| Vec::with_capacity(10));
+        let mut h_tables: Box<[HTable; 32]> = vec![HTable::zeroed(); 32]
+            .into_boxed_slice()
+            .try_into()
+            .expect("one mask table per byte");
         for r in 0..10 {
+            if r >= self.rounds {
+                // Past a reduced round count; never evaluated.
+                h_tables.zeroize();
+                rounds.push(RoundTables::new_zeroed());
+                if let Some(masks) = &mut masks {
+                    masks.push(h_tables.clone());
+                }
+                continue;
+            }
+            let last = r + 1 == self.rounds;
             let a_curr = &a_encodings[r];
-            let identity_output = Affine256::identity();
-            let next_affine = if r == 9 {
-                mout_encoding.as_ref().unwrap_or(&identity_output)
+            let next_affine = if last {
+                boundary.mout.as_ref().unwrap_or(&identity_output)
             } else {
                 &a_encodings[r + 1]
             };
-            let linear_layer = if r == 9 { &sr_only } else { &mc_sr };
-            let round_key_block = duplicate_round_key(round_keys.get(r + 1));
+            let linear_layer = if last {
+                &spec.last_linear_layer
+            } else {
+                &spec.linear_layer
+            };
+            let round_start = Instant::now();
             let round_tables = build_round(
                 &mut self.rng,
                 a_curr,
                 next_affine,
                 linear_layer,
-                &round_key_block,
+                spec.sbox,
+                &spec.round_keys[r + 1],
+                self.config.use_masks,
+                self.parallel,
+                &mut scratch.lin,
+                &mut h_tables,
             );
             rounds.push(round_tables);
+            if let Some(masks) = &mut masks {
+                masks.push(h_tables.clone());
+            }
+            stats.rounds.push(round_start.elapsed());
         }
+        // Everything below is key-dependent or reveals the internal or external encodings.
+        key0_affine.zeroize();
+        keyed.zeroize();
+        a_encodings.zeroize();
+        a1_inv.zeroize();
+        scratch.zeroize();
+        h_tables.zeroize();
 
         let rounds: [RoundTables; 10] = rounds
             .try_into()
@@ -100,89 +593,373 @@ impl<R: RngCore + CryptoRng> Generator<R> {
             encodings: ExternalEncodings {
                 input: input_encoding,
                 output: None, // output encoding is folded into round 10
+                per_byte: boundary.per_byte,
             },
-            params: Default::default(),
+            params: InstanceParams {
+                rounds: self.rounds,
+                ..Default::default()
+            },
+            masks,
+        }
+    }
+}
+
+/// The round function and round keys of the cipher an instance computes.
+///
+/// Decryption uses the equivalent inverse cipher, whose rounds have the encryption shape:
+/// an S-box layer, a linear layer, then a round key.
+struct CipherSpec {
+    /// Doubled round keys: `round_keys[0]` is added before round 1 and `round_keys[r]` at the
+    /// end of round `r`.
+    round_keys: [DoubleBlock; 11],
+    sbox: fn(u8) -> u8,
+    /// `MC ∘ SR`, or `MC⁻¹ ∘ SR⁻¹` when decrypting, for rounds 1 to 9.
+    linear_layer: Matrix256,
+    /// `SR`, or `SR⁻¹` when decrypting, for the last round.
+    ///
+    /// Kept dense rather than as a [`crate::Permutation256`]: it only enters the one solve
+    /// against `A^(11)⁻¹` per instance, whose cost does not depend on the right-hand side.
+    last_linear_layer: Matrix256,
+}
+
+impl CipherSpec {
+    fn new(key: &Aes128Key, direction: Direction) -> Self {
+        match direction {
+            Direction::Encrypt => {
+                let mut round_keys = expand_key(key);
+                let spec = Self {
+                    round_keys: core::array::from_fn(|r| duplicate_round_key(&round_keys, r)),
+                    sbox,
+                    linear_layer: mc_sr_matrix_256(),
+                    last_linear_layer: sr_matrix_256(),
+                };
+                round_keys.zeroize();
+                spec
+            }
+            Direction::Decrypt => {
+                // The equivalent inverse cipher consumes its round keys from last to first.
+                let mut round_keys = expand_key_dec(key);
+                let spec = Self {
+                    round_keys: core::array::from_fn(|r| duplicate_round_key(&round_keys, 10 - r)),
+                    sbox: inv_sbox,
+                    linear_layer: inv_mc_inv_sr_matrix_256(),
+                    last_linear_layer: inv_sr_matrix_256(),
+                };
+                round_keys.zeroize();
+                spec
+            }
         }
     }
 }
 
+impl Drop for CipherSpec {
+    fn drop(&mut self) {
+        self.round_keys.zeroize();
+    }
+}
+
+/// External encodings around the table network: `before_key` is undone before the initial
+/// AddRoundKey, `min` follows it, and `mout` is folded into the last round.
+struct Boundary {
+    before_key: Option<Affine256>,
+    min: Affine256,
+    mout: Option<Affine256>,
+    per_byte: Option<PerByteEncodings>,
+}
+
+impl Boundary {
+    /// No external encodings.
+    fn none() -> Self {
+        Self {
+            before_key: None,
+            min: Affine256::identity(),
+            mout: None,
+            per_byte: None,
+        }
+    }
+
+    /// Per-byte encodings are removed before the initial AddRoundKey, so that the caller can
+    /// apply them without the key.
+    fn per_byte(per_byte: PerByteEncodings) -> Self {
+        let mut input_inv = per_byte
+            .input
+            .map(|map| map.invert().expect("random affine maps are invertible"));
+        let before_key = Affine256::concat_bytes(&input_inv);
+        input_inv.zeroize();
+        Self {
+            before_key: Some(before_key),
+            min: Affine256::identity(),
+            mout: Some(Affine256::concat_bytes(&per_byte.output)),
+            per_byte: Some(per_byte),
+        }
+    }
+
+    /// The boundary of the decryption instance that inverts an encryption instance with this
+    /// boundary and initial round key `key0`.
+    ///
+    /// Decryption takes the encryption output, so it first undoes `mout`. It ends by adding
+    /// `k0`, which leaves `Min(B(q) ⊕ k0) ⊕ k0` for the encryption input `q`, where `B` is
+    /// `before_key`; folding that map as the output encoding returns `q`.
+    fn inverse(&self, key0: &DoubleBlock) -> Self {
+        let mut key0_affine = Affine256::new(Matrix256::identity(), (*key0).into());
+        let mut keyed = match &self.before_key {
+            Some(before_key) => before_key.then(&key0_affine),
+            None => key0_affine.clone(),
+        };
+        let mut through_min = keyed.then(&self.min);
+        let mout = through_min.then(&key0_affine);
+        key0_affine.zeroize();
+        keyed.zeroize();
+        through_min.zeroize();
+        let invert = |map: Affine8| map.invert().expect("per-byte encodings are invertible");
+        Self {
+            before_key: self.mout.clone(),
+            min: Affine256::identity(),
+            mout: Some(mout),
+            per_byte: self.per_byte.map(|per_byte| PerByteEncodings {
+                input: per_byte.output.map(invert),
+                output: per_byte.input.map(invert),
+            }),
+        }
+    }
+
+    /// The folded `Min` and `Mout`, for a boundary without per-byte encodings.
+    fn secrets(&self, key0: &DoubleBlock) -> ExternalEncodingSecrets {
+        let mout = self.mout.clone().unwrap_or_else(Affine256::identity);
+        ExternalEncodingSecrets::new(self.min.clone(), mout, key0)
+    }
+}
+
+impl Zeroize for Boundary {
+    fn zeroize(&mut self) {
+        self.before_key.zeroize();
+        self.min.zeroize();
+        self.mout.zeroize();
+    }
+}
+
+/// Builds the tables of one round, with S-box `sbox` and linear layer `L`, and draws their
+/// masks into `h_tables` (all zero unless `use_masks`); `b_lin` is scratch space for
+/// `A^(r+1)⁻¹ · L`.
+#[allow(clippy::too_many_arguments)]
 fn build_round<R: RngCore + CryptoRng>(
     rng: &mut R,
     a_curr: &Affine256,
     next_affine: &Affine256,
     linear_layer: &Matrix256,
-    round_key_block: &[u8; 32],
+    sbox: fn(u8) -> u8,
+    round_key_block: &DoubleBlock,
+    use_masks: bool,
+    parallel: bool,
+    b_lin: &mut Matrix256,
+    h_tables: &mut [HTable; 32],
 ) -> RoundTables {
-    let next_inv = next_affine
-        .lin
-        .invert()
-        .expect("next affine must be invertible");
-    let b_lin = next_inv.mul(linear_layer);
-    let mut b_bias_target = next_inv.apply_to_bytes(&next_affine.bias);
-    let key_contribution = next_inv.apply_to_bytes(round_key_block);
-    xor_in_place(&mut b_bias_target, &key_contribution);
-    let b_biases = split_biases(rng, &b_bias_target);
-    let b_maps: [Vec<[u8; 32]>; 32] = std::array::from_fn(|i| {
+    // Tables only read the diagonal and super-diagonal/wrap blocks of `A^(r)`.
+    debug_assert_eq!(a_curr.lin.validate_banded_unsplit(), Ok(()));
+    // Only products with `A^(r+1)⁻¹` are needed, so factor once and solve instead of
+    // forming the (dense) inverse.
+    let next_lu = next_affine.lin.lu();
+    assert!(!next_lu.is_singular(), "next affine must be invertible");
+    next_lu.solve_matrix_into(linear_layer, b_lin);
+    // `A⁻¹ · bias ⊕ A⁻¹ · key`, in one solve.
+    let mut b_bias_target = next_lu.solve_vec(
+        next_affine
+            .bias
+            .xor(&Vec256::from(*round_key_block))
+            .as_bytes(),
+    );
+    let mut b_biases = split_biases(rng, &b_bias_target);
+    b_bias_target.zeroize();
+    let mut b_maps: [Vec<DoubleBlock>; 32] = std::array::from_fn(|i| {
         let map = b_lin.submatrix_byte_map(i);
         map.into_iter().collect()
     });
 
-    let h_tables: [HTable; 32] = std::array::from_fn(|_| HTable::random(rng));
+    // The masks are 256 KiB per round, so they are drawn in place rather than on the stack.
+    for h_table in h_tables.iter_mut() {
+        *h_table = HTable::random(rng);
+    }
+    if !use_masks {
+        h_tables.zeroize();
+    }
 
     let mut round_tables = RoundTables::new_zeroed();
+    fill_round(
+        &std::array::from_fn(|i| (a_curr.lin.block(i, i), a_curr.lin.block(i, (i + 1) % 32))),
+        a_curr.bias.as_bytes(),
+        sbox,
+        &b_maps,
+        &b_biases,
+        |i, x| h_tables[i].get(x).as_bytes(),
+        &mut round_tables.tables,
+        parallel,
+        |table, x, y, value| table.set(x, y, &(*value).into()),
+    );
 
-    for i in 0..32 {
-        let block_left = a_curr.lin.block(i, i);
-        let block_right = if i == 31 {
-            a_curr.lin.block(i, 0)
-        } else {
-            a_curr.lin.block(i, i + 1)
-        };
-        let a_bias = a_curr.bias[i];
-        let b_bias = &b_biases[i];
-        let h_i = &h_tables[i];
-        let h_next = &h_tables[(i + 1) % 32];
-        let b_map = &b_maps[i];
+    b_biases.zeroize();
+    b_maps.zeroize();
+    round_tables
+}
 
-        for x in 0u16..=255 {
-            for y in 0u16..=255 {
-                let z = block_left.apply(x as u8) ^ block_right.apply(y as u8) ^ a_bias;
-                let t = sbox(z);
-                let mut value = b_map[t as usize];
-                xor_in_place(&mut value, b_bias);
-                xor_in_place(&mut value, h_i.get(x as u8));
-                xor_in_place(&mut value, h_next.get(y as u8));
-                round_tables.tables[i].set(x as u8, y as u8, &value);
-            }
-        }
+/// [`build_round`] for a single-block instance; the masks are discarded.
+fn build_round_128<R: RngCore + CryptoRng>(
+    rng: &mut R,
+    a_curr: &Affine128,
+    next_affine: &Affine128,
+    linear_layer: &Matrix128,
+    round_key: &Block,
+    use_masks: bool,
+    parallel: bool,
+) -> RoundTables128 {
+    debug_assert_eq!(a_curr.lin.validate_banded_unsplit(), Ok(()));
+    // At this width the inverse is cheap enough to form directly.
+    let mut next_inv = next_affine
+        .invert()
+        .expect("next affine must be invertible");
+    let mut b_lin = next_inv.lin.mul(linear_layer);
+    let mut keyed_bias = next_affine.bias;
+    xor_in_place(&mut keyed_bias, round_key);
+    let mut b_bias_target = next_inv.lin.apply_to_bytes(&keyed_bias);
+    let mut b_biases = split_biases(rng, &b_bias_target);
+    let mut b_maps: [Vec<Block>; 16] =
+        std::array::from_fn(|i| b_lin.submatrix_byte_map(i).to_vec());
+
+    let mut masks: [[Block; 256]; 16] = std::array::from_fn(|_| {
+        std::array::from_fn(|_| {
+            let mut mask = [0u8; 16];
+            rng.fill_bytes(&mut mask);
+            mask
+        })
+    });
+    if !use_masks {
+        masks.zeroize();
     }
 
+    let mut round_tables = RoundTables128::new_zeroed();
+    fill_round(
+        &std::array::from_fn(|i| (a_curr.lin.block(i, i), a_curr.lin.block(i, (i + 1) % 16))),
+        &a_curr.bias,
+        sbox,
+        &b_maps,
+        &b_biases,
+        |i, x| &masks[i][usize::from(x)],
+        &mut round_tables.tables,
+        parallel,
+        |table, x, y, value| table.set(x, y, value),
+    );
+
+    next_inv.zeroize();
+    b_lin.zeroize();
+    keyed_bias.zeroize();
+    b_bias_target.zeroize();
+    b_biases.zeroize();
+    b_maps.zeroize();
+    masks.zeroize();
     round_tables
 }
 
-fn split_biases<R: RngCore + CryptoRng>(rng: &mut R, target: &[u8; 32]) -> [[u8; 32]; 32] {
-    let mut biases = [[0u8; 32]; 32];
-    let mut accum = [0u8; 32];
-    for bias in biases.iter_mut().take(31) {
-        rng.fill_bytes(bias);
-        xor_in_place(&mut accum, bias);
+/// A round-table entry of either block width.
+trait EntryBlock {
+    /// Returns `map ⊕ left ⊕ right`.
+    fn table_entry(map: &Self, left: &Self, right: &Self) -> Self;
+}
+
+impl EntryBlock for DoubleBlock {
+    #[inline]
+    fn table_entry(map: &Self, left: &Self, right: &Self) -> Self {
+        simd::table_entry(map, left, right)
     }
-    let last = &mut biases[31];
-    for (dst, (&t, &acc)) in last.iter_mut().zip(target.iter().zip(accum.iter())) {
-        *dst = t ^ acc;
+}
+
+impl EntryBlock for Block {
+    #[inline]
+    fn table_entry(map: &Self, left: &Self, right: &Self) -> Self {
+        let word = u128::from_ne_bytes;
+        (word(*map) ^ word(*left) ^ word(*right)).to_ne_bytes()
     }
-    biases
 }
 
-fn xor_in_place(dst: &mut [u8; 32], src: &[u8; 32]) {
-    for (d, s) in dst.iter_mut().zip(src.iter()) {
-        *d ^= *s;
+/// Computes the `N` tables of one round of an `N`-byte block, for either width.
+///
+/// Table `i` maps `(x, y)` to `B_i(S(z)) ⊕ b_i ⊕ h_i(x) ⊕ h_{i+1}(y)`, where
+/// `z = a_blocks[i].0 · x ⊕ a_blocks[i].1 · y ⊕ a_bias[i]` is byte `i` of `A^(r)` (the second
+/// block is the super-diagonal or wrap one), `B_i` is `b_maps[i]`, `b_i` is `b_biases[i]` and
+/// `h_i` is `mask(i, ·)`. Each entry is handed to `set(&mut tables[i], x, y, entry)`; tables
+/// are independent, so with `parallel` they are filled on the rayon thread pool.
+///
+/// Everything that depends on `x` or `y` alone is tabulated first, which leaves one lookup
+/// and two XORs per entry.
+#[allow(clippy::too_many_arguments)]
+fn fill_round<'m, T: Send, const N: usize>(
+    a_blocks: &[(Matrix8, Matrix8); N],
+    a_bias: &[u8; N],
+    sbox: fn(u8) -> u8,
+    b_maps: &[Vec<[u8; N]>; N],
+    b_biases: &[[u8; N]; N],
+    mask: impl Fn(usize, u8) -> &'m [u8; N] + Sync,
+    tables: &mut [T; N],
+    parallel: bool,
+    set: impl Fn(&mut T, u8, u8, &[u8; N]) + Sync,
+) where
+    [u8; N]: EntryBlock,
+{
+    let fill = |(i, table): (usize, &mut T)| {
+        let (block_left, block_right) = &a_blocks[i];
+        let next = (i + 1) % N;
+        let left: [u8; 256] = core::array::from_fn(|x| block_left.apply(x as u8) ^ a_bias[i]);
+        let right: [u8; 256] = core::array::from_fn(|y| block_right.apply(y as u8));
+        // `B_i(S(z)) ⊕ b_i` for every `z`.
+        let mut output: Vec<[u8; N]> = (0u16..=255)
+            .map(|z| {
+                let map = &b_maps[i][usize::from(sbox(z as u8))];
+                core::array::from_fn(|k| map[k] ^ b_biases[i][k])
+            })
+            .collect();
+        for x in 0u16..=255 {
+            let x = x as u8;
+            let (z_left, mask_left) = (left[usize::from(x)], mask(i, x));
+            for y in 0u16..=255 {
+                let y = y as u8;
+                let z = z_left ^ right[usize::from(y)];
+                let value =
+                    EntryBlock::table_entry(&output[usize::from(z)], mask_left, mask(next, y));
+                set(table, x, y, &value);
+            }
+        }
+        output.zeroize();
+    };
+    #[cfg(feature = "parallel")]
+    if parallel {
+        use rayon::prelude::*;
+        tables.par_iter_mut().enumerate().for_each(fill);
+        return;
     }
+    #[cfg(not(feature = "parallel"))]
+    debug_assert!(!parallel, "parallel builds need the `parallel` feature");
+    tables.iter_mut().enumerate().for_each(fill);
+}
+
+/// Splits `target` into `N` random shares whose XOR is `target`; the last share is derived.
+fn split_biases<R: RngCore + CryptoRng, const N: usize>(
+    rng: &mut R,
+    target: &[u8; N],
+) -> [[u8; N]; N] {
+    let mut biases = [[0u8; N]; N];
+    let mut accum = *target;
+    for bias in biases.iter_mut().take(N - 1) {
+        rng.fill_bytes(bias);
+        for (acc, byte) in accum.iter_mut().zip(bias.iter()) {
+            *acc ^= byte;
+        }
+    }
+    biases[N - 1] = accum;
+    accum.zeroize();
+    biases
 }
 
-fn duplicate_round_key(round_key: &[u8; 16]) -> [u8; 32] {
-    let mut block = [0u8; 32];
-    block[..16].copy_from_slice(round_key);
-    block[16..].copy_from_slice(round_key);
-    block
+fn duplicate_round_key(round_keys: &RoundKeys, round: usize) -> DoubleBlock {
+    let round_key = round_keys
+        .try_get(round)
+        .expect("AES-128 schedule has round keys 0..=10");
+    join_double(round_key, round_key)
 }
@@ -0,0 +1,186 @@
+//! GF(2) bit utilities: parities, inner products, weights and map comparison.
+//!
+//! Vectors use the crate-wide bit order: bit `i` is bit `i % 8` (LSB first) of byte `i / 8`,
+//! and words are little-endian segments of that byte string.
+
+use crate::simd;
+use crate::vec256::bytes_to_segments;
+
+/// Returns the parity of `value`: `true` when it has an odd number of ones.
+#[inline]
+pub fn parity_u8(value: u8) -> bool {
+    value.count_ones() & 1 == 1
+}
+
+/// Returns the parity of `value`.
+#[inline]
+pub fn parity_u64(value: u64) -> bool {
+    value.count_ones() & 1 == 1
+}
+
+/// Returns the parity of a 256-bit vector held as four words.
+///
+/// Parity is linear, so the words are folded with XOR before a single popcount.
+#[inline]
+pub fn parity_u64x4(words: &[u64; 4]) -> bool {
+    parity_u64(words[0] ^ words[1] ^ words[2] ^ words[3])
+}
+
+/// Returns the inner product `a · b` over GF(2), the parity of `a & b`.
+#[inline]
+pub fn dot(a: &[u8; 32], b: &[u8; 32]) -> bool {
+    simd::row_parity(&bytes_to_segments(a), &bytes_to_segments(b))
+}
+
+/// Returns the number of ones in a 256-bit vector.
+#[inline]
+pub fn hamming_weight_256(value: &[u8; 32]) -> u32 {
+    bytes_to_segments(value)
+        .iter()
+        .map(|word| word.count_ones())
+        .sum()
+}
+
+/// Reverses the bit order of a byte, converting between LSB-first and MSB-first.
+#[inline]
+pub fn swap_bit_order_u8(value: u8) -> u8 {
+    value.reverse_bits()
+}
+
+/// Returns the first input on which `left` and `right` disagree, with both outputs, trying
+/// zero and then the basis vectors `e_0, e_1, …` in bit order.
+///
+/// Agreement on the basis vectors decides equality of linear maps, and agreement on zero as
+/// well decides it for affine maps. Works for any block width, e.g. 16- or 32-byte states.
+pub fn basis_disagreement<const N: usize>(
+    left: impl Fn(&[u8; N]) -> [u8; N],
+    right: impl Fn(&[u8; N]) -> [u8; N],
+) -> Option<([u8; N], [u8; N], [u8; N])> {
+    let zero = [0u8; N];
+    core::iter::once(zero)
+        .chain((0..8 * N).map(|bit| {
+            let mut basis = zero;
+            basis[bit / 8] = 1 << (bit % 8);
+            basis
+        }))
+        .map(|x| (x, left(&x), right(&x)))
+        .find(|(_, l, r)| l != r)
+}
+
+/// Asserts that two linear or affine maps on byte blocks are equal, by comparing them on zero
+/// and every basis vector.
+///
+/// Both arguments are `Fn(&[u8; N]) -> [u8; N]`; the panic message names the first input on
+/// which they differ and both outputs.
+///
+/// ```
+/// use wbaes_gen::{assert_linear_map_eq, mc_sr_matrix_256, Matrix256};
+///
+/// let layer = mc_sr_matrix_256();
+/// let twice = layer.mul(&layer);
+/// assert_linear_map_eq!(
+///     |x| twice.apply_to_bytes(x),
+///     |x| layer.apply_to_bytes(&layer.apply_to_bytes(x))
+/// );
+/// ```
+#[macro_export]
+macro_rules! assert_linear_map_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        if let Some((input, left, right)) = $crate::gf2::basis_disagreement($left, $right) {
+            panic!(
+                "maps differ at input {:02x?}\n  left: {:02x?}\n right: {:02x?}",
+                input, left, right
+            );
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn byte_parity_and_bit_order() {
+        assert!(!parity_u8(0x00));
+        assert!(!parity_u8(0xff));
+        assert!(!parity_u8(0x55) && !parity_u8(0xaa));
+        assert!(parity_u8(0x01) && parity_u8(0x80) && parity_u8(0x7f));
+        assert_eq!((0..=255u8).filter(|&value| parity_u8(value)).count(), 128);
+
+        assert_eq!(swap_bit_order_u8(0x00), 0x00);
+        assert_eq!(swap_bit_order_u8(0xff), 0xff);
+        assert_eq!(swap_bit_order_u8(0x55), 0xaa);
+        assert_eq!(swap_bit_order_u8(0x01), 0x80);
+        assert_eq!(swap_bit_order_u8(0x0f), 0xf0);
+        assert!((0..=255u8).all(|value| swap_bit_order_u8(swap_bit_order_u8(value)) == value));
+    }
+
+    #[test]
+    fn word_parity() {
+        assert!(!parity_u64(0) && !parity_u64(u64::MAX));
+        assert!(parity_u64(1 << 63));
+        assert!(!parity_u64x4(&[0; 4]));
+        assert!(!parity_u64x4(&[u64::MAX; 4]));
+        assert!(!parity_u64x4(&[0x5555_5555_5555_5555; 4]));
+        assert!(parity_u64x4(&[0, 0, 0, 1 << 63]));
+        assert!(parity_u64x4(&[1, 1, 1, 0]));
+        assert!(!parity_u64x4(&[1, 1, 0, 0]));
+    }
+
+    #[test]
+    fn dot_and_weight() {
+        let zero = [0x00u8; 32];
+        let ones = [0xffu8; 32];
+        let even = [0x55u8; 32];
+        let odd = [0xaau8; 32];
+        assert_eq!(hamming_weight_256(&zero), 0);
+        assert_eq!(hamming_weight_256(&ones), 256);
+        assert_eq!(hamming_weight_256(&even), 128);
+        assert_eq!(hamming_weight_256(&odd), 128);
+
+        assert!(!dot(&zero, &ones));
+        assert!(!dot(&ones, &ones));
+        assert!(!dot(&even, &odd));
+        let mut single = zero;
+        single[31] = 0x80;
+        assert_eq!(hamming_weight_256(&single), 1);
+        assert!(dot(&single, &ones));
+        assert!(!dot(&single, &even));
+        assert!(dot(&single, &odd));
+
+        // The inner product is the parity of the AND, bit for bit.
+        let mut a = [0u8; 32];
+        let mut b = [0u8; 32];
+        for i in 0..32 {
+            a[i] = (i as u8).wrapping_mul(37) ^ 0x5a;
+            b[i] = (i as u8).wrapping_mul(101) ^ 0xc3;
+        }
+        let and: [u8; 32] = core::array::from_fn(|i| a[i] & b[i]);
+        assert_eq!(dot(&a, &b), hamming_weight_256(&and) % 2 == 1);
+    }
+
+    #[test]
+    fn basis_disagreement_finds_first_differing_input() {
+        let shift = |x: &[u8; 2]| [x[0] << 1, x[1]];
+        assert_eq!(basis_disagreement(shift, shift), None);
+        let other = |x: &[u8; 2]| if x[1] & 0x04 != 0 { [0, 0] } else { shift(x) };
+        assert_eq!(
+            basis_disagreement(shift, other),
+            Some(([0x00, 0x04], [0x00, 0x04], [0x00, 0x00]))
+        );
+        let biased = |x: &[u8; 2]| [x[0] << 1, x[1] ^ 1];
+        assert_eq!(
+            basis_disagreement(shift, biased),
+            Some(([0x00, 0x00], [0x00, 0x00], [0x00, 0x01]))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "maps differ at input [00, 04]")]
+    fn assert_linear_map_eq_reports_input() {
+        crate::assert_linear_map_eq!(|x: &[u8; 2]| [x[0], x[1]], |x: &[u8; 2]| [
+            x[0],
+            x[1] & !0x04
+        ]);
+    }
+}
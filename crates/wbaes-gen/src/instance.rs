@@ -1,19 +1,38 @@
 //! Instance representation and serialization helpers.
 
-use serde::{Deserialize, Serialize};
+use std::fmt;
 
-use crate::affine::Affine256;
-use crate::tables::RoundTables;
+use aes_core::DoubleBlock;
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use zeroize::Zeroize;
+
+use crate::affine::{Affine128, Affine256, Affine8};
+use crate::fingerprint::serialized_sha256;
+use crate::matrix::Matrix256;
+use crate::tables::{HTable, RoundTables, RoundTables128};
 
 /// Scheme identifier.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum SchemeId {
     /// Baek–Cheon–Hong revisited white-box AES (JCN 2016).
     BaekCheonHong2016,
+    /// Chow–Eisen–Johnson–van Oorschot white-box AES (SAC 2002).
+    Chow2002,
+}
+
+/// Direction of AES-128 that an instance computes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Direction {
+    /// The forward cipher.
+    #[default]
+    Encrypt,
+    /// The equivalent inverse cipher (FIPS-197 §5.3.5).
+    Decrypt,
 }
 
 /// Static parameters describing the instance.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct InstanceParams {
     /// Number of rounds (10 for AES-128).
     pub rounds: usize,
@@ -29,6 +48,16 @@ pub struct InstanceParams {
     pub scheme: SchemeId,
     /// Version tag for future compatibility changes.
     pub version: u32,
+    /// Identifier shared by the two halves of a pair from
+    /// [`crate::Generator::generate_instance_pair`], so that mismatched halves can be refused.
+    ///
+    /// Serialized with `direction` in a trailing section, and only when present (see
+    /// [`WbInstance256::to_bytes`]).
+    #[serde(skip)]
+    pub pair_id: Option<[u8; 16]>,
+    /// Direction the tables compute; only the decryption half of a pair decrypts.
+    #[serde(skip)]
+    pub direction: Direction,
 }
 
 impl Default for InstanceParams {
@@ -41,6 +70,33 @@ impl Default for InstanceParams {
             ma_bits: 256,
             scheme: SchemeId::BaekCheonHong2016,
             version: 1,
+            pair_id: None,
+            direction: Direction::Encrypt,
+        }
+    }
+}
+
+impl InstanceParams {
+    /// Parameters of a [`WbInstance128`]: one AES state, 16→128-bit tables.
+    pub fn single_block() -> Self {
+        Self {
+            block_bytes: 16,
+            table_output_bits: 128,
+            ma_bits: 128,
+            ..Self::default()
+        }
+    }
+
+    /// Parameters of a [`crate::WbInstanceChow`]: one AES state, 8→32-bit tables and 32-bit
+    /// mixing bijections.
+    pub fn chow() -> Self {
+        Self {
+            block_bytes: 16,
+            table_input_bits: 8,
+            table_output_bits: 32,
+            ma_bits: 32,
+            scheme: SchemeId::Chow2002,
+            ..Self::default()
         }
     }
 }
@@ -52,10 +108,173 @@ pub struct ExternalEncodings {
     pub input: Affine256,
     /// Optional output encoding to apply after the final round (if not already folded).
     pub output: Option<Affine256>,
+    /// Byte-wise encodings the caller applies around the runtime, for instances generated
+    /// with [`crate::ExternalEncodingKind::PerByte`].
+    ///
+    /// Serialized after the instance parameters and only when present (see
+    /// [`WbInstance256::to_bytes`]), so it is skipped here.
+    #[serde(skip)]
+    pub per_byte: Option<PerByteEncodings>,
 }
 
-/// Complete white-box AES-256-bit instance (two AES-128 blocks).
+/// Per-byte external encodings, applied by the caller with one byte map per position.
+///
+/// The runtime maps `input(p)` to `output⁻¹(AES(p))`: encode each plaintext byte with
+/// `input[i]` before encrypting and each ciphertext byte with `output[i]` afterwards to
+/// recover the AES ciphertext.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PerByteEncodings {
+    /// Encodings of the plaintext bytes.
+    pub input: [Affine8; 32],
+    /// Decodings of the ciphertext bytes.
+    pub output: [Affine8; 32],
+}
+
+impl PerByteEncodings {
+    /// Encodes a plaintext block for the runtime.
+    pub fn encode_input(&self, block: &mut [u8; 32]) {
+        for (byte, map) in block.iter_mut().zip(self.input.iter()) {
+            *byte = map.apply(*byte);
+        }
+    }
+
+    /// Decodes a runtime output block into the AES ciphertext.
+    pub fn decode_output(&self, block: &mut [u8; 32]) {
+        for (byte, map) in block.iter_mut().zip(self.output.iter()) {
+            *byte = map.apply(*byte);
+        }
+    }
+
+    /// Returns the input encodings as byte lookup tables: `tables[i][x] = input[i](x)`.
+    pub fn input_tables(&self) -> [[u8; 256]; 32] {
+        self.input
+            .map(|map| core::array::from_fn(|x| map.apply(x as u8)))
+    }
+
+    /// Returns the output decodings as byte lookup tables.
+    pub fn output_tables(&self) -> [[u8; 256]; 32] {
+        self.output
+            .map(|map| core::array::from_fn(|x| map.apply(x as u8)))
+    }
+}
+
+/// The dense or banded external encodings folded into an instance, returned by
+/// [`crate::Generator::generate_instance_with_encodings`].
+///
+/// These are secrets for the trusted side: whoever holds them can strip the external
+/// encodings, and with `min` the initial round key is exposed too. The runtime computes
+/// `mout⁻¹ ∘ AES ∘ E`, where `E` undoes `min` in front of the initial AddRoundKey; use
+/// [`Self::encode_input`] and [`Self::decode_output`] around it. Wiped on drop.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExternalEncodingSecrets {
+    /// `Min`, applied right after the initial AddRoundKey.
+    pub min: Affine256,
+    /// `Mout`, which maps the runtime output to the AES ciphertext.
+    pub mout: Affine256,
+    /// `x ↦ Min⁻¹(x ⊕ k0) ⊕ k0`, so that callers need not know `k0`.
+    input: Affine256,
+}
+
+impl ExternalEncodingSecrets {
+    /// Collects `min` and `mout` for an instance with initial round key `key0`.
+    pub(crate) fn new(min: Affine256, mout: Affine256, key0: &DoubleBlock) -> Self {
+        let mut key0_affine = Affine256::new(Matrix256::identity(), (*key0).into());
+        let mut through_min = key0_affine.then(&min);
+        let mut plaintext_side = through_min.then(&key0_affine);
+        let input = plaintext_side
+            .invert()
+            .expect("external encodings are invertible");
+        key0_affine.zeroize();
+        through_min.zeroize();
+        plaintext_side.zeroize();
+        Self { min, mout, input }
+    }
+
+    /// Encodes a plaintext block for the runtime.
+    pub fn encode_input(&self, block: &[u8; 32]) -> [u8; 32] {
+        self.input.apply(block)
+    }
+
+    /// Decodes a runtime output block into the AES ciphertext.
+    pub fn decode_output(&self, block: &[u8; 32]) -> [u8; 32] {
+        self.mout.apply(block)
+    }
+}
+
+impl Zeroize for ExternalEncodingSecrets {
+    fn zeroize(&mut self) {
+        self.min.zeroize();
+        self.mout.zeroize();
+        self.input.zeroize();
+    }
+}
+
+impl Drop for ExternalEncodingSecrets {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+/// Reasons [`WbInstance256::from_bytes_validated`] rejects an instance.
+#[derive(Debug)]
+pub enum InstanceError {
+    /// The bytes are not a serialized instance.
+    Decode(bincode::Error),
+    /// The parameters differ from the supported set, [`InstanceParams::default`] (or
+    /// [`InstanceParams::single_block`] for a [`WbInstance128`]).
+    UnsupportedParams(InstanceParams),
+    /// The linear part of the input encoding is not invertible.
+    SingularInputEncoding,
+    /// The linear part of the output encoding is not invertible.
+    SingularOutputEncoding,
+    /// The per-byte encoding of byte `byte` is not invertible.
+    SingularPerByteEncoding {
+        /// Byte position of the offending map.
+        byte: usize,
+    },
+    /// The retained masks cover `rounds` rounds instead of one set per round.
+    MaskRoundCount {
+        /// Number of mask sets found.
+        rounds: usize,
+    },
+}
+
+impl fmt::Display for InstanceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Decode(err) => write!(f, "cannot decode instance: {err}"),
+            Self::UnsupportedParams(params) => {
+                write!(f, "unsupported instance parameters: {params:?}")
+            }
+            Self::SingularInputEncoding => write!(f, "input encoding is not invertible"),
+            Self::SingularOutputEncoding => write!(f, "output encoding is not invertible"),
+            Self::SingularPerByteEncoding { byte } => {
+                write!(f, "per-byte encoding of byte {byte} is not invertible")
+            }
+            Self::MaskRoundCount { rounds } => {
+                write!(f, "retained masks cover {rounds} rounds instead of 10")
+            }
+        }
+    }
+}
+
+impl std::error::Error for InstanceError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Decode(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Complete white-box AES-256-bit instance (two AES-128 blocks).
+///
+/// Serializes as its rounds, encodings and parameters, followed by a tagged section for
+/// [`ExternalEncodings::per_byte`], for [`Self::masks`] and for the pair identity in
+/// [`InstanceParams::pair_id`], each only when present, so instances without them keep the
+/// original layout. Deserializing through serde reads the original layout only; use
+/// [`Self::from_bytes`] to pick up the trailing sections.
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct WbInstance256 {
     /// Round tables for 10 rounds.
     pub rounds: [RoundTables; 10],
@@ -63,6 +282,88 @@ pub struct WbInstance256 {
     pub encodings: ExternalEncodings,
     /// Static parameters.
     pub params: InstanceParams,
+    /// Mask tables `h_i` of each round, in round order, when the generator was asked to
+    /// retain them (see [`crate::GeneratorConfig::retain_masks`]).
+    ///
+    /// Research-only: anyone holding them can strip the masks from every table entry. Each
+    /// round's set is 256 KiB, so it is boxed.
+    pub masks: Option<Vec<Box<[HTable; 32]>>>,
+}
+
+/// The serialized fields of [`WbInstance256`] before the optional per-byte encodings.
+#[derive(Deserialize)]
+struct InstanceBody {
+    rounds: [RoundTables; 10],
+    encodings: ExternalEncodings,
+    params: InstanceParams,
+}
+
+/// An optional section serialized after [`InstanceBody`], tagged by its variant index.
+#[derive(Serialize)]
+enum SectionRef<'a> {
+    PerByte(&'a PerByteEncodings),
+    Masks(&'a [Box<[HTable; 32]>]),
+    Pair(PairSection),
+}
+
+/// Owned counterpart of [`SectionRef`], with the same variant order.
+#[derive(Deserialize)]
+enum Section {
+    PerByte(Box<PerByteEncodings>),
+    Masks(Vec<Box<[HTable; 32]>>),
+    Pair(PairSection),
+}
+
+/// The serialized pair identity of an instance.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+struct PairSection {
+    id: [u8; 16],
+    direction: Direction,
+}
+
+impl Serialize for WbInstance256 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let per_byte = self.encodings.per_byte.as_ref();
+        let masks = self.masks.as_deref();
+        let pair = self.params.pair_id.map(|id| PairSection {
+            id,
+            direction: self.params.direction,
+        });
+        let fields = 3
+            + usize::from(per_byte.is_some())
+            + usize::from(masks.is_some())
+            + usize::from(pair.is_some());
+        let mut state = serializer.serialize_struct("WbInstance256", fields)?;
+        state.serialize_field("rounds", &self.rounds)?;
+        state.serialize_field("encodings", &self.encodings)?;
+        state.serialize_field("params", &self.params)?;
+        if let Some(per_byte) = per_byte {
+            state.serialize_field("per_byte", &SectionRef::PerByte(per_byte))?;
+        }
+        if let Some(masks) = masks {
+            state.serialize_field("masks", &SectionRef::Masks(masks))?;
+        }
+        if let Some(pair) = pair {
+            state.serialize_field("pair", &SectionRef::Pair(pair))?;
+        }
+        state.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for WbInstance256 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let InstanceBody {
+            rounds,
+            encodings,
+            params,
+        } = InstanceBody::deserialize(deserializer)?;
+        Ok(Self {
+            rounds,
+            encodings,
+            params,
+            masks: None,
+        })
+    }
 }
 
 impl WbInstance256 {
@@ -71,16 +372,132 @@ impl WbInstance256 {
         bincode::serialize(self)
     }
 
+    /// Deserializes an instance with `bincode`, including the trailing sections.
+    ///
+    /// Instances serialized before the sections existed have no trailing data and load
+    /// unchanged.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, bincode::Error> {
+        let mut reader = bytes;
+        let mut instance: Self = bincode::deserialize_from(&mut reader)?;
+        while !reader.is_empty() {
+            match bincode::deserialize_from(&mut reader)? {
+                Section::PerByte(per_byte) => instance.encodings.per_byte = Some(*per_byte),
+                Section::Masks(masks) => instance.masks = Some(masks),
+                Section::Pair(pair) => {
+                    instance.params.pair_id = Some(pair.id);
+                    instance.params.direction = pair.direction;
+                }
+            }
+        }
+        Ok(instance)
+    }
+
+    /// Deserializes an instance and checks the invariants the runtime relies on.
+    ///
+    /// The parameters must be the supported defaults up to the pair identity, every external
+    /// encoding (per-byte ones included) must be invertible, and retained masks must cover
+    /// every round. The input encoding is deliberately not required to be banded: it is
+    /// `A^(1)⁻¹ ∘ Min ∘ AddRoundKey₀`, and the inverse of a banded matrix is dense.
+    pub fn from_bytes_validated(bytes: &[u8]) -> Result<Self, InstanceError> {
+        let instance = Self::from_bytes(bytes).map_err(InstanceError::Decode)?;
+        let unpaired = InstanceParams {
+            pair_id: None,
+            direction: Direction::Encrypt,
+            ..instance.params
+        };
+        if unpaired != InstanceParams::default() {
+            return Err(InstanceError::UnsupportedParams(instance.params));
+        }
+        if !instance.encodings.input.lin.is_invertible() {
+            return Err(InstanceError::SingularInputEncoding);
+        }
+        if let Some(output) = &instance.encodings.output {
+            if !output.lin.is_invertible() {
+                return Err(InstanceError::SingularOutputEncoding);
+            }
+        }
+        if let Some(per_byte) = &instance.encodings.per_byte {
+            let mut maps = per_byte.input.iter().zip(per_byte.output.iter());
+            if let Some(byte) = maps.position(|(input, output)| {
+                !input.lin.is_invertible() || !output.lin.is_invertible()
+            }) {
+                return Err(InstanceError::SingularPerByteEncoding { byte });
+            }
+        }
+        if let Some(masks) = &instance.masks {
+            if masks.len() != instance.rounds.len() {
+                return Err(InstanceError::MaskRoundCount {
+                    rounds: masks.len(),
+                });
+            }
+        }
+        Ok(instance)
+    }
+
+    /// Returns the SHA-256 fingerprint of the serialized instance.
+    ///
+    /// This is the canonical instance identifier. It equals the SHA-256 of
+    /// [`Self::to_bytes`], so it matches the digest of an instance file written by the CLI, is
+    /// stable across runs and platforms, and survives re-serialization. Comparing
+    /// fingerprints avoids walking the ~640 MiB of table data that `==` compares.
+    pub fn fingerprint(&self) -> [u8; 32] {
+        serialized_sha256(self)
+    }
+}
+
+/// Single-block white-box AES-128 instance: 16 tables of 16→128-bit entries per round.
+///
+/// The 256-bit construction on one AES state, with a quarter of its table data (160 MiB), for
+/// quick experiments and teaching. Its only encoding is the input encoding; there are no
+/// external encodings.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WbInstance128 {
+    /// Round tables for 10 rounds.
+    pub rounds: [RoundTables128; 10],
+    /// Input encoding `A^(1)⁻¹ ∘ AddRoundKey₀`.
+    pub input: Affine128,
+    /// Static parameters.
+    pub params: InstanceParams,
+}
+
+impl WbInstance128 {
+    /// Serializes the instance with `bincode`.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, bincode::Error> {
+        bincode::serialize(self)
+    }
+
     /// Deserializes an instance with `bincode`.
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, bincode::Error> {
         bincode::deserialize(bytes)
     }
+
+    /// Deserializes an instance and checks that its parameters are
+    /// [`InstanceParams::single_block`] and its input encoding is invertible.
+    pub fn from_bytes_validated(bytes: &[u8]) -> Result<Self, InstanceError> {
+        let instance = Self::from_bytes(bytes).map_err(InstanceError::Decode)?;
+        if instance.params != InstanceParams::single_block() {
+            return Err(InstanceError::UnsupportedParams(instance.params));
+        }
+        if !instance.input.lin.is_invertible() {
+            return Err(InstanceError::SingularInputEncoding);
+        }
+        Ok(instance)
+    }
+
+    /// Returns the SHA-256 of [`Self::to_bytes`], as [`WbInstance256::fingerprint`] does.
+    pub fn fingerprint(&self) -> [u8; 32] {
+        serialized_sha256(self)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::tables::RoundTables;
+    use crate::tables::{RoundTables, RoundTables128};
+    use crate::vec256::Vec256;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+    use sha2::{Digest, Sha256};
 
     #[test]
     fn serialize_roundtrip() {
@@ -89,13 +506,261 @@ mod tests {
             encodings: ExternalEncodings {
                 input: Affine256::identity(),
                 output: None,
+                per_byte: None,
             },
             params: InstanceParams::default(),
+            masks: None,
         };
         let bytes = instance.to_bytes().expect("serialize");
         let decoded = WbInstance256::from_bytes(&bytes).expect("deserialize");
         assert_eq!(decoded.params.rounds, 10);
         assert_eq!(decoded.encodings.output, None);
-        assert_eq!(decoded.rounds[0].tables[0].get(0, 0), [0u8; 32]);
+        assert_eq!(decoded.rounds[0].tables[0].get(0, 0), Vec256::ZERO);
+    }
+
+    #[test]
+    fn single_block_roundtrip_and_validation() {
+        let mut instance = WbInstance128 {
+            rounds: std::array::from_fn(|_| RoundTables128::new_zeroed()),
+            input: Affine128::identity(),
+            params: InstanceParams::single_block(),
+        };
+        instance.rounds[3].tables[15].set(1, 2, &[0x3c; 16]);
+        let bytes = instance.to_bytes().expect("serialize");
+        assert_eq!(
+            WbInstance128::from_bytes_validated(&bytes).unwrap(),
+            instance
+        );
+        assert_eq!(
+            instance.fingerprint(),
+            <[u8; 32]>::from(Sha256::digest(&bytes))
+        );
+
+        // A 256-bit parameter set is refused.
+        instance.params = InstanceParams::default();
+        assert!(matches!(
+            WbInstance128::from_bytes_validated(&instance.to_bytes().unwrap()),
+            Err(InstanceError::UnsupportedParams(params)) if params.block_bytes == 32
+        ));
+        instance.params = InstanceParams::single_block();
+        instance.input.lin = crate::Matrix128::zero();
+        assert!(matches!(
+            WbInstance128::from_bytes_validated(&instance.to_bytes().unwrap()),
+            Err(InstanceError::SingularInputEncoding)
+        ));
+    }
+
+    #[test]
+    fn from_bytes_validated_rejects_broken_instances() {
+        let valid = WbInstance256 {
+            rounds: std::array::from_fn(|_| RoundTables::new_zeroed()),
+            encodings: ExternalEncodings {
+                input: Affine256::identity(),
+                output: Some(Affine256::identity()),
+                per_byte: None,
+            },
+            params: InstanceParams::default(),
+            masks: None,
+        };
+        let bytes = valid.to_bytes().unwrap();
+        assert_eq!(WbInstance256::from_bytes_validated(&bytes).unwrap(), valid);
+        assert!(matches!(
+            WbInstance256::from_bytes_validated(&bytes[..100]),
+            Err(InstanceError::Decode(_))
+        ));
+
+        let mut instance = valid.clone();
+        instance.params.rounds = 12;
+        assert!(matches!(
+            WbInstance256::from_bytes_validated(&instance.to_bytes().unwrap()),
+            Err(InstanceError::UnsupportedParams(params)) if params.rounds == 12
+        ));
+
+        let mut instance = valid.clone();
+        instance.encodings.input.lin = crate::Matrix256::zero();
+        assert!(matches!(
+            WbInstance256::from_bytes_validated(&instance.to_bytes().unwrap()),
+            Err(InstanceError::SingularInputEncoding)
+        ));
+
+        let mut instance = valid;
+        instance.encodings.output = Some(Affine256::new(crate::Matrix256::zero(), Vec256::ZERO));
+        let err = WbInstance256::from_bytes_validated(&instance.to_bytes().unwrap()).unwrap_err();
+        assert!(matches!(err, InstanceError::SingularOutputEncoding));
+        assert_eq!(err.to_string(), "output encoding is not invertible");
+    }
+
+    #[test]
+    fn per_byte_encodings_trail_the_original_layout() {
+        let mut rng = ChaCha20Rng::from_seed([60u8; 32]);
+        let mut instance = WbInstance256 {
+            rounds: std::array::from_fn(|_| RoundTables::new_zeroed()),
+            encodings: ExternalEncodings {
+                input: Affine256::identity(),
+                output: None,
+                per_byte: None,
+            },
+            params: InstanceParams::default(),
+            masks: None,
+        };
+        let original = instance.to_bytes().expect("serialize");
+        let per_byte = PerByteEncodings {
+            input: std::array::from_fn(|_| Affine8::random(&mut rng)),
+            output: std::array::from_fn(|_| Affine8::random(&mut rng)),
+        };
+        instance.encodings.per_byte = Some(per_byte);
+        let bytes = instance.to_bytes().expect("serialize");
+
+        // The original layout is a prefix, so older readers still decode the rest.
+        assert_eq!(&bytes[..original.len()], &original[..]);
+        assert_eq!(
+            bytes[original.len()..original.len() + 4],
+            0u32.to_le_bytes()
+        );
+        assert_eq!(
+            &bytes[original.len() + 4..],
+            &bincode::serialize(&per_byte).unwrap()[..]
+        );
+        assert_eq!(WbInstance256::from_bytes(&bytes).unwrap(), instance);
+        assert_eq!(
+            WbInstance256::from_bytes_validated(&bytes).unwrap(),
+            instance
+        );
+        let plain: WbInstance256 = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(plain.encodings.per_byte, None);
+        assert_eq!(
+            WbInstance256::from_bytes(&original)
+                .unwrap()
+                .encodings
+                .per_byte,
+            None
+        );
+        assert!(WbInstance256::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+
+        let mut block: [u8; 32] = std::array::from_fn(|i| i as u8);
+        let input_tables = per_byte.input_tables();
+        let expected: [u8; 32] = std::array::from_fn(|i| input_tables[i][i]);
+        per_byte.encode_input(&mut block);
+        assert_eq!(block, expected);
+        per_byte.decode_output(&mut block);
+        let output_tables = per_byte.output_tables();
+        assert_eq!(
+            block,
+            std::array::from_fn(|i| output_tables[i][usize::from(expected[i])])
+        );
+
+        let mut singular = per_byte;
+        singular.output[5] = Affine8::new(crate::Matrix8::zero(), 0);
+        instance.encodings.per_byte = Some(singular);
+        let err = WbInstance256::from_bytes_validated(&instance.to_bytes().unwrap()).unwrap_err();
+        assert!(matches!(
+            err,
+            InstanceError::SingularPerByteEncoding { byte: 5 }
+        ));
+        assert_eq!(
+            err.to_string(),
+            "per-byte encoding of byte 5 is not invertible"
+        );
+    }
+
+    #[test]
+    fn retained_masks_follow_per_byte_encodings() {
+        let mut rng = ChaCha20Rng::from_seed([62u8; 32]);
+        let mut instance = WbInstance256 {
+            rounds: std::array::from_fn(|_| RoundTables::new_zeroed()),
+            encodings: ExternalEncodings {
+                input: Affine256::identity(),
+                output: None,
+                per_byte: None,
+            },
+            params: InstanceParams::default(),
+            masks: None,
+        };
+        let original = instance.to_bytes().expect("serialize");
+        let masks: Vec<Box<[HTable; 32]>> = (0..10)
+            .map(|_| Box::new(std::array::from_fn(|_| HTable::random(&mut rng))))
+            .collect();
+        instance.masks = Some(masks.clone());
+        let bytes = instance.to_bytes().expect("serialize");
+        assert_eq!(&bytes[..original.len()], &original[..]);
+        assert_eq!(
+            bytes[original.len()..original.len() + 4],
+            1u32.to_le_bytes()
+        );
+        assert_eq!(WbInstance256::from_bytes(&bytes).unwrap(), instance);
+
+        // Both sections, per-byte encodings first.
+        instance.encodings.per_byte = Some(PerByteEncodings {
+            input: [Affine8::identity(); 32],
+            output: [Affine8::identity(); 32],
+        });
+        let bytes = instance.to_bytes().expect("serialize");
+        assert_eq!(
+            bytes[original.len()..original.len() + 4],
+            0u32.to_le_bytes()
+        );
+        assert_eq!(
+            WbInstance256::from_bytes_validated(&bytes).unwrap(),
+            instance
+        );
+
+        instance.masks = Some(masks[..9].to_vec());
+        let err = WbInstance256::from_bytes_validated(&instance.to_bytes().unwrap()).unwrap_err();
+        assert!(matches!(err, InstanceError::MaskRoundCount { rounds: 9 }));
+        assert_eq!(
+            err.to_string(),
+            "retained masks cover 9 rounds instead of 10"
+        );
+
+        let mut unknown = original;
+        unknown.extend_from_slice(&3u32.to_le_bytes());
+        assert!(WbInstance256::from_bytes(&unknown).is_err());
+    }
+
+    #[test]
+    fn pair_identity_trails_the_other_sections() {
+        let mut instance = WbInstance256 {
+            rounds: std::array::from_fn(|_| RoundTables::new_zeroed()),
+            encodings: ExternalEncodings {
+                input: Affine256::identity(),
+                output: None,
+                per_byte: None,
+            },
+            params: InstanceParams::default(),
+            masks: None,
+        };
+        let original = instance.to_bytes().expect("serialize");
+        instance.params.pair_id = Some([0xa5; 16]);
+        instance.params.direction = Direction::Decrypt;
+        let bytes = instance.to_bytes().expect("serialize");
+        assert_eq!(&bytes[..original.len()], &original[..]);
+        assert_eq!(
+            bytes[original.len()..original.len() + 4],
+            2u32.to_le_bytes()
+        );
+        assert_eq!(bytes.len(), original.len() + 4 + 16 + 4);
+        assert_eq!(
+            WbInstance256::from_bytes_validated(&bytes).unwrap(),
+            instance
+        );
+        let plain: WbInstance256 = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(plain.params, InstanceParams::default());
+    }
+
+    #[test]
+    fn fingerprint_matches_digest_of_serialized_bytes() {
+        let instance = WbInstance256 {
+            rounds: std::array::from_fn(|_| RoundTables::new_zeroed()),
+            encodings: ExternalEncodings {
+                input: Affine256::identity(),
+                output: None,
+                per_byte: None,
+            },
+            params: InstanceParams::default(),
+            masks: None,
+        };
+        let bytes = instance.to_bytes().expect("serialize");
+        let expected: [u8; 32] = Sha256::digest(&bytes).into();
+        assert_eq!(instance.fingerprint(), expected);
     }
 }
@@ -11,15 +11,48 @@
 #![deny(missing_docs)]
 
 mod affine;
+pub mod analysis;
+pub mod canonical;
+mod chow;
+#[cfg(test)]
+mod distribution;
+mod dyn_matrix;
+pub mod evaluation;
+mod fingerprint;
 mod generator;
+pub mod gf2;
 mod instance;
 mod linear;
+mod lu;
 mod matrix;
+mod permutation;
+mod simd;
+mod sparse;
+mod stats;
 mod tables;
+mod vec256;
 
-pub use affine::{Affine256, Affine8};
-pub use generator::{Generator, GeneratorConfig};
-pub use instance::{ExternalEncodings, InstanceParams, SchemeId, WbInstance256};
-pub use linear::{mc_sr_matrix_128, mc_sr_matrix_256, sr_matrix_128, sr_matrix_256};
-pub use matrix::{Matrix128, Matrix256, Matrix8};
-pub use tables::{RoundTables, Table16x256};
+pub use affine::{Affine128, Affine256, Affine4, Affine8, ConcatenatedNibbleEncoding};
+pub use canonical::{CanonicalFormatError, CanonicalText};
+pub use chow::{ChowConfig, ChowRound, Table8x32, Table8x8, WbInstanceChow, XorTable, XorTree};
+pub use dyn_matrix::{DimensionMismatch, DynMatrixGf2};
+pub use generator::{
+    ExternalEncodingKind, ExternalEncodingSource, GenError, GeneratedInstance, Generator,
+    GeneratorConfig,
+};
+pub use instance::{
+    Direction, ExternalEncodingSecrets, ExternalEncodings, InstanceError, InstanceParams,
+    PerByteEncodings, SchemeId, WbInstance128, WbInstance256,
+};
+pub use linear::{
+    inv_mc_inv_sr_matrix_128, inv_mc_inv_sr_matrix_256, inv_mc_sr_matrix_128, inv_mc_sr_matrix_256,
+    inv_sr_matrix_128, inv_sr_matrix_256, mc_matrix_128, mc_sr_matrix_128, mc_sr_matrix_256,
+    sr_matrix_128, sr_matrix_256,
+};
+pub use lu::Lu256;
+pub use matrix::{BandedStructureError, BitLayoutError, Matrix128, Matrix256, Matrix8};
+pub use permutation::Permutation256;
+pub use sparse::SparseMatrix256;
+pub use stats::{GenStats, SamplerCounts};
+pub use tables::{HTable, RoundTables, RoundTables128, Table16, Table16x128, Table16x256};
+pub use vec256::Vec256;
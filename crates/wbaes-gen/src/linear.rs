@@ -1,129 +1,313 @@
 //! AES linear layer matrices.
+//!
+//! Built from the byte-level constants in [`aes_core::linear`]: each output
+//! byte is a GF(2^8)-linear combination of input bytes, and each coefficient
+//! becomes the 8×8 GF(2) block of multiplication by it.
 
-use core::convert::TryInto;
+use aes_core::gf256::mul as gmul;
+use aes_core::linear::{
+    inv_shift_rows_permutation, shift_rows_permutation, INV_MIX_COLUMNS_MATRIX, MIX_COLUMNS_MATRIX,
+};
 
-use aes_core::round::{mix_columns, shift_rows};
-use aes_core::Block;
+use crate::matrix::{Matrix128, Matrix256, Matrix8};
 
-use crate::matrix::{Matrix128, Matrix256};
+/// `coefficients[i][j]`: GF(2^8) factor of input byte `j` in output byte `i`.
+type ByteCoefficients = [[u8; 16]; 16];
+
+/// Byte coefficients of a gather: output byte `i` is input byte `perm[i]`.
+fn permutation_coefficients(perm: &[usize; 16]) -> ByteCoefficients {
+    let mut coefficients = [[0u8; 16]; 16];
+    for (out, &source) in perm.iter().enumerate() {
+        coefficients[out][source] = 1;
+    }
+    coefficients
+}
+
+/// Byte coefficients of a column mix: every state column times `matrix`.
+fn column_mix_coefficients(matrix: &[[u8; 4]; 4]) -> ByteCoefficients {
+    let mut coefficients = [[0u8; 16]; 16];
+    for col in 0..4 {
+        for (row, factors) in matrix.iter().enumerate() {
+            for (k, &factor) in factors.iter().enumerate() {
+                coefficients[row + 4 * col][k + 4 * col] = factor;
+            }
+        }
+    }
+    coefficients
+}
+
+/// Byte coefficients of `outer ∘ inner` (apply `inner` first).
+fn compose(outer: &ByteCoefficients, inner: &ByteCoefficients) -> ByteCoefficients {
+    let mut coefficients = [[0u8; 16]; 16];
+    for (out, row) in coefficients.iter_mut().enumerate() {
+        for (input, slot) in row.iter_mut().enumerate() {
+            for (mid, &factor) in outer[out].iter().enumerate() {
+                *slot ^= gmul(factor, inner[mid][input]);
+            }
+        }
+    }
+    coefficients
+}
+
+fn sr_coefficients() -> ByteCoefficients {
+    permutation_coefficients(&shift_rows_permutation())
+}
+
+fn mc_coefficients() -> ByteCoefficients {
+    column_mix_coefficients(&MIX_COLUMNS_MATRIX)
+}
+
+fn mc_sr_coefficients() -> ByteCoefficients {
+    compose(&mc_coefficients(), &sr_coefficients())
+}
+
+fn inv_sr_coefficients() -> ByteCoefficients {
+    permutation_coefficients(&inv_shift_rows_permutation())
+}
+
+/// `(MC ∘ SR)⁻¹ = SR⁻¹ ∘ MC⁻¹`.
+fn inv_mc_sr_coefficients() -> ByteCoefficients {
+    compose(
+        &inv_sr_coefficients(),
+        &column_mix_coefficients(&INV_MIX_COLUMNS_MATRIX),
+    )
+}
+
+/// `MC⁻¹ ∘ SR⁻¹`, the round layer of the equivalent inverse cipher.
+fn inv_mc_inv_sr_coefficients() -> ByteCoefficients {
+    compose(
+        &column_mix_coefficients(&INV_MIX_COLUMNS_MATRIX),
+        &inv_sr_coefficients(),
+    )
+}
+
+/// Calls `set_block(out, input, M_c)` for every non-zero coefficient `c`.
+fn place_blocks(
+    coefficients: &ByteCoefficients,
+    mut set_block: impl FnMut(usize, usize, &Matrix8),
+) {
+    for (out, row) in coefficients.iter().enumerate() {
+        for (input, &factor) in row.iter().enumerate() {
+            if factor != 0 {
+                set_block(out, input, &Matrix8::gf256_mul(factor));
+            }
+        }
+    }
+}
+
+fn matrix_128(coefficients: &ByteCoefficients) -> Matrix128 {
+    let mut matrix = Matrix128::zero();
+    place_blocks(coefficients, |out, input, block| {
+        matrix.set_block(out, input, block)
+    });
+    matrix
+}
+
+/// Returns the matrix for `MC` (no ShiftRows) on a single 128-bit AES state.
+pub fn mc_matrix_128() -> Matrix128 {
+    matrix_128(&mc_coefficients())
+}
 
 /// Returns the matrix for `MC ∘ SR` on a single 128-bit AES state.
+///
+/// ShiftRows is applied first, so this equals `mc_matrix_128().mul(&sr_matrix_128())`.
 pub fn mc_sr_matrix_128() -> Matrix128 {
-    Matrix128::from_linear_transform(|state: &mut [u8; 16]| {
-        shift_rows(state);
-        mix_columns(state);
-    })
+    matrix_128(&mc_sr_coefficients())
 }
 
 /// Returns the block-diagonal matrix for `MC ∘ SR` on two concatenated AES states (256 bits).
 pub fn mc_sr_matrix_256() -> Matrix256 {
-    Matrix256::from_linear_transform(|state: &mut [u8; 32]| {
-        let (first, second) = state.split_at_mut(16);
-        apply_mc_sr(first);
-        apply_mc_sr(second);
-    })
+    let half = mc_sr_matrix_128();
+    Matrix256::from_matrix128_pair(&half, &half)
 }
 
 /// Returns the matrix for `SR` (no MixColumns) on a single 128-bit AES state.
 pub fn sr_matrix_128() -> Matrix128 {
-    Matrix128::from_linear_transform(|state: &mut [u8; 16]| {
-        shift_rows(state);
-    })
+    matrix_128(&sr_coefficients())
 }
 
 /// Returns the block-diagonal matrix for `SR` on two concatenated AES states (256 bits).
 pub fn sr_matrix_256() -> Matrix256 {
-    Matrix256::from_linear_transform(|state: &mut [u8; 32]| {
-        let (first, second) = state.split_at_mut(16);
-        apply_sr(first);
-        apply_sr(second);
-    })
+    let half = sr_matrix_128();
+    Matrix256::from_matrix128_pair(&half, &half)
+}
+
+/// Returns the matrix for `(MC ∘ SR)⁻¹ = SR⁻¹ ∘ MC⁻¹` on a single 128-bit AES state.
+pub fn inv_mc_sr_matrix_128() -> Matrix128 {
+    matrix_128(&inv_mc_sr_coefficients())
+}
+
+/// Returns the block-diagonal matrix for `(MC ∘ SR)⁻¹` on two concatenated AES states.
+pub fn inv_mc_sr_matrix_256() -> Matrix256 {
+    let half = inv_mc_sr_matrix_128();
+    Matrix256::from_matrix128_pair(&half, &half)
+}
+
+/// Returns the matrix for `SR⁻¹` on a single 128-bit AES state.
+pub fn inv_sr_matrix_128() -> Matrix128 {
+    matrix_128(&inv_sr_coefficients())
+}
+
+/// Returns the block-diagonal matrix for `SR⁻¹` on two concatenated AES states.
+pub fn inv_sr_matrix_256() -> Matrix256 {
+    let half = inv_sr_matrix_128();
+    Matrix256::from_matrix128_pair(&half, &half)
 }
 
-fn apply_mc_sr(state: &mut [u8]) {
-    let block: &mut Block = state
-        .try_into()
-        .expect("apply_mc_sr expects a 16-byte AES state");
-    shift_rows(block);
-    mix_columns(block);
+/// Returns the matrix for `MC⁻¹ ∘ SR⁻¹` on a single 128-bit AES state.
+///
+/// InvShiftRows is applied first, as in the rounds of the equivalent inverse cipher
+/// (FIPS-197 §5.3.5); this is not the inverse of [`mc_sr_matrix_128`].
+pub fn inv_mc_inv_sr_matrix_128() -> Matrix128 {
+    matrix_128(&inv_mc_inv_sr_coefficients())
 }
 
-fn apply_sr(state: &mut [u8]) {
-    let block: &mut Block = state
-        .try_into()
-        .expect("apply_sr expects a 16-byte AES state");
-    shift_rows(block);
+/// Returns the block-diagonal matrix for `MC⁻¹ ∘ SR⁻¹` on two concatenated AES states.
+pub fn inv_mc_inv_sr_matrix_256() -> Matrix256 {
+    let half = inv_mc_inv_sr_matrix_128();
+    Matrix256::from_matrix128_pair(&half, &half)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use rand::RngCore;
-    use rand::SeedableRng;
-    use rand_chacha::ChaCha20Rng;
+    use crate::assert_linear_map_eq;
+    use aes_core::round::{inv_mix_columns, inv_shift_rows, mix_columns, shift_rows};
+    use aes_core::Block;
 
-    #[test]
-    fn mc_sr_128_matches_aes_round_linear_layer() {
-        let matrix = mc_sr_matrix_128();
-        let mut rng = ChaCha20Rng::from_seed([20u8; 32]);
-        for _ in 0..32 {
-            let mut state = [0u8; 16];
-            rng.fill_bytes(&mut state);
-            let mut expected = state;
-            shift_rows(&mut expected);
-            mix_columns(&mut expected);
-            let actual = matrix.apply_to_bytes(&state);
-            assert_eq!(actual, expected);
+    fn mc_sr(state: &mut Block) {
+        shift_rows(state);
+        mix_columns(state);
+    }
+
+    fn inv_mc_sr(state: &mut Block) {
+        inv_mix_columns(state);
+        inv_shift_rows(state);
+    }
+
+    /// Applies `transform` to each 16-byte AES state of a copy of `input`.
+    fn per_state<const N: usize>(input: &[u8; N], transform: fn(&mut Block)) -> [u8; N] {
+        let mut output = *input;
+        for state in output.chunks_exact_mut(16) {
+            transform(state.try_into().expect("16-byte state"));
         }
+        output
     }
 
     #[test]
-    fn mc_sr_256_matches_two_block_application() {
-        let matrix = mc_sr_matrix_256();
-        let mut rng = ChaCha20Rng::from_seed([21u8; 32]);
-        for _ in 0..32 {
-            let mut state = [0u8; 32];
-            rng.fill_bytes(&mut state);
-            let mut expected = state;
-            {
-                let (first, second) = expected.split_at_mut(16);
-                apply_mc_sr(first);
-                apply_mc_sr(second);
-            }
-            let actual = matrix.apply_to_bytes(&state);
-            assert_eq!(actual, expected);
+    fn data_built_matrices_match_probed_transforms() {
+        assert_eq!(mc_sr_matrix_128(), Matrix128::from_linear_transform(mc_sr));
+        assert_eq!(
+            sr_matrix_256(),
+            Matrix256::from_linear_transform(|state| *state = per_state(state, shift_rows))
+        );
+    }
+
+    #[test]
+    fn pair_built_256_matrices_match_block_placement() {
+        for coefficients in [mc_sr_coefficients(), sr_coefficients()] {
+            let mut blocks = Vec::new();
+            place_blocks(&coefficients, |out, input, block| {
+                blocks.push(((out, input), *block));
+                blocks.push(((out + 16, input + 16), *block));
+            });
+            let half = matrix_128(&coefficients);
+            assert_eq!(
+                Matrix256::from_matrix128_pair(&half, &half),
+                Matrix256::from_blocks(&blocks)
+            );
         }
     }
 
     #[test]
-    fn sr_128_matches_aes_shift_rows() {
-        let matrix = sr_matrix_128();
-        let mut rng = ChaCha20Rng::from_seed([30u8; 32]);
-        for _ in 0..32 {
-            let mut state = [0u8; 16];
-            rng.fill_bytes(&mut state);
-            let mut expected = state;
-            shift_rows(&mut expected);
-            let actual = matrix.apply_to_bytes(&state);
-            assert_eq!(actual, expected);
+    fn mc_sr_applies_shift_rows_first() {
+        assert_eq!(mc_matrix_128().mul(&sr_matrix_128()), mc_sr_matrix_128());
+        assert_ne!(sr_matrix_128().mul(&mc_matrix_128()), mc_sr_matrix_128());
+        let mc = mc_matrix_128();
+        assert_linear_map_eq!(|x: &[u8; 16]| mc.apply_to_bytes(x), |x: &[u8; 16]| {
+            per_state(x, mix_columns)
+        });
+    }
+
+    #[test]
+    fn inverse_matrices_match_inverse_transforms() {
+        let (inv_mc_sr_128, inv_sr_128) = (inv_mc_sr_matrix_128(), inv_sr_matrix_128());
+        assert_linear_map_eq!(
+            |x: &[u8; 16]| inv_mc_sr_128.apply_to_bytes(x),
+            |x: &[u8; 16]| per_state(x, inv_mc_sr)
+        );
+        assert_linear_map_eq!(
+            |x: &[u8; 16]| inv_sr_128.apply_to_bytes(x),
+            |x: &[u8; 16]| per_state(x, inv_shift_rows)
+        );
+        assert_eq!(
+            inv_mc_sr_128.mul(&mc_sr_matrix_128()),
+            Matrix128::identity()
+        );
+        assert_eq!(inv_sr_128.mul(&sr_matrix_128()), Matrix128::identity());
+
+        let (inv_mc_sr_256, inv_sr_256) = (inv_mc_sr_matrix_256(), inv_sr_matrix_256());
+        assert_linear_map_eq!(
+            |x: &[u8; 32]| inv_mc_sr_256.apply_to_bytes(x),
+            |x: &[u8; 32]| per_state(x, inv_mc_sr)
+        );
+        assert_linear_map_eq!(
+            |x: &[u8; 32]| inv_sr_256.apply_to_bytes(x),
+            |x: &[u8; 32]| per_state(x, inv_shift_rows)
+        );
+    }
+
+    #[test]
+    fn equivalent_inverse_layer_applies_inv_shift_rows_first() {
+        fn inv_mc_inv_sr(state: &mut Block) {
+            inv_shift_rows(state);
+            inv_mix_columns(state);
         }
+        let (half, full) = (inv_mc_inv_sr_matrix_128(), inv_mc_inv_sr_matrix_256());
+        assert_linear_map_eq!(|x: &[u8; 16]| half.apply_to_bytes(x), |x: &[u8; 16]| {
+            per_state(x, inv_mc_inv_sr)
+        });
+        assert_linear_map_eq!(|x: &[u8; 32]| full.apply_to_bytes(x), |x: &[u8; 32]| {
+            per_state(x, inv_mc_inv_sr)
+        });
+        assert_ne!(half, inv_mc_sr_matrix_128());
     }
 
     #[test]
-    fn sr_256_matches_two_block_shift_rows() {
-        let matrix = sr_matrix_256();
-        let mut rng = ChaCha20Rng::from_seed([31u8; 32]);
-        for _ in 0..32 {
-            let mut state = [0u8; 32];
-            rng.fill_bytes(&mut state);
-            let mut expected = state;
-            {
-                let (first, second) = expected.split_at_mut(16);
-                apply_sr(first);
-                apply_sr(second);
+    fn inverse_256_matrices_match_inverted_forward_matrices() {
+        assert_eq!(Some(inv_mc_sr_matrix_256()), mc_sr_matrix_256().invert());
+        assert_eq!(Some(inv_sr_matrix_256()), sr_matrix_256().invert());
+    }
+
+    #[test]
+    fn gf256_mul_matrix_matches_field_multiplication() {
+        for c in [0x01, 0x02, 0x03, 0x09, 0x0b, 0x0d, 0x0e, 0x53] {
+            let matrix = Matrix8::gf256_mul(c);
+            for x in 0..=255u8 {
+                assert_eq!(matrix.apply(x), aes_core::gf256::mul(c, x));
             }
-            let actual = matrix.apply_to_bytes(&state);
-            assert_eq!(actual, expected);
         }
     }
+
+    #[test]
+    fn mc_sr_matches_aes_round_linear_layer() {
+        let (half, full) = (mc_sr_matrix_128(), mc_sr_matrix_256());
+        assert_linear_map_eq!(|x: &[u8; 16]| half.apply_to_bytes(x), |x: &[u8; 16]| {
+            per_state(x, mc_sr)
+        });
+        assert_linear_map_eq!(|x: &[u8; 32]| full.apply_to_bytes(x), |x: &[u8; 32]| {
+            per_state(x, mc_sr)
+        });
+    }
+
+    #[test]
+    fn sr_matches_aes_shift_rows() {
+        let (half, full) = (sr_matrix_128(), sr_matrix_256());
+        assert_linear_map_eq!(|x: &[u8; 16]| half.apply_to_bytes(x), |x: &[u8; 16]| {
+            per_state(x, shift_rows)
+        });
+        assert_linear_map_eq!(|x: &[u8; 32]| full.apply_to_bytes(x), |x: &[u8; 32]| {
+            per_state(x, shift_rows)
+        });
+    }
 }
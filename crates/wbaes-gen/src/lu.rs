@@ -0,0 +1,236 @@
+//! LU factorization of 256×256 GF(2) matrices.
+
+use aes_core::DoubleBlock;
+
+use crate::matrix::{zeroed_rows, Matrix256};
+use crate::simd;
+use crate::vec256::{bytes_to_segments, segments_to_bytes};
+
+/// Factorization `P · A = L · U` of a [`Matrix256`], produced by [`Matrix256::lu`].
+///
+/// `L` is unit lower-triangular, `U` upper-triangular and `P` a row permutation. Solving
+/// against the factors costs two triangular sweeps, so a matrix that is applied inverted to
+/// several vectors or matrices is factored once instead of inverted explicitly.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Lu256 {
+    /// Strictly lower part of `L`; its unit diagonal is implicit.
+    lower: Matrix256,
+    upper: Matrix256,
+    permutation: [u8; 256],
+    singular: bool,
+}
+
+impl Matrix256 {
+    /// Factors the matrix by Gaussian elimination with row pivoting.
+    ///
+    /// Singular matrices still factor (`U` then has zeros on its diagonal); check
+    /// [`Lu256::is_singular`] before solving.
+    pub fn lu(&self) -> Lu256 {
+        let mut upper = zeroed_rows();
+        upper.copy_from_slice(self.words());
+        let mut lower = zeroed_rows();
+        let mut permutation: [u8; 256] = core::array::from_fn(|row| row as u8);
+        let mut singular = false;
+        for col in 0..256 {
+            let (seg, mask) = (col / 64, 1u64 << (col % 64));
+            let Some(pivot) = (col..256).find(|&row| upper[row][seg] & mask != 0) else {
+                singular = true;
+                continue;
+            };
+            upper.swap(col, pivot);
+            lower.swap(col, pivot);
+            permutation.swap(col, pivot);
+            let pivot_row = upper[col];
+            for (row, multipliers) in upper[col + 1..].iter_mut().zip(lower[col + 1..].iter_mut()) {
+                if row[seg] & mask != 0 {
+                    simd::xor_words(row, &pivot_row);
+                    multipliers[seg] |= mask;
+                }
+            }
+        }
+        Lu256 {
+            lower: Matrix256::from_words(lower),
+            upper: Matrix256::from_words(upper),
+            permutation,
+            singular,
+        }
+    }
+}
+
+impl Lu256 {
+    /// Returns true if the factored matrix is singular; the `solve_*` methods then panic.
+    pub fn is_singular(&self) -> bool {
+        self.singular
+    }
+
+    /// Returns `L`, including its unit diagonal.
+    pub fn lower(&self) -> Matrix256 {
+        self.lower.add(&Matrix256::identity())
+    }
+
+    /// Returns `U`.
+    pub fn upper(&self) -> &Matrix256 {
+        &self.upper
+    }
+
+    /// Returns the row permutation: row `i` of `P · A` is row `permutation()[i]` of `A`.
+    pub fn permutation(&self) -> &[u8; 256] {
+        &self.permutation
+    }
+
+    /// Returns `x` with `A · x = b`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the factored matrix is singular.
+    pub fn solve_vec(&self, b: &DoubleBlock) -> DoubleBlock {
+        assert!(!self.singular, "cannot solve against a singular matrix");
+        let b = bytes_to_segments(b);
+        // Forward sweep `L · y = P · b`; bits of `y` at and after `i` are still zero, so the
+        // parity only picks up the multipliers left of the diagonal.
+        let mut y = [0u64; 4];
+        for (i, multipliers) in self.lower.words().iter().enumerate() {
+            let source = usize::from(self.permutation[i]);
+            let bit = (b[source / 64] >> (source % 64)) & 1 == 1;
+            if bit ^ simd::row_parity(multipliers, &y) {
+                y[i / 64] |= 1u64 << (i % 64);
+            }
+        }
+        // Backward sweep `U · x = y`, symmetrically.
+        let mut x = [0u64; 4];
+        for (i, row) in self.upper.words().iter().enumerate().rev() {
+            let bit = (y[i / 64] >> (i % 64)) & 1 == 1;
+            if bit ^ simd::row_parity(row, &x) {
+                x[i / 64] |= 1u64 << (i % 64);
+            }
+        }
+        segments_to_bytes(&x)
+    }
+
+    /// Returns `X` with `A · X = rhs`, i.e. `A⁻¹ · rhs`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the factored matrix is singular.
+    pub fn solve_matrix(&self, rhs: &Matrix256) -> Matrix256 {
+        let mut out = Matrix256::zero();
+        self.solve_matrix_into(rhs, &mut out);
+        out
+    }
+
+    /// Writes `A⁻¹ · rhs` into `out`, reusing its allocation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the factored matrix is singular.
+    pub fn solve_matrix_into(&self, rhs: &Matrix256, out: &mut Matrix256) {
+        assert!(!self.singular, "cannot solve against a singular matrix");
+        let rhs = rhs.words();
+        let rows = out.words_mut();
+        for (i, multipliers) in self.lower.words().iter().enumerate() {
+            let mut acc = rhs[usize::from(self.permutation[i])];
+            for_each_bit(multipliers, |j| simd::xor_words(&mut acc, &rows[j]));
+            rows[i] = acc;
+        }
+        for (i, row) in self.upper.words().iter().enumerate().rev() {
+            let mut acc = rows[i];
+            for_each_bit(row, |j| {
+                if j != i {
+                    simd::xor_words(&mut acc, &rows[j]);
+                }
+            });
+            rows[i] = acc;
+        }
+    }
+}
+
+/// Calls `f` with the index of every set bit of `words`, in increasing order.
+fn for_each_bit(words: &[u64; 4], mut f: impl FnMut(usize)) {
+    for (seg, &word) in words.iter().enumerate() {
+        let mut bits = word;
+        while bits != 0 {
+            f(seg * 64 + bits.trailing_zeros() as usize);
+            bits &= bits - 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{RngCore, SeedableRng};
+    use rand_chacha::ChaCha20Rng;
+
+    fn permuted(a: &Matrix256, permutation: &[u8; 256]) -> Matrix256 {
+        let mut rows = zeroed_rows();
+        for (row, &source) in rows.iter_mut().zip(permutation.iter()) {
+            *row = a.words()[usize::from(source)];
+        }
+        Matrix256::from_words(rows)
+    }
+
+    #[test]
+    fn factors_multiply_back() {
+        let mut rng = ChaCha20Rng::from_seed([50u8; 32]);
+        for a in [
+            Matrix256::random_sparse_unsplit(&mut rng),
+            Matrix256::random_invertible_dense(&mut rng),
+            Matrix256::identity(),
+        ] {
+            let lu = a.lu();
+            assert!(!lu.is_singular());
+            let lower = lu.lower();
+            assert_eq!(lower.mul(lu.upper()), permuted(&a, lu.permutation()));
+            for i in 0..256 {
+                for j in 0..256 {
+                    let entry = |m: &Matrix256| (m.words()[i][j / 64] >> (j % 64)) & 1 == 1;
+                    assert!(j < i || entry(&lower) == (j == i));
+                    assert!(j > i || entry(lu.upper()) == (j == i));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn solves_match_explicit_inverse() {
+        let mut rng = ChaCha20Rng::from_seed([51u8; 32]);
+        let rhs = Matrix256::random_invertible_dense(&mut rng);
+        for a in [
+            Matrix256::random_sparse_unsplit(&mut rng),
+            Matrix256::random_invertible_dense(&mut rng),
+        ] {
+            let lu = a.lu();
+            let inv = a.invert().expect("sampled invertible");
+            assert_eq!(lu.solve_matrix(&rhs), inv.mul(&rhs));
+            assert_eq!(lu.solve_matrix(&Matrix256::identity()), inv);
+            let mut out = Matrix256::identity();
+            lu.solve_matrix_into(&rhs, &mut out);
+            assert_eq!(out, inv.mul(&rhs));
+            for _ in 0..16 {
+                let mut b = [0u8; 32];
+                rng.fill_bytes(&mut b);
+                let x = lu.solve_vec(&b);
+                assert_eq!(x, inv.apply_to_bytes(&b));
+                assert_eq!(a.apply_to_bytes(&x), b);
+            }
+        }
+    }
+
+    #[test]
+    fn singular_matrices_are_flagged() {
+        let mut rng = ChaCha20Rng::from_seed([52u8; 32]);
+        let mut a = Matrix256::random_sparse_unsplit(&mut rng);
+        a.set_block(7, 7, &crate::Matrix8::zero());
+        a.set_block(7, 8, &crate::Matrix8::zero());
+        let lu = a.lu();
+        assert!(lu.is_singular());
+        assert_eq!(lu.lower().mul(lu.upper()), permuted(&a, lu.permutation()));
+        assert!(Matrix256::zero().lu().is_singular());
+    }
+
+    #[test]
+    #[should_panic(expected = "singular")]
+    fn solving_against_singular_matrix_panics() {
+        Matrix256::zero().lu().solve_vec(&[1u8; 32]);
+    }
+}
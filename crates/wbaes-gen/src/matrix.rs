@@ -1,13 +1,34 @@
 //! GF(2) matrix utilities for 8×8 and 256×256 dimensions.
+//!
+//! # Bit order
+//!
+//! Vectors index bits LSB first: bit `i` is bit `i % 8` of byte `i / 8`, so byte 0 bit 0 is
+//! index 0. Matrices follow suit: entry `(row, col)` of a [`Matrix8`] is bit `col` of row byte
+//! `row`, and of a [`Matrix128`]/[`Matrix256`] bit `col % 64` of row word `col / 64`. Tools
+//! such as SageMath or numpy usually print rows MSB first; use
+//! [`Matrix8::to_bit_rows_msb_first`] and the `*_row_major_bits` conversions to exchange
+//! matrices with them.
 
 use core::convert::TryInto;
+use core::fmt;
 
+use aes_core::DoubleBlock;
+use rand::seq::SliceRandom;
 use rand::{CryptoRng, RngCore};
-use serde::{Deserialize, Serialize};
+use serde::de::{Error as _, SeqAccess, Visitor};
+use serde::ser::SerializeTuple;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_big_array::BigArray;
+use zeroize::Zeroize;
+
+use crate::fingerprint::serialized_sha256;
+use crate::sparse::SparseMatrix256;
+use crate::stats::{GenStats, SamplerCounts};
+use crate::vec256::{bytes_to_segments, segments_to_bytes};
+use crate::{gf2, simd};
 
 /// 8×8 binary matrix over GF(2), stored row-major with each row packed into a `u8`.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Matrix8 {
     rows: [u8; 8],
 }
@@ -36,26 +57,54 @@ impl Matrix8 {
         Self { rows }
     }
 
-    /// Generates a uniformly random invertible matrix, retrying until one is found.
+    /// Generates a random invertible matrix as `P · L · U`, invertible by construction.
+    ///
+    /// `L` and `U` are random unit lower- and upper-triangular matrices and `P` a random row
+    /// permutation. Every element of GL(8, 2) can be produced, but not uniformly: matrices with
+    /// several `P · L · U` factorisations are more likely than others. That bias is irrelevant
+    /// for encodings, which only need a large, unpredictable set of bijections.
     pub fn random_invertible<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
-        loop {
-            let candidate = Self::random(rng);
-            if candidate.is_invertible() {
-                return candidate;
-            }
+        // 28 strictly-lower and 28 strictly-upper entries, drawn from one 64-bit word.
+        let mut bits = rng.next_u64();
+        let mut lower = Self::identity();
+        let mut upper = Self::identity();
+        for i in 1..8 {
+            let below = (1u8 << i) - 1;
+            lower.rows[i] |= bits as u8 & below;
+            bits >>= i;
+            upper.rows[7 - i] |= (bits as u8 & below) << (8 - i);
+            bits >>= i;
         }
+        let mut product = lower.mul(&upper);
+        product.rows.shuffle(rng);
+        product
+    }
+
+    /// [`Self::random_invertible`], counting the draw into `stats`.
+    pub fn random_invertible_with_stats<R: RngCore + CryptoRng>(
+        rng: &mut R,
+        stats: &mut GenStats,
+    ) -> Self {
+        stats.counts.matrix8_invertible += 1;
+        Self::random_invertible(rng)
     }
 
     /// Applies the matrix to an 8-bit value, treating bits as a column vector.
     pub fn apply(&self, value: u8) -> u8 {
         let mut out = 0u8;
         for (row_idx, row) in self.rows.iter().enumerate() {
-            let parity = (row & value).count_ones() as u8 & 1;
-            out |= parity << row_idx;
+            out |= u8::from(gf2::parity_u8(row & value)) << row_idx;
         }
         out
     }
 
+    /// Adds two matrices (entry-wise XOR).
+    pub fn add(&self, rhs: &Self) -> Self {
+        Self {
+            rows: core::array::from_fn(|i| self.rows[i] ^ rhs.rows[i]),
+        }
+    }
+
     /// Multiplies two matrices (`self * rhs`).
     pub fn mul(&self, rhs: &Self) -> Self {
         let mut result = Self::zero();
@@ -110,10 +159,216 @@ impl Matrix8 {
     pub fn rows(&self) -> &[u8; 8] {
         &self.rows
     }
+
+    /// Builds a matrix from packed rows: bit `col` of `rows[row]` is entry `(row, col)`.
+    pub fn from_rows(rows: [u8; 8]) -> Self {
+        Self { rows }
+    }
+
+    /// Returns the rows packed MSB first: bit `7 - col` of row byte `row` is entry `(row, col)`.
+    ///
+    /// Printing each byte as `{:08b}` then shows the row left to right, column 0 first.
+    pub fn to_bit_rows_msb_first(&self) -> [u8; 8] {
+        self.rows.map(gf2::swap_bit_order_u8)
+    }
+
+    /// Inverse of [`Matrix8::to_bit_rows_msb_first`].
+    pub fn from_bit_rows_msb_first(rows: [u8; 8]) -> Self {
+        Self {
+            rows: rows.map(gf2::swap_bit_order_u8),
+        }
+    }
+
+    /// Builds a matrix from packed columns: bit `row` of `cols[col]` is entry `(row, col)`.
+    pub fn from_cols(cols: [u8; 8]) -> Self {
+        Self { rows: cols }.transpose()
+    }
+
+    /// Reads entry `(row, col)`.
+    pub fn bit(&self, row: usize, col: usize) -> bool {
+        (self.rows[row] >> col) & 1 == 1
+    }
+
+    /// Writes entry `(row, col)`.
+    pub fn set_bit(&mut self, row: usize, col: usize, value: bool) {
+        if value {
+            self.rows[row] |= 1 << col;
+        } else {
+            self.rows[row] &= !(1 << col);
+        }
+    }
+
+    /// Returns the transpose.
+    pub fn transpose(&self) -> Self {
+        let mut rows = [0u8; 8];
+        for (col, out) in rows.iter_mut().enumerate() {
+            for row in 0..8 {
+                *out |= ((self.rows[row] >> col) & 1) << row;
+            }
+        }
+        Self { rows }
+    }
+
+    /// Returns the rank over GF(2), by forward elimination on a copy of the rows.
+    pub fn rank(&self) -> u32 {
+        let mut rows = self.rows;
+        let mut rank = 0;
+        for col in 0..8 {
+            let Some(pivot) = (rank..8).find(|&row| (rows[row] >> col) & 1 == 1) else {
+                continue;
+            };
+            rows.swap(rank, pivot);
+            for row in rank + 1..8 {
+                if (rows[row] >> col) & 1 == 1 {
+                    rows[row] ^= rows[rank];
+                }
+            }
+            rank += 1;
+        }
+        rank as u32
+    }
+
+    /// Returns some `x` with `self · x = b`, or `None` if the system is inconsistent.
+    ///
+    /// Free variables are set to zero.
+    pub fn solve(&self, b: u8) -> Option<u8> {
+        let (_, rhs, pivots) = self.reduce(b);
+        if rhs.checked_shr(pivots.len() as u32).unwrap_or(0) != 0 {
+            return None;
+        }
+        let mut x = 0u8;
+        for (i, &col) in pivots.iter().enumerate() {
+            x |= ((rhs >> i) & 1) << col;
+        }
+        Some(x)
+    }
+
+    /// Returns a basis of `{x : self · x = 0}`, one vector per non-pivot column.
+    pub fn kernel_basis(&self) -> Vec<u8> {
+        let (rows, _, pivots) = self.reduce(0);
+        (0..8)
+            .filter(|col| !pivots.contains(col))
+            .map(|free| {
+                let mut x = 1u8 << free;
+                for (row, &col) in rows.iter().zip(pivots.iter()) {
+                    x |= ((row >> free) & 1) << col;
+                }
+                x
+            })
+            .collect()
+    }
+
+    /// Gauss–Jordan elimination of `[self | b]`: returns the reduced rows, the reduced
+    /// right-hand side (bit `i` for row `i`), and the pivot column of each leading row.
+    fn reduce(&self, b: u8) -> ([u8; 8], u8, Vec<usize>) {
+        let mut rows = self.rows;
+        let mut rhs = b;
+        let mut pivots = Vec::with_capacity(8);
+        for col in 0..8 {
+            let rank = pivots.len();
+            let Some(pivot) = (rank..8).find(|&row| (rows[row] >> col) & 1 == 1) else {
+                continue;
+            };
+            rows.swap(rank, pivot);
+            rhs = swap_bits(rhs, rank, pivot);
+            for row in 0..8 {
+                if row != rank && (rows[row] >> col) & 1 == 1 {
+                    rows[row] ^= rows[rank];
+                    rhs ^= ((rhs >> rank) & 1) << row;
+                }
+            }
+            pivots.push(col);
+        }
+        (rows, rhs, pivots)
+    }
+
+    /// Returns `self^exp` by square-and-multiply; `self^0` is the identity.
+    pub fn pow(&self, exp: u32) -> Self {
+        let mut result = Self::identity();
+        let mut base = *self;
+        let mut exp = exp;
+        while exp != 0 {
+            if exp & 1 == 1 {
+                result = result.mul(&base);
+            }
+            base = base.mul(&base);
+            exp >>= 1;
+        }
+        result
+    }
+
+    /// Returns the order of the matrix in GL(8, 2), or `None` if it is singular.
+    ///
+    /// Found by repeated multiplication, which is short: no element of GL(8, 2) has order
+    /// above 255.
+    pub fn multiplicative_order(&self) -> Option<u32> {
+        if !self.is_invertible() {
+            return None;
+        }
+        let mut power = *self;
+        let mut order = 1;
+        while power != Self::identity() {
+            power = power.mul(self);
+            order += 1;
+        }
+        Some(order)
+    }
+
+    /// Returns the minimal polynomial as a bit mask: bit `i` is the coefficient of `x^i`.
+    ///
+    /// Found as the first linear dependency among `I, M, M², …`, each flattened to 64 bits;
+    /// the result is monic of degree at most 8.
+    pub fn minimal_polynomial(&self) -> u16 {
+        // Reduced powers with their pivot bit and the combination of powers they stand for.
+        let mut basis: Vec<(u32, u64, u16)> = Vec::with_capacity(8);
+        let mut power = Self::identity();
+        for degree in 0..=8 {
+            let mut vector = u64::from_le_bytes(power.rows);
+            let mut combination = 1u16 << degree;
+            for &(pivot, basis_vector, basis_combination) in &basis {
+                if (vector >> pivot) & 1 == 1 {
+                    vector ^= basis_vector;
+                    combination ^= basis_combination;
+                }
+            }
+            if vector == 0 {
+                return combination;
+            }
+            basis.push((63 - vector.leading_zeros(), vector, combination));
+            power = power.mul(self);
+        }
+        unreachable!("Cayley–Hamilton bounds the degree by 8")
+    }
+
+    /// Returns the matrix of multiplication by `c` in the AES field GF(2^8).
+    pub fn gf256_mul(c: u8) -> Self {
+        let mut rows = [0u8; 8];
+        for bit in 0..8 {
+            let product = aes_core::gf256::mul(c, 1u8 << bit);
+            for (row_idx, row) in rows.iter_mut().enumerate() {
+                *row |= ((product >> row_idx) & 1) << bit;
+            }
+        }
+        Self { rows }
+    }
+}
+
+// Being `Copy`, a wiped value says nothing about copies made earlier; the generator keeps its
+// secret 8×8 blocks inside `Matrix256` and `Affine256`, which are not `Copy`.
+impl Zeroize for Matrix8 {
+    fn zeroize(&mut self) {
+        self.rows.zeroize();
+    }
+}
+
+impl Zeroize for Matrix128 {
+    fn zeroize(&mut self) {
+        self.rows.zeroize();
+    }
 }
 
 /// 128×128 binary matrix over GF(2), stored row-major with two `u64` segments per row.
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Matrix128 {
     #[serde(with = "BigArray")]
     rows: [[u64; 2]; 128],
@@ -138,6 +393,16 @@ impl Matrix128 {
         Self { rows }
     }
 
+    /// Wraps packed rows.
+    pub(crate) fn from_words(rows: [[u64; 2]; 128]) -> Self {
+        Self { rows }
+    }
+
+    /// Borrows the packed rows.
+    pub(crate) fn words(&self) -> &[[u64; 2]; 128] {
+        &self.rows
+    }
+
     fn set_bit(&mut self, row: usize, col: usize, value: bool) {
         let segment = col / 64;
         let offset = col % 64;
@@ -149,17 +414,90 @@ impl Matrix128 {
         }
     }
 
+    /// Generates a sparse unsplit matrix: the 16-block analogue of
+    /// [`Matrix256::random_sparse_unsplit`], with the wrap block at `(15, 0)`.
+    pub fn random_sparse_unsplit<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
+        let mut mat = Self::zero();
+        for block in 0..16 {
+            let diag = Matrix8::random_invertible(rng);
+            mat.set_block(block, block, &diag);
+        }
+        for block in 0..15 {
+            let super_block = Matrix8::random(rng);
+            mat.set_block(block, block + 1, &super_block);
+        }
+        let correction =
+            band_correction(16, |row_block, col_block| mat.block(row_block, col_block))
+                .expect("diagonal blocks are invertible");
+        let mut counts = SamplerCounts::default();
+        mat.set_block(15, 0, &sample_wrap_block(rng, &correction, &mut counts));
+        debug_assert!(mat.is_invertible());
+        mat
+    }
+
+    /// Checks the banded structure of [`Self::random_sparse_unsplit`] on the 16×16 block grid.
+    pub fn validate_banded_unsplit(&self) -> Result<(), BandedStructureError> {
+        validate_band(16, |row_block, col_block| self.block(row_block, col_block))
+    }
+
+    /// Returns true if [`Self::validate_banded_unsplit`] accepts the matrix.
+    pub fn is_sparse_unsplit(&self) -> bool {
+        self.validate_banded_unsplit().is_ok()
+    }
+
+    /// Attempts to invert the matrix via bit-sliced Gaussian elimination.
+    pub fn invert(&self) -> Option<Self> {
+        let mut left = self.rows;
+        let mut right = Self::identity().rows;
+
+        for col in 0..128 {
+            let (seg, mask) = (col / 64, 1u64 << (col % 64));
+            let pivot = (col..128).find(|&row| left[row][seg] & mask != 0)?;
+            left.swap(pivot, col);
+            right.swap(pivot, col);
+            let (pivot_left, pivot_right) = (left[col], right[col]);
+            for row in 0..128 {
+                if row != col && left[row][seg] & mask != 0 {
+                    for s in 0..2 {
+                        left[row][s] ^= pivot_left[s];
+                        right[row][s] ^= pivot_right[s];
+                    }
+                }
+            }
+        }
+
+        Some(Self { rows: right })
+    }
+
+    /// Returns true if the matrix is invertible.
+    ///
+    /// Stops at the first column without a pivot, so singular matrices are rejected early.
+    pub fn is_invertible(&self) -> bool {
+        let mut rows = self.rows;
+        for col in 0..128 {
+            let (seg, mask) = (col / 64, 1u64 << (col % 64));
+            let Some(pivot) = (col..128).find(|&row| rows[row][seg] & mask != 0) else {
+                return false;
+            };
+            rows.swap(col, pivot);
+            let pivot_row = rows[col];
+            for row in &mut rows[col + 1..] {
+                if row[seg] & mask != 0 {
+                    row[0] ^= pivot_row[0];
+                    row[1] ^= pivot_row[1];
+                }
+            }
+        }
+        true
+    }
+
     /// Applies the matrix to a 128-bit vector represented as 16 bytes.
     pub fn apply_to_bytes(&self, input: &[u8; 16]) -> [u8; 16] {
         let input_segments = bytes128_to_segments(input);
         let mut output_segments = [0u64; 2];
 
         for (row_idx, row) in self.rows.iter().enumerate() {
-            let mut acc = 0u32;
-            for seg in 0..2 {
-                acc ^= (row[seg] & input_segments[seg]).count_ones();
-            }
-            if acc & 1 == 1 {
+            if gf2::parity_u64((row[0] & input_segments[0]) ^ (row[1] & input_segments[1])) {
                 let segment = row_idx / 64;
                 let offset = row_idx % 64;
                 output_segments[segment] |= 1u64 << offset;
@@ -169,6 +507,35 @@ impl Matrix128 {
         segments128_to_bytes(&output_segments)
     }
 
+    /// Applies the matrix to a 128-bit vector in place.
+    pub fn apply_in_place(&self, input: &mut [u8; 16]) {
+        *input = self.apply_to_bytes(input);
+    }
+
+    /// Returns the map `u8 -> 128-bit` for the given byte position, using the current linear map.
+    pub fn submatrix_byte_map(&self, byte_index: usize) -> [[u8; 16]; 256] {
+        assert!(byte_index < 16, "byte index out of range");
+        let (seg, shift) = (byte_index / 8, (byte_index % 8) * 8);
+        let mut columns = [[0u64; 2]; 8];
+        for (row_idx, row) in self.rows.iter().enumerate() {
+            let mut bits = (row[seg] >> shift) as u8;
+            while bits != 0 {
+                let bit = bits.trailing_zeros() as usize;
+                columns[bit][row_idx / 64] |= 1u64 << (row_idx % 64);
+                bits &= bits - 1;
+            }
+        }
+        let mut table = [[0u64; 2]; 256];
+        for value in 1..256usize {
+            let mut entry = table[value & (value - 1)];
+            let column = columns[value.trailing_zeros() as usize];
+            entry[0] ^= column[0];
+            entry[1] ^= column[1];
+            table[value] = entry;
+        }
+        table.map(|entry| segments128_to_bytes(&entry))
+    }
+
     /// Multiplies two matrices (`self * rhs`).
     pub fn mul(&self, rhs: &Self) -> Self {
         let mut result = Self::zero();
@@ -190,6 +557,45 @@ impl Matrix128 {
         result
     }
 
+    /// Sets an 8×8 block at `(row_block, col_block)` to `block`.
+    pub fn set_block(&mut self, row_block: usize, col_block: usize, block: &Matrix8) {
+        assert!(row_block < 16 && col_block < 16, "block index out of range");
+        for (row, row_bits) in block.rows.iter().enumerate() {
+            for bit in 0..8 {
+                let value = (row_bits >> bit) & 1 == 1;
+                self.set_bit(row_block * 8 + row, col_block * 8 + bit, value);
+            }
+        }
+    }
+
+    /// Returns the 8×8 block at `(row_block, col_block)`.
+    pub fn block(&self, row_block: usize, col_block: usize) -> Matrix8 {
+        assert!(row_block < 16 && col_block < 16, "block index out of range");
+        let rows = core::array::from_fn(|row| {
+            let word = self.rows[row_block * 8 + row][col_block / 8];
+            (word >> ((col_block % 8) * 8)) as u8
+        });
+        Matrix8 { rows }
+    }
+
+    /// Returns one byte (0 or 1) per entry, row-major: entry `(row, col)` is at `row * 128 + col`.
+    pub fn to_row_major_bits(&self) -> Vec<u8> {
+        self.rows
+            .iter()
+            .flat_map(|row| (0..128).map(move |col| ((row[col / 64] >> (col % 64)) & 1) as u8))
+            .collect()
+    }
+
+    /// Inverse of [`Matrix128::to_row_major_bits`].
+    pub fn from_row_major_bits(bits: &[u8]) -> Result<Self, BitLayoutError> {
+        check_bits(bits, 128 * 128)?;
+        let mut matrix = Self::zero();
+        for (index, &bit) in bits.iter().enumerate() {
+            matrix.set_bit(index / 128, index % 128, bit == 1);
+        }
+        Ok(matrix)
+    }
+
     /// Builds a matrix from a linear transform applied to 16-byte inputs.
     pub fn from_linear_transform(transform: impl Fn(&mut [u8; 16])) -> Self {
         let mut matrix = Self::zero();
@@ -207,24 +613,193 @@ impl Matrix128 {
     }
 }
 
+/// Violation of the sparse unsplit (banded) block structure.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BandedStructureError {
+    /// A block outside the diagonal, first super-diagonal, and wrap-around `(last, 0)` block
+    /// is non-zero.
+    OffBandBlock {
+        /// Row index of the offending 8×8 block.
+        row_block: usize,
+        /// Column index of the offending 8×8 block.
+        col_block: usize,
+    },
+    /// A diagonal block is not invertible.
+    SingularDiagonal {
+        /// Index of the offending diagonal block.
+        block: usize,
+    },
+}
+
+impl fmt::Display for BandedStructureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OffBandBlock {
+                row_block,
+                col_block,
+            } => write!(
+                f,
+                "non-zero block ({row_block}, {col_block}) outside the band"
+            ),
+            Self::SingularDiagonal { block } => {
+                write!(f, "diagonal block ({block}, {block}) is singular")
+            }
+        }
+    }
+}
+
+impl std::error::Error for BandedStructureError {}
+
+/// Rejected one-byte-per-bit input to a `from_*bits` conversion.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BitLayoutError {
+    /// The input does not hold exactly one byte per bit.
+    Length {
+        /// Number of bits the target type holds.
+        expected: usize,
+        /// Length of the rejected input.
+        actual: usize,
+    },
+    /// A byte is neither 0 nor 1.
+    NotABit {
+        /// Position of the offending byte.
+        index: usize,
+        /// Its value.
+        value: u8,
+    },
+}
+
+impl fmt::Display for BitLayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Length { expected, actual } => {
+                write!(f, "expected {expected} bits, got {actual}")
+            }
+            Self::NotABit { index, value } => {
+                write!(f, "byte {index} is {value}; bits must be 0 or 1")
+            }
+        }
+    }
+}
+
+impl std::error::Error for BitLayoutError {}
+
+/// Checks that `bits` holds exactly `expected` bytes, each 0 or 1.
+pub(crate) fn check_bits(bits: &[u8], expected: usize) -> Result<(), BitLayoutError> {
+    if bits.len() != expected {
+        return Err(BitLayoutError::Length {
+            expected,
+            actual: bits.len(),
+        });
+    }
+    match bits.iter().position(|&bit| bit > 1) {
+        Some(index) => Err(BitLayoutError::NotABit {
+            index,
+            value: bits[index],
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Set bits in the left operand above which [`Matrix256::mul`] switches to four Russians.
+///
+/// The row-XOR loop costs one 256-bit XOR per set bit, the table method a fixed 32 × 511 XORs
+/// plus lookups; on x86-64 they break even at a density of about 7/16.
+const FOUR_RUSSIANS_MIN_ONES: u32 = 28 * 1024;
+
+/// Row storage of a [`Matrix256`]: 8 KiB, so it always lives on the heap.
+pub(crate) type Rows256 = [[u64; 4]; 256];
+
+/// Allocates zeroed rows without building the array on the stack first.
+pub(crate) fn zeroed_rows() -> Box<Rows256> {
+    vec![[0u64; 4]; 256]
+        .into_boxed_slice()
+        .try_into()
+        .expect("vector has 256 rows")
+}
+
+impl Zeroize for Matrix256 {
+    fn zeroize(&mut self) {
+        for row in self.rows.iter_mut() {
+            row.zeroize();
+        }
+    }
+}
+
+/// Lookup tables of a banded right operand for [`Matrix256::mul_banded_rhs`].
+///
+/// Block row `k` of the operand only reaches the eight block columns starting at
+/// `starts[k]`; `tables[k][v]` packs, little-endian, the eight output bytes that a left-row
+/// byte `v` at position `k` contributes to them.
+struct BandTables {
+    starts: [usize; 32],
+    tables: Vec<[u64; 256]>,
+}
+
+impl BandTables {
+    /// Tabulates `rhs`, or returns `None` if some block row spans more than eight block columns.
+    fn new(rhs: &SparseMatrix256) -> Option<Self> {
+        let mut used = [0u32; 32];
+        for ((row_block, col_block), _) in rhs.nonzero_blocks() {
+            used[row_block] |= 1 << col_block;
+        }
+        let mut starts = [0; 32];
+        for (start, &mask) in starts.iter_mut().zip(used.iter()) {
+            *start = (0..32).find(|&col| mask.rotate_right(col as u32) < 1 << 8)?;
+        }
+        let mut packed = [[0u64; 8]; 32];
+        for ((row_block, col_block), block) in rhs.nonzero_blocks() {
+            let offset = (col_block + 32 - starts[row_block]) % 32;
+            for (word, &row) in packed[row_block].iter_mut().zip(block.rows().iter()) {
+                *word |= u64::from(row) << (offset * 8);
+            }
+        }
+        let mut tables = vec![[0u64; 256]; 32];
+        for (table, packed) in tables.iter_mut().zip(packed.iter()) {
+            for value in 1..256usize {
+                table[value] = table[value & (value - 1)] ^ packed[value.trailing_zeros() as usize];
+            }
+        }
+        Some(Self { starts, tables })
+    }
+}
+
 /// 256×256 binary matrix over GF(2), stored row-major, four `u64` segments per row.
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// The rows are boxed so that matrices can be moved and returned cheaply. The serialized
+/// form is a fixed-length 8192-byte array: each row's words in order, little-endian, with no
+/// length prefix, whatever the serializer's integer encoding.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Matrix256 {
-    #[serde(with = "BigArray")]
-    rows: [[u64; 4]; 256],
+    rows: Box<Rows256>,
 }
 
 impl Matrix256 {
     /// Returns the zero matrix.
     pub fn zero() -> Self {
         Self {
-            rows: [[0u64; 4]; 256],
+            rows: zeroed_rows(),
         }
     }
 
+    /// Wraps packed rows.
+    pub(crate) fn from_words(rows: Box<Rows256>) -> Self {
+        Self { rows }
+    }
+
+    /// Borrows the packed rows.
+    pub(crate) fn words(&self) -> &Rows256 {
+        &self.rows
+    }
+
+    /// Mutably borrows the packed rows.
+    pub(crate) fn words_mut(&mut self) -> &mut Rows256 {
+        &mut self.rows
+    }
+
     /// Returns the identity matrix.
     pub fn identity() -> Self {
-        let mut rows = [[0u64; 4]; 256];
+        let mut rows = zeroed_rows();
         for (i, row) in rows.iter_mut().enumerate() {
             let segment = i / 64;
             let offset = i % 64;
@@ -263,6 +838,7 @@ impl Matrix256 {
 
     /// Sets an 8×8 block at `(row_block, col_block)` to `block`.
     pub fn set_block(&mut self, row_block: usize, col_block: usize, block: &Matrix8) {
+        assert!(row_block < 32 && col_block < 32, "block index out of range");
         self.clear_block(row_block, col_block);
         for row in 0..8 {
             let row_bits = block.rows[row];
@@ -277,6 +853,7 @@ impl Matrix256 {
 
     /// Returns the 8×8 block at `(row_block, col_block)`.
     pub fn block(&self, row_block: usize, col_block: usize) -> Matrix8 {
+        assert!(row_block < 32 && col_block < 32, "block index out of range");
         let mut rows = [0u8; 8];
         for (row_offset, row_slot) in rows.iter_mut().enumerate() {
             let mut row_bits = 0u8;
@@ -290,55 +867,415 @@ impl Matrix256 {
         Matrix8 { rows }
     }
 
+    /// Builds a matrix from `((row_block, col_block), block)` entries; all other blocks are zero.
+    ///
+    /// Entries are applied in order, so a repeated coordinate keeps the last block.
+    pub fn from_blocks(blocks: &[((usize, usize), Matrix8)]) -> Self {
+        let mut mat = Self::zero();
+        for &((row_block, col_block), ref block) in blocks {
+            mat.set_block(row_block, col_block, block);
+        }
+        mat
+    }
+
+    /// Builds a matrix block by block: `block(row_block, col_block)` gives the 8×8 block at
+    /// that position, `None` meaning zero.
+    pub fn from_block_fn(block: impl Fn(usize, usize) -> Option<Matrix8>) -> Self {
+        let mut mat = Self::zero();
+        for row_block in 0..32 {
+            for col_block in 0..32 {
+                if let Some(value) = block(row_block, col_block) {
+                    mat.set_block(row_block, col_block, &value);
+                }
+            }
+        }
+        mat
+    }
+
+    /// Builds the block-diagonal matrix with `blocks[i]` at `(i, i)`.
+    pub fn block_diag32(blocks: &[Matrix8; 32]) -> Self {
+        Self::from_block_fn(|row_block, col_block| {
+            (row_block == col_block).then(|| blocks[row_block])
+        })
+    }
+
+    /// Builds `diag(low, high)`: `low` acts on bytes 0..16 and `high` on bytes 16..32.
+    pub fn from_matrix128_pair(low: &Matrix128, high: &Matrix128) -> Self {
+        let mut mat = Self::zero();
+        for (row, &[a, b]) in low.rows.iter().enumerate() {
+            mat.rows[row] = [a, b, 0, 0];
+        }
+        for (row, &[a, b]) in high.rows.iter().enumerate() {
+            mat.rows[128 + row] = [0, 0, a, b];
+        }
+        mat
+    }
+
     /// Generates a sparse unsplit matrix with the banded structure described in the revisited scheme.
     ///
     /// Non-zero blocks appear only on the diagonal, first super-diagonal, and the wrap-around block
     /// from the last row block to the first column block. Diagonal blocks are guaranteed invertible;
-    /// generation retries until the full 256×256 matrix is invertible.
+    /// only the wrap block is resampled until the full 256×256 matrix is invertible, which is
+    /// decided by an 8×8 check (see `band_correction`).
     pub fn random_sparse_unsplit<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
-        loop {
-            let mut mat = Self::zero();
-            for block in 0..32 {
-                let diag = Matrix8::random_invertible(rng);
-                mat.set_block(block, block, &diag);
+        Self::random_sparse_unsplit_with_stats(rng, &mut GenStats::default())
+    }
+
+    /// [`Self::random_sparse_unsplit`], counting its draws into `stats`.
+    pub fn random_sparse_unsplit_with_stats<R: RngCore + CryptoRng>(
+        rng: &mut R,
+        stats: &mut GenStats,
+    ) -> Self {
+        let mut mat = Self::zero();
+        for block in 0..32 {
+            let diag = Matrix8::random_invertible_with_stats(rng, stats);
+            mat.set_block(block, block, &diag);
+        }
+        for block in 0..31 {
+            let super_block = Matrix8::random(rng);
+            mat.set_block(block, block + 1, &super_block);
+        }
+        let correction = mat
+            .band_correction()
+            .expect("diagonal blocks are invertible");
+        mat.set_block(
+            31,
+            0,
+            &sample_wrap_block(rng, &correction, &mut stats.counts),
+        );
+        debug_assert!(mat.is_invertible());
+        mat
+    }
+
+    /// Generates a random invertible matrix whose non-zero blocks lie on the diagonal and the
+    /// first `bandwidth` super-diagonals, wrapping around: block row `i` may only be non-zero
+    /// in block columns `i, i + 1, …, i + bandwidth` (mod 32).
+    ///
+    /// Diagonal blocks are invertible. Bandwidth 1 is [`Self::random_sparse_unsplit`]; wider
+    /// bands redraw their wrap-around blocks until the whole matrix is invertible, which
+    /// usually takes a few attempts.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless `1 <= bandwidth <= 31`.
+    pub fn random_banded<R: RngCore + CryptoRng>(rng: &mut R, bandwidth: usize) -> Self {
+        assert!(
+            (1..32).contains(&bandwidth),
+            "bandwidth must be between 1 and 31"
+        );
+        if bandwidth == 1 {
+            return Self::random_sparse_unsplit(rng);
+        }
+        let mut mat = Self::zero();
+        for block in 0..32 {
+            mat.set_block(block, block, &Matrix8::random_invertible(rng));
+            for offset in 1..=bandwidth {
+                if block + offset < 32 {
+                    mat.set_block(block, block + offset, &Matrix8::random(rng));
+                }
             }
-            for block in 0..31 {
-                let super_block = Matrix8::random(rng);
-                mat.set_block(block, block + 1, &super_block);
+        }
+        loop {
+            for block in 32 - bandwidth..32 {
+                for offset in 32 - block..=bandwidth {
+                    mat.set_block(block, block + offset - 32, &Matrix8::random(rng));
+                }
             }
-            let wrap_block = Matrix8::random(rng);
-            mat.set_block(31, 0, &wrap_block);
-
             if mat.is_invertible() {
                 return mat;
             }
         }
     }
 
-    /// Multiplies two matrices (`self * rhs`).
-    pub fn mul(&self, rhs: &Self) -> Self {
-        let mut result = Self::zero();
-        for (row_idx, row) in self.rows.iter().enumerate() {
-            let mut accum = [0u64; 4];
-            for (segment_idx, segment) in row.iter().enumerate() {
-                let mut bits = *segment;
-                while bits != 0 {
+    /// Returns the smallest bandwidth, in the sense of [`Self::random_banded`], whose band
+    /// contains every non-zero block.
+    pub fn block_bandwidth(&self) -> usize {
+        let mut bandwidth = 0;
+        for row_block in 0..32 {
+            for col_block in 0..32 {
+                if self.block(row_block, col_block) != Matrix8::zero() {
+                    bandwidth = bandwidth.max((col_block + 32 - row_block) % 32);
+                }
+            }
+        }
+        bandwidth
+    }
+
+    /// Generates a dense random invertible matrix as `P · L · U`, invertible by construction.
+    ///
+    /// `L` and `U` are random unit lower- and upper-triangular and `P` is a random row
+    /// permutation. As with [`Matrix8::random_invertible`], every invertible matrix can occur
+    /// but the distribution over GL(256, 2) is not exactly uniform.
+    pub fn random_invertible_dense<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
+        let mut lower = Self::identity();
+        let mut upper = Self::identity();
+        for i in 0..256 {
+            for seg in 0..4 {
+                let (lo, hi) = (seg * 64, seg * 64 + 64);
+                let below = if i >= hi {
+                    !0
+                } else if i > lo {
+                    (1u64 << (i - lo)) - 1
+                } else {
+                    0
+                };
+                let above = if i < lo {
+                    !0
+                } else if i + 1 < hi {
+                    !0u64 << (i + 1 - lo)
+                } else {
+                    0
+                };
+                lower.rows[i][seg] |= rng.next_u64() & below;
+                upper.rows[i][seg] |= rng.next_u64() & above;
+            }
+        }
+        let mut product = lower.mul(&upper);
+        product.rows.shuffle(rng);
+        product
+    }
+
+    /// Reference generator that redraws every block until the full matrix is invertible.
+    ///
+    /// Kept to benchmark and cross-check [`Self::random_sparse_unsplit`]; the two produce the
+    /// same structure but consume randomness differently.
+    pub fn random_sparse_unsplit_naive<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
+        loop {
+            let mut mat = Self::zero();
+            for block in 0..32 {
+                let diag = Matrix8::random_invertible(rng);
+                mat.set_block(block, block, &diag);
+            }
+            for block in 0..31 {
+                let super_block = Matrix8::random(rng);
+                mat.set_block(block, block + 1, &super_block);
+            }
+            let wrap_block = Matrix8::random(rng);
+            mat.set_block(31, 0, &wrap_block);
+
+            if mat.is_invertible() {
+                return mat;
+            }
+        }
+    }
+
+    /// Returns `(D_0⁻¹ S_0) ⋯ (D_30⁻¹ S_30) · D_31⁻¹` for a banded matrix with invertible
+    /// diagonal blocks `D_i` and super-diagonal blocks `S_i`, or `None` if a `D_i` is singular.
+    ///
+    /// A kernel vector `x` must satisfy `x_i = D_i⁻¹ S_i x_{i+1}` along the band and
+    /// `x_31 = D_31⁻¹ W x_0` at the wrap, so `x_0 = C W x_0` for this correction `C`. The matrix
+    /// is therefore invertible exactly when `I + C W` is.
+    fn band_correction(&self) -> Option<Matrix8> {
+        band_correction(32, |row_block, col_block| self.block(row_block, col_block))
+    }
+
+    /// Checks the structural invariants the round tables rely on.
+    ///
+    /// Only the diagonal, first super-diagonal, and `(31, 0)` wrap blocks may be non-zero,
+    /// and every diagonal block must be invertible. Blocks are scanned row by row and the
+    /// first violation is reported with its block coordinates.
+    pub fn validate_banded_unsplit(&self) -> Result<(), BandedStructureError> {
+        validate_band(32, |row_block, col_block| self.block(row_block, col_block))
+    }
+
+    /// Returns true if [`Self::validate_banded_unsplit`] accepts the matrix.
+    pub fn is_sparse_unsplit(&self) -> bool {
+        self.validate_banded_unsplit().is_ok()
+    }
+
+    /// Lists the coordinates of all non-zero 8×8 blocks, row by row.
+    pub fn band_profile(&self) -> Vec<(usize, usize)> {
+        (0..32)
+            .flat_map(|row_block| (0..32).map(move |col_block| (row_block, col_block)))
+            .filter(|&(row_block, col_block)| self.block(row_block, col_block) != Matrix8::zero())
+            .collect()
+    }
+
+    /// Adds two matrices (entry-wise XOR).
+    pub fn add(&self, rhs: &Self) -> Self {
+        let mut result = self.clone();
+        for (row, other) in result.rows.iter_mut().zip(rhs.rows.iter()) {
+            for (dst, src) in row.iter_mut().zip(other.iter()) {
+                *dst ^= *src;
+            }
+        }
+        result
+    }
+
+    /// Multiplies two matrices (`self * rhs`).
+    ///
+    /// Dense left operands use [`Self::mul_four_russians`]; sparse ones, such as banded
+    /// encodings, are cheaper with [`Self::mul_naive`], whose cost grows with the set bits.
+    pub fn mul(&self, rhs: &Self) -> Self {
+        let mut result = Self::zero();
+        self.mul_into(rhs, &mut result);
+        result
+    }
+
+    /// Writes `self * rhs` into `out`, reusing its allocation; dispatches like [`Self::mul`].
+    pub fn mul_into(&self, rhs: &Self, out: &mut Self) {
+        if self.is_dense_for_mul() {
+            self.mul_four_russians_into(rhs, out);
+        } else {
+            self.mul_naive_into(rhs, out);
+        }
+    }
+
+    /// Multiplies by a banded right operand, touching only its non-zero 8×8 blocks.
+    ///
+    /// Returns `None` unless every block row of `rhs` has its non-zero blocks within a cyclic
+    /// window of at most eight block columns; banded encodings use two. Each output row then
+    /// costs one table lookup per byte of the left row instead of one row XOR per set bit.
+    pub fn mul_banded_rhs(&self, rhs: &SparseMatrix256) -> Option<Self> {
+        let tables = BandTables::new(rhs)?;
+        let mut out = Self::zero();
+        self.mul_band_tables_into(&tables, &mut out);
+        Some(out)
+    }
+
+    /// Writes `self * rhs` into `out`, given the band tables of `rhs`.
+    fn mul_band_tables_into(&self, tables: &BandTables, out: &mut Self) {
+        for (row, out_row) in self.rows.iter().zip(out.rows.iter_mut()) {
+            let input = segments_to_bytes(row);
+            // One spare word so that a window starting at any block column fits; the overflow
+            // past byte 32 wraps around to the front.
+            let mut acc = [0u64; 5];
+            for (row_block, &byte) in input.iter().enumerate() {
+                let value = tables.tables[row_block][usize::from(byte)];
+                let start = tables.starts[row_block];
+                let (word, shift) = (start / 8, (start % 8) * 8);
+                acc[word] ^= value << shift;
+                if shift != 0 {
+                    acc[word + 1] ^= value >> (64 - shift);
+                }
+            }
+            *out_row = [acc[0] ^ acc[4], acc[1], acc[2], acc[3]];
+        }
+    }
+
+    /// Returns whether [`Self::mul`] takes the four Russians path for this left operand.
+    fn is_dense_for_mul(&self) -> bool {
+        let ones: u32 = self
+            .rows
+            .iter()
+            .flatten()
+            .map(|word| word.count_ones())
+            .sum();
+        ones >= FOUR_RUSSIANS_MIN_ONES
+    }
+
+    /// Returns `self^exp` by square-and-multiply; `self^0` is the identity.
+    pub fn pow(&self, exp: u32) -> Self {
+        let mut result = Self::identity();
+        let mut base = self.clone();
+        let mut exp = exp;
+        while exp != 0 {
+            if exp & 1 == 1 {
+                result = result.mul(&base);
+            }
+            exp >>= 1;
+            if exp != 0 {
+                base = base.mul(&base);
+            }
+        }
+        result
+    }
+
+    /// Multiplies two matrices with the method of four Russians.
+    ///
+    /// For each group of eight `rhs` rows, all 256 XOR combinations are tabulated once; every
+    /// output row then takes one lookup per group, indexed by the matching byte of its row.
+    pub fn mul_four_russians(&self, rhs: &Self) -> Self {
+        let mut result = Self::zero();
+        self.mul_four_russians_into(rhs, &mut result);
+        result
+    }
+
+    /// Four Russians product into `out`, building one group's table at a time.
+    fn mul_four_russians_into(&self, rhs: &Self, out: &mut Self) {
+        out.rows.fill([0u64; 4]);
+        let mut table = zeroed_rows();
+        for group in 0..32 {
+            rhs.fill_group_table(group, &mut table);
+            self.accumulate_group(group, &table, out);
+        }
+    }
+
+    /// Fills `table[mask]` with the XOR of the rows `group * 8 + i` for each bit `i` of `mask`.
+    fn fill_group_table(&self, group: usize, table: &mut Rows256) {
+        table[0] = [0u64; 4];
+        for index in 1..256usize {
+            let mut entry = table[index & (index - 1)];
+            let low = index.trailing_zeros() as usize;
+            simd::xor_words(&mut entry, &self.rows[group * 8 + low]);
+            table[index] = entry;
+        }
+    }
+
+    /// XORs into each row of `out` the `table` entry selected by byte `group` of `self`'s row.
+    fn accumulate_group(&self, group: usize, table: &Rows256, out: &mut Self) {
+        let (seg, shift) = (group / 8, (group % 8) * 8);
+        for (row, acc) in self.rows.iter().zip(out.rows.iter_mut()) {
+            simd::xor_words(acc, &table[((row[seg] >> shift) & 0xff) as usize]);
+        }
+    }
+
+    /// Multiplies two matrices by XORing one `rhs` row per set bit of `self`.
+    ///
+    /// Also the reference for the other multiplication paths in tests and benches.
+    pub fn mul_naive(&self, rhs: &Self) -> Self {
+        let mut result = Self::zero();
+        self.mul_naive_into(rhs, &mut result);
+        result
+    }
+
+    fn mul_naive_into(&self, rhs: &Self, out: &mut Self) {
+        for (row, out_row) in self.rows.iter().zip(out.rows.iter_mut()) {
+            let mut accum = [0u64; 4];
+            for (segment_idx, segment) in row.iter().enumerate() {
+                let mut bits = *segment;
+                while bits != 0 {
                     let bit = bits.trailing_zeros() as usize;
-                    let source_row = segment_idx * 64 + bit;
-                    for (seg_idx, accum_seg) in accum.iter_mut().enumerate() {
-                        *accum_seg ^= rhs.rows[source_row][seg_idx];
-                    }
+                    simd::xor_words(&mut accum, &rhs.rows[segment_idx * 64 + bit]);
                     bits &= bits - 1;
                 }
             }
-            result.rows[row_idx] = accum;
+            *out_row = accum;
+        }
+    }
+
+    /// Returns the transpose.
+    pub fn transpose(&self) -> Self {
+        let mut result = Self::zero();
+        for row in 0..256 {
+            for col in 0..256 {
+                if self.bit(row, col) {
+                    result.set_bit(col, row, true);
+                }
+            }
         }
         result
     }
 
+    /// Returns row `row` as a 256-bit vector in the byte layout of [`Self::apply_to_bytes`].
+    pub fn row(&self, row: usize) -> DoubleBlock {
+        segments_to_bytes(&self.rows[row])
+    }
+
+    /// Returns column `col` as a 256-bit vector, i.e. the image of basis vector `e_col`.
+    pub fn col(&self, col: usize) -> DoubleBlock {
+        let mut out = [0u8; 32];
+        for row in 0..256 {
+            if self.bit(row, col) {
+                out[row / 8] |= 1 << (row % 8);
+            }
+        }
+        out
+    }
+
     /// Attempts to invert the matrix via bit-sliced Gaussian elimination.
     pub fn invert(&self) -> Option<Self> {
-        let mut left = self.rows;
+        let mut left = self.rows.clone();
         let mut right = Self::identity().rows;
 
         for col in 0..256 {
@@ -371,21 +1308,116 @@ impl Matrix256 {
     }
 
     /// Returns true if the matrix is invertible.
+    ///
+    /// Stops at the first column without a pivot, so singular matrices are rejected early.
     pub fn is_invertible(&self) -> bool {
-        self.invert().is_some()
+        self.forward_eliminate(true) == 256
+    }
+
+    /// Returns the rank over GF(2), by forward elimination on a copy of the rows.
+    pub fn rank(&self) -> u32 {
+        self.forward_eliminate(false)
+    }
+
+    /// Row-reduces a copy to echelon form and counts pivots, optionally giving up at the
+    /// first pivotless column. Unlike [`Self::invert`] no identity is carried along.
+    fn forward_eliminate(&self, stop_on_deficit: bool) -> u32 {
+        let mut rows = self.rows.clone();
+        let mut rank = 0;
+        for col in 0..256 {
+            let (seg, mask) = (col / 64, 1u64 << (col % 64));
+            let Some(pivot) = (rank..256).find(|&row| rows[row][seg] & mask != 0) else {
+                if stop_on_deficit {
+                    break;
+                }
+                continue;
+            };
+            rows.swap(rank, pivot);
+            let pivot_row = rows[rank];
+            for row in &mut rows[rank + 1..] {
+                if row[seg] & mask != 0 {
+                    for (dst, src) in row.iter_mut().zip(pivot_row.iter()).skip(seg) {
+                        *dst ^= *src;
+                    }
+                }
+            }
+            rank += 1;
+        }
+        rank as u32
+    }
+
+    /// Returns some `x` with `self · x = b`, or `None` if the system is inconsistent.
+    ///
+    /// Free variables are set to zero.
+    pub fn solve(&self, b: &DoubleBlock) -> Option<DoubleBlock> {
+        let (_, rhs, pivots) = self.reduce(b);
+        if rhs[pivots.len()..].iter().any(|&bit| bit) {
+            return None;
+        }
+        let mut x = [0u8; 32];
+        for (&col, &bit) in pivots.iter().zip(rhs.iter()) {
+            x[col / 8] |= u8::from(bit) << (col % 8);
+        }
+        Some(x)
+    }
+
+    /// Returns a basis of `{x : self · x = 0}`, one vector per non-pivot column.
+    pub fn kernel_basis(&self) -> Vec<DoubleBlock> {
+        let (rows, _, pivots) = self.reduce(&[0u8; 32]);
+        let mut is_pivot = [false; 256];
+        for &col in &pivots {
+            is_pivot[col] = true;
+        }
+        (0..256)
+            .filter(|&col| !is_pivot[col])
+            .map(|free| {
+                let mut x = [0u8; 32];
+                x[free / 8] |= 1 << (free % 8);
+                for (row, &col) in rows.iter().zip(pivots.iter()) {
+                    if (row[free / 64] >> (free % 64)) & 1 == 1 {
+                        x[col / 8] |= 1 << (col % 8);
+                    }
+                }
+                x
+            })
+            .collect()
+    }
+
+    /// Gauss–Jordan elimination of `[self | b]`: returns the reduced rows, the reduced
+    /// right-hand side, and the pivot column of each leading row.
+    fn reduce(&self, b: &DoubleBlock) -> (Box<Rows256>, [bool; 256], Vec<usize>) {
+        let mut rows = self.rows.clone();
+        let mut rhs: [bool; 256] = core::array::from_fn(|row| (b[row / 8] >> (row % 8)) & 1 == 1);
+        let mut pivots = Vec::new();
+        for col in 0..256 {
+            let rank = pivots.len();
+            let (seg, mask) = (col / 64, 1u64 << (col % 64));
+            let Some(pivot) = (rank..256).find(|&row| rows[row][seg] & mask != 0) else {
+                continue;
+            };
+            rows.swap(rank, pivot);
+            rhs.swap(rank, pivot);
+            let (pivot_row, pivot_rhs) = (rows[rank], rhs[rank]);
+            for (row, (bits, target)) in rows.iter_mut().zip(rhs.iter_mut()).enumerate() {
+                if row != rank && bits[seg] & mask != 0 {
+                    for (dst, src) in bits.iter_mut().zip(pivot_row.iter()) {
+                        *dst ^= *src;
+                    }
+                    *target ^= pivot_rhs;
+                }
+            }
+            pivots.push(col);
+        }
+        (rows, rhs, pivots)
     }
 
     /// Applies the matrix to a 256-bit vector represented as 32 bytes.
-    pub fn apply_to_bytes(&self, input: &[u8; 32]) -> [u8; 32] {
+    pub fn apply_to_bytes(&self, input: &DoubleBlock) -> DoubleBlock {
         let input_segments = bytes_to_segments(input);
         let mut output_segments = [0u64; 4];
 
         for (row_idx, row) in self.rows.iter().enumerate() {
-            let mut acc = 0u32;
-            for seg in 0..4 {
-                acc ^= (row[seg] & input_segments[seg]).count_ones();
-            }
-            if acc & 1 == 1 {
+            if simd::row_parity(row, &input_segments) {
                 let segment = row_idx / 64;
                 let offset = row_idx % 64;
                 output_segments[segment] |= 1u64 << offset;
@@ -396,40 +1428,90 @@ impl Matrix256 {
     }
 
     /// Applies the matrix to a 256-bit vector in place.
-    pub fn apply_in_place(&self, input: &mut [u8; 32]) {
+    pub fn apply_in_place(&self, input: &mut DoubleBlock) {
         *input = self.apply_to_bytes(input);
     }
 
+    /// Applies the matrix to every vector in `inputs`.
+    ///
+    /// Works column-wise: for each input byte position the images of all 256 byte values are
+    /// tabulated once from eight matrix columns, and every input then costs one lookup per
+    /// position. Positions that are zero in all inputs are skipped.
+    pub fn apply_to_many(&self, inputs: &[DoubleBlock]) -> Vec<DoubleBlock> {
+        let mut outputs = vec![[0u64; 4]; inputs.len()];
+        let mut table = zeroed_rows();
+        for byte_index in 0..32 {
+            if inputs.iter().all(|input| input[byte_index] == 0) {
+                continue;
+            }
+            self.fill_byte_table(byte_index, &mut table);
+            for (input, output) in inputs.iter().zip(outputs.iter_mut()) {
+                simd::xor_words(output, &table[usize::from(input[byte_index])]);
+            }
+        }
+        outputs.iter().map(segments_to_bytes).collect()
+    }
+
+    /// Fills `table[v]` with the image of the vector holding `v` at `byte_index`, zero elsewhere.
+    fn fill_byte_table(&self, byte_index: usize, table: &mut Rows256) {
+        let (seg, shift) = (byte_index / 8, (byte_index % 8) * 8);
+        let mut columns = [[0u64; 4]; 8];
+        for (row_idx, row) in self.rows.iter().enumerate() {
+            let mut bits = (row[seg] >> shift) as u8;
+            while bits != 0 {
+                let bit = bits.trailing_zeros() as usize;
+                columns[bit][row_idx / 64] |= 1u64 << (row_idx % 64);
+                bits &= bits - 1;
+            }
+        }
+        table[0] = [0u64; 4];
+        for value in 1..256usize {
+            let mut entry = table[value & (value - 1)];
+            simd::xor_words(&mut entry, &columns[value.trailing_zeros() as usize]);
+            table[value] = entry;
+        }
+    }
+
     /// Returns the map `u8 -> 256-bit` for the given byte position, using the current linear map.
-    pub fn submatrix_byte_map(&self, byte_index: usize) -> [[u8; 32]; 256] {
+    pub fn submatrix_byte_map(&self, byte_index: usize) -> [DoubleBlock; 256] {
         assert!(byte_index < 32, "byte index out of range");
+        let inputs: Vec<DoubleBlock> = (0..=255u8)
+            .map(|value| {
+                let mut input = [0u8; 32];
+                input[byte_index] = value;
+                input
+            })
+            .collect();
+        self.apply_to_many(&inputs)
+            .try_into()
+            .expect("one image per byte value")
+    }
 
-        let mut basis_outputs = [[0u8; 32]; 8];
-        for (bit, output) in basis_outputs.iter_mut().enumerate() {
-            let mut input = [0u8; 32];
-            input[byte_index] = 1u8 << bit;
-            *output = self.apply_to_bytes(&input);
-        }
+    /// Returns one byte (0 or 1) per entry, row-major: entry `(row, col)` is at `row * 256 + col`.
+    pub fn to_row_major_bits(&self) -> Vec<u8> {
+        self.rows
+            .iter()
+            .flat_map(|row| (0..256).map(move |col| ((row[col / 64] >> (col % 64)) & 1) as u8))
+            .collect()
+    }
 
-        let mut map = [[0u8; 32]; 256];
-        for value in 1u16..=255 {
-            let mut acc = [0u8; 32];
-            let mut v = value as u8;
-            let mut bit = 0;
-            while v != 0 {
-                if v & 1 == 1 {
-                    xor_bytes(&mut acc, &basis_outputs[bit]);
-                }
-                v >>= 1;
-                bit += 1;
-            }
-            map[value as usize] = acc;
+    /// Inverse of [`Matrix256::to_row_major_bits`].
+    pub fn from_row_major_bits(bits: &[u8]) -> Result<Self, BitLayoutError> {
+        check_bits(bits, 256 * 256)?;
+        let mut matrix = Self::zero();
+        for (index, &bit) in bits.iter().enumerate() {
+            matrix.set_bit(index / 256, index % 256, bit == 1);
         }
-        map
+        Ok(matrix)
+    }
+
+    /// Returns the SHA-256 of the serialized matrix, a stable identifier across runs.
+    pub fn fingerprint(&self) -> [u8; 32] {
+        serialized_sha256(self)
     }
 
     /// Builds a matrix from a linear transform applied to 32-byte inputs.
-    pub fn from_linear_transform(transform: impl Fn(&mut [u8; 32])) -> Self {
+    pub fn from_linear_transform(transform: impl Fn(&mut DoubleBlock)) -> Self {
         let mut matrix = Self::zero();
         for col in 0..256 {
             let mut vector = [0u8; 32];
@@ -443,36 +1525,153 @@ impl Matrix256 {
         }
         matrix
     }
+
+    /// Returns whether `f` agrees with the matrix on `samples` random inputs.
+    ///
+    /// A probabilistic equality check for maps given as closures: two distinct linear maps
+    /// agree on a random input with probability at most 1/2, so 64 samples are plenty. Use
+    /// [`crate::assert_linear_map_eq!`] for a deterministic check.
+    pub fn agrees_with<R: RngCore>(
+        &self,
+        f: impl Fn(&DoubleBlock) -> DoubleBlock,
+        samples: usize,
+        rng: &mut R,
+    ) -> bool {
+        (0..samples).all(|_| {
+            let mut x = [0u8; 32];
+            rng.fill_bytes(&mut x);
+            self.apply_to_bytes(&x) == f(&x)
+        })
+    }
 }
 
-fn bytes128_to_segments(bytes: &[u8; 16]) -> [u64; 2] {
-    [
-        u64::from_le_bytes(bytes[0..8].try_into().expect("slice length 8")),
-        u64::from_le_bytes(bytes[8..16].try_into().expect("slice length 8")),
-    ]
+/// Serialized size of a [`Matrix256`] in bytes.
+const MATRIX256_BYTES: usize = 256 * 4 * 8;
+
+impl Serialize for Matrix256 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut tuple = serializer.serialize_tuple(MATRIX256_BYTES)?;
+        for byte in self
+            .rows
+            .iter()
+            .flatten()
+            .flat_map(|word| word.to_le_bytes())
+        {
+            tuple.serialize_element(&byte)?;
+        }
+        tuple.end()
+    }
 }
 
-fn segments128_to_bytes(segments: &[u64; 2]) -> [u8; 16] {
-    let mut out = [0u8; 16];
-    for (idx, segment) in segments.iter().enumerate() {
-        let bytes = segment.to_le_bytes();
-        let start = idx * 8;
-        out[start..start + 8].copy_from_slice(&bytes);
+impl<'de> Deserialize<'de> for Matrix256 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct BytesVisitor;
+
+        impl<'de> Visitor<'de> for BytesVisitor {
+            type Value = Matrix256;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{MATRIX256_BYTES} matrix bytes")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut matrix = Matrix256::zero();
+                for (index, word) in matrix.rows.iter_mut().flatten().enumerate() {
+                    let mut bytes = [0u8; 8];
+                    for (offset, byte) in bytes.iter_mut().enumerate() {
+                        *byte = seq
+                            .next_element()?
+                            .ok_or_else(|| A::Error::invalid_length(index * 8 + offset, &self))?;
+                    }
+                    *word = u64::from_le_bytes(bytes);
+                }
+                Ok(matrix)
+            }
+        }
+
+        deserializer.deserialize_tuple(MATRIX256_BYTES, BytesVisitor)
     }
-    out
 }
 
-fn bytes_to_segments(bytes: &[u8; 32]) -> [u64; 4] {
+/// Returns `(D_0⁻¹ S_0) ⋯ (D_{n-2}⁻¹ S_{n-2}) · D_{n-1}⁻¹` for an `n`-block band, or `None` if
+/// a diagonal block is singular; see [`Matrix256::random_sparse_unsplit`].
+fn band_correction(blocks: usize, block: impl Fn(usize, usize) -> Matrix8) -> Option<Matrix8> {
+    let mut chain = Matrix8::identity();
+    for index in 0..blocks {
+        let diag_inv = block(index, index).invert()?;
+        chain = chain.mul(&diag_inv);
+        if index + 1 < blocks {
+            chain = chain.mul(&block(index, index + 1));
+        }
+    }
+    Some(chain)
+}
+
+/// Returns true if `I + correction · wrap_block` is invertible, i.e. the closed band is.
+fn wrap_closes_band(correction: &Matrix8, wrap_block: &Matrix8) -> bool {
+    let mut closed = correction.mul(wrap_block);
+    for (i, row) in closed.rows.iter_mut().enumerate() {
+        *row ^= 1 << i;
+    }
+    closed.is_invertible()
+}
+
+/// Draws wrap blocks until one closes the band.
+///
+/// `W = 0` always succeeds, so this terminates; each draw succeeds with probability ≈ 0.29.
+fn sample_wrap_block<R: RngCore + CryptoRng>(
+    rng: &mut R,
+    correction: &Matrix8,
+    counts: &mut SamplerCounts,
+) -> Matrix8 {
+    loop {
+        let wrap_block = Matrix8::random(rng);
+        counts.band_wrap_attempts += 1;
+        if wrap_closes_band(correction, &wrap_block) {
+            counts.band_wrap_successes += 1;
+            return wrap_block;
+        }
+    }
+}
+
+/// Checks the banded block structure of an `n × n` block grid, row by row.
+fn validate_band(
+    blocks: usize,
+    block: impl Fn(usize, usize) -> Matrix8,
+) -> Result<(), BandedStructureError> {
+    for row_block in 0..blocks {
+        for col_block in 0..blocks {
+            let value = block(row_block, col_block);
+            if col_block == row_block {
+                if !value.is_invertible() {
+                    return Err(BandedStructureError::SingularDiagonal { block: row_block });
+                }
+            } else if col_block != (row_block + 1) % blocks && value != Matrix8::zero() {
+                return Err(BandedStructureError::OffBandBlock {
+                    row_block,
+                    col_block,
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Exchanges bits `a` and `b` of `value`.
+fn swap_bits(value: u8, a: usize, b: usize) -> u8 {
+    let differ = ((value >> a) ^ (value >> b)) & 1;
+    value ^ (differ << a) ^ (differ << b)
+}
+
+fn bytes128_to_segments(bytes: &[u8; 16]) -> [u64; 2] {
     [
         u64::from_le_bytes(bytes[0..8].try_into().expect("slice length 8")),
         u64::from_le_bytes(bytes[8..16].try_into().expect("slice length 8")),
-        u64::from_le_bytes(bytes[16..24].try_into().expect("slice length 8")),
-        u64::from_le_bytes(bytes[24..32].try_into().expect("slice length 8")),
     ]
 }
 
-fn segments_to_bytes(segments: &[u64; 4]) -> [u8; 32] {
-    let mut out = [0u8; 32];
+fn segments128_to_bytes(segments: &[u64; 2]) -> [u8; 16] {
+    let mut out = [0u8; 16];
     for (idx, segment) in segments.iter().enumerate() {
         let bytes = segment.to_le_bytes();
         let start = idx * 8;
@@ -481,18 +1680,105 @@ fn segments_to_bytes(segments: &[u64; 4]) -> [u8; 32] {
     out
 }
 
-fn xor_bytes(dst: &mut [u8; 32], src: &[u8; 32]) {
-    for (d, s) in dst.iter_mut().zip(src.iter()) {
-        *d ^= *s;
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
     use rand::SeedableRng;
     use rand_chacha::ChaCha20Rng;
 
+    #[test]
+    fn matrix8_transpose_and_bit_accessors() {
+        let mut rng = ChaCha20Rng::from_seed([43u8; 32]);
+        for _ in 0..64 {
+            let m = Matrix8::random(&mut rng);
+            assert_eq!(m.transpose().transpose(), m);
+            assert_eq!(Matrix8::from_cols(*m.transpose().rows()), m);
+            for row in 0..8 {
+                for col in 0..8 {
+                    assert_eq!(m.bit(row, col), m.transpose().bit(col, row));
+                }
+            }
+        }
+
+        let mut m = Matrix8::zero();
+        m.set_bit(2, 5, true);
+        assert!(m.bit(2, 5));
+        assert_eq!(m, Matrix8::from_rows([0, 0, 1 << 5, 0, 0, 0, 0, 0]));
+        assert_eq!(Matrix8::from_cols([0, 0, 0, 0, 0, 1 << 2, 0, 0]), m);
+        m.set_bit(2, 5, false);
+        assert_eq!(m, Matrix8::zero());
+    }
+
+    #[test]
+    fn matrix8_rank() {
+        assert_eq!(Matrix8::identity().rank(), 8);
+        assert_eq!(Matrix8::zero().rank(), 0);
+        let duplicated = Matrix8::from_rows([1, 2, 4, 8, 16, 32, 64, 64]);
+        assert_eq!(duplicated.rank(), 7);
+
+        let mut rng = ChaCha20Rng::from_seed([29u8; 32]);
+        let mut invertible = 0;
+        for _ in 0..500 {
+            let m = Matrix8::random(&mut rng);
+            assert_eq!(m.is_invertible(), m.rank() == 8);
+            assert_eq!(m.rank(), m.transpose().rank());
+            invertible += usize::from(m.rank() == 8);
+        }
+        assert!(invertible > 0 && invertible < 500);
+    }
+
+    #[test]
+    fn matrix8_random_invertible_is_spread_out() {
+        let mut rng = ChaCha20Rng::from_seed([31u8; 32]);
+        let mut seen = std::collections::HashSet::new();
+        let mut identities = 0;
+        let mut ones = [[0u32; 8]; 8];
+        for _ in 0..10_000 {
+            let m = Matrix8::random_invertible(&mut rng);
+            assert!(m.is_invertible());
+            identities += usize::from(m == Matrix8::identity());
+            for (row, counts) in ones.iter_mut().enumerate() {
+                for (col, count) in counts.iter_mut().enumerate() {
+                    *count += u32::from(m.bit(row, col));
+                }
+            }
+            seen.insert(m.rows);
+        }
+        assert!(identities <= 1);
+        assert!(seen.len() > 9_990);
+        // Each entry should be set in roughly half of the samples.
+        for count in ones.iter().flatten() {
+            assert!((3_500..6_500).contains(count), "entry set {count} times");
+        }
+    }
+
+    #[test]
+    fn matrix8_solve_and_kernel() {
+        let mut rng = ChaCha20Rng::from_seed([38u8; 32]);
+        for _ in 0..32 {
+            let m = Matrix8::random_invertible(&mut rng);
+            let b = rng.next_u32() as u8;
+            assert_eq!(m.solve(b), Some(m.invert().unwrap().apply(b)));
+            assert!(m.kernel_basis().is_empty());
+        }
+        for _ in 0..200 {
+            let m = Matrix8::random(&mut rng);
+            let kernel = m.kernel_basis();
+            assert_eq!(kernel.len() as u32, 8 - m.rank());
+            for &v in &kernel {
+                assert_ne!(v, 0);
+                assert_eq!(m.apply(v), 0);
+            }
+            let x = rng.next_u32() as u8;
+            let solution = m.solve(m.apply(x)).expect("image is solvable");
+            assert_eq!(m.apply(solution), m.apply(x));
+        }
+        let singular = Matrix8::from_rows([1, 2, 4, 8, 16, 32, 64, 0]);
+        assert_eq!(singular.kernel_basis(), vec![0x80]);
+        assert_eq!(singular.solve(0x80), None);
+        assert_eq!(Matrix8::zero().kernel_basis().len(), 8);
+    }
+
     #[test]
     fn matrix8_inversion_roundtrip() {
         let mut rng = ChaCha20Rng::from_seed([1u8; 32]);
@@ -541,6 +1827,542 @@ mod tests {
         }
     }
 
+    #[test]
+    fn matrix256_transpose_rows_and_columns() {
+        let mut rng = ChaCha20Rng::from_seed([30u8; 32]);
+        let m = Matrix256::random_sparse_unsplit(&mut rng);
+        let t = m.transpose();
+        assert_eq!(t.transpose(), m);
+        assert_eq!(Matrix256::identity().transpose(), Matrix256::identity());
+
+        for i in (0..256).step_by(7) {
+            let mut basis = [0u8; 32];
+            basis[i / 8] = 1 << (i % 8);
+            assert_eq!(m.apply_to_bytes(&basis), m.col(i));
+            assert_eq!(t.row(i), m.col(i));
+        }
+
+        // The super-diagonal band of the original becomes a sub-diagonal band.
+        for block in 0..31 {
+            assert_eq!(
+                t.block(block + 1, block),
+                m.block(block, block + 1).transpose()
+            );
+            assert_eq!(t.block(block, block + 1), Matrix8::zero());
+        }
+        assert_eq!(t.block(0, 31), m.block(31, 0).transpose());
+        assert!((0..31).any(|block| t.block(block + 1, block) != Matrix8::zero()));
+    }
+
+    #[test]
+    fn random_sparse_unsplit_validates() {
+        let mut rng = ChaCha20Rng::from_seed([7u8; 32]);
+        for _ in 0..4 {
+            let m = Matrix256::random_sparse_unsplit(&mut rng);
+            assert_eq!(m.validate_banded_unsplit(), Ok(()));
+        }
+    }
+
+    #[test]
+    fn random_sparse_unsplit_is_banded_and_invertible_across_seeds() {
+        for seed in 0..100u8 {
+            let mut rng = ChaCha20Rng::from_seed([seed; 32]);
+            let m = Matrix256::random_sparse_unsplit(&mut rng);
+            assert_eq!(m.validate_banded_unsplit(), Ok(()), "seed {seed}");
+            assert!(m.is_invertible(), "seed {seed}");
+        }
+        let mut rng = ChaCha20Rng::from_seed([100u8; 32]);
+        let naive = Matrix256::random_sparse_unsplit_naive(&mut rng);
+        assert_eq!(naive.validate_banded_unsplit(), Ok(()));
+        assert!(naive.is_invertible());
+    }
+
+    #[test]
+    fn random_banded_fills_exactly_its_band() {
+        let mut rng = ChaCha20Rng::from_seed([65u8; 32]);
+        let mut reference = ChaCha20Rng::from_seed([65u8; 32]);
+        assert_eq!(
+            Matrix256::random_banded(&mut rng, 1),
+            Matrix256::random_sparse_unsplit(&mut reference)
+        );
+        for bandwidth in [2, 3, 31] {
+            let m = Matrix256::random_banded(&mut rng, bandwidth);
+            assert!(m.is_invertible(), "bandwidth {bandwidth}");
+            assert_eq!(m.block_bandwidth(), bandwidth);
+            for block in 0..32 {
+                assert!(m.block(block, block).is_invertible());
+            }
+            assert!(m.validate_banded_unsplit().is_err());
+        }
+        assert_eq!(Matrix256::identity().block_bandwidth(), 0);
+        assert_eq!(
+            Matrix256::random_sparse_unsplit(&mut rng).block_bandwidth(),
+            1
+        );
+    }
+
+    #[test]
+    fn band_correction_decides_invertibility() {
+        let mut rng = ChaCha20Rng::from_seed([32u8; 32]);
+        let mut singular = 0;
+        for _ in 0..50 {
+            let mut m = Matrix256::random_sparse_unsplit(&mut rng);
+            m.set_block(31, 0, &Matrix8::random(&mut rng));
+            let correction = m.band_correction().expect("diagonal blocks are invertible");
+            let predicted = wrap_closes_band(&correction, &m.block(31, 0));
+            assert_eq!(predicted, m.is_invertible());
+            singular += usize::from(!predicted);
+        }
+        assert!(singular > 0, "no singular band matrices sampled");
+    }
+
+    #[test]
+    fn validate_banded_unsplit_rejects_singular_diagonal() {
+        let mut m = Matrix256::identity();
+        m.set_block(5, 5, &Matrix8::zero());
+        assert_eq!(
+            m.validate_banded_unsplit(),
+            Err(BandedStructureError::SingularDiagonal { block: 5 })
+        );
+    }
+
+    #[test]
+    fn validate_banded_unsplit_rejects_off_band_block() {
+        let mut m = Matrix256::identity();
+        m.set_block(3, 4, &Matrix8::identity());
+        m.set_block(31, 0, &Matrix8::identity());
+        assert_eq!(m.validate_banded_unsplit(), Ok(()));
+
+        m.set_block(10, 20, &Matrix8::identity());
+        assert_eq!(
+            m.validate_banded_unsplit(),
+            Err(BandedStructureError::OffBandBlock {
+                row_block: 10,
+                col_block: 20
+            })
+        );
+    }
+
+    #[test]
+    fn is_sparse_unsplit_checks_each_rule() {
+        let mut rng = ChaCha20Rng::from_seed([45u8; 32]);
+        let banded = Matrix256::random_sparse_unsplit(&mut rng);
+        assert!(banded.is_sparse_unsplit());
+        assert!(Matrix256::identity().is_sparse_unsplit());
+
+        // Off-band block, including the mirrored wrap position (0, 31).
+        for (row_block, col_block) in [(0, 2), (0, 31), (17, 3)] {
+            let mut m = banded.clone();
+            m.set_block(row_block, col_block, &Matrix8::identity());
+            assert!(!m.is_sparse_unsplit(), "({row_block}, {col_block})");
+        }
+        // Singular diagonal block, both zero and non-zero.
+        let mut m = banded.clone();
+        m.set_block(9, 9, &Matrix8::zero());
+        assert!(!m.is_sparse_unsplit());
+        m.set_block(9, 9, &Matrix8::from_rows([1, 2, 4, 8, 16, 32, 64, 64]));
+        assert!(!m.is_sparse_unsplit());
+        assert!(!Matrix256::zero().is_sparse_unsplit());
+    }
+
+    #[test]
+    fn band_profile_lists_non_zero_blocks() {
+        assert_eq!(Matrix256::zero().band_profile(), vec![]);
+        let diagonal: Vec<_> = (0..32).map(|i| (i, i)).collect();
+        assert_eq!(Matrix256::identity().band_profile(), diagonal);
+
+        let mut m = Matrix256::identity();
+        m.set_block(31, 0, &Matrix8::identity());
+        m.set_block(4, 5, &Matrix8::gf256_mul(3));
+        let profile = m.band_profile();
+        assert_eq!(profile.len(), 34);
+        assert!(profile.contains(&(31, 0)) && profile.contains(&(4, 5)));
+        assert_eq!(profile.last(), Some(&(31, 31)));
+    }
+
+    #[test]
+    fn matrix256_rank() {
+        assert_eq!(Matrix256::identity().rank(), 256);
+        assert_eq!(Matrix256::zero().rank(), 0);
+        assert!(!Matrix256::zero().is_invertible());
+
+        let mut rng = ChaCha20Rng::from_seed([30u8; 32]);
+        let mut m = Matrix256::random_sparse_unsplit(&mut rng);
+        assert_eq!(m.rank(), 256);
+        assert_eq!(m.transpose().rank(), 256);
+        assert!(m.is_invertible());
+
+        m.rows[200] = m.rows[17];
+        assert!(m.rank() <= 255);
+        assert!(!m.is_invertible());
+        assert!(m.invert().is_none());
+    }
+
+    #[test]
+    fn matrix256_mul_matches_naive() {
+        let mut rng = ChaCha20Rng::from_seed([33u8; 32]);
+        let random_dense = |rng: &mut ChaCha20Rng| {
+            let mut m = Matrix256::zero();
+            for row in m.rows.iter_mut() {
+                for word in row.iter_mut() {
+                    *word = rng.next_u64();
+                }
+            }
+            m
+        };
+        for _ in 0..16 {
+            let a = random_dense(&mut rng);
+            let b = random_dense(&mut rng);
+            assert_eq!(a.mul_four_russians(&b), a.mul_naive(&b));
+            assert_eq!(a.mul(&b), a.mul_naive(&b));
+            let sparse = Matrix256::random_sparse_unsplit(&mut rng);
+            assert_eq!(sparse.mul_four_russians(&a), sparse.mul_naive(&a));
+            assert_eq!(a.mul_four_russians(&sparse), a.mul_naive(&sparse));
+            assert_eq!(sparse.mul(&a), sparse.mul_naive(&a));
+        }
+        let a = random_dense(&mut rng);
+        assert_eq!(a.mul_four_russians(&Matrix256::identity()), a);
+        assert_eq!(Matrix256::identity().mul_four_russians(&a), a);
+        assert_eq!(a.mul_four_russians(&Matrix256::zero()), Matrix256::zero());
+    }
+
+    #[test]
+    fn matrix256_agrees_with_closures() {
+        let mut rng = ChaCha20Rng::from_seed([35u8; 32]);
+        let a = Matrix256::random_sparse_unsplit(&mut rng);
+        let b = Matrix256::random_invertible_dense(&mut rng);
+        let product = a.mul(&b);
+        let composed = |x: &DoubleBlock| a.apply_to_bytes(&b.apply_to_bytes(x));
+        assert!(product.agrees_with(composed, 64, &mut rng));
+        assert!(!b.mul(&a).agrees_with(composed, 64, &mut rng));
+        assert!(Matrix256::zero().agrees_with(|_| [0u8; 32], 64, &mut rng));
+        crate::assert_linear_map_eq!(|x| product.apply_to_bytes(x), composed);
+    }
+
+    #[test]
+    fn mul_banded_rhs_matches_naive() {
+        let mut rng = ChaCha20Rng::from_seed([34u8; 32]);
+        let dense = Matrix256::random_invertible_dense(&mut rng);
+        let banded = Matrix256::random_sparse_unsplit(&mut rng);
+        for rhs in [&banded, &Matrix256::identity(), &Matrix256::zero()] {
+            let sparse = SparseMatrix256::from_dense(rhs);
+            assert_eq!(dense.mul_banded_rhs(&sparse), Some(dense.mul_naive(rhs)));
+            assert_eq!(banded.mul_banded_rhs(&sparse), Some(banded.mul_naive(rhs)));
+        }
+
+        // Windows of the maximal width, starting mid-word and wrapping past block column 31.
+        let mut wide = SparseMatrix256::zero();
+        for row_block in 0..32 {
+            for offset in [3, 6, 10] {
+                wide.set_block(
+                    row_block,
+                    (row_block + offset) % 32,
+                    &Matrix8::random_invertible(&mut rng),
+                );
+            }
+        }
+        let wide_dense = wide.to_dense();
+        assert_eq!(
+            dense.mul_banded_rhs(&wide),
+            Some(dense.mul_naive(&wide_dense))
+        );
+
+        wide.set_block(5, 4, &Matrix8::identity());
+        assert_eq!(dense.mul_banded_rhs(&wide), None);
+        let layer = SparseMatrix256::from_dense(&crate::linear::mc_sr_matrix_256());
+        assert_eq!(dense.mul_banded_rhs(&layer), None);
+    }
+
+    #[test]
+    fn matrix256_block_constructors() {
+        let mut rng = ChaCha20Rng::from_seed([36u8; 32]);
+        let diag: [Matrix8; 32] = core::array::from_fn(|_| Matrix8::random_invertible(&mut rng));
+        let extra = Matrix8::random_invertible(&mut rng);
+
+        let mut expected = Matrix256::zero();
+        for (i, block) in diag.iter().enumerate() {
+            expected.set_block(i, i, block);
+        }
+        assert_eq!(Matrix256::block_diag32(&diag), expected);
+
+        let mut entries: Vec<_> = diag.iter().enumerate().map(|(i, b)| ((i, i), *b)).collect();
+        entries.push(((31, 0), extra));
+        expected.set_block(31, 0, &extra);
+        assert_eq!(Matrix256::from_blocks(&entries), expected);
+        entries.push(((31, 0), Matrix8::zero()));
+        expected.set_block(31, 0, &Matrix8::zero());
+        assert_eq!(Matrix256::from_blocks(&entries), expected);
+
+        assert_eq!(
+            Matrix256::block_diag32(&[Matrix8::identity(); 32]),
+            Matrix256::identity()
+        );
+        let dense = Matrix256::random_invertible_dense(&mut rng);
+        assert_eq!(
+            Matrix256::from_block_fn(|r, c| Some(dense.block(r, c))),
+            dense
+        );
+        let banded = Matrix256::random_sparse_unsplit(&mut rng);
+        let band = Matrix256::from_block_fn(|r, c| {
+            (c == r || c == (r + 1) % 32).then(|| banded.block(r, c))
+        });
+        assert_eq!(band, banded);
+        assert_eq!(Matrix256::from_block_fn(|_, _| None), Matrix256::zero());
+        assert_eq!(
+            Matrix256::from_matrix128_pair(&Matrix128::identity(), &Matrix128::identity()),
+            Matrix256::identity()
+        );
+    }
+
+    #[test]
+    fn from_matrix128_pair_acts_on_each_half() {
+        let low = Matrix128::from_linear_transform(|state| state.rotate_left(3));
+        let high =
+            Matrix128::from_linear_transform(|state| state.iter_mut().for_each(|b| *b ^= *b >> 1));
+        let pair = Matrix256::from_matrix128_pair(&low, &high);
+        let mut rng = ChaCha20Rng::from_seed([37u8; 32]);
+        for _ in 0..16 {
+            let mut input = [0u8; 32];
+            rng.fill_bytes(&mut input);
+            let (first, second) = aes_core::split_double(&input);
+            let expected =
+                aes_core::join_double(&low.apply_to_bytes(first), &high.apply_to_bytes(second));
+            assert_eq!(pair.apply_to_bytes(&input), expected);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "block index out of range")]
+    fn matrix256_set_block_rejects_out_of_range() {
+        Matrix256::zero().set_block(32, 0, &Matrix8::identity());
+    }
+
+    #[test]
+    #[should_panic(expected = "block index out of range")]
+    fn matrix256_from_blocks_rejects_out_of_range() {
+        Matrix256::from_blocks(&[((0, 32), Matrix8::identity())]);
+    }
+
+    #[test]
+    #[should_panic(expected = "block index out of range")]
+    fn matrix256_block_rejects_out_of_range() {
+        Matrix256::identity().block(40, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "block index out of range")]
+    fn matrix128_set_block_rejects_out_of_range() {
+        Matrix128::zero().set_block(0, 16, &Matrix8::identity());
+    }
+
+    #[test]
+    #[should_panic(expected = "block index out of range")]
+    fn matrix128_block_rejects_out_of_range() {
+        Matrix128::zero().block(16, 0);
+    }
+
+    #[test]
+    fn matrix128_blocks_roundtrip() {
+        let mut m = Matrix128::identity();
+        let block = Matrix8::gf256_mul(3);
+        m.set_block(4, 9, &block);
+        assert_eq!(m.block(4, 9), block);
+        assert_eq!(m.block(7, 7), Matrix8::identity());
+        assert_eq!(m.block(9, 4), Matrix8::zero());
+        m.set_block(7, 7, &Matrix8::zero());
+        assert_eq!(m.block(7, 7), Matrix8::zero());
+    }
+
+    #[test]
+    fn matrix128_sparse_structure_across_seeds() {
+        for seed in 0..50u8 {
+            let mut rng = ChaCha20Rng::from_seed([seed; 32]);
+            let m = Matrix128::random_sparse_unsplit(&mut rng);
+            assert_eq!(m.validate_banded_unsplit(), Ok(()), "seed {seed}");
+            assert!(m.is_invertible(), "seed {seed}");
+            for row_block in 0..16 {
+                for col_block in 0..16 {
+                    let allowed = col_block == row_block || col_block == (row_block + 1) % 16;
+                    if !allowed {
+                        assert_eq!(m.block(row_block, col_block), Matrix8::zero());
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn matrix128_validate_banded_unsplit_rejects_each_rule() {
+        let mut m = Matrix128::identity();
+        m.set_block(3, 4, &Matrix8::identity());
+        m.set_block(15, 0, &Matrix8::identity());
+        assert!(m.is_sparse_unsplit());
+
+        m.set_block(0, 15, &Matrix8::identity());
+        assert_eq!(
+            m.validate_banded_unsplit(),
+            Err(BandedStructureError::OffBandBlock {
+                row_block: 0,
+                col_block: 15
+            })
+        );
+        let mut m = Matrix128::identity();
+        m.set_block(5, 5, &Matrix8::zero());
+        assert_eq!(
+            m.validate_banded_unsplit(),
+            Err(BandedStructureError::SingularDiagonal { block: 5 })
+        );
+        assert!(!Matrix128::zero().is_sparse_unsplit());
+    }
+
+    #[test]
+    fn matrix128_inversion_roundtrip() {
+        let mut rng = ChaCha20Rng::from_seed([47u8; 32]);
+        let m = Matrix128::random_sparse_unsplit(&mut rng);
+        let inv = m.invert().expect("matrix must be invertible");
+        assert_eq!(m.mul(&inv), Matrix128::identity());
+        assert_eq!(inv.mul(&m), Matrix128::identity());
+
+        let mut input = [0u8; 16];
+        rng.fill_bytes(&mut input);
+        let mut state = input;
+        m.apply_in_place(&mut state);
+        assert_eq!(state, m.apply_to_bytes(&input));
+        inv.apply_in_place(&mut state);
+        assert_eq!(state, input);
+
+        let mut singular = m.clone();
+        singular.set_block(6, 6, &Matrix8::zero());
+        singular.set_block(6, 7, &Matrix8::zero());
+        assert!(!singular.is_invertible());
+        assert_eq!(singular.invert(), None);
+    }
+
+    #[test]
+    fn matrix128_mul_matches_sequential_application() {
+        let mut rng = ChaCha20Rng::from_seed([48u8; 32]);
+        let a = Matrix128::random_sparse_unsplit(&mut rng);
+        let b = Matrix128::from_linear_transform(|state| state.rotate_left(5));
+        let product = a.mul(&b);
+        for _ in 0..16 {
+            let mut input = [0u8; 16];
+            rng.fill_bytes(&mut input);
+            assert_eq!(
+                product.apply_to_bytes(&input),
+                a.apply_to_bytes(&b.apply_to_bytes(&input))
+            );
+        }
+    }
+
+    #[test]
+    fn matrix128_submatrix_byte_map_matches_direct_application() {
+        let mut rng = ChaCha20Rng::from_seed([49u8; 32]);
+        let m = Matrix128::random_sparse_unsplit(&mut rng);
+        for byte_index in [0usize, 7, 15] {
+            let map = m.submatrix_byte_map(byte_index);
+            for value in 0..=255u8 {
+                let mut input = [0u8; 16];
+                input[byte_index] = value;
+                assert_eq!(map[usize::from(value)], m.apply_to_bytes(&input));
+            }
+        }
+    }
+
+    #[test]
+    fn matrix256_ops_fit_in_a_small_stack() {
+        let worker = std::thread::Builder::new()
+            .stack_size(256 * 1024)
+            .spawn(|| {
+                let mut rng = ChaCha20Rng::from_seed([34u8; 32]);
+                let matrices: Vec<Matrix256> = (0..10)
+                    .map(|_| Matrix256::random_sparse_unsplit(&mut rng))
+                    .collect();
+                for m in &matrices {
+                    let inv = m.invert().expect("invertible");
+                    assert_eq!(m.mul(&inv), Matrix256::identity());
+                    assert_eq!(inv.mul_four_russians(m), Matrix256::identity());
+                    assert_eq!(inv.rank(), 256);
+                }
+                let a = crate::Affine256::random_sparse_unsplit(&mut rng);
+                let composed = a.compose(&a.invert().expect("invertible"));
+                assert_eq!(composed, crate::Affine256::identity());
+            })
+            .expect("spawn worker");
+        worker.join().expect("worker must not overflow its stack");
+    }
+
+    #[test]
+    fn matrix256_serializes_as_inline_rows() {
+        let mut rng = ChaCha20Rng::from_seed([35u8; 32]);
+        let m = Matrix256::random_sparse_unsplit(&mut rng);
+        let bytes = bincode::serialize(&m).expect("serialize");
+        let expected: Vec<u8> = m
+            .rows
+            .iter()
+            .flatten()
+            .flat_map(|word| word.to_le_bytes())
+            .collect();
+        assert_eq!(bytes, expected);
+        assert_eq!(bincode::deserialize::<Matrix256>(&bytes).unwrap(), m);
+        assert!(bincode::deserialize::<Matrix256>(&bytes[..bytes.len() - 8]).is_err());
+    }
+
+    #[test]
+    fn matrix256_solve_and_kernel() {
+        let mut rng = ChaCha20Rng::from_seed([39u8; 32]);
+        let m = Matrix256::random_sparse_unsplit(&mut rng);
+        let inv = m.invert().unwrap();
+        for _ in 0..8 {
+            let mut b = [0u8; 32];
+            rng.fill_bytes(&mut b);
+            assert_eq!(m.solve(&b), Some(inv.apply_to_bytes(&b)));
+        }
+        assert!(m.kernel_basis().is_empty());
+
+        let mut deficient = m.clone();
+        deficient.rows[3] = deficient.rows[100];
+        deficient.rows[7] = [0; 4];
+        deficient.rows[200] = deficient.rows[10];
+        let kernel = deficient.kernel_basis();
+        assert_eq!(kernel.len() as u32, 256 - deficient.rank());
+        assert!(kernel.len() >= 3);
+        for v in &kernel {
+            assert_ne!(v, &[0u8; 32]);
+            assert_eq!(deficient.apply_to_bytes(v), [0u8; 32]);
+        }
+        let mut x = [0u8; 32];
+        rng.fill_bytes(&mut x);
+        let b = deficient.apply_to_bytes(&x);
+        let solution = deficient.solve(&b).expect("image is solvable");
+        assert_eq!(deficient.apply_to_bytes(&solution), b);
+        let mut off_image = b;
+        off_image[0] ^= 1 << 7;
+        assert_eq!(deficient.solve(&off_image), None);
+        assert_eq!(Matrix256::zero().kernel_basis().len(), 256);
+    }
+
+    #[test]
+    fn random_invertible_dense_is_invertible_and_not_banded() {
+        let mut rng = ChaCha20Rng::from_seed([44u8; 32]);
+        let mut first_rows = Vec::new();
+        for _ in 0..8 {
+            let m = Matrix256::random_invertible_dense(&mut rng);
+            assert!(m.is_invertible());
+            assert!(m.validate_banded_unsplit().is_err());
+            let off_band = (0..32)
+                .flat_map(|r| (0..32).map(move |c| (r, c)))
+                .filter(|&(r, c)| c != r && c != (r + 1) % 32)
+                .filter(|&(r, c)| m.block(r, c) != Matrix8::zero())
+                .count();
+            assert!(off_band > 900, "{off_band} non-zero off-band blocks");
+            let ones: u32 = m.rows.iter().flatten().map(|w| w.count_ones()).sum();
+            assert!((24_000..41_000).contains(&ones), "{ones} ones");
+            first_rows.push(m.rows[0]);
+        }
+        first_rows.dedup();
+        assert_eq!(first_rows.len(), 8);
+    }
+
     #[test]
     fn matrix256_inversion_roundtrip() {
         let mut rng = ChaCha20Rng::from_seed([4u8; 32]);
@@ -579,6 +2401,171 @@ mod tests {
         }
     }
 
+    #[test]
+    fn apply_to_many_matches_single_application() {
+        let mut rng = ChaCha20Rng::from_seed([46u8; 32]);
+        let banded = Matrix256::random_sparse_unsplit(&mut rng);
+        let dense = Matrix256::random_invertible_dense(&mut rng);
+        let mut inputs: Vec<DoubleBlock> = (0..300)
+            .map(|_| {
+                let mut input = [0u8; 32];
+                rng.fill_bytes(&mut input);
+                input
+            })
+            .collect();
+        // Sparse inputs exercise the skipped byte positions.
+        inputs.push([0u8; 32]);
+        let mut single = [0u8; 32];
+        single[17] = 0xa5;
+        inputs.push(single);
+        for m in [&banded, &dense] {
+            let expected: Vec<_> = inputs.iter().map(|x| m.apply_to_bytes(x)).collect();
+            assert_eq!(m.apply_to_many(&inputs), expected);
+            assert_eq!(m.apply_to_many(&inputs[300..]), expected[300..]);
+        }
+        assert!(dense.apply_to_many(&[]).is_empty());
+    }
+
+    #[test]
+    fn matrix8_pow_order_and_minimal_polynomial() {
+        let identity = Matrix8::identity();
+        assert_eq!(identity.multiplicative_order(), Some(1));
+        assert_eq!(identity.minimal_polynomial(), 0b11);
+        assert_eq!(Matrix8::zero().multiplicative_order(), None);
+        assert_eq!(Matrix8::zero().minimal_polynomial(), 0b10);
+
+        // Multiplication by x is the companion matrix of the AES polynomial.
+        let companion = Matrix8::gf256_mul(0x02);
+        assert_eq!(companion.minimal_polynomial(), 0x11b);
+        let field_order = |g: u8| {
+            let (mut power, mut order) = (g, 1);
+            while power != 1 {
+                power = aes_core::gf256::mul(power, g);
+                order += 1;
+            }
+            order
+        };
+        assert_eq!(companion.multiplicative_order(), Some(field_order(0x02)));
+        assert_eq!(companion.multiplicative_order(), Some(51));
+        let generator = Matrix8::gf256_mul(0x03);
+        assert_eq!(generator.multiplicative_order(), Some(field_order(0x03)));
+        assert_eq!(generator.multiplicative_order(), Some(255));
+        assert_eq!(generator.pow(255), identity);
+        assert_eq!(generator.pow(85), Matrix8::gf256_mul(field_pow(0x03, 85)));
+
+        let mut rng = ChaCha20Rng::from_seed([65u8; 32]);
+        for _ in 0..32 {
+            let m = Matrix8::random_invertible(&mut rng);
+            let order = m.multiplicative_order().unwrap();
+            assert_eq!(m.pow(order), identity);
+            assert_eq!(m.pow(order + 1), m);
+            assert_eq!(m.pow(0), identity);
+            assert_eq!(m.pow(3), m.mul(&m).mul(&m));
+            // The minimal polynomial annihilates M and has degree at most 8.
+            let poly = m.minimal_polynomial();
+            assert!(poly < 0x200 && poly.leading_zeros() >= 7);
+            let value = (0..=8)
+                .filter(|i| (poly >> i) & 1 == 1)
+                .fold(Matrix8::zero(), |acc, i| acc.add(&m.pow(i)));
+            assert_eq!(value, Matrix8::zero());
+        }
+    }
+
+    fn field_pow(base: u8, exp: u32) -> u8 {
+        (0..exp).fold(1, |acc, _| aes_core::gf256::mul(acc, base))
+    }
+
+    #[test]
+    fn mul_into_variants_match_mul() {
+        let mut rng = ChaCha20Rng::from_seed([67u8; 32]);
+        let sparse = Matrix256::random_sparse_unsplit(&mut rng);
+        let dense = sparse.invert().unwrap();
+        let layer = crate::linear::mc_sr_matrix_256();
+        // Start from a non-zero matrix to check that stale contents are overwritten.
+        let mut out = Matrix256::random_invertible_dense(&mut rng);
+        for (a, b) in [(&sparse, &dense), (&dense, &sparse), (&dense, &layer)] {
+            a.mul_into(b, &mut out);
+            assert_eq!(out, a.mul_naive(b));
+        }
+    }
+
+    #[test]
+    fn matrix256_pow_matches_repeated_mul() {
+        let mut rng = ChaCha20Rng::from_seed([66u8; 32]);
+        let m = Matrix256::random_sparse_unsplit(&mut rng);
+        assert_eq!(m.pow(0), Matrix256::identity());
+        assert_eq!(m.pow(1), m);
+        let mut expected = Matrix256::identity();
+        for _ in 0..5 {
+            expected = expected.mul(&m);
+        }
+        assert_eq!(m.pow(5), expected);
+        let mc_sr = crate::linear::mc_sr_matrix_256();
+        assert_eq!(mc_sr.pow(12), mc_sr.pow(4).pow(3));
+        // Block-diagonal powers act blockwise: 3 generates GF(2^8)^*.
+        let diag = Matrix256::block_diag32(&[Matrix8::gf256_mul(3); 32]);
+        assert_eq!(diag.pow(255), Matrix256::identity());
+        assert_eq!(
+            diag.pow(17),
+            Matrix256::block_diag32(&[Matrix8::gf256_mul(field_pow(3, 17)); 32])
+        );
+    }
+
+    #[test]
+    fn matrix8_msb_first_rows() {
+        let msb_first = Matrix8::identity().to_bit_rows_msb_first();
+        assert_eq!(msb_first, [0x80, 0x40, 0x20, 0x10, 0x08, 0x04, 0x02, 0x01]);
+        // Multiplication by x: row 0 reads column 7 (the reduction bit), row 1 columns 0 and 7.
+        let times_x = Matrix8::gf256_mul(2).to_bit_rows_msb_first();
+        assert_eq!(format!("{:08b}", times_x[0]), "00000001");
+        assert_eq!(format!("{:08b}", times_x[1]), "10000001");
+        let mut rng = ChaCha20Rng::from_seed([63u8; 32]);
+        for _ in 0..16 {
+            let m = Matrix8::random(&mut rng);
+            assert_eq!(
+                Matrix8::from_bit_rows_msb_first(m.to_bit_rows_msb_first()),
+                m
+            );
+        }
+    }
+
+    #[test]
+    fn mc_sr_matrix_128_row_major_bits_roundtrip() {
+        let mc_sr = crate::linear::mc_sr_matrix_128();
+        let bits = mc_sr.to_row_major_bits();
+        assert_eq!(bits.len(), 128 * 128);
+        // Output bit 0 is bit 0 of 2·s0 ⊕ 3·s5 ⊕ s10 ⊕ s15.
+        let row0: Vec<usize> = (0..128).filter(|&col| bits[col] == 1).collect();
+        assert_eq!(row0, [7, 40, 47, 80, 120]);
+        assert_eq!(Matrix128::from_row_major_bits(&bits), Ok(mc_sr));
+
+        let wide = crate::linear::mc_sr_matrix_256();
+        assert_eq!(
+            Matrix256::from_row_major_bits(&wide.to_row_major_bits()),
+            Ok(wide)
+        );
+    }
+
+    #[test]
+    fn row_major_bits_reject_malformed_input() {
+        assert_eq!(
+            Matrix256::from_row_major_bits(&[0; 100]),
+            Err(BitLayoutError::Length {
+                expected: 65536,
+                actual: 100
+            })
+        );
+        let mut bits = Matrix128::identity().to_row_major_bits();
+        bits[300] = b'1';
+        assert_eq!(
+            Matrix128::from_row_major_bits(&bits),
+            Err(BitLayoutError::NotABit {
+                index: 300,
+                value: b'1'
+            })
+        );
+    }
+
     #[test]
     fn matrix128_from_linear_transform_identity() {
         let identity = Matrix128::from_linear_transform(|_| {});
@@ -0,0 +1,189 @@
+//! Byte permutations of 256-bit states.
+
+use aes_core::DoubleBlock;
+
+use crate::matrix::{Matrix256, Matrix8};
+
+/// Permutation of the 32 bytes of a double block, as a gather: `out[i] = in[map[i]]`.
+///
+/// Equivalent to a [`Matrix256`] whose only non-zero blocks are identities, one per block row
+/// and column, but applied in 32 byte moves.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Permutation256 {
+    map: [usize; 32],
+}
+
+impl Permutation256 {
+    /// Returns the identity permutation.
+    pub fn identity() -> Self {
+        Self {
+            map: core::array::from_fn(|i| i),
+        }
+    }
+
+    /// Wraps a gather map, or returns `None` if it is not a bijection on `0..32`.
+    pub fn new(map: [usize; 32]) -> Option<Self> {
+        let mut seen = [false; 32];
+        for &source in &map {
+            if source >= 32 || seen[source] {
+                return None;
+            }
+            seen[source] = true;
+        }
+        Some(Self { map })
+    }
+
+    /// Applies the 16-byte gather `half` to both halves independently.
+    pub fn from_halves(half: &[usize; 16]) -> Option<Self> {
+        Self::new(core::array::from_fn(|i| half[i % 16] + 16 * (i / 16)))
+    }
+
+    /// Returns the gather map.
+    pub fn map(&self) -> &[usize; 32] {
+        &self.map
+    }
+
+    /// Applies the permutation.
+    pub fn apply(&self, input: &DoubleBlock) -> DoubleBlock {
+        core::array::from_fn(|i| input[self.map[i]])
+    }
+
+    /// Returns the inverse permutation.
+    pub fn invert(&self) -> Self {
+        let mut map = [0usize; 32];
+        for (out, &source) in self.map.iter().enumerate() {
+            map[source] = out;
+        }
+        Self { map }
+    }
+
+    /// Composes `self` after `other` (i.e., `self ∘ other`).
+    pub fn compose(&self, other: &Self) -> Self {
+        Self {
+            map: core::array::from_fn(|i| other.map[self.map[i]]),
+        }
+    }
+
+    /// Returns the equivalent permutation matrix.
+    pub fn to_matrix256(&self) -> Matrix256 {
+        let blocks: Vec<_> = self
+            .map
+            .iter()
+            .enumerate()
+            .map(|(out, &source)| ((out, source), Matrix8::identity()))
+            .collect();
+        Matrix256::from_blocks(&blocks)
+    }
+}
+
+impl Matrix256 {
+    /// Returns the byte permutation this matrix performs, if it is one.
+    ///
+    /// That is the case when every block row holds exactly one non-zero block, that block is
+    /// the identity, and no two rows read the same block column.
+    pub fn try_into_permutation(&self) -> Option<Permutation256> {
+        let mut map = [0usize; 32];
+        for (row_block, slot) in map.iter_mut().enumerate() {
+            let mut source = None;
+            for col_block in 0..32 {
+                let block = self.block(row_block, col_block);
+                if block == Matrix8::zero() {
+                    continue;
+                }
+                if block != Matrix8::identity() || source.is_some() {
+                    return None;
+                }
+                source = Some(col_block);
+            }
+            *slot = source?;
+        }
+        Permutation256::new(map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linear::{mc_sr_matrix_256, sr_matrix_256};
+    use aes_core::round::shift_rows;
+    use aes_core::Block;
+    use rand::{RngCore, SeedableRng};
+    use rand_chacha::ChaCha20Rng;
+
+    fn random_permutation(rng: &mut ChaCha20Rng) -> Permutation256 {
+        let mut map: [usize; 32] = core::array::from_fn(|i| i);
+        for i in (1..32).rev() {
+            map.swap(i, rng.next_u32() as usize % (i + 1));
+        }
+        Permutation256::new(map).expect("shuffled identity")
+    }
+
+    #[test]
+    fn matrix_round_trip_preserves_behaviour() {
+        let mut rng = ChaCha20Rng::from_seed([50u8; 32]);
+        for _ in 0..16 {
+            let perm = random_permutation(&mut rng);
+            let matrix = perm.to_matrix256();
+            assert_eq!(matrix.try_into_permutation(), Some(perm));
+            let mut value = [0u8; 32];
+            rng.fill_bytes(&mut value);
+            assert_eq!(perm.apply(&value), matrix.apply_to_bytes(&value));
+            assert_eq!(perm.invert().apply(&perm.apply(&value)), value);
+        }
+    }
+
+    #[test]
+    fn compose_matches_sequential_application() {
+        let mut rng = ChaCha20Rng::from_seed([51u8; 32]);
+        let a = random_permutation(&mut rng);
+        let b = random_permutation(&mut rng);
+        let mut value = [0u8; 32];
+        rng.fill_bytes(&mut value);
+        assert_eq!(a.compose(&b).apply(&value), a.apply(&b.apply(&value)));
+        assert_eq!(a.compose(&a.invert()), Permutation256::identity());
+        assert_eq!(
+            a.compose(&b).to_matrix256(),
+            a.to_matrix256().mul(&b.to_matrix256())
+        );
+    }
+
+    #[test]
+    fn shift_rows_matrix_is_a_permutation() {
+        let perm = sr_matrix_256()
+            .try_into_permutation()
+            .expect("SR is a byte permutation");
+        assert_eq!(
+            Permutation256::from_halves(&aes_core::linear::shift_rows_permutation()),
+            Some(perm)
+        );
+        let mut rng = ChaCha20Rng::from_seed([52u8; 32]);
+        for _ in 0..16 {
+            let mut value = [0u8; 32];
+            rng.fill_bytes(&mut value);
+            let mut expected = value;
+            for half in expected.chunks_exact_mut(16) {
+                let block: &mut Block = half.try_into().unwrap();
+                shift_rows(block);
+            }
+            assert_eq!(perm.apply(&value), expected);
+        }
+    }
+
+    #[test]
+    fn non_permutations_are_rejected() {
+        assert!(mc_sr_matrix_256().try_into_permutation().is_none());
+        assert!(Matrix256::zero().try_into_permutation().is_none());
+        let mut duplicated = Matrix256::identity();
+        duplicated.set_block(1, 1, &Matrix8::zero());
+        duplicated.set_block(1, 0, &Matrix8::identity());
+        assert!(duplicated.try_into_permutation().is_none());
+        let mut scaled = Matrix256::identity();
+        scaled.set_block(5, 5, &Matrix8::gf256_mul(2));
+        assert!(scaled.try_into_permutation().is_none());
+
+        assert!(Permutation256::new([0; 32]).is_none());
+        let mut out_of_range: [usize; 32] = core::array::from_fn(|i| i);
+        out_of_range[31] = 32;
+        assert!(Permutation256::new(out_of_range).is_none());
+    }
+}
@@ -0,0 +1,136 @@
+//! 256-bit vector kernels shared by the matrix code and the table builder.
+//!
+//! With the `simd` feature these are implemented on `wide` lanes (`u64x4`, `u8x32`); without
+//! it, on plain words and bytes. Both paths are bit-for-bit identical.
+
+use aes_core::DoubleBlock;
+
+#[cfg(not(feature = "simd"))]
+pub(crate) use scalar::{row_parity, table_entry, xor32_in_place, xor_words};
+#[cfg(feature = "simd")]
+pub(crate) use vector::{row_parity, table_entry, xor32_in_place, xor_words};
+
+/// Word- and byte-wise reference implementations.
+#[cfg_attr(feature = "simd", allow(dead_code))]
+mod scalar {
+    use super::DoubleBlock;
+
+    /// XORs `rhs` into `dst`.
+    #[inline]
+    pub(crate) fn xor32_in_place(dst: &mut DoubleBlock, rhs: &DoubleBlock) {
+        aes_core::xor32_in_place(dst, rhs);
+    }
+
+    /// XORs the four words of `rhs` into `dst`.
+    #[inline]
+    pub(crate) fn xor_words(dst: &mut [u64; 4], rhs: &[u64; 4]) {
+        for (d, r) in dst.iter_mut().zip(rhs.iter()) {
+            *d ^= *r;
+        }
+    }
+
+    /// Returns the parity of `row · input` over GF(2).
+    #[inline]
+    pub(crate) fn row_parity(row: &[u64; 4], input: &[u64; 4]) -> bool {
+        crate::gf2::parity_u64x4(&core::array::from_fn(|seg| row[seg] & input[seg]))
+    }
+
+    /// Returns `map ^ left ^ right`, one round-table entry.
+    #[inline]
+    pub(crate) fn table_entry(
+        map: &DoubleBlock,
+        left: &DoubleBlock,
+        right: &DoubleBlock,
+    ) -> DoubleBlock {
+        let word = |block: &DoubleBlock, k: usize| {
+            u128::from_ne_bytes(block[16 * k..16 * k + 16].try_into().expect("16-byte word"))
+        };
+        let mut entry = [0u8; 32];
+        for (k, out) in entry.chunks_exact_mut(16).enumerate() {
+            out.copy_from_slice(&(word(map, k) ^ word(left, k) ^ word(right, k)).to_ne_bytes());
+        }
+        entry
+    }
+}
+
+/// `wide` lane implementations.
+#[cfg(feature = "simd")]
+mod vector {
+    use wide::{u64x4, u8x32};
+
+    use super::DoubleBlock;
+
+    /// XORs `rhs` into `dst`.
+    #[inline]
+    pub(crate) fn xor32_in_place(dst: &mut DoubleBlock, rhs: &DoubleBlock) {
+        *dst = (u8x32::from(*dst) ^ u8x32::from(*rhs)).to_array();
+    }
+
+    /// XORs the four words of `rhs` into `dst`.
+    #[inline]
+    pub(crate) fn xor_words(dst: &mut [u64; 4], rhs: &[u64; 4]) {
+        *dst = (u64x4::from(*dst) ^ u64x4::from(*rhs)).to_array();
+    }
+
+    /// Returns the parity of `row · input` over GF(2).
+    #[inline]
+    pub(crate) fn row_parity(row: &[u64; 4], input: &[u64; 4]) -> bool {
+        crate::gf2::parity_u64x4(&(u64x4::from(*row) & u64x4::from(*input)).to_array())
+    }
+
+    /// Returns `map ^ left ^ right`, one round-table entry.
+    #[inline]
+    pub(crate) fn table_entry(
+        map: &DoubleBlock,
+        left: &DoubleBlock,
+        right: &DoubleBlock,
+    ) -> DoubleBlock {
+        (u8x32::from(*map) ^ u8x32::from(*left) ^ u8x32::from(*right)).to_array()
+    }
+}
+
+#[cfg(all(test, feature = "simd"))]
+mod tests {
+    use super::{scalar, vector};
+    use rand::{Rng, RngCore, SeedableRng};
+    use rand_chacha::ChaCha20Rng;
+
+    fn random_block(rng: &mut ChaCha20Rng) -> [u8; 32] {
+        let mut block = [0u8; 32];
+        rng.fill_bytes(&mut block);
+        block
+    }
+
+    #[test]
+    fn byte_kernels_match_scalar() {
+        let mut rng = ChaCha20Rng::from_seed([60u8; 32]);
+        for _ in 0..256 {
+            let [a, b, c] = core::array::from_fn(|_| random_block(&mut rng));
+            let (mut lhs, mut rhs) = (a, a);
+            scalar::xor32_in_place(&mut lhs, &b);
+            vector::xor32_in_place(&mut rhs, &b);
+            assert_eq!(lhs, rhs);
+            assert_eq!(
+                scalar::table_entry(&a, &b, &c),
+                vector::table_entry(&a, &b, &c)
+            );
+        }
+    }
+
+    #[test]
+    fn word_kernels_match_scalar() {
+        let mut rng = ChaCha20Rng::from_seed([61u8; 32]);
+        for _ in 0..256 {
+            let row: [u64; 4] = rng.gen();
+            let input: [u64; 4] = rng.gen();
+            assert_eq!(
+                scalar::row_parity(&row, &input),
+                vector::row_parity(&row, &input)
+            );
+            let (mut lhs, mut rhs) = (row, row);
+            scalar::xor_words(&mut lhs, &input);
+            vector::xor_words(&mut rhs, &input);
+            assert_eq!(lhs, rhs);
+        }
+    }
+}
@@ -0,0 +1,187 @@
+//! Block-sparse storage for 256×256 matrices built from 8×8 blocks.
+
+use std::collections::BTreeMap;
+
+use aes_core::DoubleBlock;
+
+use crate::matrix::{Matrix256, Matrix8};
+
+/// Above this many block products, [`SparseMatrix256::mul`] multiplies densely instead.
+const DENSE_MUL_THRESHOLD: usize = 2048;
+
+/// 256×256 GF(2) matrix stored as its non-zero 8×8 blocks.
+///
+/// A sparse unsplit matrix has at most 64 non-zero blocks (diagonal, super-diagonal and
+/// wrap), against 1024 for a dense one. Zero blocks are never stored.
+///
+/// The generator and the instance format do not use it yet: the generator only reads single
+/// blocks of `A^(r)`, which [`Matrix256::block`] already provides, and the stored input
+/// encoding `A^(1)⁻¹ ∘ Min ∘ ARK` is dense, so instance files keep their size.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SparseMatrix256 {
+    blocks: BTreeMap<(usize, usize), Matrix8>,
+}
+
+impl SparseMatrix256 {
+    /// Returns the zero matrix.
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    /// Returns the identity matrix.
+    pub fn identity() -> Self {
+        let mut mat = Self::zero();
+        for block in 0..32 {
+            mat.set_block(block, block, &Matrix8::identity());
+        }
+        mat
+    }
+
+    /// Collects the non-zero blocks of a dense matrix.
+    pub fn from_dense(dense: &Matrix256) -> Self {
+        let mut mat = Self::zero();
+        for row_block in 0..32 {
+            for col_block in 0..32 {
+                mat.set_block(row_block, col_block, &dense.block(row_block, col_block));
+            }
+        }
+        mat
+    }
+
+    /// Expands into a dense matrix.
+    pub fn to_dense(&self) -> Matrix256 {
+        Matrix256::from_block_fn(|row_block, col_block| {
+            self.blocks.get(&(row_block, col_block)).copied()
+        })
+    }
+
+    /// Returns the 8×8 block at `(row_block, col_block)`.
+    pub fn block(&self, row_block: usize, col_block: usize) -> Matrix8 {
+        assert!(row_block < 32 && col_block < 32, "block index out of range");
+        self.blocks
+            .get(&(row_block, col_block))
+            .copied()
+            .unwrap_or_else(Matrix8::zero)
+    }
+
+    /// Sets the 8×8 block at `(row_block, col_block)`; a zero block is removed.
+    pub fn set_block(&mut self, row_block: usize, col_block: usize, block: &Matrix8) {
+        assert!(row_block < 32 && col_block < 32, "block index out of range");
+        if *block == Matrix8::zero() {
+            self.blocks.remove(&(row_block, col_block));
+        } else {
+            self.blocks.insert((row_block, col_block), *block);
+        }
+    }
+
+    /// Iterates over the non-zero blocks in row-major block order.
+    pub(crate) fn nonzero_blocks(&self) -> impl Iterator<Item = ((usize, usize), &Matrix8)> {
+        self.blocks.iter().map(|(&coords, block)| (coords, block))
+    }
+
+    /// Returns the number of stored (non-zero) blocks.
+    pub fn block_count(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// Applies the matrix to a 256-bit vector represented as 32 bytes.
+    pub fn apply_to_bytes(&self, input: &DoubleBlock) -> DoubleBlock {
+        let mut out = [0u8; 32];
+        for (&(row_block, col_block), block) in &self.blocks {
+            out[row_block] ^= block.apply(input[col_block]);
+        }
+        out
+    }
+
+    /// Multiplies two matrices (`self * rhs`).
+    ///
+    /// The product is formed block by block unless that would take more than a few thousand
+    /// 8×8 products, in which case both sides are densified and multiplied as [`Matrix256`].
+    pub fn mul(&self, rhs: &Self) -> Self {
+        let mut rhs_rows = [0usize; 32];
+        for &(row_block, _) in rhs.blocks.keys() {
+            rhs_rows[row_block] += 1;
+        }
+        let products: usize = self.blocks.keys().map(|&(_, k)| rhs_rows[k]).sum();
+        if products > DENSE_MUL_THRESHOLD {
+            return Self::from_dense(&self.to_dense().mul(&rhs.to_dense()));
+        }
+
+        let mut result = Self::zero();
+        for (&(row_block, k), left) in &self.blocks {
+            for (&(_, col_block), right) in rhs.blocks.range((k, 0)..(k + 1, 0)) {
+                let sum = result.block(row_block, col_block).add(&left.mul(right));
+                result.set_block(row_block, col_block, &sum);
+            }
+        }
+        result
+    }
+
+    /// Inverts the matrix densely; the inverse of a banded matrix is generally dense.
+    pub fn invert(&self) -> Option<Matrix256> {
+        self.to_dense().invert()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{RngCore, SeedableRng};
+    use rand_chacha::ChaCha20Rng;
+
+    #[test]
+    fn dense_roundtrip_keeps_only_band_blocks() {
+        let mut rng = ChaCha20Rng::from_seed([40u8; 32]);
+        let dense = Matrix256::random_sparse_unsplit(&mut rng);
+        let sparse = SparseMatrix256::from_dense(&dense);
+        assert!(sparse.block_count() <= 64);
+        assert_eq!(sparse.to_dense(), dense);
+        assert_eq!(
+            SparseMatrix256::identity().to_dense(),
+            Matrix256::identity()
+        );
+        assert_eq!(SparseMatrix256::zero().block_count(), 0);
+    }
+
+    #[test]
+    fn apply_matches_dense() {
+        let mut rng = ChaCha20Rng::from_seed([41u8; 32]);
+        for _ in 0..8 {
+            let dense = Matrix256::random_sparse_unsplit(&mut rng);
+            let sparse = SparseMatrix256::from_dense(&dense);
+            let mut value = [0u8; 32];
+            rng.fill_bytes(&mut value);
+            assert_eq!(sparse.apply_to_bytes(&value), dense.apply_to_bytes(&value));
+        }
+    }
+
+    #[test]
+    fn mul_matches_dense_on_both_paths() {
+        let mut rng = ChaCha20Rng::from_seed([42u8; 32]);
+        let dense_a = Matrix256::random_sparse_unsplit(&mut rng);
+        let dense_b = Matrix256::random_sparse_unsplit(&mut rng);
+        let a = SparseMatrix256::from_dense(&dense_a);
+        let b = SparseMatrix256::from_dense(&dense_b);
+
+        // Banded times banded stays sparse: diagonal plus two super-diagonals.
+        let product = a.mul(&b);
+        assert!(product.block_count() <= 96);
+        assert_eq!(product.to_dense(), dense_a.mul(&dense_b));
+
+        // Mixed and fully dense operands; the latter takes the densified path.
+        let dense_inv = dense_a.invert().unwrap();
+        let inverse = SparseMatrix256::from_dense(&dense_inv);
+        assert_eq!(inverse.mul(&a), SparseMatrix256::identity());
+        assert_eq!(inverse.mul(&b).to_dense(), dense_inv.mul(&dense_b));
+        assert_eq!(inverse.mul(&inverse).to_dense(), dense_inv.mul(&dense_inv));
+    }
+
+    #[test]
+    fn invert_matches_dense() {
+        let mut rng = ChaCha20Rng::from_seed([43u8; 32]);
+        let dense = Matrix256::random_sparse_unsplit(&mut rng);
+        let sparse = SparseMatrix256::from_dense(&dense);
+        assert_eq!(sparse.invert(), dense.invert());
+        assert!(SparseMatrix256::zero().invert().is_none());
+    }
+}
@@ -0,0 +1,38 @@
+//! Sampling and timing instrumentation for instance generation.
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// Counters and timings collected by the `*_with_stats` samplers and
+/// [`crate::Generator::generate_instance_with_stats`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GenStats {
+    /// Sampler counters; deterministic for a fixed seed.
+    pub counts: SamplerCounts,
+    /// Time spent sampling and composing the encodings.
+    pub encodings: Duration,
+    /// Time spent building each round's tables, in round order.
+    pub rounds: Vec<Duration>,
+    /// Wall time of the whole generation.
+    pub total: Duration,
+}
+
+/// Attempt and success counts of the rejection samplers.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SamplerCounts {
+    /// Calls to `Matrix8::random_invertible`, which is invertible by construction and never
+    /// rejects.
+    pub matrix8_invertible: u64,
+    /// Wrap blocks drawn while closing a band in `random_sparse_unsplit`.
+    pub band_wrap_attempts: u64,
+    /// Wrap blocks accepted, one per banded matrix.
+    pub band_wrap_successes: u64,
+}
+
+impl SamplerCounts {
+    /// Returns the number of rejected wrap blocks.
+    pub fn band_wrap_rejections(&self) -> u64 {
+        self.band_wrap_attempts - self.band_wrap_successes
+    }
+}
@@ -1,42 +1,133 @@
 //! Table representations for white-box AES rounds.
 
+use core::fmt;
+
 use rand::{CryptoRng, RngCore};
-use serde::{Deserialize, Serialize};
+use serde::de::{Error as _, SeqAccess, Visitor};
+use serde::ser::SerializeTuple;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use zeroize::Zeroize;
+
+use aes_core::Block;
+
+use crate::fingerprint::serialized_sha256;
+use crate::vec256::Vec256;
 
-const ENTRY_BYTES: usize = 32;
 const ENTRIES: usize = 1 << 16;
 
-/// A 16→256-bit table `(x, y) ∈ u8 × u8 → 256-bit value`.
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
-pub struct Table16x256 {
+/// A table `(x, y) ∈ u8 × u8 → BYTES-byte value`.
+///
+/// Serializes as one byte string (2 MiB for 256-bit entries), so formats with a native bytes
+/// type read it in bulk.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Table16<const BYTES: usize> {
     data: Box<[u8]>,
 }
 
-impl Table16x256 {
+/// A 16→256-bit table, as used by [`WbInstance256`](crate::WbInstance256).
+pub type Table16x256 = Table16<32>;
+
+/// A 16→128-bit table, as used by [`WbInstance128`](crate::WbInstance128).
+pub type Table16x128 = Table16<16>;
+
+impl<const BYTES: usize> Table16<BYTES> {
     /// Allocates a zeroed table.
     pub fn new_zeroed() -> Self {
         Self {
-            data: vec![0u8; ENTRIES * ENTRY_BYTES].into_boxed_slice(),
+            data: vec![0u8; ENTRIES * BYTES].into_boxed_slice(),
         }
     }
 
+    fn entry(&self, x: u8, y: u8) -> &[u8] {
+        let start = entry_index(x, y) * BYTES;
+        &self.data[start..start + BYTES]
+    }
+
+    fn entry_mut(&mut self, x: u8, y: u8) -> &mut [u8] {
+        let start = entry_index(x, y) * BYTES;
+        &mut self.data[start..start + BYTES]
+    }
+}
+
+impl Table16x256 {
     /// Writes the entry for `(x, y)`.
-    pub fn set(&mut self, x: u8, y: u8, value: &[u8; 32]) {
-        let idx = entry_index(x, y);
-        let start = idx * ENTRY_BYTES;
-        self.data[start..start + ENTRY_BYTES].copy_from_slice(value);
+    pub fn set(&mut self, x: u8, y: u8, value: &Vec256) {
+        self.entry_mut(x, y).copy_from_slice(value.as_bytes());
     }
 
     /// Reads the entry for `(x, y)`.
-    pub fn get(&self, x: u8, y: u8) -> [u8; 32] {
-        let idx = entry_index(x, y);
-        let start = idx * ENTRY_BYTES;
+    pub fn get(&self, x: u8, y: u8) -> Vec256 {
         let mut out = [0u8; 32];
-        out.copy_from_slice(&self.data[start..start + ENTRY_BYTES]);
+        out.copy_from_slice(self.entry(x, y));
+        out.into()
+    }
+}
+
+impl Table16x128 {
+    /// Writes the entry for `(x, y)`.
+    pub fn set(&mut self, x: u8, y: u8, value: &Block) {
+        self.entry_mut(x, y).copy_from_slice(value);
+    }
+
+    /// Reads the entry for `(x, y)`.
+    pub fn get(&self, x: u8, y: u8) -> Block {
+        let mut out = [0u8; 16];
+        out.copy_from_slice(self.entry(x, y));
         out
     }
 }
 
+impl<const BYTES: usize> Serialize for Table16<BYTES> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&self.data)
+    }
+}
+
+impl<'de, const BYTES: usize> Deserialize<'de> for Table16<BYTES> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct DataVisitor<const BYTES: usize>;
+
+        impl<const BYTES: usize> DataVisitor<BYTES> {
+            fn checked<E: serde::de::Error>(data: Box<[u8]>) -> Result<Table16<BYTES>, E> {
+                if data.len() != ENTRIES * BYTES {
+                    return Err(E::invalid_length(data.len(), &DataVisitor::<BYTES>));
+                }
+                Ok(Table16 { data })
+            }
+        }
+
+        impl<'de, const BYTES: usize> Visitor<'de> for DataVisitor<BYTES> {
+            type Value = Table16<BYTES>;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{} table bytes", ENTRIES * BYTES)
+            }
+
+            fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+                Self::checked(v.into())
+            }
+
+            fn visit_byte_buf<E: serde::de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+                Self::checked(v.into_boxed_slice())
+            }
+
+            // Self-describing formats without a bytes type (JSON) hand back a sequence.
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut data = Vec::with_capacity(ENTRIES * BYTES);
+                while let Some(byte) = seq.next_element()? {
+                    if data.len() == ENTRIES * BYTES {
+                        return Err(A::Error::invalid_length(data.len() + 1, &self));
+                    }
+                    data.push(byte);
+                }
+                Self::checked(data.into_boxed_slice())
+            }
+        }
+
+        deserializer.deserialize_byte_buf(DataVisitor::<BYTES>)
+    }
+}
+
 /// Collection of 32 tables for one round.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct RoundTables {
@@ -51,6 +142,11 @@ impl RoundTables {
             tables: std::array::from_fn(|_| Table16x256::new_zeroed()),
         }
     }
+
+    /// Returns the SHA-256 of the serialized round, a stable identifier across runs.
+    pub fn fingerprint(&self) -> [u8; 32] {
+        serialized_sha256(self)
+    }
 }
 
 impl Default for RoundTables {
@@ -59,29 +155,107 @@ impl Default for RoundTables {
     }
 }
 
+/// Collection of 16 tables for one round of a single-block instance.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoundTables128 {
+    /// The 16 tables for the round, indexed by byte position.
+    pub tables: [Table16x128; 16],
+}
+
+impl RoundTables128 {
+    /// Allocates zeroed tables for the round.
+    pub fn new_zeroed() -> Self {
+        Self {
+            tables: std::array::from_fn(|_| Table16x128::new_zeroed()),
+        }
+    }
+
+    /// Returns the SHA-256 of the serialized round, a stable identifier across runs.
+    pub fn fingerprint(&self) -> [u8; 32] {
+        serialized_sha256(self)
+    }
+}
+
+impl Default for RoundTables128 {
+    fn default() -> Self {
+        Self::new_zeroed()
+    }
+}
+
 /// Random mask table `h_i: u8 -> 256-bit`.
-#[derive(Clone, Debug)]
+///
+/// Serializes as its 256 masks in input order, without a length prefix.
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct HTable {
-    data: [[u8; 32]; 256],
+    data: [Vec256; 256],
 }
 
 impl HTable {
+    /// An all-zero mask table.
+    pub(crate) fn zeroed() -> Self {
+        Self {
+            data: [Vec256::ZERO; 256],
+        }
+    }
+
     /// Generates a random mask table.
     pub fn random<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
-        let mut data = [[0u8; 32]; 256];
-        for entry in data.iter_mut() {
-            rng.fill_bytes(entry);
+        Self {
+            data: core::array::from_fn(|_| Vec256::random(rng)),
         }
-        Self { data }
     }
 
     /// Returns the mask for input `x`.
     #[inline]
-    pub fn get(&self, x: u8) -> &[u8; 32] {
+    pub fn get(&self, x: u8) -> &Vec256 {
         &self.data[x as usize]
     }
 }
 
+impl Zeroize for HTable {
+    fn zeroize(&mut self) {
+        for mask in self.data.iter_mut() {
+            mask.zeroize();
+        }
+    }
+}
+
+impl Serialize for HTable {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut tuple = serializer.serialize_tuple(self.data.len())?;
+        for mask in &self.data {
+            tuple.serialize_element(mask)?;
+        }
+        tuple.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for HTable {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct MasksVisitor;
+
+        impl<'de> Visitor<'de> for MasksVisitor {
+            type Value = HTable;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "256 masks")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut data = [Vec256::ZERO; 256];
+                for (index, mask) in data.iter_mut().enumerate() {
+                    *mask = seq
+                        .next_element()?
+                        .ok_or_else(|| A::Error::invalid_length(index, &self))?;
+                }
+                Ok(HTable { data })
+            }
+        }
+
+        deserializer.deserialize_tuple(256, MasksVisitor)
+    }
+}
+
 #[inline]
 const fn entry_index(x: u8, y: u8) -> usize {
     ((x as usize) << 8) | y as usize
@@ -91,21 +265,67 @@ const fn entry_index(x: u8, y: u8) -> usize {
 mod tests {
     use super::*;
 
+    #[test]
+    fn table_serializes_as_length_prefixed_bytes() {
+        let mut table = Table16x256::new_zeroed();
+        table.set(3, 4, &Vec256::new([0x5a; 32]));
+        let bytes = bincode::serialize(&table).expect("serialize");
+        assert_eq!(bytes.len(), 8 + ENTRIES * 32);
+        assert_eq!(bytes[..8], ((ENTRIES * 32) as u64).to_le_bytes());
+        assert_eq!(bytes[8..], *table.data);
+        assert_eq!(bincode::deserialize::<Table16x256>(&bytes).unwrap(), table);
+
+        let short = bincode::serialize(&vec![0u8; ENTRIES * 32 - 1]).unwrap();
+        assert!(bincode::deserialize::<Table16x256>(&short).is_err());
+        // A 128-bit table is the right length for neither width but its own.
+        let narrow = bincode::serialize(&Table16x128::new_zeroed()).unwrap();
+        assert!(bincode::deserialize::<Table16x256>(&narrow).is_err());
+    }
+
     #[test]
     fn table_roundtrip() {
         let mut table = Table16x256::new_zeroed();
-        let mut value = [0u8; 32];
-        value[0] = 0xaa;
-        value[31] = 0x55;
+        let mut bytes = [0u8; 32];
+        bytes[0] = 0xaa;
+        bytes[31] = 0x55;
+        let value = Vec256::from(bytes);
         table.set(1, 2, &value);
         assert_eq!(table.get(1, 2), value);
-        assert_eq!(table.get(0, 0), [0u8; 32]);
+        assert_eq!(table.get(0, 0), Vec256::ZERO);
+    }
+
+    #[test]
+    fn table128_roundtrip() {
+        let mut table = Table16x128::new_zeroed();
+        let value: Block = core::array::from_fn(|i| i as u8 ^ 0xc3);
+        table.set(255, 7, &value);
+        assert_eq!(table.get(255, 7), value);
+        assert_eq!(table.get(7, 255), [0u8; 16]);
+        let bytes = bincode::serialize(&table).expect("serialize");
+        assert_eq!(bytes.len(), 8 + ENTRIES * 16);
+        assert_eq!(bincode::deserialize::<Table16x128>(&bytes).unwrap(), table);
+    }
+
+    #[test]
+    fn htable_serializes_as_its_masks() {
+        use rand::SeedableRng;
+
+        let table = HTable::random(&mut rand_chacha::ChaCha20Rng::from_seed([61u8; 32]));
+        let bytes = bincode::serialize(&table).expect("serialize");
+        assert_eq!(bytes.len(), 256 * 32);
+        assert_eq!(bytes[32 * 7..32 * 8], *table.get(7).as_bytes());
+        assert_eq!(bincode::deserialize::<HTable>(&bytes).unwrap(), table);
+        assert!(bincode::deserialize::<HTable>(&bytes[..bytes.len() - 1]).is_err());
+
+        let mut table = table;
+        table.zeroize();
+        assert!((0..=255u8).all(|x| table.get(x).is_zero()));
     }
 
     #[test]
     fn roundtables_initially_zero() {
         let round = RoundTables::new_zeroed();
-        assert_eq!(round.tables[0].get(0, 0), [0u8; 32]);
-        assert_eq!(round.tables[31].get(255, 255), [0u8; 32]);
+        assert_eq!(round.tables[0].get(0, 0), Vec256::ZERO);
+        assert_eq!(round.tables[31].get(255, 255), Vec256::ZERO);
     }
 }
@@ -0,0 +1,211 @@
+//! 256-bit GF(2) vectors.
+
+use core::fmt;
+
+use aes_core::{double_block_hex, DoubleBlock};
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroize;
+
+use crate::matrix::{check_bits, BitLayoutError};
+use crate::{gf2, simd};
+
+/// 256-bit GF(2) vector: an encoded state, a bias, or a mask.
+///
+/// Bit `i` is bit `i % 8` (LSB first) of byte `i / 8`, matching [`crate::Matrix256`] rows and
+/// columns. Serializes exactly like the underlying `[u8; 32]`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Vec256(DoubleBlock);
+
+impl Vec256 {
+    /// The zero vector.
+    pub const ZERO: Self = Self([0u8; 32]);
+
+    /// Wraps 32 bytes.
+    pub const fn new(bytes: DoubleBlock) -> Self {
+        Self(bytes)
+    }
+
+    /// Draws a uniformly random vector.
+    pub fn random<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
+        let mut bytes = [0u8; 32];
+        rng.fill_bytes(&mut bytes);
+        Self(bytes)
+    }
+
+    /// Borrows the bytes.
+    pub fn as_bytes(&self) -> &DoubleBlock {
+        &self.0
+    }
+
+    /// Returns the bytes.
+    pub fn to_bytes(self) -> DoubleBlock {
+        self.0
+    }
+
+    /// Returns `self ⊕ rhs`.
+    #[must_use]
+    pub fn xor(&self, rhs: &Self) -> Self {
+        let mut out = *self;
+        out.xor_in_place(rhs);
+        out
+    }
+
+    /// XORs `rhs` into `self`.
+    pub fn xor_in_place(&mut self, rhs: &Self) {
+        simd::xor32_in_place(&mut self.0, &rhs.0);
+    }
+
+    /// Returns the inner product `self · rhs` over GF(2), the parity of `self & rhs`.
+    pub fn parity_and(&self, rhs: &Self) -> bool {
+        gf2::dot(&self.0, &rhs.0)
+    }
+
+    /// Returns bit `index`.
+    pub fn bit(&self, index: usize) -> bool {
+        assert!(index < 256, "bit index out of range");
+        (self.0[index / 8] >> (index % 8)) & 1 == 1
+    }
+
+    /// Sets bit `index` to `value`.
+    pub fn set_bit(&mut self, index: usize, value: bool) {
+        assert!(index < 256, "bit index out of range");
+        let mask = 1u8 << (index % 8);
+        if value {
+            self.0[index / 8] |= mask;
+        } else {
+            self.0[index / 8] &= !mask;
+        }
+    }
+
+    /// Returns one byte (0 or 1) per bit, in index order.
+    pub fn to_bits(&self) -> Vec<u8> {
+        (0..256).map(|index| u8::from(self.bit(index))).collect()
+    }
+
+    /// Inverse of [`Vec256::to_bits`].
+    pub fn from_bits(bits: &[u8]) -> Result<Self, BitLayoutError> {
+        check_bits(bits, 256)?;
+        let mut vec = Self::ZERO;
+        for (index, &bit) in bits.iter().enumerate() {
+            vec.set_bit(index, bit == 1);
+        }
+        Ok(vec)
+    }
+
+    /// Returns `true` for the zero vector.
+    pub fn is_zero(&self) -> bool {
+        *self == Self::ZERO
+    }
+}
+
+impl From<DoubleBlock> for Vec256 {
+    fn from(bytes: DoubleBlock) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<Vec256> for DoubleBlock {
+    fn from(vec: Vec256) -> Self {
+        vec.0
+    }
+}
+
+impl AsRef<[u8]> for Vec256 {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Zeroize for Vec256 {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl fmt::Display for Vec256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&double_block_hex(&self.0))
+    }
+}
+
+/// Packs 32 bytes into four little-endian words, bit `i` landing in word `i / 64`.
+pub(crate) fn bytes_to_segments(bytes: &[u8; 32]) -> [u64; 4] {
+    [
+        u64::from_le_bytes(bytes[0..8].try_into().expect("slice length 8")),
+        u64::from_le_bytes(bytes[8..16].try_into().expect("slice length 8")),
+        u64::from_le_bytes(bytes[16..24].try_into().expect("slice length 8")),
+        u64::from_le_bytes(bytes[24..32].try_into().expect("slice length 8")),
+    ]
+}
+
+/// Inverse of [`bytes_to_segments`].
+pub(crate) fn segments_to_bytes(segments: &[u64; 4]) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    for (idx, segment) in segments.iter().enumerate() {
+        let bytes = segment.to_le_bytes();
+        let start = idx * 8;
+        out[start..start + 8].copy_from_slice(&bytes);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Matrix256;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    #[test]
+    fn bits_follow_matrix_convention() {
+        let mut vec = Vec256::ZERO;
+        vec.set_bit(9, true);
+        assert_eq!(vec.as_bytes()[1], 0b10);
+        assert!(vec.bit(9) && !vec.bit(8));
+        vec.set_bit(9, false);
+        assert!(vec.is_zero());
+
+        let mut rng = ChaCha20Rng::from_seed([62u8; 32]);
+        let matrix = Matrix256::random_sparse_unsplit(&mut rng);
+        let input = Vec256::random(&mut rng);
+        let output = Vec256::from(matrix.apply_to_bytes(input.as_bytes()));
+        for row in 0..256 {
+            let row_vec = Vec256::from(matrix.row(row));
+            assert_eq!(row_vec.parity_and(&input), output.bit(row));
+        }
+    }
+
+    #[test]
+    fn bits_roundtrip() {
+        let mut rng = ChaCha20Rng::from_seed([64u8; 32]);
+        let vec = Vec256::random(&mut rng);
+        let bits = vec.to_bits();
+        assert!((0..256).all(|i| bits[i] == u8::from(vec.bit(i))));
+        assert_eq!(Vec256::from_bits(&bits), Ok(vec));
+        assert!(Vec256::from_bits(&bits[1..]).is_err());
+        let mut bad = bits;
+        bad[7] = 2;
+        assert_eq!(
+            Vec256::from_bits(&bad),
+            Err(BitLayoutError::NotABit { index: 7, value: 2 })
+        );
+    }
+
+    #[test]
+    fn xor_display_and_serde() {
+        let a = Vec256::new([0x0f; 32]);
+        let b = Vec256::new([0xff; 32]);
+        let sum = a.xor(&b);
+        assert_eq!(sum, Vec256::new([0xf0; 32]));
+        let mut c = sum;
+        c.xor_in_place(&b);
+        assert_eq!(c, a);
+        assert_eq!(sum.to_string(), "f0".repeat(32));
+
+        let encoded = bincode::serialize(&sum).unwrap();
+        assert_eq!(encoded, bincode::serialize(&[0xf0u8; 32]).unwrap());
+        assert_eq!(bincode::deserialize::<Vec256>(&encoded).unwrap(), sum);
+    }
+}
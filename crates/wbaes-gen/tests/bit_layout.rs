@@ -0,0 +1,28 @@
+//! Loads matrices dumped by external tools through the row-major bit conversions.
+//!
+//! `data/mc_sr_256.txt` holds `MC ∘ SR` on two AES states as 256 lines of 256 `0`/`1`
+//! characters, row-major, as written by `numpy.savetxt(path, m, fmt="%d", delimiter="")`. It
+//! was computed from the AES definitions, independently of this crate, with bit `i` of the
+//! state being bit `i % 8` of byte `i / 8`.
+
+use std::path::PathBuf;
+
+use wbaes_gen::{mc_sr_matrix_256, Matrix256};
+
+fn load_bit_rows(name: &str) -> Vec<u8> {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/data")
+        .join(name);
+    let text = std::fs::read_to_string(&path).expect("fixture is readable");
+    text.lines()
+        .flat_map(|line| line.bytes().map(|c| c - b'0'))
+        .collect()
+}
+
+#[test]
+fn external_mc_sr_dump_loads_to_expected_matrix() {
+    let bits = load_bit_rows("mc_sr_256.txt");
+    let loaded = Matrix256::from_row_major_bits(&bits).expect("fixture is well formed");
+    assert_eq!(loaded, mc_sr_matrix_256());
+    assert_eq!(loaded.to_row_major_bits(), bits);
+}
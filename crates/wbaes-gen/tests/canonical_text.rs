@@ -0,0 +1,35 @@
+//! Pins the canonical text form against a fixture shared with external tooling.
+//!
+//! `data/affine256_canonical.txt` is `Affine256::random_sparse_unsplit` from
+//! `ChaCha20Rng::from_seed([71; 32])`. It can be read without this crate, e.g. in Python:
+//!
+//! ```text
+//! lines = open(path).read().splitlines()
+//! bias = int.from_bytes(bytes.fromhex(lines[1][5:]), "little")
+//! rows = [int.from_bytes(bytes.fromhex(l), "little") for l in lines[2:]]
+//! y = sum((bin(r & x).count("1") & 1) << i for i, r in enumerate(rows)) ^ bias
+//! ```
+//!
+//! with vectors as little-endian integers. `APPLIED` was computed that way.
+
+use std::path::PathBuf;
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use wbaes_gen::{Affine256, CanonicalText};
+
+/// The fixture map applied to bytes `1, 8, 15, …` (byte `i` is `7i + 1`).
+const APPLIED: &str = "7d2a4c522f7116ee36c5a8c53db32a565ef23ee669d2932abccae8139e122966";
+
+#[test]
+fn fixture_matches_seeded_affine() {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/data/affine256_canonical.txt");
+    let text = std::fs::read_to_string(path).expect("fixture is readable");
+    let loaded = Affine256::from_canonical_string(&text).expect("fixture is well formed");
+    let mut rng = ChaCha20Rng::from_seed([71u8; 32]);
+    assert_eq!(loaded, Affine256::random_sparse_unsplit(&mut rng));
+    assert_eq!(loaded.to_canonical_string(), text);
+
+    let input: [u8; 32] = core::array::from_fn(|i| i as u8 * 7 + 1);
+    assert_eq!(aes_core::double_block_hex(&loaded.apply(&input)), APPLIED);
+}
@@ -0,0 +1,133 @@
+//! Property tests for `DynMatrixGf2`: rank bounds, inverses, kernels, and conversions to and
+//! from the fixed-size matrices.
+//!
+//! Dimensions straddle the 64-bit word boundary so that multi-word rows and partial last words
+//! are both exercised.
+
+use proptest::prelude::*;
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use wbaes_gen::{DimensionMismatch, DynMatrixGf2, Matrix128, Matrix256, Matrix8};
+
+fn matrix(rows: usize, cols: usize) -> impl Strategy<Value = DynMatrixGf2> {
+    prop::collection::vec(any::<bool>(), rows * cols)
+        .prop_map(move |bits| DynMatrixGf2::from_fn(rows, cols, |row, col| bits[row * cols + col]))
+}
+
+fn any_matrix() -> impl Strategy<Value = DynMatrixGf2> {
+    (1usize..80, 1usize..80).prop_flat_map(|(rows, cols)| matrix(rows, cols))
+}
+
+/// Pairs of matrices whose product is defined.
+fn product_pair() -> impl Strategy<Value = (DynMatrixGf2, DynMatrixGf2)> {
+    (1usize..70, 1usize..70, 1usize..70)
+        .prop_flat_map(|(rows, inner, cols)| (matrix(rows, inner), matrix(inner, cols)))
+}
+
+fn bits_of(x: u8) -> Vec<bool> {
+    (0..8).map(|bit| (x >> bit) & 1 == 1).collect()
+}
+
+proptest! {
+    #[test]
+    fn rank_is_bounded_by_both_dimensions(a in any_matrix()) {
+        let rank = a.rank() as usize;
+        prop_assert!(rank <= a.rows().min(a.cols()));
+        prop_assert_eq!(a.transpose().rank() as usize, rank);
+        prop_assert_eq!(a.transpose().transpose(), a);
+    }
+
+    #[test]
+    fn product_rank_and_transpose((a, b) in product_pair()) {
+        let product = a.mul(&b);
+        prop_assert!(product.rank() <= a.rank().min(b.rank()));
+        prop_assert_eq!(product.transpose(), b.transpose().mul(&a.transpose()));
+    }
+
+    #[test]
+    fn inverse_exists_exactly_for_full_rank(a in (1usize..100).prop_flat_map(|n| matrix(n, n))) {
+        let n = a.rows();
+        match a.invert() {
+            Some(inv) => {
+                prop_assert_eq!(a.rank() as usize, n);
+                prop_assert_eq!(a.mul(&inv), DynMatrixGf2::identity(n));
+                prop_assert_eq!(inv.mul(&a), DynMatrixGf2::identity(n));
+            }
+            None => prop_assert!((a.rank() as usize) < n),
+        }
+    }
+
+    #[test]
+    fn kernel_basis_spans_the_null_space(a in any_matrix()) {
+        let basis = a.kernel_basis();
+        prop_assert_eq!(basis.len(), a.cols() - a.rank() as usize);
+        for x in &basis {
+            prop_assert!(a.apply(x).iter().all(|&bit| !bit));
+        }
+        // The basis vectors are independent: stacked as rows, they have full rank.
+        if !basis.is_empty() {
+            let stacked = DynMatrixGf2::from_fn(basis.len(), a.cols(), |row, col| basis[row][col]);
+            prop_assert_eq!(stacked.rank() as usize, basis.len());
+        }
+    }
+
+    #[test]
+    fn solve_inverts_apply(a in any_matrix(), seed in any::<u64>()) {
+        let x: Vec<bool> = (0..a.cols()).map(|col| (seed >> (col % 64)) & 1 == 1).collect();
+        let b = a.apply(&x);
+        let solution = a.solve(&b).expect("b is in the image");
+        prop_assert_eq!(a.apply(&solution), b);
+    }
+
+    #[test]
+    fn matrix8_conversion_round_trips(rows in any::<[u8; 8]>(), x in any::<u8>()) {
+        let m = Matrix8::from_rows(rows);
+        let dynamic = DynMatrixGf2::from(&m);
+        prop_assert_eq!(Matrix8::try_from(&dynamic), Ok(m));
+        prop_assert_eq!(dynamic.apply(&bits_of(x)), bits_of(m.apply(x)));
+        prop_assert_eq!(dynamic.rank(), m.rank());
+    }
+}
+
+#[test]
+fn wide_conversions_round_trip_and_agree_with_apply() {
+    let mut rng = ChaCha20Rng::from_seed([63u8; 32]);
+    let m128 = Matrix128::random_sparse_unsplit(&mut rng);
+    assert_eq!(
+        Matrix128::try_from(&DynMatrixGf2::from(&m128)),
+        Ok(m128.clone())
+    );
+
+    let m256 = Matrix256::random_invertible_dense(&mut rng);
+    let dynamic = DynMatrixGf2::from(&m256);
+    assert_eq!(Matrix256::try_from(&dynamic), Ok(m256.clone()));
+    let input: [u8; 32] = core::array::from_fn(|i| (i as u8).wrapping_mul(37));
+    let bits: Vec<bool> = input.iter().flat_map(|&byte| bits_of(byte)).collect();
+    let expected: Vec<bool> = m256
+        .apply_to_bytes(&input)
+        .iter()
+        .flat_map(|&byte| bits_of(byte))
+        .collect();
+    assert_eq!(dynamic.apply(&bits), expected);
+    let inverse = Matrix256::try_from(&dynamic.invert().expect("invertible")).unwrap();
+    assert_eq!(Some(inverse), m256.invert());
+}
+
+#[test]
+fn conversions_reject_other_dimensions() {
+    // A byte-to-row map: 32×8.
+    let byte_map = DynMatrixGf2::from_fn(32, 8, |row, col| row % 8 == col);
+    assert_eq!(byte_map.rank(), 8);
+    assert_eq!(byte_map.invert(), None);
+    let err = Matrix8::try_from(&byte_map).unwrap_err();
+    assert_eq!(
+        err,
+        DimensionMismatch {
+            expected: (8, 8),
+            actual: (32, 8)
+        }
+    );
+    assert_eq!(err.to_string(), "expected a 8×8 matrix, got 32×8");
+    assert!(Matrix128::try_from(&DynMatrixGf2::identity(256)).is_err());
+    assert!(Matrix256::try_from(&DynMatrixGf2::identity(128)).is_err());
+}
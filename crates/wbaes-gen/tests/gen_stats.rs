@@ -0,0 +1,71 @@
+//! Sampler counts and timings from `generate_instance_with_stats`.
+
+use aes_core::Aes128Key;
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use wbaes_gen::{Affine256, GenStats, Generator, GeneratorConfig};
+
+fn generate(seed: u8) -> (wbaes_gen::WbInstance256, GenStats) {
+    let config = GeneratorConfig {
+        external_encodings: true,
+        ..GeneratorConfig::default()
+    };
+    let mut generator = Generator::with_config(ChaCha20Rng::from_seed([seed; 32]), config);
+    generator.generate_instance_with_stats(&Aes128Key::from([0x2b; 16]))
+}
+
+#[test]
+fn instance_stats_are_consistent_and_deterministic() {
+    let (instance, stats) = generate(3);
+    let counts = stats.counts;
+    // Ten banded A^(r), each with 32 diagonal blocks and one accepted wrap block.
+    assert_eq!(counts.band_wrap_successes, 10);
+    assert_eq!(counts.matrix8_invertible, 10 * 32);
+    assert!(counts.band_wrap_attempts >= counts.band_wrap_successes);
+    assert_eq!(
+        counts.band_wrap_rejections(),
+        counts.band_wrap_attempts - 10
+    );
+    assert_eq!(stats.rounds.len(), 10);
+    let phases = stats.encodings + stats.rounds.iter().sum::<std::time::Duration>();
+    assert!(phases <= stats.total);
+
+    // Collecting stats consumes no randomness.
+    let fingerprint = instance.fingerprint();
+    drop(instance);
+    let mut generator = Generator::with_config(
+        ChaCha20Rng::from_seed([3; 32]),
+        GeneratorConfig {
+            external_encodings: true,
+            ..GeneratorConfig::default()
+        },
+    );
+    let plain = generator.generate_instance(&Aes128Key::from([0x2b; 16]));
+    assert_eq!(plain.fingerprint(), fingerprint);
+    drop(plain);
+
+    let (_, again) = generate(3);
+    assert_eq!(again.counts, counts);
+}
+
+#[test]
+fn sampler_counts_accumulate() {
+    let mut rng = ChaCha20Rng::from_seed([70u8; 32]);
+    let mut stats = GenStats::default();
+    for _ in 0..64 {
+        Affine256::random_sparse_unsplit_with_stats(&mut rng, &mut stats);
+    }
+    let counts = stats.counts;
+    assert_eq!(counts.band_wrap_successes, 64);
+    assert_eq!(counts.matrix8_invertible, 64 * 32);
+    // Each wrap draw succeeds with probability about 0.29.
+    assert!(counts.band_wrap_rejections() > 0);
+    assert!(counts.band_wrap_attempts < 64 * 10);
+
+    let mut rng = ChaCha20Rng::from_seed([70u8; 32]);
+    let mut replay = GenStats::default();
+    for _ in 0..64 {
+        Affine256::random_sparse_unsplit_with_stats(&mut rng, &mut replay);
+    }
+    assert_eq!(replay, stats);
+}
@@ -0,0 +1,56 @@
+//! The external encodings of a generated instance, checked against a replay of its draws.
+
+use aes_core::{join_double, Aes128Key};
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use wbaes_gen::{assert_linear_map_eq, Affine256, Generator, GeneratorConfig};
+
+const KEY: [u8; 16] = [
+    0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f, 0x3c,
+];
+
+/// Replays the generator's first draws: `A^(1)`, then `Min` when external encodings are on.
+fn replay_encodings(seed: [u8; 32], external_encodings: bool) -> (Affine256, Affine256) {
+    let mut rng = ChaCha20Rng::from_seed(seed);
+    let a1 = Affine256::random_sparse_unsplit(&mut rng);
+    for _ in 1..10 {
+        Affine256::random_sparse_unsplit(&mut rng);
+    }
+    let min = if external_encodings {
+        Affine256::random_dense(&mut rng)
+    } else {
+        Affine256::identity()
+    };
+    (a1, min)
+}
+
+#[test]
+fn input_encoding_is_min_after_whitening_under_a1() {
+    let key0 = join_double(&KEY, &KEY);
+    for (seed, external_encodings) in [([41u8; 32], true), ([42u8; 32], false)] {
+        let config = GeneratorConfig {
+            external_encodings,
+            ..GeneratorConfig::default()
+        };
+        let instance = Generator::with_config(ChaCha20Rng::from_seed(seed), config)
+            .generate_instance(&Aes128Key::from(KEY));
+        let input = &instance.encodings.input;
+        let (a1, min) = replay_encodings(seed, external_encodings);
+
+        // `A^(1) ∘ input = Min ∘ (x ⊕ k0)`.
+        let whitened_min = |x: &[u8; 32]| {
+            let mut state = *x;
+            for (byte, k) in state.iter_mut().zip(key0.iter()) {
+                *byte ^= k;
+            }
+            min.apply(&state)
+        };
+        assert_linear_map_eq!(|x| a1.apply(&input.apply(x)), whitened_min);
+        assert!(input.then(&a1).agrees_with(
+            whitened_min,
+            64,
+            &mut ChaCha20Rng::from_seed([0; 32])
+        ));
+        assert!(instance.encodings.output.is_none());
+    }
+}
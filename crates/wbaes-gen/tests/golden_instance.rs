@@ -0,0 +1,123 @@
+//! Golden-instance regression tests.
+//!
+//! Generation is deterministic in the generator seed and key, so a fixed pair must always
+//! yield the same instance. The instance is identified by its fingerprint, which is cheap to
+//! compare and pinpoints which component drifted when a golden value changes.
+//!
+//! The SHA-256 digests of the serialized `[42; 32]` instances additionally pin the byte
+//! format and the generator's RNG consumption order (documented on `Generator`). A legitimate
+//! change to either must update these constants together with `InstanceParams::version`.
+
+use aes_core::Aes128Key;
+use sha2::{Digest, Sha256};
+use wbaes_gen::{Generator, GeneratorConfig, WbInstance256};
+
+const KEY: [u8; 16] = [
+    0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f, 0x3c,
+];
+
+fn generate(seed: u8, external_encodings: bool) -> WbInstance256 {
+    let mut gen = Generator::from_seed_with_config(
+        [seed; 32],
+        GeneratorConfig {
+            external_encodings,
+            ..GeneratorConfig::default()
+        },
+    );
+    gen.generate_instance(&Aes128Key::from(KEY))
+}
+
+fn hex(bytes: &[u8; 32]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+#[test]
+fn golden_instance_fingerprints() {
+    let instance = generate(1, false);
+    assert_eq!(
+        hex(&instance.encodings.input.lin.fingerprint()),
+        "618a0cee5aa679a3f6f18308571d3c29860941b829ddee2bb6937884f3163be5"
+    );
+    assert_eq!(
+        hex(&instance.encodings.input.fingerprint()),
+        "8215d71f250f5c7a1b93f9d621ef393c9570c51da08dca1c7ef8aa4a2f231eb7"
+    );
+    assert_eq!(
+        hex(&instance.rounds[0].fingerprint()),
+        "c57541e1f383467b3985a74eb6b342ca7abe444c0c2bb98b3e37c795bc113a57"
+    );
+    assert_eq!(
+        hex(&instance.rounds[9].fingerprint()),
+        "da4d6115d10e8bcc7f0bf7e07a41b3161e5390e42b941e61ab6ef2e8aa5ecab8"
+    );
+    assert_eq!(
+        hex(&instance.fingerprint()),
+        "76860ec1ce3e27f9007200b48decb124e09640b1ba362210ab5df19479af06ba"
+    );
+
+    let bytes = instance.to_bytes().expect("serialize");
+    let fingerprint = instance.fingerprint();
+    drop(instance);
+    let reloaded = WbInstance256::from_bytes(&bytes).expect("deserialize");
+    drop(bytes);
+    assert_eq!(reloaded.fingerprint(), fingerprint);
+}
+
+#[test]
+fn golden_fingerprint_with_external_encodings() {
+    let instance = generate(1, true);
+    assert_eq!(
+        hex(&instance.fingerprint()),
+        "797ddaff9457fd942b8ed1f9c5e83475b25c8edf5ca86c67ce9693b16091d509"
+    );
+}
+
+fn serialized_digest(instance: &WbInstance256) -> String {
+    let bytes = instance.to_bytes().expect("serialize");
+    hex(&Sha256::digest(&bytes).into())
+}
+
+#[test]
+fn golden_serialized_digest() {
+    let instance = generate(42, false);
+    assert_eq!(instance.params.version, 1);
+    assert_eq!(
+        serialized_digest(&instance),
+        "3933ee295f7f690bc0f4570d106c462d829d182f134894000ed697034893b7da"
+    );
+}
+
+#[test]
+fn golden_serialized_digest_with_external_encodings() {
+    let instance = generate(42, true);
+    assert_eq!(instance.params.version, 1);
+    assert_eq!(
+        serialized_digest(&instance),
+        "4d8d5cba515c930fadad84ad58f525287cc14ea0c4f64bf0a8f3aa623750f9cd"
+    );
+}
+
+#[test]
+fn different_seeds_give_different_fingerprints() {
+    let a = generate(2, false).fingerprint();
+    let b = generate(3, false).fingerprint();
+    assert_ne!(a, b);
+}
+
+/// Pins the rounds that multiply by each linear layer, with a dense `Mout` after the last.
+#[test]
+fn golden_round_fingerprints_with_external_encodings() {
+    let instance = generate(4, true);
+    assert_eq!(
+        hex(&instance.encodings.input.fingerprint()),
+        "91428c6f1d8c67ffcd19d952a13905034cf69407257f0e6acdbe56e5b3817b1c"
+    );
+    assert_eq!(
+        hex(&instance.rounds[0].fingerprint()),
+        "d5157bb81f996d8f40910cd6a509be566048a5b8f72dc4945b7714d105d0e604"
+    );
+    assert_eq!(
+        hex(&instance.rounds[9].fingerprint()),
+        "71bd2c0843601e7ff0be4525292ef3d16f1956d3f3c7f371b42ef5dc80cfb73f"
+    );
+}
@@ -0,0 +1,39 @@
+//! Single-block instances: serialization and their size relative to two-block instances.
+
+use aes_core::Aes128Key;
+use wbaes_gen::{
+    Affine256, ExternalEncodings, Generator, InstanceParams, RoundTables, WbInstance128,
+    WbInstance256,
+};
+
+#[test]
+fn single_block_instance_roundtrips_at_half_the_bytes_per_table() {
+    let instance =
+        Generator::from_seed([47u8; 32]).generate_instance_128(&Aes128Key::from([9; 16]));
+    assert_eq!(instance.params, InstanceParams::single_block());
+    assert!(instance.input.lin.is_invertible());
+    let bytes = instance.to_bytes().expect("serialize");
+    let fingerprint = instance.fingerprint();
+    drop(instance);
+    let reloaded = WbInstance128::from_bytes_validated(&bytes).expect("deserialize");
+    assert_eq!(reloaded.fingerprint(), fingerprint);
+    drop(reloaded);
+
+    // Only the sizes matter here, so zeroed tables stand in for a generated 256-bit instance.
+    let wide = WbInstance256 {
+        rounds: std::array::from_fn(|_| RoundTables::new_zeroed()),
+        encodings: ExternalEncodings {
+            input: Affine256::identity(),
+            output: None,
+            per_byte: None,
+        },
+        params: InstanceParams::default(),
+        masks: None,
+    };
+    let wide_len = wide.to_bytes().expect("serialize").len();
+    drop(wide);
+    let per_table = bytes.len() as f64 / (10.0 * 16.0);
+    let wide_per_table = wide_len as f64 / (10.0 * 32.0);
+    let ratio = per_table / wide_per_table;
+    assert!((ratio - 0.5).abs() < 0.01, "ratio {ratio}");
+}
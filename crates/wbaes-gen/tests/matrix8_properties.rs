@@ -0,0 +1,126 @@
+//! Property tests for the 8×8 GF(2) algebra and `Affine8`, plus sampling statistics for
+//! `Matrix8::random_invertible`.
+//!
+//! Matrices are drawn from all 2^64 row patterns, so the algebra is checked independently of
+//! the generator's own sampler. Every property that involves `apply` is checked on all 256
+//! inputs.
+
+use std::collections::HashSet;
+
+use proptest::prelude::*;
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use wbaes_gen::{Affine8, Matrix8};
+
+fn matrix8() -> impl Strategy<Value = Matrix8> {
+    any::<[u8; 8]>().prop_map(Matrix8::from_rows)
+}
+
+fn invertible_matrix8() -> impl Strategy<Value = Matrix8> {
+    matrix8().prop_filter("singular", Matrix8::is_invertible)
+}
+
+fn affine8() -> impl Strategy<Value = Affine8> {
+    (invertible_matrix8(), any::<u8>()).prop_map(|(lin, bias)| Affine8::new(lin, bias))
+}
+
+proptest! {
+    #[test]
+    fn mul_is_associative(a in matrix8(), b in matrix8(), c in matrix8()) {
+        prop_assert_eq!(a.mul(&b).mul(&c), a.mul(&b.mul(&c)));
+    }
+
+    #[test]
+    fn transpose_reverses_products(a in matrix8(), b in matrix8()) {
+        prop_assert_eq!(a.mul(&b).transpose(), b.transpose().mul(&a.transpose()));
+        prop_assert_eq!(a.transpose().transpose(), a);
+    }
+
+    #[test]
+    fn inverse_is_two_sided(a in invertible_matrix8()) {
+        let inv = a.invert().expect("filtered to invertible");
+        prop_assert_eq!(a.mul(&inv), Matrix8::identity());
+        prop_assert_eq!(inv.mul(&a), Matrix8::identity());
+    }
+
+    #[test]
+    fn singular_matrices_have_no_inverse(a in matrix8()) {
+        prop_assert_eq!(a.invert().is_some(), a.rank() == 8);
+    }
+
+    #[test]
+    fn mul_matches_sequential_application(a in matrix8(), b in matrix8()) {
+        let product = a.mul(&b);
+        for x in 0..=255u8 {
+            prop_assert_eq!(product.apply(x), a.apply(b.apply(x)));
+        }
+    }
+
+    #[test]
+    fn apply_is_linear(a in matrix8()) {
+        prop_assert_eq!(a.apply(0), 0);
+        for x in 0..=255u8 {
+            for bit in 0..8 {
+                let y = 1u8 << bit;
+                prop_assert_eq!(a.apply(x ^ y), a.apply(x) ^ a.apply(y));
+            }
+        }
+    }
+
+    #[test]
+    fn affine8_compose_matches_apply(f in affine8(), g in affine8()) {
+        let composed = f.compose(&g);
+        for x in 0..=255u8 {
+            prop_assert_eq!(composed.apply(x), f.apply(g.apply(x)));
+        }
+    }
+
+    #[test]
+    fn affine8_inverse_undoes_apply(f in affine8()) {
+        let inv = f.invert().expect("linear part is invertible");
+        prop_assert_eq!(f.compose(&inv), Affine8::identity());
+        prop_assert_eq!(inv.compose(&f), Affine8::identity());
+        for x in 0..=255u8 {
+            prop_assert_eq!(inv.apply(f.apply(x)), x);
+        }
+    }
+}
+
+/// Samples `random_invertible` 10 000 times from a fixed seed.
+///
+/// |GL(8, 2)| ≈ 5.3 · 10^18, so even the non-uniform `P · L · U` sampler should essentially
+/// never repeat: the birthday bound puts the expected number of duplicates near 10^-11. The
+/// entry frequencies are only checked loosely, since that sampler is known to favour ones in
+/// the first columns (about 0.56 against 128/255 for uniform sampling).
+#[test]
+fn random_invertible_sampling_statistics() {
+    const SAMPLES: usize = 10_000;
+    let mut rng = ChaCha20Rng::from_seed([77u8; 32]);
+    let mut seen = HashSet::with_capacity(SAMPLES);
+    let mut ones = [[0usize; 8]; 8];
+    let mut first_rows = [0usize; 256];
+    for _ in 0..SAMPLES {
+        let m = Matrix8::random_invertible(&mut rng);
+        assert!(m.is_invertible(), "singular sample {m:?}");
+        assert!(seen.insert(m), "duplicate sample {m:?}");
+        first_rows[usize::from(m.rows()[0])] += 1;
+        for (row, counts) in ones.iter_mut().enumerate() {
+            for (col, count) in counts.iter_mut().enumerate() {
+                *count += usize::from(m.bit(row, col));
+            }
+        }
+    }
+
+    for (row, counts) in ones.iter().enumerate() {
+        for (col, &count) in counts.iter().enumerate() {
+            let frequency = count as f64 / SAMPLES as f64;
+            assert!(
+                (0.4..0.6).contains(&frequency),
+                "entry ({row}, {col}) is one in {frequency:.3} of samples"
+            );
+        }
+    }
+    // An invertible matrix has no zero row; every other first row should show up.
+    assert_eq!(first_rows[0], 0);
+    assert!(first_rows[1..].iter().all(|&count| count > 0));
+}
@@ -0,0 +1,33 @@
+//! Wall-clock smoke test for the 256×256 linear algebra.
+//!
+//! Benchmarks live in `benches/matrix_bench.rs`; this only catches complexity regressions (an
+//! accidental O(n⁴) inversion is three orders of magnitude slower) without flaking on a busy
+//! machine, so the bound is far above the expected time.
+
+use std::time::{Duration, Instant};
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use wbaes_gen::Matrix256;
+
+/// Inverting 16 dense matrices takes about 10 ms in the test profile.
+const INVERT_BUDGET: Duration = Duration::from_secs(2);
+
+#[test]
+fn dense_inversion_stays_cubic() {
+    let mut rng = ChaCha20Rng::from_seed([11u8; 32]);
+    let matrices: Vec<Matrix256> = (0..16)
+        .map(|_| Matrix256::random_invertible_dense(&mut rng))
+        .collect();
+    let start = Instant::now();
+    let inverses: Vec<Option<Matrix256>> = matrices.iter().map(Matrix256::invert).collect();
+    let elapsed = start.elapsed();
+    for (matrix, inverse) in matrices.iter().zip(inverses) {
+        let inverse = inverse.expect("matrix is invertible");
+        assert_eq!(inverse.mul(matrix), Matrix256::identity());
+    }
+    assert!(
+        elapsed < INVERT_BUDGET,
+        "16 inversions took {elapsed:?}, budget {INVERT_BUDGET:?}"
+    );
+}
@@ -0,0 +1,103 @@
+//! Retained masks, stripped from the tables to expose the affine contributions `B(S(·))`.
+//!
+//! Table `i` of round `r` stores `B_i(S(z)) ⊕ b_i ⊕ h_i(x) ⊕ h_{i+1}(y)`, where `z` is byte `i`
+//! of `A^(r)` applied to the state, `B_i` is byte column `i` of `A^(r+1)⁻¹ · L`, and the bias
+//! shares `b_i` XOR to `A^(r+1)⁻¹ (k_r ⊕ bias)`. With the masks known, every entry of every
+//! table can be checked against a replay of the encodings.
+
+use aes_core::{expand_key, join_double, sbox, Aes128Key};
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use wbaes_gen::{
+    mc_sr_matrix_256, sr_matrix_256, Affine256, Generator, GeneratorConfig, HTable, Matrix256,
+    RoundTables, Vec256,
+};
+
+const KEY: [u8; 16] = [
+    0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f, 0x3c,
+];
+
+/// Checks one round's tables against `a_curr`, `next` and the linear layer, returning the
+/// XOR of the recovered bias shares.
+fn check_round(
+    tables: &RoundTables,
+    masks: &[HTable; 32],
+    a_curr: &Affine256,
+    next: &Affine256,
+    linear_layer: &Matrix256,
+) -> Vec256 {
+    let next_inv = next.invert().expect("encodings are invertible");
+    let b = next_inv.lin.mul(linear_layer);
+    let mut shares = Vec256::ZERO;
+    for i in 0..32 {
+        let left = a_curr.lin.block(i, i);
+        let right = a_curr.lin.block(i, (i + 1) % 32);
+        let byte_map = b.submatrix_byte_map(i);
+        let unmasked = |x: u8, y: u8| {
+            let z = left.apply(x) ^ right.apply(y) ^ a_curr.bias.as_bytes()[i];
+            let entry = tables.tables[i]
+                .get(x, y)
+                .xor(masks[i].get(x))
+                .xor(masks[(i + 1) % 32].get(y));
+            (entry, Vec256::from(byte_map[usize::from(sbox(z))]))
+        };
+        let (entry, contribution) = unmasked(0, 0);
+        let share = entry.xor(&contribution);
+        for (x, y) in [(0x01, 0x00), (0x00, 0x80), (0x53, 0xca), (0xff, 0xff)] {
+            let (entry, contribution) = unmasked(x, y);
+            assert_eq!(
+                entry.xor(&share),
+                contribution,
+                "table {i} at ({x:#04x}, {y:#04x})"
+            );
+        }
+        shares.xor_in_place(&share);
+    }
+    shares
+}
+
+#[test]
+fn stripped_tables_expose_the_affine_contributions() {
+    let seed = [43u8; 32];
+    let key = Aes128Key::from(KEY);
+    let config = GeneratorConfig {
+        retain_masks: true,
+        ..GeneratorConfig::default()
+    };
+    let instance =
+        Generator::with_config(ChaCha20Rng::from_seed(seed), config).generate_instance(&key);
+    let masks = instance.masks.as_ref().expect("masks are retained");
+    assert_eq!(masks.len(), 10);
+
+    // Retaining the masks consumes no randomness, so the tables are the default ones.
+    let plain = Generator::new(ChaCha20Rng::from_seed(seed)).generate_instance(&key);
+    assert!(plain.masks.is_none());
+    for (retained, plain) in instance.rounds.iter().zip(plain.rounds.iter()) {
+        assert_eq!(retained.fingerprint(), plain.fingerprint());
+    }
+
+    let mut rng = ChaCha20Rng::from_seed(seed);
+    let a: Vec<Affine256> = (0..10)
+        .map(|_| Affine256::random_sparse_unsplit(&mut rng))
+        .collect();
+    let round_keys = expand_key(&key);
+    let identity = Affine256::identity();
+    for r in [0, 4, 9] {
+        let (next, linear_layer) = if r == 9 {
+            (&identity, sr_matrix_256())
+        } else {
+            (&a[r + 1], mc_sr_matrix_256())
+        };
+        let shares = check_round(&instance.rounds[r], &masks[r], &a[r], next, &linear_layer);
+        let round_key = round_keys
+            .try_get(r + 1)
+            .expect("AES-128 has 11 round keys");
+        let key_block = join_double(round_key, round_key);
+        let next_inv = next.invert().expect("encodings are invertible");
+        assert_eq!(
+            shares,
+            Vec256::from(next_inv.apply(&key_block)),
+            "round {r}"
+        );
+    }
+}
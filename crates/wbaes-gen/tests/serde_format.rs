@@ -0,0 +1,39 @@
+//! Pins the bincode layout of the matrix and affine types.
+//!
+//! `data/affine256_v1.bin` is `Affine256::random_sparse_unsplit` from
+//! `ChaCha20Rng::from_seed([25; 32])`, serialized before `Matrix256` and `Table16x256` got their
+//! hand-written serde impls. Instances are pinned byte-for-byte by `golden_instance.rs`.
+
+use std::path::PathBuf;
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use wbaes_gen::{Affine256, Matrix256};
+
+fn fixture(name: &str) -> Vec<u8> {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/data")
+        .join(name);
+    std::fs::read(&path).expect("fixture is readable")
+}
+
+#[test]
+fn v1_affine_fixture_loads_and_reserializes_identically() {
+    let bytes = fixture("affine256_v1.bin");
+    let loaded: Affine256 = bincode::deserialize(&bytes).expect("fixture is well formed");
+    let mut rng = ChaCha20Rng::from_seed([25u8; 32]);
+    assert_eq!(loaded, Affine256::random_sparse_unsplit(&mut rng));
+    assert_eq!(bincode::serialize(&loaded).unwrap(), bytes);
+}
+
+#[test]
+fn serialized_sizes_have_no_overhead() {
+    let mut rng = ChaCha20Rng::from_seed([26u8; 32]);
+    let affine = Affine256::random_dense(&mut rng);
+    assert_eq!(bincode::serialized_size(&affine.lin).unwrap(), 8192);
+    assert_eq!(bincode::serialized_size(&affine).unwrap(), 8192 + 32);
+    assert_eq!(
+        bincode::serialized_size(&Matrix256::identity()).unwrap(),
+        8192
+    );
+}
@@ -0,0 +1,315 @@
+//! Versioned container header for ciphertext files.
+//!
+//! Layout (all integers little-endian):
+//!
+//! ```text
+//! magic        4 bytes   "WBCT"
+//! version      u8        FRAME_VERSION
+//! mode         u8        FrameMode
+//! flags        u8        bit 0: original length present
+//! nonce_len    u8        0..=MAX_NONCE_BYTES
+//! nonce        nonce_len bytes
+//! fingerprint  32 bytes  WbInstance256::fingerprint of the producing instance
+//! orig_len     u64       only when flag bit 0 is set
+//! checksum     u32       CRC-32 (IEEE) over all preceding header bytes
+//! ciphertext   ...
+//! ```
+//!
+//! The checksum detects accidental corruption of the header only; it is not an
+//! authentication tag.
+
+use core::fmt;
+
+use aes_core::ct_eq;
+
+/// Magic bytes identifying a framed ciphertext.
+pub const FRAME_MAGIC: [u8; 4] = *b"WBCT";
+/// Current container version.
+pub const FRAME_VERSION: u8 = 1;
+/// Largest nonce/IV accepted in a header.
+pub const MAX_NONCE_BYTES: usize = 32;
+
+const FLAG_ORIGINAL_LEN: u8 = 0x01;
+
+/// Mode of operation used to produce the ciphertext body.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FrameMode {
+    /// Independent 32-byte white-box blocks (no chaining, no nonce).
+    Block256,
+}
+
+impl FrameMode {
+    fn to_byte(self) -> u8 {
+        match self {
+            Self::Block256 => 1,
+        }
+    }
+
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            1 => Some(Self::Block256),
+            _ => None,
+        }
+    }
+}
+
+/// Parsed ciphertext header.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FrameHeader {
+    /// Mode that produced the ciphertext.
+    pub mode: FrameMode,
+    /// Nonce or IV (empty for modes without one).
+    pub nonce: Vec<u8>,
+    /// Fingerprint of the instance that produced the ciphertext.
+    pub fingerprint: [u8; 32],
+    /// Plaintext length before padding, for padded modes.
+    pub original_len: Option<u64>,
+}
+
+impl FrameHeader {
+    /// Creates a header for unpadded `Block256` ciphertext.
+    pub fn block256(fingerprint: [u8; 32]) -> Self {
+        Self {
+            mode: FrameMode::Block256,
+            nonce: Vec::new(),
+            fingerprint,
+            original_len: None,
+        }
+    }
+
+    /// Checks that the header was produced by the instance with `expected` fingerprint.
+    pub fn check_fingerprint(&self, expected: &[u8; 32]) -> Result<(), FramingError> {
+        if ct_eq(&self.fingerprint, expected) {
+            Ok(())
+        } else {
+            Err(FramingError::FingerprintMismatch)
+        }
+    }
+}
+
+/// Errors produced while reading or writing a ciphertext header.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FramingError {
+    /// Input ended before the header was complete.
+    Truncated,
+    /// The magic bytes do not match [`FRAME_MAGIC`].
+    BadMagic,
+    /// The header version is not supported.
+    UnsupportedVersion(u8),
+    /// The mode byte is unknown.
+    UnknownMode(u8),
+    /// Reserved flag bits are set.
+    UnknownFlags(u8),
+    /// The nonce exceeds [`MAX_NONCE_BYTES`].
+    NonceTooLong(usize),
+    /// The header checksum does not match its contents.
+    ChecksumMismatch,
+    /// The header names a different instance than the one supplied.
+    FingerprintMismatch,
+}
+
+impl fmt::Display for FramingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Truncated => write!(f, "ciphertext header is truncated"),
+            Self::BadMagic => write!(f, "missing ciphertext header magic"),
+            Self::UnsupportedVersion(v) => write!(f, "unsupported ciphertext header version {v}"),
+            Self::UnknownMode(m) => write!(f, "unknown ciphertext mode {m}"),
+            Self::UnknownFlags(flags) => write!(f, "unknown ciphertext header flags {flags:#04x}"),
+            Self::NonceTooLong(len) => write!(f, "nonce of {len} bytes exceeds header limit"),
+            Self::ChecksumMismatch => write!(f, "ciphertext header checksum mismatch"),
+            Self::FingerprintMismatch => {
+                write!(f, "ciphertext was produced by a different instance")
+            }
+        }
+    }
+}
+
+impl std::error::Error for FramingError {}
+
+/// Appends the encoded header to `out`.
+pub fn write_header(out: &mut Vec<u8>, header: &FrameHeader) -> Result<(), FramingError> {
+    if header.nonce.len() > MAX_NONCE_BYTES {
+        return Err(FramingError::NonceTooLong(header.nonce.len()));
+    }
+    let start = out.len();
+    out.extend_from_slice(&FRAME_MAGIC);
+    out.push(FRAME_VERSION);
+    out.push(header.mode.to_byte());
+    out.push(if header.original_len.is_some() {
+        FLAG_ORIGINAL_LEN
+    } else {
+        0
+    });
+    out.push(header.nonce.len() as u8);
+    out.extend_from_slice(&header.nonce);
+    out.extend_from_slice(&header.fingerprint);
+    if let Some(len) = header.original_len {
+        out.extend_from_slice(&len.to_le_bytes());
+    }
+    let checksum = crc32(&out[start..]);
+    out.extend_from_slice(&checksum.to_le_bytes());
+    Ok(())
+}
+
+/// Parses a header from the front of `input`, returning it with the remaining ciphertext.
+pub fn read_header(input: &[u8]) -> Result<(FrameHeader, &[u8]), FramingError> {
+    let mut reader = Reader {
+        data: input,
+        pos: 0,
+    };
+    if reader.take(4)? != FRAME_MAGIC {
+        return Err(FramingError::BadMagic);
+    }
+    let version = reader.byte()?;
+    if version != FRAME_VERSION {
+        return Err(FramingError::UnsupportedVersion(version));
+    }
+    let mode_byte = reader.byte()?;
+    let flags = reader.byte()?;
+    let nonce_len = reader.byte()? as usize;
+    if nonce_len > MAX_NONCE_BYTES {
+        return Err(FramingError::NonceTooLong(nonce_len));
+    }
+    let nonce = reader.take(nonce_len)?.to_vec();
+    let mut fingerprint = [0u8; 32];
+    fingerprint.copy_from_slice(reader.take(32)?);
+    let original_len = if flags & FLAG_ORIGINAL_LEN != 0 {
+        let mut len = [0u8; 8];
+        len.copy_from_slice(reader.take(8)?);
+        Some(u64::from_le_bytes(len))
+    } else {
+        None
+    };
+    let header_end = reader.pos;
+    let mut checksum = [0u8; 4];
+    checksum.copy_from_slice(reader.take(4)?);
+    if !ct_eq(&crc32(&input[..header_end]).to_le_bytes(), &checksum) {
+        return Err(FramingError::ChecksumMismatch);
+    }
+    if flags & !FLAG_ORIGINAL_LEN != 0 {
+        return Err(FramingError::UnknownFlags(flags));
+    }
+    let mode = FrameMode::from_byte(mode_byte).ok_or(FramingError::UnknownMode(mode_byte))?;
+
+    let header = FrameHeader {
+        mode,
+        nonce,
+        fingerprint,
+        original_len,
+    };
+    Ok((header, &input[reader.pos..]))
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], FramingError> {
+        let end = self.pos.checked_add(len).ok_or(FramingError::Truncated)?;
+        let slice = self
+            .data
+            .get(self.pos..end)
+            .ok_or(FramingError::Truncated)?;
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn byte(&mut self) -> Result<u8, FramingError> {
+        Ok(self.take(1)?[0])
+    }
+}
+
+/// CRC-32 (IEEE 802.3, reflected polynomial 0xEDB88320).
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xedb8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_header() -> FrameHeader {
+        FrameHeader {
+            mode: FrameMode::Block256,
+            nonce: vec![0xa5; 12],
+            fingerprint: [0x3c; 32],
+            original_len: Some(45),
+        }
+    }
+
+    #[test]
+    fn crc32_matches_reference_check_value() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+    }
+
+    #[test]
+    fn header_round_trip_with_and_without_optional_fields() {
+        let ciphertext = [0x11u8; 64];
+        for header in [sample_header(), FrameHeader::block256([7u8; 32])] {
+            let mut framed = Vec::new();
+            write_header(&mut framed, &header).expect("write header");
+            framed.extend_from_slice(&ciphertext);
+            let (decoded, body) = read_header(&framed).expect("read header");
+            assert_eq!(decoded, header);
+            assert_eq!(body, &ciphertext);
+        }
+    }
+
+    #[test]
+    fn fingerprint_mismatch_is_detected() {
+        let header = FrameHeader::block256([1u8; 32]);
+        assert_eq!(header.check_fingerprint(&[1u8; 32]), Ok(()));
+        assert_eq!(
+            header.check_fingerprint(&[2u8; 32]),
+            Err(FramingError::FingerprintMismatch)
+        );
+    }
+
+    #[test]
+    fn tampered_header_is_rejected() {
+        let mut framed = Vec::new();
+        write_header(&mut framed, &sample_header()).expect("write header");
+        for idx in 0..framed.len() {
+            for bit in 0..8 {
+                let mut tampered = framed.clone();
+                tampered[idx] ^= 1 << bit;
+                assert!(
+                    read_header(&tampered).is_err(),
+                    "flip of bit {bit} in byte {idx} was accepted"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn truncated_header_is_rejected() {
+        let mut framed = Vec::new();
+        write_header(&mut framed, &sample_header()).expect("write header");
+        for len in 0..framed.len() {
+            assert!(read_header(&framed[..len]).is_err());
+        }
+        assert_eq!(read_header(&framed[..3]), Err(FramingError::Truncated));
+    }
+
+    #[test]
+    fn oversized_nonce_is_rejected() {
+        let mut header = sample_header();
+        header.nonce = vec![0u8; MAX_NONCE_BYTES + 1];
+        let mut out = Vec::new();
+        assert_eq!(
+            write_header(&mut out, &header),
+            Err(FramingError::NonceTooLong(MAX_NONCE_BYTES + 1))
+        );
+    }
+}
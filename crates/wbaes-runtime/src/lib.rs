@@ -8,7 +8,16 @@
 #![forbid(unsafe_code)]
 #![deny(missing_docs)]
 
-use wbaes_gen::{ExternalEncodings, RoundTables, WbInstance256};
+pub mod framing;
+
+use core::fmt;
+
+use aes_core::round::shift_rows;
+use aes_core::{xor_in_place, Block};
+use wbaes_gen::{
+    ChowRound, Direction, ExternalEncodings, RoundTables, RoundTables128, Vec256, WbInstance128,
+    WbInstance256, WbInstanceChow,
+};
 
 /// White-box AES-256-bit cipher (two AES-128 blocks).
 pub struct WbCipher256 {
@@ -22,9 +31,15 @@ impl WbCipher256 {
     }
 
     /// Encrypts a 32-byte block in place.
+    ///
+    /// This evaluates the table network, so for the decryption half of an instance pair it
+    /// decrypts; prefer [`WbCipherPair256`] there. Only the first
+    /// [`InstanceParams::rounds`](wbaes_gen::InstanceParams::rounds) rounds are evaluated: all
+    /// ten, unless the instance comes from a reduced-rounds test generator.
     pub fn encrypt_block(&self, block: &mut [u8; 32]) {
         apply_input_encoding(&self.instance.encodings, block);
-        for round in &self.instance.rounds {
+        let rounds = self.instance.params.rounds;
+        for round in self.instance.rounds.iter().take(rounds) {
             apply_round(round, block);
         }
         if let Some(output_enc) = &self.instance.encodings.output {
@@ -48,31 +63,184 @@ impl WbCipher256 {
     }
 }
 
+/// Single-block white-box AES-128 cipher, for [`WbInstance128`].
+pub struct WbCipher128 {
+    instance: WbInstance128,
+}
+
+impl WbCipher128 {
+    /// Creates a new runtime cipher from a generated instance.
+    pub fn new(instance: WbInstance128) -> Self {
+        Self { instance }
+    }
+
+    /// Encrypts a 16-byte block in place.
+    pub fn encrypt_block(&self, block: &mut Block) {
+        self.instance.input.apply_in_place(block);
+        for round in &self.instance.rounds {
+            apply_round_128(round, block);
+        }
+    }
+
+    /// Borrows the underlying instance.
+    pub fn instance(&self) -> &WbInstance128 {
+        &self.instance
+    }
+}
+
+/// White-box AES-128 cipher for the Chow et al. construction, for [`WbInstanceChow`].
+pub struct WbCipherChow {
+    instance: WbInstanceChow,
+}
+
+impl WbCipherChow {
+    /// Creates a new runtime cipher from a generated instance.
+    pub fn new(instance: WbInstanceChow) -> Self {
+        Self { instance }
+    }
+
+    /// Encrypts a 16-byte block in place.
+    pub fn encrypt_block(&self, block: &mut Block) {
+        for round in &self.instance.rounds {
+            apply_round_chow(round, block);
+        }
+        shift_rows(block);
+        for (byte, table) in block.iter_mut().zip(&self.instance.final_round) {
+            *byte = table.get(*byte);
+        }
+    }
+
+    /// Borrows the underlying instance.
+    pub fn instance(&self) -> &WbInstanceChow {
+        &self.instance
+    }
+}
+
+/// Reasons [`WbCipherPair256::new`] refuses two instances.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PairError {
+    /// One of the instances was not generated as half of a pair.
+    Unpaired,
+    /// The instances belong to different pairs.
+    Mismatched,
+    /// The halves are swapped: the first must encrypt and the second decrypt.
+    WrongDirection,
+}
+
+impl fmt::Display for PairError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unpaired => write!(f, "instance is not part of a matched pair"),
+            Self::Mismatched => write!(f, "instances belong to different pairs"),
+            Self::WrongDirection => {
+                write!(
+                    f,
+                    "expected the encryption half first and the decryption half second"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for PairError {}
+
+/// Matched encryption and decryption instances from
+/// [`wbaes_gen::Generator::generate_instance_pair`].
+///
+/// The ciphertext stays under the pair's external output encoding: [`Self::decrypt_block`]
+/// undoes [`Self::encrypt_block`] without the AES ciphertext appearing in between. Per-byte
+/// encodings, if any, are still applied by the caller around the encryption half.
+pub struct WbCipherPair256 {
+    encrypt: WbCipher256,
+    decrypt: WbCipher256,
+}
+
+impl WbCipherPair256 {
+    /// Pairs the two halves, refusing instances that were not generated together.
+    pub fn new(encrypt: WbInstance256, decrypt: WbInstance256) -> Result<Self, PairError> {
+        Self::validate(&encrypt, &decrypt)?;
+        Ok(Self {
+            encrypt: WbCipher256::new(encrypt),
+            decrypt: WbCipher256::new(decrypt),
+        })
+    }
+
+    /// Checks that `encrypt` and `decrypt` are the two halves of one pair, in that order.
+    pub fn validate(encrypt: &WbInstance256, decrypt: &WbInstance256) -> Result<(), PairError> {
+        match (encrypt.params.pair_id, decrypt.params.pair_id) {
+            (Some(enc_id), Some(dec_id)) if enc_id != dec_id => return Err(PairError::Mismatched),
+            (Some(_), Some(_)) => {}
+            _ => return Err(PairError::Unpaired),
+        }
+        if encrypt.params.direction != Direction::Encrypt
+            || decrypt.params.direction != Direction::Decrypt
+        {
+            return Err(PairError::WrongDirection);
+        }
+        Ok(())
+    }
+
+    /// Encrypts a 32-byte block in place.
+    pub fn encrypt_block(&self, block: &mut [u8; 32]) {
+        self.encrypt.encrypt_block(block);
+    }
+
+    /// Decrypts a 32-byte block produced by [`Self::encrypt_block`] in place.
+    pub fn decrypt_block(&self, block: &mut [u8; 32]) {
+        self.decrypt.encrypt_block(block);
+    }
+
+    /// Borrows the encryption half.
+    pub fn encryption(&self) -> &WbCipher256 {
+        &self.encrypt
+    }
+
+    /// Borrows the decryption half.
+    pub fn decryption(&self) -> &WbCipher256 {
+        &self.decrypt
+    }
+}
+
 fn apply_input_encoding(encodings: &ExternalEncodings, state: &mut [u8; 32]) {
     encodings.input.apply_in_place(state);
 }
 
 fn apply_round(round: &RoundTables, state: &mut [u8; 32]) {
-    let mut acc = [0u8; 32];
+    let mut acc = Vec256::ZERO;
     for (i, table) in round.tables.iter().enumerate() {
         let x = state[i];
         let y = state[(i + 1) % 32];
-        let entry = table.get(x, y);
-        for (dst, src) in acc.iter_mut().zip(entry.iter()) {
-            *dst ^= *src;
-        }
+        acc.xor_in_place(&table.get(x, y));
+    }
+    *state = acc.into();
+}
+
+fn apply_round_128(round: &RoundTables128, state: &mut Block) {
+    let mut acc = [0u8; 16];
+    for (i, table) in round.tables.iter().enumerate() {
+        xor_in_place(&mut acc, &table.get(state[i], state[(i + 1) % 16]));
     }
     *state = acc;
 }
 
+fn apply_round_chow(round: &ChowRound, state: &mut Block) {
+    shift_rows(state);
+    for (column, bytes) in state.chunks_exact_mut(4).enumerate() {
+        let words = core::array::from_fn(|k| round.ty_boxes[4 * column + k].get(bytes[k]));
+        let mixed = round.ty_xor[column].apply(words).to_le_bytes();
+        let words = core::array::from_fn(|k| round.mb_inv[4 * column + k].get(mixed[k]));
+        bytes.copy_from_slice(&round.mb_xor[column].apply(words).to_le_bytes());
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use aes_core::{encrypt_block, expand_key, Aes128Key};
+    use aes_core::{encrypt_block, expand_key, join_double, xor32_in_place, Aes128Key};
     use rand::RngCore;
     use rand::SeedableRng;
     use rand_chacha::ChaCha20Rng;
-    use wbaes_gen::{Generator, GeneratorConfig};
+    use wbaes_gen::{Affine256, ChowConfig, ExternalEncodingKind, Generator, GeneratorConfig};
 
     #[test]
     fn runtime_matches_aes_for_random_inputs() {
@@ -85,6 +253,7 @@ mod tests {
             ChaCha20Rng::from_seed([7u8; 32]),
             GeneratorConfig {
                 external_encodings: false,
+                ..GeneratorConfig::default()
             },
         );
         let instance = gen.generate_instance(&key);
@@ -111,6 +280,131 @@ mod tests {
         }
     }
 
+    #[test]
+    fn single_block_runtime_matches_aes_for_random_keys() {
+        let mut rng = ChaCha20Rng::from_seed([15u8; 32]);
+        for seed in 0..2u8 {
+            let mut key_bytes = [0u8; 16];
+            rng.fill_bytes(&mut key_bytes);
+            let key = Aes128Key::from(key_bytes);
+            let instance = Generator::from_seed([16 + seed; 32]).generate_instance_128(&key);
+            let cipher = WbCipher128::new(instance);
+
+            let round_keys = expand_key(&key);
+            for _ in 0..4 {
+                let mut block = [0u8; 16];
+                rng.fill_bytes(&mut block);
+                let expected = encrypt_block(&block, &round_keys);
+                cipher.encrypt_block(&mut block);
+                assert_eq!(block, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn chow_runtime_matches_aes_for_random_keys() {
+        let mut rng = ChaCha20Rng::from_seed([17u8; 32]);
+        for nibble_encodings in [false, true] {
+            let mut key_bytes = [0u8; 16];
+            rng.fill_bytes(&mut key_bytes);
+            let key = Aes128Key::from(key_bytes);
+            let config = ChowConfig { nibble_encodings };
+            let instance = Generator::from_seed([18; 32]).generate_chow_instance(&key, config);
+            let cipher = WbCipherChow::new(instance);
+
+            let round_keys = expand_key(&key);
+            for _ in 0..16 {
+                let mut block = [0u8; 16];
+                rng.fill_bytes(&mut block);
+                let expected = encrypt_block(&block, &round_keys);
+                cipher.encrypt_block(&mut block);
+                assert_eq!(block, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn external_encodings_wrap_reference_aes() {
+        let key = Aes128Key::from([0x2b; 16]);
+        let seed = [11u8; 32];
+        let mut gen = Generator::with_config(
+            ChaCha20Rng::from_seed(seed),
+            GeneratorConfig {
+                external_encodings: true,
+                ..GeneratorConfig::default()
+            },
+        );
+        let cipher = WbCipher256::new(gen.generate_instance(&key));
+
+        // Replay the generator's draws: ten A^(r), then Min and Mout.
+        let mut replay = ChaCha20Rng::from_seed(seed);
+        for _ in 0..10 {
+            Affine256::random_sparse_unsplit(&mut replay);
+        }
+        let min = Affine256::random_dense(&mut replay);
+        let mout = Affine256::random_dense(&mut replay);
+        assert!(min.lin.validate_banded_unsplit().is_err());
+
+        // Min sits right after the initial AddRoundKey, so the state entering round 1
+        // is Min(x ⊕ k0) instead of x ⊕ k0; the output leaves encoded as Mout⁻¹(y).
+        let round_keys = expand_key(&key);
+        let k0 = join_double(
+            round_keys.try_get(0).unwrap(),
+            round_keys.try_get(0).unwrap(),
+        );
+        let mut rng = ChaCha20Rng::from_seed([12u8; 32]);
+        for _ in 0..3 {
+            let mut plaintext = [0u8; 32];
+            rng.fill_bytes(&mut plaintext);
+            let mut keyed = plaintext;
+            xor32_in_place(&mut keyed, &k0);
+            let mut equivalent = min.apply(&keyed);
+            xor32_in_place(&mut equivalent, &k0);
+
+            let mut block = plaintext;
+            cipher.encrypt_block(&mut block);
+            assert_eq!(
+                mout.apply(&block),
+                aes_core::encrypt_pair(&equivalent, &round_keys)
+            );
+        }
+    }
+
+    #[test]
+    fn per_byte_encodings_applied_externally_give_reference_aes() {
+        let key = Aes128Key::from([0x2b; 16]);
+        let mut gen = Generator::with_config(
+            ChaCha20Rng::from_seed([13u8; 32]),
+            GeneratorConfig {
+                external_encodings: true,
+                external_encoding_kind: ExternalEncodingKind::PerByte,
+                ..GeneratorConfig::default()
+            },
+        );
+        let instance = gen.generate_instance(&key);
+        let per_byte = instance
+            .encodings
+            .per_byte
+            .expect("per-byte encodings are stored");
+        assert_eq!(instance.encodings.output, None);
+        let cipher = WbCipher256::new(instance);
+
+        let round_keys = expand_key(&key);
+        let mut rng = ChaCha20Rng::from_seed([14u8; 32]);
+        for _ in 0..3 {
+            let mut plaintext = [0u8; 32];
+            rng.fill_bytes(&mut plaintext);
+            let expected = aes_core::encrypt_pair(&plaintext, &round_keys);
+
+            let mut block = plaintext;
+            per_byte.encode_input(&mut block);
+            cipher.encrypt_block(&mut block);
+            assert_ne!(block, expected);
+            per_byte.decode_output(&mut block);
+            assert_eq!(block, expected);
+        }
+    }
+
     #[test]
     fn encrypt_pair_matches_encrypt_block() {
         let mut rng = ChaCha20Rng::from_seed([9u8; 32]);
@@ -0,0 +1,150 @@
+//! Generator ↔ runtime round-trip properties over the generator configuration space.
+//!
+//! Every case samples a configuration, a key, a generator seed, a round count,
+//! and plaintexts. [`GeneratorConfig::validate`], the check the generator
+//! itself runs, splits the cases: rejected configurations must fail generation
+//! with the same error, and accepted ones must generate an instance that the
+//! runtime, with the external encodings stripped, evaluates to reference AES
+//! reduced to the same rounds. When a new `GeneratorConfig` option lands,
+//! [`config_strategy`] stops compiling until it samples the option; proptest
+//! shrinks failures to a minimal configuration.
+//!
+//! Budget: instances are toy-size, one or two rounds from
+//! [`Generator::set_rounds`] (the `reduced-rounds` feature, enabled for these
+//! tests), so [`CASES`] cases fit an unoptimized CI run at about 1 s each.
+//! Two rounds cover a MixColumns round and the last round; the ten-round path
+//! is covered by the golden instances and the other runtime tests.
+
+use aes_core::{encrypt_block_rounds, expand_key, Aes128Key, RoundKeys};
+use proptest::prelude::*;
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use wbaes_gen::{
+    Affine256, ExternalEncodingKind, ExternalEncodingSource, GeneratedInstance, Generator,
+    GeneratorConfig, Matrix256, Vec256,
+};
+use wbaes_runtime::WbCipher256;
+
+const CASES: u32 = 256;
+
+/// Samples every `GeneratorConfig` field independently.
+///
+/// The fields are listed without `..Default::default()` so that a new one has
+/// to be sampled here. Values `GeneratorConfig::validate` rejects are included
+/// on purpose (bandwidths 0 and 32, singular provided maps), so the space is
+/// the builder's validity check rather than a hand-picked list: dense, banded
+/// and provided external encodings are stripped with the secrets the
+/// generator returns, per-byte ones are applied around the runtime by the
+/// test, retained masks must not change the tables, and unmasked tables must
+/// still compute AES.
+fn config_strategy() -> impl Strategy<Value = GeneratorConfig> {
+    let kind = prop_oneof![
+        Just(ExternalEncodingKind::Dense),
+        Just(ExternalEncodingKind::PerByte),
+        Just(ExternalEncodingKind::Banded),
+    ];
+    let provided = (any::<[u8; 32]>(), any::<bool>(), prop::bool::weighted(0.1)).prop_map(
+        |(seed, with_mout, singular)| {
+            let mut rng = ChaCha20Rng::from_seed(seed);
+            let min = if singular {
+                Affine256::new(Matrix256::zero(), Vec256::ZERO)
+            } else {
+                Affine256::random_dense(&mut rng)
+            };
+            let mout = with_mout.then(|| Affine256::random_dense(&mut rng));
+            ExternalEncodingSource::Provided { min, mout }
+        },
+    );
+    let source = prop_oneof![
+        Just(ExternalEncodingSource::None),
+        Just(ExternalEncodingSource::Random),
+        provided,
+    ];
+    (
+        any::<bool>(),
+        kind,
+        any::<bool>(),
+        0usize..=32,
+        any::<bool>(),
+        source,
+    )
+        .prop_map(
+            |(
+                external_encodings,
+                external_encoding_kind,
+                retain_masks,
+                encoding_bandwidth,
+                use_masks,
+                external,
+            )| GeneratorConfig {
+                external_encodings,
+                external_encoding_kind,
+                retain_masks,
+                encoding_bandwidth,
+                use_masks,
+                external,
+            },
+        )
+}
+
+/// Reference AES on both halves, reduced to `rounds` rounds.
+fn encrypt_pair_rounds(
+    plaintext: &[u8; 32],
+    round_keys: &RoundKeys<11>,
+    rounds: usize,
+) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    for (half, block) in out.chunks_exact_mut(16).zip(plaintext.chunks_exact(16)) {
+        let block = block.try_into().expect("16-byte half");
+        half.copy_from_slice(&encrypt_block_rounds(&block, round_keys, rounds));
+    }
+    out
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig {
+        cases: CASES,
+        ..ProptestConfig::default()
+    })]
+
+    #[test]
+    fn runtime_matches_reference_aes(
+        config in config_strategy(),
+        key_bytes in any::<[u8; 16]>(),
+        gen_seed in any::<[u8; 32]>(),
+        rounds in 1usize..=2,
+        plaintexts in prop::collection::vec(any::<[u8; 32]>(), 1..4),
+    ) {
+        let key = Aes128Key::from(key_bytes);
+        let mut gen = Generator::with_config(ChaCha20Rng::from_seed(gen_seed), config.clone());
+        gen.set_rounds(rounds);
+        let result = gen.try_generate_instance_with_encodings(&key);
+        if let Err(err) = config.validate() {
+            prop_assert_eq!(result.err(), Some(err));
+            return Ok(());
+        }
+        let GeneratedInstance { instance, external } = result.expect("validated configuration");
+        prop_assert_eq!(instance.masks.is_some(), config.retain_masks);
+        let per_byte = instance.encodings.per_byte;
+        let cipher = WbCipher256::new(instance);
+        let round_keys = expand_key(&key);
+
+        for plaintext in plaintexts {
+            let mut block = plaintext;
+            if let Some(external) = &external {
+                block = external.encode_input(&block);
+            }
+            if let Some(per_byte) = &per_byte {
+                per_byte.encode_input(&mut block);
+            }
+            cipher.encrypt_block(&mut block);
+            if let Some(per_byte) = &per_byte {
+                per_byte.decode_output(&mut block);
+            }
+            if let Some(external) = &external {
+                block = external.decode_output(&block);
+            }
+            prop_assert_eq!(block, encrypt_pair_rounds(&plaintext, &round_keys, rounds));
+        }
+    }
+}
@@ -0,0 +1,232 @@
+//! Instances with folded external encodings, checked against reference AES by replaying the
+//! generator's draws of `Min` and `Mout`, with the maps the generator returns, and with maps
+//! supplied by the caller.
+//!
+//! The runtime computes `Mout⁻¹ ∘ AES' ∘ Min`, where `Min` is inserted right after the
+//! initial AddRoundKey. Feeding it `Min⁻¹(p ⊕ k0) ⊕ k0` and applying `Mout` to its output
+//! must therefore give `AES(p)`.
+
+use aes_core::{encrypt_pair, expand_key, join_double, xor32_in_place, Aes128Key};
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use wbaes_gen::{
+    Affine256, ExternalEncodingKind, ExternalEncodingSource, GenError, Generator, GeneratorConfig,
+    Matrix256, Vec256,
+};
+use wbaes_runtime::WbCipher256;
+
+/// Replays the generator's draws up to `(Min, Mout)`.
+fn replay_external(seed: [u8; 32], config: &GeneratorConfig) -> (Affine256, Affine256) {
+    let mut rng = ChaCha20Rng::from_seed(seed);
+    for _ in 0..10 {
+        Affine256::random_sparse_unsplit(&mut rng);
+    }
+    let mut draw = || match config.external_encoding_kind {
+        ExternalEncodingKind::Dense => Affine256::random_dense(&mut rng),
+        ExternalEncodingKind::Banded => {
+            Affine256::random_banded(&mut rng, config.encoding_bandwidth)
+        }
+        ExternalEncodingKind::PerByte => unreachable!("per-byte encodings are not folded"),
+    };
+    let min = draw();
+    let mout = draw();
+    (min, mout)
+}
+
+#[test]
+fn folded_external_encodings_wrap_reference_aes() {
+    let key = Aes128Key::from([0x5c; 16]);
+    let round_keys = expand_key(&key);
+    let k0 = round_keys.try_get(0).expect("AES-128 has 11 round keys");
+    let k0 = join_double(k0, k0);
+    let configs = [
+        (ExternalEncodingKind::Dense, 1),
+        (ExternalEncodingKind::Banded, 2),
+        (ExternalEncodingKind::Banded, 3),
+    ];
+    for (index, (kind, bandwidth)) in configs.into_iter().enumerate() {
+        let seed = [70 + index as u8; 32];
+        let config = GeneratorConfig {
+            external_encodings: true,
+            external_encoding_kind: kind,
+            encoding_bandwidth: bandwidth,
+            ..GeneratorConfig::default()
+        };
+        let instance = Generator::with_config(ChaCha20Rng::from_seed(seed), config.clone())
+            .generate_instance(&key);
+        let cipher = WbCipher256::new(instance);
+        let (min, mout) = replay_external(seed, &config);
+        if kind == ExternalEncodingKind::Banded {
+            assert_eq!(min.lin.block_bandwidth(), bandwidth);
+            assert_eq!(mout.lin.block_bandwidth(), bandwidth);
+        }
+        let min_inv = min.invert().expect("encodings are invertible");
+
+        let mut rng = ChaCha20Rng::from_seed([80u8; 32]);
+        for _ in 0..4 {
+            let mut plaintext = [0u8; 32];
+            rng.fill_bytes(&mut plaintext);
+
+            let mut block = plaintext;
+            xor32_in_place(&mut block, &k0);
+            let mut block = min_inv.apply(&block);
+            xor32_in_place(&mut block, &k0);
+            cipher.encrypt_block(&mut block);
+            assert_eq!(
+                mout.apply(&block),
+                encrypt_pair(&plaintext, &round_keys),
+                "{kind:?}, bandwidth {bandwidth}"
+            );
+        }
+    }
+}
+
+#[test]
+fn returned_secrets_strip_external_encodings() {
+    let key = Aes128Key::from([0x3d; 16]);
+    let round_keys = expand_key(&key);
+    let kinds = [
+        (ExternalEncodingKind::Dense, 1),
+        (ExternalEncodingKind::Banded, 2),
+    ];
+    for (index, (kind, bandwidth)) in kinds.into_iter().enumerate() {
+        let seed = [90 + index as u8; 32];
+        let config = GeneratorConfig {
+            external_encodings: true,
+            external_encoding_kind: kind,
+            encoding_bandwidth: bandwidth,
+            ..GeneratorConfig::default()
+        };
+        let generated = Generator::from_seed_with_config(seed, config.clone())
+            .generate_instance_with_encodings(&key);
+        let secrets = generated.external.expect("folded encodings are returned");
+        let (min, mout) = replay_external(seed, &config);
+        assert_eq!(secrets.min, min);
+        assert_eq!(secrets.mout, mout);
+        let cipher = WbCipher256::new(generated.instance);
+
+        let mut rng = ChaCha20Rng::from_seed([95u8; 32]);
+        for _ in 0..4 {
+            let mut plaintext = [0u8; 32];
+            rng.fill_bytes(&mut plaintext);
+            let mut block = secrets.encode_input(&plaintext);
+            cipher.encrypt_block(&mut block);
+            assert_eq!(
+                secrets.decode_output(&block),
+                encrypt_pair(&plaintext, &round_keys),
+                "{kind:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn no_secrets_without_folded_encodings() {
+    let key = Aes128Key::from([0x3d; 16]);
+    for config in [
+        GeneratorConfig::default(),
+        GeneratorConfig {
+            external_encodings: true,
+            external_encoding_kind: ExternalEncodingKind::PerByte,
+            ..GeneratorConfig::default()
+        },
+    ] {
+        let generated = Generator::from_seed_with_config([97; 32], config)
+            .generate_instance_with_encodings(&key);
+        assert!(generated.external.is_none());
+    }
+}
+
+#[test]
+fn provided_encodings_are_folded_like_random_ones() {
+    let key = Aes128Key::from([0x71; 16]);
+    let round_keys = expand_key(&key);
+    let k0 = round_keys.try_get(0).expect("AES-128 has 11 round keys");
+    let k0 = join_double(k0, k0);
+    let mut maps = ChaCha20Rng::from_seed([98u8; 32]);
+    let min = Affine256::random_dense(&mut maps);
+    let mout = Affine256::random_dense(&mut maps);
+    let min_inv = min.invert().expect("encodings are invertible");
+
+    for mout in [Some(mout), None] {
+        let config = GeneratorConfig {
+            external: ExternalEncodingSource::Provided {
+                min: min.clone(),
+                mout: mout.clone(),
+            },
+            ..GeneratorConfig::default()
+        };
+        let generated = Generator::from_seed_with_config([99; 32], config)
+            .generate_instance_with_encodings(&key);
+        let secrets = generated.external.expect("provided encodings are returned");
+        assert_eq!(secrets.min, min);
+        let cipher = WbCipher256::new(generated.instance);
+
+        let mut rng = ChaCha20Rng::from_seed([100u8; 32]);
+        for _ in 0..4 {
+            let mut plaintext = [0u8; 32];
+            rng.fill_bytes(&mut plaintext);
+
+            // Strip exactly the maps we supplied.
+            let mut block = plaintext;
+            xor32_in_place(&mut block, &k0);
+            let mut block = min_inv.apply(&block);
+            xor32_in_place(&mut block, &k0);
+            cipher.encrypt_block(&mut block);
+            let output = match &mout {
+                Some(mout) => mout.apply(&block),
+                None => block,
+            };
+            assert_eq!(output, encrypt_pair(&plaintext, &round_keys));
+        }
+    }
+}
+
+#[test]
+fn singular_provided_encodings_are_rejected() {
+    let key = Aes128Key::from([0x71; 16]);
+    let singular = Affine256::new(Matrix256::zero(), Vec256::ZERO);
+    for (min, mout) in [
+        (singular.clone(), None),
+        (Affine256::identity(), Some(singular.clone())),
+    ] {
+        let config = GeneratorConfig {
+            external: ExternalEncodingSource::Provided { min, mout },
+            ..GeneratorConfig::default()
+        };
+        let mut gen = Generator::from_seed_with_config([1; 32], config);
+        assert_eq!(
+            gen.try_generate_instance(&key).err(),
+            Some(GenError::SingularEncoding)
+        );
+        // Every fallible entry point runs the same check.
+        assert_eq!(
+            gen.try_generate_instance_with_encodings(&key).err(),
+            Some(GenError::SingularEncoding)
+        );
+        assert_eq!(
+            gen.try_generate_instance_pair(&key).err(),
+            Some(GenError::SingularEncoding)
+        );
+    }
+}
+
+#[test]
+fn out_of_range_bandwidth_is_rejected() {
+    let key = Aes128Key::from([0x72; 16]);
+    for bandwidth in [0, 32] {
+        let config = GeneratorConfig {
+            external_encodings: true,
+            external_encoding_kind: ExternalEncodingKind::Banded,
+            encoding_bandwidth: bandwidth,
+            ..GeneratorConfig::default()
+        };
+        let result = Generator::from_seed_with_config([1; 32], config).try_generate_instance(&key);
+        let err = result.expect_err("bandwidth is rejected");
+        assert_eq!(err, GenError::InvalidBandwidth(bandwidth));
+        assert_eq!(
+            err.to_string(),
+            format!("encoding bandwidth {bandwidth} is outside 1..=31")
+        );
+    }
+}
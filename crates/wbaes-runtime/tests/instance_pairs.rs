@@ -0,0 +1,107 @@
+//! Matched encryption/decryption pairs: decrypting with the second half undoes the first,
+//! whatever external encodings the pair shares.
+
+use aes_core::{encrypt_pair, expand_key, Aes128Key};
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use wbaes_gen::{Direction, ExternalEncodingKind, Generator, GeneratorConfig};
+use wbaes_runtime::{PairError, WbCipherPair256};
+
+const KEY: [u8; 16] = [
+    0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f, 0x3c,
+];
+
+#[test]
+fn decryption_half_inverts_encryption_half() {
+    let key = Aes128Key::from(KEY);
+    let round_keys = expand_key(&key);
+    let configs = [
+        GeneratorConfig::default(),
+        GeneratorConfig {
+            external_encodings: true,
+            ..GeneratorConfig::default()
+        },
+        GeneratorConfig {
+            external_encodings: true,
+            external_encoding_kind: ExternalEncodingKind::PerByte,
+            ..GeneratorConfig::default()
+        },
+    ];
+    let mut rng = ChaCha20Rng::from_seed([91u8; 32]);
+    let mut buffer = vec![0u8; 4 * 32];
+    rng.fill_bytes(&mut buffer);
+
+    for (index, config) in configs.into_iter().enumerate() {
+        let label = format!("{config:?}");
+        let encodings = config.external_encodings;
+        let (mut enc, mut dec) = Generator::from_seed_with_config([90 + index as u8; 32], config)
+            .generate_instance_pair(&key);
+        assert_eq!(enc.params.direction, Direction::Encrypt);
+        assert_eq!(dec.params.direction, Direction::Decrypt);
+        assert!(enc.params.pair_id.is_some());
+        assert_eq!(enc.params.pair_id, dec.params.pair_id);
+
+        // Mismatches are refused without consuming the instances.
+        let pair_id = dec.params.pair_id;
+        assert_eq!(
+            WbCipherPair256::validate(&dec, &enc),
+            Err(PairError::WrongDirection)
+        );
+        dec.params.pair_id = Some([0xff; 16]);
+        assert_eq!(
+            WbCipherPair256::validate(&enc, &dec),
+            Err(PairError::Mismatched)
+        );
+        dec.params.pair_id = None;
+        assert_eq!(
+            WbCipherPair256::validate(&enc, &dec),
+            Err(PairError::Unpaired)
+        );
+        dec.params.pair_id = pair_id;
+
+        // The per-byte encodings of the decryption half undo those of the encryption half.
+        let per_byte = enc.encodings.per_byte.take();
+        let dec_per_byte = dec.encodings.per_byte;
+        assert_eq!(per_byte.is_some(), dec_per_byte.is_some(), "{label}");
+        let pair = WbCipherPair256::new(enc, dec).expect("halves of one pair");
+
+        let mut encrypted = buffer.clone();
+        for chunk in encrypted.chunks_exact_mut(32) {
+            let block: &mut [u8; 32] = chunk.try_into().unwrap();
+            let plaintext = *block;
+            if let Some(per_byte) = &per_byte {
+                per_byte.encode_input(block);
+            }
+            pair.encrypt_block(block);
+            let mut ciphertext = *block;
+            if let Some(per_byte) = &per_byte {
+                per_byte.decode_output(&mut ciphertext);
+            }
+            if encodings && per_byte.is_none() {
+                assert_ne!(*block, encrypt_pair(&plaintext, &round_keys), "{label}");
+                continue;
+            }
+            // The AES ciphertext is visible, and the decryption half on its own is AES
+            // decryption under the swapped per-byte encodings.
+            assert_eq!(ciphertext, encrypt_pair(&plaintext, &round_keys), "{label}");
+            if let Some(dec_per_byte) = &dec_per_byte {
+                dec_per_byte.encode_input(&mut ciphertext);
+            }
+            pair.decrypt_block(&mut ciphertext);
+            if let Some(dec_per_byte) = &dec_per_byte {
+                dec_per_byte.decode_output(&mut ciphertext);
+            }
+            assert_eq!(ciphertext, plaintext, "{label}");
+        }
+        assert_ne!(encrypted, buffer, "{label}");
+
+        for chunk in encrypted.chunks_exact_mut(32) {
+            let block: &mut [u8; 32] = chunk.try_into().unwrap();
+            pair.decrypt_block(block);
+            if let Some(dec_per_byte) = &dec_per_byte {
+                dec_per_byte.decode_output(block);
+            }
+        }
+        assert_eq!(encrypted, buffer, "{label}");
+    }
+}
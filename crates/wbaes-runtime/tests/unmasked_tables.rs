@@ -0,0 +1,59 @@
+//! Debug instances without H-table masks, for bisecting mismatches against AES.
+//!
+//! An unmasked instance that fails points at the affine algebra; a masked one that fails
+//! while its unmasked twin passes points at the mask bookkeeping.
+
+use aes_core::{encrypt_pair, expand_key, Aes128Key};
+use wbaes_gen::{Generator, GeneratorConfig, WbInstance256};
+use wbaes_runtime::WbCipher256;
+
+const SEED: [u8; 32] = [61u8; 32];
+const KEY: [u8; 16] = [0x5a; 16];
+
+fn generate(use_masks: bool) -> WbInstance256 {
+    let config = GeneratorConfig {
+        use_masks,
+        ..GeneratorConfig::default()
+    };
+    Generator::from_seed_with_config(SEED, config).generate_instance(&Aes128Key::from(KEY))
+}
+
+fn ciphertexts(instance: WbInstance256) -> Vec<[u8; 32]> {
+    let cipher = WbCipher256::new(instance);
+    (0..4u8)
+        .map(|i| {
+            let mut block = [i.wrapping_mul(0x3b); 32];
+            block[0] = i;
+            cipher.encrypt_block(&mut block);
+            block
+        })
+        .collect()
+}
+
+#[test]
+fn unmasked_instance_matches_aes() {
+    let round_keys = expand_key(&Aes128Key::from(KEY));
+    for (i, ciphertext) in ciphertexts(generate(false)).into_iter().enumerate() {
+        let i = i as u8;
+        let mut plaintext = [i.wrapping_mul(0x3b); 32];
+        plaintext[0] = i;
+        assert_eq!(ciphertext, encrypt_pair(&plaintext, &round_keys));
+    }
+}
+
+#[test]
+fn masks_change_every_round_but_not_the_ciphertext() {
+    // One instance at a time: each is 640 MiB.
+    let masked = generate(true);
+    let masked_rounds: Vec<_> = masked.rounds.iter().map(|r| r.fingerprint()).collect();
+    let masked_input = masked.encodings.input.clone();
+    let masked_ciphertexts = ciphertexts(masked);
+
+    let unmasked = generate(false);
+    // Randomness is drawn identically, so only the tables differ.
+    assert_eq!(unmasked.encodings.input, masked_input);
+    for (r, round) in unmasked.rounds.iter().enumerate() {
+        assert_ne!(round.fingerprint(), masked_rounds[r], "round {r}");
+    }
+    assert_eq!(ciphertexts(unmasked), masked_ciphertexts);
+}